@@ -5,9 +5,11 @@
 // internal
 use crate::{import_csv::load_data_from_csv, types::HandwrittenExample};
 use model::{
-    network::types::classifier::ClassifierNetwork,
+    data::precision::Precision,
+    network::{config_types::hyper_params::HyperParams, types::classifier::ClassifierNetwork},
     optimization::{
-        batch_norm::NormalizationType, learning_decay::LearningDecayType, momentum::DescentType,
+        batch_norm::NormalizationType, learning_decay::LearningDecayType,
+        learning_schedule::LearningRateSchedule, momentum::DescentType,
     },
     regularization::{dropout::NetworkMaskType, penalty::PenaltyConfig},
     trainer::{trainer_params::TrainerConfig, SupervisedTrainer},
@@ -23,31 +25,43 @@ pub fn train_dataset() {
         vec![50],
         penalty_config,
         NetworkMaskType::none(),
-        LearningDecayType::constant(0.04),
-        DescentType::none(),
-        NormalizationType::batch_norm(0.9),
+        HyperParams::new(
+            LearningDecayType::constant(0.04),
+            DescentType::none(),
+            NormalizationType::batch_norm(0.9),
+            Precision::F32,
+            LearningRateSchedule::constant(),
+        ),
     );
 
     let train: Vec<HandwrittenExample> =
-        load_data_from_csv("../data/mnist_train.csv", 0, 60000).expect("Failed to read data");
+        load_data_from_csv("../data/mnist_train.csv", 0, 50000).expect("Failed to read data");
+    let validation: Vec<HandwrittenExample> =
+        load_data_from_csv("../data/mnist_train.csv", 50000, 10000).expect("Failed to read data");
     let test: Vec<HandwrittenExample> =
         load_data_from_csv("../data/mnist_test.csv", 0, 10000).expect("Failed to read data");
 
-    let config: TrainerConfig<HandwrittenExample> = TrainerConfig::new(5, 16, train, test);
+    let config: TrainerConfig<HandwrittenExample> =
+        TrainerConfig::new(5, 16, train, validation, test);
 
     let mut trainer: SupervisedTrainer<ClassifierNetwork, HandwrittenExample> =
         SupervisedTrainer::new(classifier, config);
 
-    trainer.train("test/mnist_config.json");
+    trainer
+        .train("test/mnist_config.json")
+        .expect("Training failed");
 }
 
 #[cfg(test)]
 mod tests {
     use model::{
-        data::{data_container::DataContainer, Data},
-        network::{types::classifier::ClassifierNetwork, Network},
+        data::{data_container::DataContainer, precision::Precision, Data},
+        network::{
+            config_types::hyper_params::HyperParams, types::classifier::ClassifierNetwork, Network,
+        },
         optimization::{
-            batch_norm::NormalizationType, learning_decay::LearningDecayType, momentum::DescentType,
+            batch_norm::NormalizationType, learning_decay::LearningDecayType,
+            learning_schedule::LearningRateSchedule, momentum::DescentType,
         },
         regularization::{
             dropout::NetworkMaskType,
@@ -67,9 +81,13 @@ mod tests {
             vec![50],
             penalty_config,
             NetworkMaskType::None,
-            LearningDecayType::rms_prop(0.01, 0.95),
-            DescentType::nesterov(0.95),
-            NormalizationType::none(),
+            HyperParams::new(
+                LearningDecayType::rms_prop(0.01, 0.95),
+                DescentType::nesterov(0.95),
+                NormalizationType::none(),
+                Precision::F32,
+                LearningRateSchedule::constant(),
+            ),
         );
 
         let data =
@@ -84,20 +102,22 @@ mod tests {
                 responses.push(label.get_response());
             }
 
-            classifier.train(
-                DataContainer::Batch(inputs),
-                DataContainer::Batch(responses),
-            );
+            classifier
+                .train(
+                    DataContainer::batch(inputs),
+                    DataContainer::batch(responses),
+                )
+                .unwrap();
         }
 
         let test_label_one = &data[0];
         let test_input_one = DataContainer::Inference(test_label_one.get_input());
-        let output_one = classifier.predict(test_input_one);
+        let output_one = classifier.predict(test_input_one).unwrap();
         println!("Output (5): {:?}", output_one);
 
         let test_label_two = &data[1];
         let test_input_two = DataContainer::Inference(test_label_two.get_input());
-        let output_two = classifier.predict(test_input_two);
+        let output_two = classifier.predict(test_input_two).unwrap();
         println!("Output (0): {:?}", output_two);
 
         classifier
@@ -115,12 +135,12 @@ mod tests {
 
         let test_label_one = &data[0];
         let test_input_one = DataContainer::Inference(test_label_one.get_input());
-        let output_one = classifier.predict(test_input_one);
+        let output_one = classifier.predict(test_input_one).unwrap();
         println!("Output (5): {:?}", output_one);
 
         let test_label_two = &data[1];
         let test_input_two = DataContainer::Inference(test_label_two.get_input());
-        let output_two = classifier.predict(test_input_two);
+        let output_two = classifier.predict(test_input_two).unwrap();
         println!("Output (0): {:?}", output_two);
     }
 
@@ -134,22 +154,29 @@ mod tests {
             vec![50],
             penalty_config,
             NetworkMaskType::none(),
-            LearningDecayType::rms_prop(0.01, 0.9),
-            DescentType::nesterov(0.4),
-            NormalizationType::batch_norm(0.9),
+            HyperParams::new(
+                LearningDecayType::rms_prop(0.01, 0.9),
+                DescentType::nesterov(0.4),
+                NormalizationType::batch_norm(0.9),
+                Precision::F32,
+                LearningRateSchedule::constant(),
+            ),
         );
 
         let train: Vec<HandwrittenExample> =
             load_data_from_csv("../data/mnist_train.csv", 0, 100).expect("Failed to read data");
+        let validation: Vec<HandwrittenExample> =
+            load_data_from_csv("../data/mnist_train.csv", 100, 20).expect("Failed to read data");
         let test: Vec<HandwrittenExample> =
             load_data_from_csv("../data/mnist_test.csv", 0, 20).expect("Failed to read data");
 
-        let config: TrainerConfig<HandwrittenExample> = TrainerConfig::new(5, 4, train, test);
+        let config: TrainerConfig<HandwrittenExample> =
+            TrainerConfig::new(5, 4, train, validation, test);
 
         let mut trainer: SupervisedTrainer<ClassifierNetwork, HandwrittenExample> =
             SupervisedTrainer::new(classifier, config);
 
-        trainer.train("test/mnist_small.json");
+        trainer.train("test/mnist_small.json").unwrap();
     }
 
     #[test]
@@ -159,15 +186,18 @@ mod tests {
 
         let train: Vec<HandwrittenExample> =
             load_data_from_csv("../data/mnist_train.csv", 0, 100).expect("Failed to read data");
+        let validation: Vec<HandwrittenExample> =
+            load_data_from_csv("../data/mnist_train.csv", 100, 20).expect("Failed to read data");
         let test: Vec<HandwrittenExample> =
             load_data_from_csv("../data/mnist_test.csv", 0, 20).expect("Failed to read data");
 
-        let config: TrainerConfig<HandwrittenExample> = TrainerConfig::new(5, 4, train, test);
+        let config: TrainerConfig<HandwrittenExample> =
+            TrainerConfig::new(5, 4, train, validation, test);
 
         let mut trainer: SupervisedTrainer<ClassifierNetwork, HandwrittenExample> =
             SupervisedTrainer::new(classifier, config);
 
-        trainer.train("test/mnist_small.json");
+        trainer.train("test/mnist_small.json").unwrap();
     }
 
     #[test]
@@ -179,12 +209,12 @@ mod tests {
 
         let test_label_one = &data[0];
         let test_input_one = DataContainer::Inference(test_label_one.get_input());
-        let output_one = classifier.predict(test_input_one);
+        let output_one = classifier.predict(test_input_one).unwrap();
         println!("Output (7): {:?}", output_one);
 
         let test_label_two = &data[1];
         let test_input_two = DataContainer::Inference(test_label_two.get_input());
-        let output_two = classifier.predict(test_input_two);
+        let output_two = classifier.predict(test_input_two).unwrap();
         println!("Output (2): {:?}", output_two);
     }
 
@@ -197,22 +227,29 @@ mod tests {
             vec![72],
             penalty_config,
             NetworkMaskType::None,
-            LearningDecayType::constant(0.01),
-            DescentType::nesterov(0.95),
-            NormalizationType::none(),
+            HyperParams::new(
+                LearningDecayType::constant(0.01),
+                DescentType::nesterov(0.95),
+                NormalizationType::none(),
+                Precision::F32,
+                LearningRateSchedule::constant(),
+            ),
         );
 
         let train: Vec<HandwrittenExample> =
             load_data_from_csv("../data/mnist_train.csv", 0, 1000).expect("Failed to read data");
+        let validation: Vec<HandwrittenExample> =
+            load_data_from_csv("../data/mnist_train.csv", 1000, 200).expect("Failed to read data");
         let test: Vec<HandwrittenExample> =
             load_data_from_csv("../data/mnist_test.csv", 0, 500).expect("Failed to read data");
 
-        let config: TrainerConfig<HandwrittenExample> = TrainerConfig::new(3, 4, train, test);
+        let config: TrainerConfig<HandwrittenExample> =
+            TrainerConfig::new(3, 4, train, validation, test);
 
         let mut trainer: SupervisedTrainer<ClassifierNetwork, HandwrittenExample> =
             SupervisedTrainer::new(classifier, config);
 
-        trainer.train("test/mnist_med.json");
+        trainer.train("test/mnist_med.json").unwrap();
     }
 
     #[test]
@@ -223,15 +260,19 @@ mod tests {
         let train: Vec<HandwrittenExample> =
             load_data_from_csv("../data/mnist_train.csv", 35000, 5000)
                 .expect("Failed to read data");
+        let validation: Vec<HandwrittenExample> =
+            load_data_from_csv("../data/mnist_train.csv", 40000, 1000)
+                .expect("Failed to read data");
         let test: Vec<HandwrittenExample> =
             load_data_from_csv("../data/mnist_test.csv", 0, 1000).expect("Failed to read data");
 
-        let config: TrainerConfig<HandwrittenExample> = TrainerConfig::new(2, 4, train, test);
+        let config: TrainerConfig<HandwrittenExample> =
+            TrainerConfig::new(2, 4, train, validation, test);
 
         let mut trainer: SupervisedTrainer<ClassifierNetwork, HandwrittenExample> =
             SupervisedTrainer::new(classifier, config);
 
-        trainer.train("test/mnist_med.json");
+        trainer.train("test/mnist_med.json").unwrap();
     }
 
     #[test]
@@ -243,12 +284,12 @@ mod tests {
 
         let test_label_one = &data[0];
         let test_input_one = DataContainer::Inference(test_label_one.get_input());
-        let output_one = classifier.predict(test_input_one);
+        let output_one = classifier.predict(test_input_one).unwrap();
         println!("Output (7): {:?}", output_one);
 
         let test_label_two = &data[1];
         let test_input_two = DataContainer::Inference(test_label_two.get_input());
-        let output_two = classifier.predict(test_input_two);
+        let output_two = classifier.predict(test_input_two).unwrap();
         println!("Output (2): {:?}", output_two);
     }
 
@@ -262,12 +303,16 @@ mod tests {
         let test: Vec<HandwrittenExample> =
             load_data_from_csv("../data/mnist_test.csv", 0, 10000).expect("Failed to read data");
 
-        let config: TrainerConfig<HandwrittenExample> = TrainerConfig::new(1, 4, train, test);
+        let config: TrainerConfig<HandwrittenExample> =
+            TrainerConfig::new(1, 4, train, Vec::new(), test);
         let trainer: SupervisedTrainer<ClassifierNetwork, HandwrittenExample> =
             SupervisedTrainer::new(classifier, config);
 
-        let error = trainer.evaluate();
+        let report = trainer.evaluate().unwrap();
+        let metrics = report.metrics().classification().unwrap();
 
-        println!("Total Accuracy: {:?}", error);
+        println!("Total Accuracy: {:?}", metrics.accuracy());
+        println!("Macro Average: {:?}", metrics.macro_average());
+        println!("Confusion Matrix: {:?}", metrics.confusion_matrix());
     }
 }
@@ -12,7 +12,7 @@ use ndarray::{arr1, Array1};
 const ROW_LENGTH: usize = 28 * 28 + 1;
 const OUTPUT_LENGTH: usize = 10;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct HandwrittenExample {
     label: usize,
     data: Array1<f32>,
@@ -85,4 +85,8 @@ impl SupervisedExample for HandwrittenExample {
         }
         panic!("[MNIST] Invalid data format for test error processing");
     }
+
+    fn get_label_key(&self) -> usize {
+        self.label
+    }
 }
@@ -1,21 +1,27 @@
 // builtin
-use core::panic;
 
 // external
-use ndarray::{arr1, Array1, Array2};
-use ndarray_rand::{
-    rand_distr::{Bernoulli, Distribution},
-    RandomExt,
-};
+use ndarray::{Array1, Array2, ArrayD, ArrayViewD, ArrayViewMutD, CowArray, IxDyn};
+use ndarray_rand::{rand_distr::Bernoulli, RandomExt};
 
 // internal
-use crate::data::operations::{
-    element_sum::DataElementSum, matmul::DataMatMul, minus::DataMinus,
-    minus_assign::DataMinusAssign, plus::DataPlus, sqrt::DataSquareRoot, sum_assign::DataSumAssign,
-    times::DataTimes, times_assign::DataTimesAssign, transpose::DataTranspose,
+use crate::data::{
+    element_fn::ElementFn,
+    error::DataError,
+    operations::{
+        broadcast::DataBroadcast, concat::DataConcat, element_sum::DataElementSum,
+        matmul::DataMatMul, minus::DataMinus, minus_assign::DataMinusAssign, plus::DataPlus,
+        sqrt::DataSquareRoot, sum_assign::DataSumAssign, times::DataTimes,
+        times_assign::DataTimesAssign, transpose::DataTranspose,
+    },
+    precision::{Element, Precision},
 };
+use crate::random::with_rng;
 pub mod data_container;
+pub mod element_fn;
+pub mod error;
 pub mod operations;
+pub mod precision;
 pub mod types;
 
 #[derive(Debug, Clone)]
@@ -23,6 +29,11 @@ pub enum Data {
     ScalarF32(f32),
     VectorF32(Array1<f32>),
     MatrixF32(Array2<f32>),
+    ArrayDF32(ArrayD<f32>),
+    ScalarF64(f64),
+    VectorF64(Array1<f64>),
+    MatrixF64(Array2<f64>),
+    ArrayDF64(ArrayD<f64>),
     None,
 }
 
@@ -40,93 +51,71 @@ impl Data {
     }
 
     pub fn zero_dim(dim: &[usize]) -> Data {
-        if dim.len() == 0 {
-            return Data::ScalarF32(0.0);
-        } else if dim.len() == 1 {
-            return Data::VectorF32(Array1::zeros(dim[0]));
-        } else if dim.len() == 2 {
-            return Data::MatrixF32(Array2::zeros((dim[0], dim[1])));
-        }
+        Data::zero_dim_as::<f32>(dim)
+    }
 
-        panic!("[ZERO_INIT] Unsupported data type dimensions");
+    pub fn zero_dim_as<T: Element>(dim: &[usize]) -> Data {
+        T::wrap(ArrayD::zeros(IxDyn(dim)))
     }
 
     pub fn one_dim(dim: &[usize]) -> Data {
-        if dim.len() == 0 {
-            return Data::ScalarF32(1.0);
-        } else if dim.len() == 1 {
-            return Data::VectorF32(Array1::ones(dim[0]));
-        } else if dim.len() == 2 {
-            return Data::MatrixF32(Array2::ones((dim[0], dim[1])));
-        }
+        Data::one_dim_as::<f32>(dim)
+    }
+
+    pub fn one_dim_as<T: Element>(dim: &[usize]) -> Data {
+        T::wrap(ArrayD::ones(IxDyn(dim)))
+    }
 
-        panic!("[ONE_INIT] Unsupported data type dimensions");
+    pub fn from_dim<T: Element>(dim: &[usize], data: Vec<T>) -> Data {
+        Data::try_from_dim(dim, data).unwrap_or_else(|err| {
+            Data::warn(&err);
+            Data::None
+        })
     }
 
-    pub fn from_dim(dim: &[usize], data: Vec<f32>) -> Data {
+    pub fn try_from_dim<T: Element>(dim: &[usize], data: Vec<T>) -> Result<Data, DataError> {
+        let invalid = |len: usize| DataError::InvalidDimensions {
+            operation: "FROM_DIM",
+            dim: dim.to_vec(),
+            len,
+        };
+
         if dim.len() == 0 {
             if data.len() > 0 {
-                return Data::ScalarF32(data[0]);
-            }
-            println!("Data::None returned on null data");
-            return Data::None;
-        } else if dim.len() == 1 {
-            if data.len() == dim[0] {
-                return Data::VectorF32(arr1(&data));
-            }
-            println!("Data::None returned on mismatched dimensions and data");
-            return Data::None;
-        } else if dim.len() == 2 {
-            if data.len() == dim[0] * dim[1] {
-                let matrix = Array2::from_shape_vec((dim[0], dim[1]), data)
-                    .expect("Couldn't create matrix from shape");
-
-                return Data::MatrixF32(matrix);
-            }
-            println!("Data::None returned on mismatched dimensions and data");
-            return Data::None;
-        } else {
-            panic!("Unsupported dimensions to coerce data to data type!");
+                return Ok(T::scalar(data[0]));
+            }
+            return Err(invalid(data.len()));
+        }
+
+        let len = data.len();
+        match ArrayD::from_shape_vec(IxDyn(dim), data) {
+            Ok(tensor) => Ok(T::wrap(tensor)),
+            Err(_) => Err(invalid(len)),
         }
     }
 
+    pub fn from_array_d<T: Element>(tensor: ArrayD<T>) -> Data {
+        T::wrap(tensor)
+    }
+
     pub fn bernoulli(probability: f32, dim: &[usize]) -> Data {
-        if dim.len() == 0 {
-            let distribution: Bernoulli = Bernoulli::new(probability.into()).unwrap();
-            let mut rng = rand::thread_rng();
-            let sample: bool = distribution.sample(&mut rng);
-
-            Data::ScalarF32(if sample { 1.0 } else { 0.0 })
-        } else if dim.len() == 1 {
-            let distribution: Bernoulli = Bernoulli::new(probability.into()).unwrap();
-            let sample: Array1<bool> = Array1::random(dim[0], distribution);
-
-            let sample_float: Array1<f32> = sample.map(|val| if *val { 1.0 } else { 0.0 });
-            Data::VectorF32(sample_float)
-        } else if dim.len() == 2 {
-            let distribution: Bernoulli = Bernoulli::new(probability.into()).unwrap();
-            let sample: Array2<bool> = Array2::random((dim[0], dim[1]), distribution);
-
-            let sample_float: Array2<f32> = sample.map(|val| if *val { 1.0 } else { 0.0 });
-            Data::MatrixF32(sample_float)
-        } else {
-            panic!("Unsupported dimensions to coerce data to data type!");
-        }
+        Data::bernoulli_as::<f32>(probability, dim)
     }
 
-    fn warn_operation(this: &Data, other: &Data, operation: &str) {
-        let this_type = this.variant_name();
-        let other_type = other.variant_name();
-        println!(
-            "Data::None returned on unsupported data type pair for operation [{operation}]: {this_type} and {other_type}!"
-        );
+    pub fn bernoulli_as<T: Element>(probability: f32, dim: &[usize]) -> Data {
+        let distribution: Bernoulli = Bernoulli::new(probability.into()).unwrap();
+        let sample: ArrayD<bool> =
+            with_rng(|rng| ArrayD::random_using(IxDyn(dim), distribution, rng));
+
+        T::wrap(sample.mapv(|val| if val { T::one() } else { T::zero() }))
     }
 
-    fn warn_mutate(this_type: &str, other: &Data, operation: &str) {
-        let other_type = other.variant_name();
-        println!(
-            "Mutation failed on unsupported data type pair for operation [{operation}]: {this_type} and {other_type}!"
-        );
+    fn warn(err: &DataError) {
+        println!("Data::None returned on {err}");
+    }
+
+    fn warn_mutate(err: &DataError) {
+        println!("Mutation failed on {err}");
     }
 }
 
@@ -136,173 +125,166 @@ impl Data {
             Data::ScalarF32(_) => "ScalarF32",
             Data::VectorF32(_) => "VectorF32",
             Data::MatrixF32(_) => "MatrixF32",
+            Data::ArrayDF32(_) => "ArrayDF32",
+            Data::ScalarF64(_) => "ScalarF64",
+            Data::VectorF64(_) => "VectorF64",
+            Data::MatrixF64(_) => "MatrixF64",
+            Data::ArrayDF64(_) => "ArrayDF64",
             Data::None => "None",
         }
     }
 
+    pub fn precision(&self) -> Precision {
+        match self {
+            Data::ScalarF64(_) | Data::VectorF64(_) | Data::MatrixF64(_) | Data::ArrayDF64(_) => {
+                Precision::F64
+            }
+            _ => Precision::F32,
+        }
+    }
+
+    pub fn to_precision(&self, precision: Precision) -> Data {
+        if self.precision() == precision {
+            return self.clone();
+        }
+
+        match precision {
+            Precision::F32 => self
+                .cast_array_d::<f32>()
+                .map(|view| f32::wrap(view.into_owned())),
+            Precision::F64 => self
+                .cast_array_d::<f64>()
+                .map(|view| f64::wrap(view.into_owned())),
+        }
+        .unwrap_or(Data::None)
+    }
+
     pub fn plus(&self, other: &Data) -> Data {
+        self.try_plus(other).unwrap_or_else(|err| {
+            Data::warn(&err);
+            Data::None
+        })
+    }
+
+    pub fn try_plus(&self, other: &Data) -> Result<Data, DataError> {
         match (self, other) {
             (Data::ScalarF32(scalar1), Data::ScalarF32(scalar2)) => {
                 DataPlus::sum_scalars(scalar1, scalar2)
             }
-            (Data::ScalarF32(scalar), Data::VectorF32(vector)) => {
-                DataPlus::sum_scalar_vector(scalar, vector)
-            }
-            (Data::ScalarF32(scalar), Data::MatrixF32(matrix)) => {
-                DataPlus::sum_scalar_matrix(scalar, matrix)
-            }
-            (Data::VectorF32(vector), Data::ScalarF32(scalar)) => {
-                DataPlus::sum_scalar_vector(scalar, vector)
-            }
-            (Data::VectorF32(vector1), Data::VectorF32(vector2)) => {
-                DataPlus::sum_vectors(vector1, vector2)
-            }
-            (Data::MatrixF32(matrix), Data::ScalarF32(scalar)) => {
-                DataPlus::sum_scalar_matrix(scalar, matrix)
-            }
-            (Data::MatrixF32(matrix1), Data::MatrixF32(matrix2)) => {
-                DataPlus::sum_matrices(matrix1, matrix2)
-            }
-            _ => {
-                Data::warn_operation(self, other, "PLUS");
-                Data::None
+            (Data::ScalarF64(scalar1), Data::ScalarF64(scalar2)) => {
+                DataPlus::sum_scalars(scalar1, scalar2)
             }
+            _ => DataPlus::sum_broadcast(self, other),
         }
     }
 
     pub fn sum_assign(&mut self, other: &Data) {
-        let variant = self.variant_name();
+        if let Err(err) = self.try_sum_assign(other) {
+            Data::warn_mutate(&err);
+        }
+    }
+
+    pub fn try_sum_assign(&mut self, other: &Data) -> Result<(), DataError> {
         match (self, other) {
             (Data::ScalarF32(l_scalar), Data::ScalarF32(r_scalar)) => {
-                DataSumAssign::sum_scalars(l_scalar, r_scalar);
+                DataSumAssign::sum_scalars(l_scalar, r_scalar)
             }
-            (Data::VectorF32(vector), Data::ScalarF32(scalar)) => {
-                DataSumAssign::sum_vector_scalar(vector, scalar);
-            }
-            (Data::VectorF32(l_vector), Data::VectorF32(r_vector)) => {
-                DataSumAssign::sum_vectors(l_vector, r_vector);
-            }
-            (Data::MatrixF32(matrix), Data::ScalarF32(scalar)) => {
-                DataSumAssign::sum_matrix_scalar(matrix, scalar);
-            }
-            (Data::MatrixF32(l_matrix), Data::MatrixF32(r_matrix)) => {
-                DataSumAssign::sum_matrices(l_matrix, r_matrix);
-            }
-            _ => {
-                Data::warn_mutate(variant, other, "PLUS_INPLACE");
+            (Data::ScalarF64(l_scalar), Data::ScalarF64(r_scalar)) => {
+                DataSumAssign::sum_scalars(l_scalar, r_scalar)
             }
+            (l_data, r_data) => DataSumAssign::sum_broadcast(l_data, r_data),
         }
     }
 
     pub fn minus(&self, other: &Data) -> Data {
+        self.try_minus(other).unwrap_or_else(|err| {
+            Data::warn(&err);
+            Data::None
+        })
+    }
+
+    pub fn try_minus(&self, other: &Data) -> Result<Data, DataError> {
         match (self, other) {
             (Data::ScalarF32(scalar1), Data::ScalarF32(scalar2)) => {
                 DataMinus::subtract_scalars(scalar1, scalar2)
             }
-            (Data::ScalarF32(scalar), Data::VectorF32(vector)) => {
-                DataMinus::subtract_scalar_vector(scalar, vector)
-            }
-            (Data::ScalarF32(scalar), Data::MatrixF32(matrix)) => {
-                DataMinus::subtract_scalar_matrix(scalar, matrix)
-            }
-            (Data::VectorF32(vector), Data::ScalarF32(scalar)) => {
-                DataMinus::subtract_vector_scalar(vector, scalar)
-            }
-            (Data::VectorF32(vector1), Data::VectorF32(vector2)) => {
-                DataMinus::subtract_vectors(vector1, vector2)
-            }
-            (Data::MatrixF32(matrix), Data::ScalarF32(scalar)) => {
-                DataMinus::subtract_matrix_scalar(matrix, scalar)
-            }
-            (Data::MatrixF32(matrix1), Data::MatrixF32(matrix2)) => {
-                DataMinus::subtract_matrices(matrix1, matrix2)
-            }
-            _ => {
-                Data::warn_operation(self, other, "MINUS");
-                Data::None
+            (Data::ScalarF64(scalar1), Data::ScalarF64(scalar2)) => {
+                DataMinus::subtract_scalars(scalar1, scalar2)
             }
+            _ => DataMinus::subtract_broadcast(self, other),
         }
     }
 
     pub fn minus_assign(&mut self, other: &Data) {
-        let variant = self.variant_name();
+        if let Err(err) = self.try_minus_assign(other) {
+            Data::warn_mutate(&err);
+        }
+    }
+
+    pub fn try_minus_assign(&mut self, other: &Data) -> Result<(), DataError> {
         match (self, other) {
             (Data::ScalarF32(l_scalar), Data::ScalarF32(r_scalar)) => {
-                DataMinusAssign::minus_scalars(l_scalar, r_scalar);
+                DataMinusAssign::minus_scalars(l_scalar, r_scalar)
             }
-            (Data::VectorF32(vector), Data::ScalarF32(scalar)) => {
-                DataMinusAssign::minus_vector_scalar(vector, scalar);
-            }
-            (Data::VectorF32(l_vector), Data::VectorF32(r_vector)) => {
-                DataMinusAssign::minus_vectors(l_vector, r_vector);
-            }
-            (Data::MatrixF32(matrix), Data::ScalarF32(scalar)) => {
-                DataMinusAssign::minus_matrix_scalar(matrix, scalar);
-            }
-            (Data::MatrixF32(l_matrix), Data::MatrixF32(r_matrix)) => {
-                DataMinusAssign::minus_matrices(l_matrix, r_matrix);
-            }
-            _ => {
-                Data::warn_mutate(variant, other, "MINUS_INPLACE");
+            (Data::ScalarF64(l_scalar), Data::ScalarF64(r_scalar)) => {
+                DataMinusAssign::minus_scalars(l_scalar, r_scalar)
             }
+            (l_data, r_data) => DataMinusAssign::minus_broadcast(l_data, r_data),
         }
     }
 
     pub fn times(&self, other: &Data) -> Data {
+        self.try_times(other).unwrap_or_else(|err| {
+            Data::warn(&err);
+            Data::None
+        })
+    }
+
+    pub fn try_times(&self, other: &Data) -> Result<Data, DataError> {
         match (self, other) {
             (Data::ScalarF32(scalar1), Data::ScalarF32(scalar2)) => {
                 DataTimes::multiply_scalars(scalar1, scalar2)
             }
-            (Data::ScalarF32(scalar), Data::VectorF32(vector)) => {
-                DataTimes::multiply_scalar_vector(scalar, vector)
-            }
-            (Data::ScalarF32(scalar), Data::MatrixF32(matrix)) => {
-                DataTimes::multiply_scalar_matrix(scalar, matrix)
-            }
-            (Data::VectorF32(vector), Data::ScalarF32(scalar)) => {
-                DataTimes::multiply_scalar_vector(scalar, vector)
-            }
-            (Data::VectorF32(vector1), Data::VectorF32(vector2)) => {
-                DataTimes::multiply_vectors(vector1, vector2)
-            }
-            (Data::MatrixF32(matrix), Data::ScalarF32(scalar)) => {
-                DataTimes::multiply_scalar_matrix(scalar, matrix)
-            }
-            (Data::MatrixF32(matrix1), Data::MatrixF32(matrix2)) => {
-                DataTimes::multiply_matrices(matrix1, matrix2)
-            }
-            _ => {
-                Data::warn_operation(self, other, "TIMES");
-                Data::None
+            (Data::ScalarF64(scalar1), Data::ScalarF64(scalar2)) => {
+                DataTimes::multiply_scalars(scalar1, scalar2)
             }
+            _ => DataTimes::multiply_broadcast(self, other),
         }
     }
 
     pub fn times_assign(&mut self, other: &Data) {
-        let variant = self.variant_name();
+        if let Err(err) = self.try_times_assign(other) {
+            Data::warn_mutate(&err);
+        }
+    }
+
+    pub fn try_times_assign(&mut self, other: &Data) -> Result<(), DataError> {
         match (self, other) {
             (Data::ScalarF32(l_scalar), Data::ScalarF32(r_scalar)) => {
-                DataTimesAssign::multiply_scalars(l_scalar, r_scalar);
-            }
-            (Data::VectorF32(vector), Data::ScalarF32(scalar)) => {
-                DataTimesAssign::multiply_vector_scalar(vector, scalar);
-            }
-            (Data::VectorF32(l_vector), Data::VectorF32(r_vector)) => {
-                DataTimesAssign::multiply_vectors(l_vector, r_vector);
-            }
-            (Data::MatrixF32(matrix), Data::ScalarF32(scalar)) => {
-                DataTimesAssign::multiply_matrix_scalar(matrix, scalar);
-            }
-            (Data::MatrixF32(l_matrix), Data::MatrixF32(r_matrix)) => {
-                DataTimesAssign::multiply_matrices(l_matrix, r_matrix);
+                DataTimesAssign::multiply_scalars(l_scalar, r_scalar)
             }
-            _ => {
-                Data::warn_mutate(variant, other, "TIMES_INPLACE");
+            (Data::ScalarF64(l_scalar), Data::ScalarF64(r_scalar)) => {
+                DataTimesAssign::multiply_scalars(l_scalar, r_scalar)
             }
+            (l_data, r_data) => DataTimesAssign::multiply_broadcast(l_data, r_data),
         }
     }
 
     pub fn matmul(&self, other: &Data) -> Data {
+        self.try_matmul(other).unwrap_or_else(|err| {
+            Data::warn(&err);
+            Data::None
+        })
+    }
+
+    pub fn try_matmul(&self, other: &Data) -> Result<Data, DataError> {
+        if self.precision() != other.precision() {
+            let precision = Precision::common(self.precision(), other.precision());
+            return self
+                .to_precision(precision)
+                .try_matmul(&other.to_precision(precision));
+        }
+
         match (self, other) {
             (Data::VectorF32(vector1), Data::VectorF32(vector2)) => {
                 DataMatMul::matmul_vectors(vector1, vector2)
@@ -316,75 +298,231 @@ impl Data {
             (Data::MatrixF32(matrix1), Data::MatrixF32(matrix2)) => {
                 DataMatMul::matmul_matrices(matrix1, matrix2)
             }
-            _ => {
-                Data::warn_operation(self, other, "MATMUL");
-                Data::None
+            (Data::VectorF32(vector), Data::ArrayDF32(tensor)) => {
+                DataMatMul::matmul_vector_tensor(vector, tensor)
+            }
+            (Data::ArrayDF32(tensor), Data::VectorF32(vector)) => {
+                DataMatMul::matmul_tensor_vector(tensor, vector)
+            }
+            (Data::MatrixF32(matrix), Data::ArrayDF32(tensor)) => {
+                DataMatMul::matmul_tensors(&matrix.view().into_dyn(), &tensor.view())
+            }
+            (Data::ArrayDF32(tensor), Data::MatrixF32(matrix)) => {
+                DataMatMul::matmul_tensors(&tensor.view(), &matrix.view().into_dyn())
+            }
+            (Data::ArrayDF32(tensor1), Data::ArrayDF32(tensor2)) => {
+                DataMatMul::matmul_tensors(&tensor1.view(), &tensor2.view())
+            }
+            (Data::VectorF64(vector1), Data::VectorF64(vector2)) => {
+                DataMatMul::matmul_vectors(vector1, vector2)
+            }
+            (Data::VectorF64(vector), Data::MatrixF64(matrix)) => {
+                DataMatMul::matmul_vector_matrix(vector, matrix)
+            }
+            (Data::MatrixF64(matrix), Data::VectorF64(vector)) => {
+                DataMatMul::matmul_matrix_vector(matrix, vector)
+            }
+            (Data::MatrixF64(matrix1), Data::MatrixF64(matrix2)) => {
+                DataMatMul::matmul_matrices(matrix1, matrix2)
+            }
+            (Data::VectorF64(vector), Data::ArrayDF64(tensor)) => {
+                DataMatMul::matmul_vector_tensor(vector, tensor)
+            }
+            (Data::ArrayDF64(tensor), Data::VectorF64(vector)) => {
+                DataMatMul::matmul_tensor_vector(tensor, vector)
+            }
+            (Data::MatrixF64(matrix), Data::ArrayDF64(tensor)) => {
+                DataMatMul::matmul_tensors(&matrix.view().into_dyn(), &tensor.view())
+            }
+            (Data::ArrayDF64(tensor), Data::MatrixF64(matrix)) => {
+                DataMatMul::matmul_tensors(&tensor.view(), &matrix.view().into_dyn())
+            }
+            (Data::ArrayDF64(tensor1), Data::ArrayDF64(tensor2)) => {
+                DataMatMul::matmul_tensors(&tensor1.view(), &tensor2.view())
             }
+            _ => Err(DataError::unsupported_pair("MATMUL", self, other)),
         }
     }
 
     pub fn transpose(&self) -> Data {
+        self.try_transpose().unwrap_or_else(|err| {
+            Data::warn(&err);
+            Data::None
+        })
+    }
+
+    pub fn try_transpose(&self) -> Result<Data, DataError> {
         match self {
-            Data::ScalarF32(scalar) => DataTranspose::transpose_scalar(scalar),
-            Data::VectorF32(vector) => DataTranspose::transpose_vector(vector),
-            Data::MatrixF32(matrix) => DataTranspose::transpose_matrix(matrix),
-            Data::None => Data::None,
+            Data::ScalarF32(scalar) => Ok(DataTranspose::transpose_scalar(scalar)),
+            Data::VectorF32(vector) => Ok(DataTranspose::transpose_vector(vector)),
+            Data::MatrixF32(matrix) => Ok(DataTranspose::transpose_matrix(matrix)),
+            Data::ArrayDF32(tensor) => Ok(DataTranspose::transpose_tensor(tensor)),
+            Data::ScalarF64(scalar) => Ok(DataTranspose::transpose_scalar(scalar)),
+            Data::VectorF64(vector) => Ok(DataTranspose::transpose_vector(vector)),
+            Data::MatrixF64(matrix) => Ok(DataTranspose::transpose_matrix(matrix)),
+            Data::ArrayDF64(tensor) => Ok(DataTranspose::transpose_tensor(tensor)),
+            Data::None => Err(DataError::invalid_operand("TRANSPOSE", self)),
         }
     }
 
     pub fn element_sum(&self) -> Data {
+        self.try_element_sum().unwrap_or_else(|err| {
+            Data::warn(&err);
+            Data::None
+        })
+    }
+
+    pub fn try_element_sum(&self) -> Result<Data, DataError> {
         match self {
-            Data::ScalarF32(scalar) => DataElementSum::element_sum_scalar(scalar),
-            Data::VectorF32(vector) => DataElementSum::element_sum_vector(vector),
-            Data::MatrixF32(matrix) => DataElementSum::element_sum_matrix(matrix),
-            Data::None => Data::None,
+            Data::ScalarF32(scalar) => Ok(DataElementSum::element_sum_scalar(scalar)),
+            Data::VectorF32(vector) => Ok(DataElementSum::element_sum_vector(vector)),
+            Data::MatrixF32(matrix) => Ok(DataElementSum::element_sum_matrix(matrix)),
+            Data::ArrayDF32(tensor) => Ok(DataElementSum::element_sum_tensor(tensor)),
+            Data::ScalarF64(scalar) => Ok(DataElementSum::element_sum_scalar(scalar)),
+            Data::VectorF64(vector) => Ok(DataElementSum::element_sum_vector(vector)),
+            Data::MatrixF64(matrix) => Ok(DataElementSum::element_sum_matrix(matrix)),
+            Data::ArrayDF64(tensor) => Ok(DataElementSum::element_sum_tensor(tensor)),
+            Data::None => Err(DataError::invalid_operand("ELEMENT_SUM", self)),
         }
     }
 
     pub fn sqrt(&self) -> Data {
+        self.try_sqrt().unwrap_or_else(|err| {
+            Data::warn(&err);
+            Data::None
+        })
+    }
+
+    pub fn try_sqrt(&self) -> Result<Data, DataError> {
         match self {
-            Data::ScalarF32(scalar) => DataSquareRoot::square_root_scalar(scalar),
-            Data::VectorF32(vector) => DataSquareRoot::square_root_vector(vector),
-            Data::MatrixF32(matrix) => DataSquareRoot::square_root_matrix(matrix),
-            Data::None => Data::None,
+            Data::ScalarF32(scalar) => Ok(DataSquareRoot::square_root_scalar(scalar)),
+            Data::VectorF32(vector) => Ok(DataSquareRoot::square_root_vector(vector)),
+            Data::MatrixF32(matrix) => Ok(DataSquareRoot::square_root_matrix(matrix)),
+            Data::ArrayDF32(tensor) => Ok(DataSquareRoot::square_root_tensor(tensor)),
+            Data::ScalarF64(scalar) => Ok(DataSquareRoot::square_root_scalar(scalar)),
+            Data::VectorF64(vector) => Ok(DataSquareRoot::square_root_vector(vector)),
+            Data::MatrixF64(matrix) => Ok(DataSquareRoot::square_root_matrix(matrix)),
+            Data::ArrayDF64(tensor) => Ok(DataSquareRoot::square_root_tensor(tensor)),
+            Data::None => Err(DataError::invalid_operand("SQRT", self)),
+        }
+    }
+
+    // Sums a gradient over the axes this data was broadcast along in the forward pass
+    pub fn try_reduce_like(&self, target: &Data) -> Result<Data, DataError> {
+        DataBroadcast::reduce_like(self, target)
+    }
+
+    // Joins the parts along their last axis, in the precision of the first part
+    pub fn try_concat(parts: &[Data]) -> Result<Data, DataError> {
+        let precision: Precision = match parts.first() {
+            Some(first) => first.precision(),
+            None => return Err(DataError::invalid_operand("CONCAT", &Data::None)),
+        };
+        let parts: Vec<Data> = parts
+            .iter()
+            .map(|part| part.to_precision(precision))
+            .collect();
+
+        match precision {
+            Precision::F32 => DataConcat::concat_last::<f32>(&parts),
+            Precision::F64 => DataConcat::concat_last::<f64>(&parts),
+        }
+    }
+
+    pub fn try_split(&self, widths: &[usize]) -> Result<Vec<Data>, DataError> {
+        match self.precision() {
+            Precision::F32 => DataConcat::split_last::<f32>(self, widths),
+            Precision::F64 => DataConcat::split_last::<f64>(self, widths),
         }
     }
 
-    pub fn apply_elementwise(&self, func: impl Fn(f32) -> f32) -> Data {
+    pub fn apply_elementwise(&self, func: impl ElementFn) -> Data {
         match self {
-            Data::ScalarF32(scalar) => Data::ScalarF32(func(*scalar)),
-            Data::VectorF32(vector) => Data::VectorF32(vector.mapv(func)),
-            Data::MatrixF32(matrix) => Data::MatrixF32(matrix.mapv(func)),
+            Data::ScalarF32(scalar) => Data::ScalarF32(func.call(*scalar)),
+            Data::VectorF32(vector) => Data::VectorF32(vector.mapv(|f| func.call(f))),
+            Data::MatrixF32(matrix) => Data::MatrixF32(matrix.mapv(|f| func.call(f))),
+            Data::ArrayDF32(tensor) => Data::ArrayDF32(tensor.mapv(|f| func.call(f))),
+            Data::ScalarF64(scalar) => Data::ScalarF64(func.call(*scalar)),
+            Data::VectorF64(vector) => Data::VectorF64(vector.mapv(|f| func.call(f))),
+            Data::MatrixF64(matrix) => Data::MatrixF64(matrix.mapv(|f| func.call(f))),
+            Data::ArrayDF64(tensor) => Data::ArrayDF64(tensor.mapv(|f| func.call(f))),
             Data::None => Data::None,
         }
     }
 
-    pub fn apply_inplace(&mut self, func: impl Fn(&mut f32)) {
+    pub fn apply_inplace(&mut self, func: impl ElementFn) {
         match self {
-            Data::ScalarF32(scalar) => func(scalar),
-            Data::VectorF32(vector) => vector.map_inplace(func),
-            Data::MatrixF32(matrix) => matrix.map_inplace(func),
-            _ => {}
+            Data::ScalarF32(scalar) => *scalar = func.call(*scalar),
+            Data::VectorF32(vector) => vector.mapv_inplace(|f| func.call(f)),
+            Data::MatrixF32(matrix) => matrix.mapv_inplace(|f| func.call(f)),
+            Data::ArrayDF32(tensor) => tensor.mapv_inplace(|f| func.call(f)),
+            Data::ScalarF64(scalar) => *scalar = func.call(*scalar),
+            Data::VectorF64(vector) => vector.mapv_inplace(|f| func.call(f)),
+            Data::MatrixF64(matrix) => matrix.mapv_inplace(|f| func.call(f)),
+            Data::ArrayDF64(tensor) => tensor.mapv_inplace(|f| func.call(f)),
+            Data::None => {}
         }
     }
 
     pub fn dim(&self) -> &[usize] {
         match self {
-            Data::ScalarF32(_) => &[1],
+            Data::ScalarF32(_) | Data::ScalarF64(_) => &[1],
             Data::VectorF32(vec) => vec.shape(),
             Data::MatrixF32(matrix) => matrix.shape(),
+            Data::ArrayDF32(tensor) => tensor.shape(),
+            Data::VectorF64(vec) => vec.shape(),
+            Data::MatrixF64(matrix) => matrix.shape(),
+            Data::ArrayDF64(tensor) => tensor.shape(),
             Data::None => &[],
         }
     }
 
+    // Unlike `dim`, scalars report no axes at all
+    pub fn shape(&self) -> Option<&[usize]> {
+        match self {
+            Data::ScalarF32(_) | Data::ScalarF64(_) => Some(&[]),
+            Data::None => None,
+            _ => Some(self.dim()),
+        }
+    }
+
+    // Views are only handed out for data already stored with the requested element type
+    pub fn view_array_d<T: Element>(&self) -> Option<ArrayViewD<'_, T>> {
+        T::view(self)
+    }
+
+    pub fn view_array_d_mut<T: Element>(&mut self) -> Option<ArrayViewMutD<'_, T>> {
+        T::view_mut(self)
+    }
+
+    // Borrows the data when it already has the requested element type, and converts it otherwise
+    pub fn cast_array_d<T: Element>(&self) -> Option<CowArray<'_, T, IxDyn>> {
+        if let Some(view) = T::view(self) {
+            return Some(view.into());
+        }
+
+        match self.precision() {
+            Precision::F32 => {
+                f32::view(self).map(|view| view.mapv(|f| T::from_f64(f as f64)).into())
+            }
+            Precision::F64 => f64::view(self).map(|view| view.mapv(T::from_f64).into()),
+        }
+    }
+
     pub fn flatten_to_vec(&self) -> Vec<f32> {
         match self {
-            Data::VectorF32(vec) => vec.to_vec(),
-            Data::MatrixF32(matrix) => matrix.flatten().to_vec(),
-            _ => {
+            Data::ScalarF32(_) | Data::ScalarF64(_) | Data::None => {
                 println!("Cannot flatten data that has dimension less than 1!");
                 Vec::new()
             }
+            _ => self.flatten_as(),
+        }
+    }
+
+    pub fn flatten_as<T: Element>(&self) -> Vec<T> {
+        match self.cast_array_d::<T>() {
+            Some(view) => view.iter().copied().collect(),
+            None => Vec::new(),
         }
     }
 }
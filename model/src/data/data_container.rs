@@ -1,15 +1,19 @@
 // builtin
 
 // external
+use ndarray::{stack, ArrayViewD, Axis};
 
 // internal
 use crate::data::{
     data_container::operations::{
-        element_sum::ContainerElementSum, matmul::ContainerMatMul, minus::ContainerMinus,
-        minus_assign::ContainerMinusAssign, plus::ContainerPlus, sqrt::ContainerSquareRoot,
-        sum_assign::ContainerSumAssign, times::ContainerTimes, times_assign::ContainerTimesAssign,
-        transpose::ContainerTranspose,
+        batch::ContainerBatch, element_sum::ContainerElementSum, matmul::ContainerMatMul,
+        minus::ContainerMinus, minus_assign::ContainerMinusAssign, plus::ContainerPlus,
+        sqrt::ContainerSquareRoot, sum_assign::ContainerSumAssign, times::ContainerTimes,
+        times_assign::ContainerTimesAssign, transpose::ContainerTranspose,
     },
+    element_fn::{ElementFn, Fill},
+    error::DataError,
+    precision::{Element, Precision},
     types::FlattenedData,
     Data,
 };
@@ -17,7 +21,8 @@ pub mod operations;
 
 #[derive(Clone, Debug)]
 pub enum DataContainer {
-    Batch(Vec<Data>),
+    // Examples stacked along a leading batch axis
+    Batch(Data),
     Inference(Data),
     Parameter(Data),
     Empty,
@@ -60,6 +65,28 @@ impl DataContainer {
         }
     }
 
+    pub fn precision(&self) -> Precision {
+        match self {
+            DataContainer::Batch(data) => data.precision(),
+            DataContainer::Inference(data) => data.precision(),
+            DataContainer::Parameter(data) => data.precision(),
+            DataContainer::Empty => Precision::default(),
+        }
+    }
+
+    pub fn to_precision(&self, precision: Precision) -> DataContainer {
+        match self {
+            DataContainer::Batch(data) => DataContainer::Batch(data.to_precision(precision)),
+            DataContainer::Inference(data) => {
+                DataContainer::Inference(data.to_precision(precision))
+            }
+            DataContainer::Parameter(data) => {
+                DataContainer::Parameter(data.to_precision(precision))
+            }
+            DataContainer::Empty => DataContainer::Empty,
+        }
+    }
+
     pub fn zero() -> DataContainer {
         DataContainer::Parameter(Data::zero())
     }
@@ -80,36 +107,123 @@ impl DataContainer {
         DataContainer::Parameter(Data::one_dim(dim))
     }
 
-    pub fn from_dim(dim: &[usize], data: Vec<f32>, container_type: ContainerType) -> DataContainer {
-        let data = Data::from_dim(dim, data);
+    pub fn zero_dim_as<T: Element>(dim: &[usize]) -> DataContainer {
+        DataContainer::Parameter(Data::zero_dim_as::<T>(dim))
+    }
 
-        if let Data::None = data {
-            println!("DataContainer::Empty returned on Data::None from dimension coercion");
-            return DataContainer::Empty;
+    pub fn one_dim_as<T: Element>(dim: &[usize]) -> DataContainer {
+        DataContainer::Parameter(Data::one_dim_as::<T>(dim))
+    }
+
+    pub fn batch(batch: Vec<Data>) -> DataContainer {
+        DataContainer::try_batch(batch).unwrap_or_else(|err| {
+            DataContainer::warn(&err);
+            DataContainer::Empty
+        })
+    }
+
+    pub fn try_batch(batch: Vec<Data>) -> Result<DataContainer, DataError> {
+        let first = match batch.first() {
+            Some(first) => first,
+            None => {
+                return Err(DataError::InvalidDimensions {
+                    operation: "BATCH",
+                    dim: Vec::new(),
+                    len: 0,
+                })
+            }
+        };
+
+        for example in &batch {
+            if example.variant_name() != first.variant_name() || example.dim() != first.dim() {
+                return Err(DataError::shape_mismatch("BATCH", first, example));
+            }
+            if example.shape().is_none() {
+                return Err(DataError::invalid_operand("BATCH", example));
+            }
+        }
+
+        let stacked = match first.precision() {
+            Precision::F32 => DataContainer::stack_as::<f32>(&batch),
+            Precision::F64 => DataContainer::stack_as::<f64>(&batch),
+        };
+        match stacked {
+            Some(stacked) => Ok(DataContainer::Batch(stacked)),
+            None => Err(DataError::shape_mismatch("BATCH", first, first)),
+        }
+    }
+
+    fn stack_as<T: Element>(batch: &[Data]) -> Option<Data> {
+        let views: Vec<ArrayViewD<T>> = batch
+            .iter()
+            .map(|example| example.view_array_d::<T>())
+            .collect::<Option<Vec<ArrayViewD<T>>>>()?;
+
+        stack(Axis(0), &views).ok().map(Data::from_array_d)
+    }
+
+    fn unstack_as<T: Element>(stacked: &Data) -> Vec<Data> {
+        match stacked.view_array_d::<T>() {
+            Some(view) => view
+                .outer_iter()
+                .map(|example| Data::from_array_d(example.to_owned()))
+                .collect(),
+            None => Vec::new(),
         }
+    }
 
-        DataContainer::data_with_type(data, container_type)
+    pub fn batch_to_vec(&self) -> Vec<Data> {
+        match self {
+            DataContainer::Batch(stacked) => match stacked.precision() {
+                Precision::F32 => DataContainer::unstack_as::<f32>(stacked),
+                Precision::F64 => DataContainer::unstack_as::<f64>(stacked),
+            },
+            DataContainer::Inference(data) => vec![data.clone()],
+            DataContainer::Parameter(data) => vec![data.clone()],
+            DataContainer::Empty => Vec::new(),
+        }
+    }
+
+    pub fn from_dim<T: Element>(
+        dim: &[usize],
+        data: Vec<T>,
+        container_type: ContainerType,
+    ) -> DataContainer {
+        DataContainer::try_from_dim(dim, data, container_type).unwrap_or_else(|err| {
+            DataContainer::warn(&err);
+            DataContainer::Empty
+        })
+    }
+
+    pub fn try_from_dim<T: Element>(
+        dim: &[usize],
+        data: Vec<T>,
+        container_type: ContainerType,
+    ) -> Result<DataContainer, DataError> {
+        let data = Data::try_from_dim(dim, data)?;
+
+        Ok(DataContainer::data_with_type(data, container_type))
     }
 }
 
 impl DataContainer {
-    fn warn_operation(this: &DataContainer, other: &DataContainer, operation: &str) {
-        let this_type = this.container_name();
-        let other_type = other.container_name();
-        println!(
-            "DataContainer::Empty returned on unsupported container type pair for operation [{operation}]: {this_type} and {other_type}!"
-        );
+    fn warn(err: &DataError) {
+        println!("DataContainer::Empty returned on {err}");
     }
 
-    fn warn_mutate(this_type: &str, other: &DataContainer, operation: &str) {
-        let other_type = other.container_name();
-        println!(
-            "DataContainer::Empty returned on unsupported container type pair for operation [{operation}]: {this_type} and {other_type}!"
-        );
+    fn warn_mutate(err: &DataError) {
+        println!("Mutation failed on {err}");
     }
 
     pub fn plus(&self, other: &DataContainer) -> DataContainer {
-        match (self, other) {
+        self.try_plus(other).unwrap_or_else(|err| {
+            DataContainer::warn(&err);
+            DataContainer::Empty
+        })
+    }
+
+    pub fn try_plus(&self, other: &DataContainer) -> Result<DataContainer, DataError> {
+        let res = match (self, other) {
             (DataContainer::Batch(batch1), DataContainer::Batch(batch2)) => {
                 ContainerPlus::sum_batches(batch1, batch2)
             }
@@ -124,6 +238,7 @@ impl DataContainer {
             }
             (DataContainer::Parameter(data), DataContainer::Batch(batch)) => {
                 ContainerPlus::sum_batch_data(batch, data)
+                    .map_err(|err| err.in_containers(other, self))
             }
             (DataContainer::Parameter(data1), DataContainer::Inference(data2)) => {
                 ContainerPlus::sum_data(data1, data2, ContainerType::Inference)
@@ -131,45 +246,61 @@ impl DataContainer {
             (DataContainer::Parameter(data1), DataContainer::Parameter(data2)) => {
                 ContainerPlus::sum_data(data1, data2, ContainerType::Parameter)
             }
-            _ => {
-                DataContainer::warn_operation(self, other, "PLUS");
-                DataContainer::Empty
-            }
-        }
+            _ => Err(DataError::unsupported_containers("PLUS", self, other)),
+        };
+
+        res.map_err(|err| err.in_containers(self, other))
     }
 
     pub fn sum_assign(&mut self, other: &DataContainer) {
-        let variant = self.container_name();
-        match (self, other) {
+        if let Err(err) = self.try_sum_assign(other) {
+            DataContainer::warn_mutate(&err);
+        }
+    }
+
+    pub fn try_sum_assign(&mut self, other: &DataContainer) -> Result<(), DataError> {
+        let (left_name, right_name) = (self.container_name(), other.container_name());
+        let res = match (self, other) {
             (DataContainer::Batch(l_batch), DataContainer::Batch(r_batch)) => {
-                ContainerSumAssign::sum_batches(l_batch, r_batch);
+                ContainerSumAssign::sum_batches(l_batch, r_batch)
             }
             (DataContainer::Batch(batch), DataContainer::Inference(data)) => {
-                ContainerSumAssign::sum_batch_data(batch, data);
+                ContainerSumAssign::sum_batch_data(batch, data)
             }
             (DataContainer::Batch(batch), DataContainer::Parameter(data)) => {
-                ContainerSumAssign::sum_batch_data(batch, data);
+                ContainerSumAssign::sum_batch_data(batch, data)
             }
             (DataContainer::Inference(l_data), DataContainer::Inference(r_data)) => {
-                ContainerSumAssign::sum_data(l_data, r_data);
+                ContainerSumAssign::sum_data(l_data, r_data)
             }
             (DataContainer::Inference(l_data), DataContainer::Parameter(r_data)) => {
-                ContainerSumAssign::sum_data(l_data, r_data);
+                ContainerSumAssign::sum_data(l_data, r_data)
             }
             (DataContainer::Parameter(l_data), DataContainer::Inference(r_data)) => {
-                ContainerSumAssign::sum_data(l_data, r_data);
+                ContainerSumAssign::sum_data(l_data, r_data)
             }
             (DataContainer::Parameter(l_data), DataContainer::Parameter(r_data)) => {
-                ContainerSumAssign::sum_data(l_data, r_data);
+                ContainerSumAssign::sum_data(l_data, r_data)
             }
-            _ => {
-                DataContainer::warn_mutate(variant, other, "SUM_INPLACE");
-            }
-        }
+            (left, right) => Err(DataError::unsupported_containers(
+                "PLUS_INPLACE",
+                left,
+                right,
+            )),
+        };
+
+        res.map_err(|err| err.tagged(left_name, right_name))
     }
 
     pub fn minus(&self, other: &DataContainer) -> DataContainer {
-        match (self, other) {
+        self.try_minus(other).unwrap_or_else(|err| {
+            DataContainer::warn(&err);
+            DataContainer::Empty
+        })
+    }
+
+    pub fn try_minus(&self, other: &DataContainer) -> Result<DataContainer, DataError> {
+        let res = match (self, other) {
             (DataContainer::Batch(batch1), DataContainer::Batch(batch2)) => {
                 ContainerMinus::subtract_batches(batch1, batch2)
             }
@@ -184,6 +315,7 @@ impl DataContainer {
             }
             (DataContainer::Parameter(data), DataContainer::Batch(batch)) => {
                 ContainerMinus::subtract_data_batch(data, batch)
+                    .map_err(|err| err.in_containers(other, self))
             }
             (DataContainer::Parameter(data1), DataContainer::Inference(data2)) => {
                 ContainerMinus::subtract_data(data1, data2, ContainerType::Inference)
@@ -191,45 +323,61 @@ impl DataContainer {
             (DataContainer::Parameter(data1), DataContainer::Parameter(data2)) => {
                 ContainerMinus::subtract_data(data1, data2, ContainerType::Parameter)
             }
-            _ => {
-                DataContainer::warn_operation(self, other, "MINUS");
-                DataContainer::Empty
-            }
-        }
+            _ => Err(DataError::unsupported_containers("MINUS", self, other)),
+        };
+
+        res.map_err(|err| err.in_containers(self, other))
     }
 
     pub fn minus_assign(&mut self, other: &DataContainer) {
-        let variant = self.container_name();
-        match (self, other) {
+        if let Err(err) = self.try_minus_assign(other) {
+            DataContainer::warn_mutate(&err);
+        }
+    }
+
+    pub fn try_minus_assign(&mut self, other: &DataContainer) -> Result<(), DataError> {
+        let (left_name, right_name) = (self.container_name(), other.container_name());
+        let res = match (self, other) {
             (DataContainer::Batch(l_batch), DataContainer::Batch(r_batch)) => {
-                ContainerMinusAssign::minus_batches(l_batch, r_batch);
+                ContainerMinusAssign::minus_batches(l_batch, r_batch)
             }
             (DataContainer::Batch(batch), DataContainer::Inference(data)) => {
-                ContainerMinusAssign::minus_batch_data(batch, data);
+                ContainerMinusAssign::minus_batch_data(batch, data)
             }
             (DataContainer::Batch(batch), DataContainer::Parameter(data)) => {
-                ContainerMinusAssign::minus_batch_data(batch, data);
+                ContainerMinusAssign::minus_batch_data(batch, data)
             }
             (DataContainer::Inference(l_data), DataContainer::Inference(r_data)) => {
-                ContainerMinusAssign::minus_data(l_data, r_data);
+                ContainerMinusAssign::minus_data(l_data, r_data)
             }
             (DataContainer::Inference(l_data), DataContainer::Parameter(r_data)) => {
-                ContainerMinusAssign::minus_data(l_data, r_data);
+                ContainerMinusAssign::minus_data(l_data, r_data)
             }
             (DataContainer::Parameter(l_data), DataContainer::Inference(r_data)) => {
-                ContainerMinusAssign::minus_data(l_data, r_data);
+                ContainerMinusAssign::minus_data(l_data, r_data)
             }
             (DataContainer::Parameter(l_data), DataContainer::Parameter(r_data)) => {
-                ContainerMinusAssign::minus_data(l_data, r_data);
-            }
-            _ => {
-                DataContainer::warn_mutate(variant, other, "MINUS_INPLACE");
+                ContainerMinusAssign::minus_data(l_data, r_data)
             }
-        }
+            (left, right) => Err(DataError::unsupported_containers(
+                "MINUS_INPLACE",
+                left,
+                right,
+            )),
+        };
+
+        res.map_err(|err| err.tagged(left_name, right_name))
     }
 
     pub fn times(&self, other: &DataContainer) -> DataContainer {
-        match (self, other) {
+        self.try_times(other).unwrap_or_else(|err| {
+            DataContainer::warn(&err);
+            DataContainer::Empty
+        })
+    }
+
+    pub fn try_times(&self, other: &DataContainer) -> Result<DataContainer, DataError> {
+        let res = match (self, other) {
             (DataContainer::Batch(batch1), DataContainer::Batch(batch2)) => {
                 ContainerTimes::multiply_batches(batch1, batch2)
             }
@@ -244,6 +392,7 @@ impl DataContainer {
             }
             (DataContainer::Parameter(data), DataContainer::Batch(batch)) => {
                 ContainerTimes::multiply_batch_data(batch, data)
+                    .map_err(|err| err.in_containers(other, self))
             }
             (DataContainer::Parameter(data1), DataContainer::Inference(data2)) => {
                 ContainerTimes::multiply_data(data1, data2, ContainerType::Inference)
@@ -251,45 +400,61 @@ impl DataContainer {
             (DataContainer::Parameter(data1), DataContainer::Parameter(data2)) => {
                 ContainerTimes::multiply_data(data1, data2, ContainerType::Parameter)
             }
-            _ => {
-                DataContainer::warn_operation(self, other, "PLUS");
-                DataContainer::Empty
-            }
-        }
+            _ => Err(DataError::unsupported_containers("TIMES", self, other)),
+        };
+
+        res.map_err(|err| err.in_containers(self, other))
     }
 
     pub fn times_assign(&mut self, other: &DataContainer) {
-        let variant = self.container_name();
-        match (self, other) {
+        if let Err(err) = self.try_times_assign(other) {
+            DataContainer::warn_mutate(&err);
+        }
+    }
+
+    pub fn try_times_assign(&mut self, other: &DataContainer) -> Result<(), DataError> {
+        let (left_name, right_name) = (self.container_name(), other.container_name());
+        let res = match (self, other) {
             (DataContainer::Batch(l_batch), DataContainer::Batch(r_batch)) => {
-                ContainerTimesAssign::times_batches(l_batch, r_batch);
+                ContainerTimesAssign::times_batches(l_batch, r_batch)
             }
             (DataContainer::Batch(batch), DataContainer::Inference(data)) => {
-                ContainerTimesAssign::times_batch_data(batch, data);
+                ContainerTimesAssign::times_batch_data(batch, data)
             }
             (DataContainer::Batch(batch), DataContainer::Parameter(data)) => {
-                ContainerTimesAssign::times_batch_data(batch, data);
+                ContainerTimesAssign::times_batch_data(batch, data)
             }
             (DataContainer::Inference(l_data), DataContainer::Inference(r_data)) => {
-                ContainerTimesAssign::times_data(l_data, r_data);
+                ContainerTimesAssign::times_data(l_data, r_data)
             }
             (DataContainer::Inference(l_data), DataContainer::Parameter(r_data)) => {
-                ContainerTimesAssign::times_data(l_data, r_data);
+                ContainerTimesAssign::times_data(l_data, r_data)
             }
             (DataContainer::Parameter(l_data), DataContainer::Inference(r_data)) => {
-                ContainerTimesAssign::times_data(l_data, r_data);
+                ContainerTimesAssign::times_data(l_data, r_data)
             }
             (DataContainer::Parameter(l_data), DataContainer::Parameter(r_data)) => {
-                ContainerTimesAssign::times_data(l_data, r_data);
-            }
-            _ => {
-                DataContainer::warn_mutate(variant, other, "TIMES_INPLACE");
+                ContainerTimesAssign::times_data(l_data, r_data)
             }
-        }
+            (left, right) => Err(DataError::unsupported_containers(
+                "TIMES_INPLACE",
+                left,
+                right,
+            )),
+        };
+
+        res.map_err(|err| err.tagged(left_name, right_name))
     }
 
     pub fn matmul(&self, other: &DataContainer) -> DataContainer {
-        match (self, other) {
+        self.try_matmul(other).unwrap_or_else(|err| {
+            DataContainer::warn(&err);
+            DataContainer::Empty
+        })
+    }
+
+    pub fn try_matmul(&self, other: &DataContainer) -> Result<DataContainer, DataError> {
+        let res = match (self, other) {
             (DataContainer::Batch(batch1), DataContainer::Batch(batch2)) => {
                 ContainerMatMul::matmul_batches(batch1, batch2)
             }
@@ -311,15 +476,21 @@ impl DataContainer {
             (DataContainer::Parameter(data1), DataContainer::Parameter(data2)) => {
                 ContainerMatMul::matmul_data(data1, data2, ContainerType::Parameter)
             }
-            _ => {
-                DataContainer::warn_operation(self, other, "MINUS");
-                DataContainer::Empty
-            }
-        }
+            _ => Err(DataError::unsupported_containers("MATMUL", self, other)),
+        };
+
+        res.map_err(|err| err.in_containers(self, other))
     }
 
     pub fn transpose(&self) -> DataContainer {
-        match self {
+        self.try_transpose().unwrap_or_else(|err| {
+            DataContainer::warn(&err);
+            DataContainer::Empty
+        })
+    }
+
+    pub fn try_transpose(&self) -> Result<DataContainer, DataError> {
+        let res = match self {
             DataContainer::Batch(batch) => ContainerTranspose::transpose_batch(batch),
             DataContainer::Inference(data) => {
                 ContainerTranspose::transpose_data(data, ContainerType::Inference)
@@ -327,12 +498,21 @@ impl DataContainer {
             DataContainer::Parameter(data) => {
                 ContainerTranspose::transpose_data(data, ContainerType::Parameter)
             }
-            DataContainer::Empty => DataContainer::Empty,
-        }
+            DataContainer::Empty => Err(DataError::invalid_operand("TRANSPOSE", &Data::None)),
+        };
+
+        res.map_err(|err| err.in_containers(self, self))
     }
 
     pub fn element_sum(&self) -> DataContainer {
-        match self {
+        self.try_element_sum().unwrap_or_else(|err| {
+            DataContainer::warn(&err);
+            DataContainer::Empty
+        })
+    }
+
+    pub fn try_element_sum(&self) -> Result<DataContainer, DataError> {
+        let res = match self {
             DataContainer::Batch(batch) => ContainerElementSum::element_sum_batch(batch),
             DataContainer::Inference(data) => {
                 ContainerElementSum::element_sum_data(data, ContainerType::Inference)
@@ -340,12 +520,21 @@ impl DataContainer {
             DataContainer::Parameter(data) => {
                 ContainerElementSum::element_sum_data(data, ContainerType::Parameter)
             }
-            DataContainer::Empty => DataContainer::Empty,
-        }
+            DataContainer::Empty => Err(DataError::invalid_operand("ELEMENT_SUM", &Data::None)),
+        };
+
+        res.map_err(|err| err.in_containers(self, self))
     }
 
     pub fn sqrt(&self) -> DataContainer {
-        match self {
+        self.try_sqrt().unwrap_or_else(|err| {
+            DataContainer::warn(&err);
+            DataContainer::Empty
+        })
+    }
+
+    pub fn try_sqrt(&self) -> Result<DataContainer, DataError> {
+        let res = match self {
             DataContainer::Batch(batch) => ContainerSquareRoot::square_root_batch(batch),
             DataContainer::Inference(data) => {
                 ContainerSquareRoot::square_root_data(data, ContainerType::Inference)
@@ -353,16 +542,119 @@ impl DataContainer {
             DataContainer::Parameter(data) => {
                 ContainerSquareRoot::square_root_data(data, ContainerType::Parameter)
             }
-            DataContainer::Empty => DataContainer::Empty,
+            DataContainer::Empty => Err(DataError::invalid_operand("SQRT", &Data::None)),
+        };
+
+        res.map_err(|err| err.in_containers(self, self))
+    }
+
+    // Sums a gradient over the axes its input was broadcast along, batches keep their batch axis
+    // and reduce each example on its own
+    pub fn try_reduce_like(&self, target: &DataContainer) -> Result<DataContainer, DataError> {
+        let example: Vec<usize> = match target {
+            DataContainer::Batch(stacked) => match stacked.shape() {
+                Some(shape) => shape[1..].to_vec(),
+                None => return Ok(self.clone()),
+            },
+            DataContainer::Inference(data) | DataContainer::Parameter(data) => match data.shape() {
+                Some(shape) => shape.to_vec(),
+                None => return Ok(self.clone()),
+            },
+            DataContainer::Empty => return Ok(self.clone()),
+        };
+
+        let res = match (self, target) {
+            (DataContainer::Batch(grad), _) => {
+                ContainerBatch::reduce_examples(grad, &example).map(DataContainer::Batch)
+            }
+            (DataContainer::Inference(grad), DataContainer::Inference(data))
+            | (DataContainer::Inference(grad), DataContainer::Parameter(data)) => grad
+                .try_reduce_like(data)
+                .map(|res| DataContainer::data_with_type(res, ContainerType::Inference)),
+            (DataContainer::Parameter(grad), DataContainer::Inference(data))
+            | (DataContainer::Parameter(grad), DataContainer::Parameter(data)) => grad
+                .try_reduce_like(data)
+                .map(|res| DataContainer::data_with_type(res, ContainerType::Parameter)),
+            _ => Ok(self.clone()),
+        };
+
+        res.map_err(|err| err.in_containers(self, target))
+    }
+
+    // Joins the features of every part, which all have to be the same kind of container. Batches
+    // are joined per example and have to agree on the batch size
+    pub fn try_concat(parts: &[DataContainer]) -> Result<DataContainer, DataError> {
+        let first: &DataContainer = match parts.first() {
+            Some(first) => first,
+            None => return Err(DataError::invalid_operand("CONCAT", &Data::None)),
+        };
+
+        let mut data: Vec<Data> = Vec::new();
+        for part in parts {
+            match (first, part) {
+                (DataContainer::Batch(_), DataContainer::Batch(part_data))
+                | (DataContainer::Inference(_), DataContainer::Inference(part_data))
+                | (DataContainer::Parameter(_), DataContainer::Parameter(part_data)) => {
+                    DataContainer::check_example_axes("CONCAT", part)?;
+                    data.push(part_data.clone());
+                }
+                _ => return Err(DataError::unsupported_containers("CONCAT", first, part)),
+            }
+        }
+
+        let joined: Data =
+            Data::try_concat(&data).map_err(|err| err.in_containers(first, first))?;
+        match first {
+            DataContainer::Batch(_) => Ok(ContainerBatch::wrap(joined)),
+            _ => Ok(DataContainer::data_with_type(
+                joined,
+                first.container_type(),
+            )),
+        }
+    }
+
+    // Cuts the features into consecutive pieces, the inverse of try_concat
+    pub fn try_split(&self, widths: &[usize]) -> Result<Vec<DataContainer>, DataError> {
+        DataContainer::check_example_axes("SPLIT", self)?;
+
+        let res = match self {
+            DataContainer::Batch(data) => data
+                .try_split(widths)
+                .map(|pieces| pieces.into_iter().map(ContainerBatch::wrap).collect()),
+            DataContainer::Inference(data) | DataContainer::Parameter(data) => {
+                data.try_split(widths).map(|pieces| {
+                    pieces
+                        .into_iter()
+                        .map(|piece| DataContainer::data_with_type(piece, self.container_type()))
+                        .collect()
+                })
+            }
+            DataContainer::Empty => Err(DataError::invalid_operand("SPLIT", &Data::None)),
+        };
+
+        res.map_err(|err| err.in_containers(self, self))
+    }
+
+    // A batch of scalars only has its batch axis, which must never be joined or cut
+    fn check_example_axes(
+        operation: &'static str,
+        container: &DataContainer,
+    ) -> Result<(), DataError> {
+        if let DataContainer::Batch(data) = container {
+            if data.shape().is_none_or(|shape| shape.len() < 2) {
+                return Err(
+                    DataError::invalid_operand(operation, data).in_containers(container, container)
+                );
+            }
         }
+
+        Ok(())
     }
 
+    // Batches are handed to `func` as one stacked tensor, so it has to work along the trailing axes
     pub fn apply_function_ref(&self, func: impl Fn(&Data) -> Data) -> DataContainer {
         match self {
-            DataContainer::Batch(batch) => {
-                let mapped: Vec<Data> = batch.iter().map(|f| func(f)).collect();
-                DataContainer::Batch(mapped)
-            }
+            DataContainer::Batch(stacked) => ContainerBatch::wrap(func(stacked)),
             DataContainer::Inference(data) => DataContainer::Inference(func(data)),
             DataContainer::Parameter(data) => DataContainer::Parameter(func(data)),
             _ => DataContainer::Empty,
@@ -371,27 +663,18 @@ impl DataContainer {
 
     pub fn apply_function(self, func: impl Fn(Data) -> Data) -> DataContainer {
         match self {
-            DataContainer::Batch(batch) => {
-                let mapped: Vec<Data> = batch.iter().map(|f| func(f.to_owned())).collect();
-                DataContainer::Batch(mapped)
-            }
+            DataContainer::Batch(stacked) => ContainerBatch::wrap(func(stacked)),
             DataContainer::Inference(data) => DataContainer::Inference(func(data)),
             DataContainer::Parameter(data) => DataContainer::Parameter(func(data)),
             _ => DataContainer::Empty,
         }
     }
 
-    pub fn apply_elementwise<F>(&self, func: F) -> DataContainer
-    where
-        F: Fn(f32) -> f32 + Copy,
-    {
+    pub fn apply_elementwise(&self, func: impl ElementFn) -> DataContainer {
         match self {
-            DataContainer::Batch(datas) => {
-                let mapped: Vec<Data> = datas
-                    .iter()
-                    .map(|data| data.apply_elementwise(func))
-                    .collect();
-                DataContainer::Batch(mapped)
+            DataContainer::Batch(stacked) => {
+                let new_data = stacked.apply_elementwise(func);
+                DataContainer::Batch(new_data)
             }
             DataContainer::Inference(data) => {
                 let new_data = data.apply_elementwise(func);
@@ -405,15 +688,10 @@ impl DataContainer {
         }
     }
 
-    pub fn apply_inplace<F>(&mut self, func: F)
-    where
-        F: Fn(&mut f32) + Copy,
-    {
+    pub fn apply_inplace(&mut self, func: impl ElementFn) {
         match self {
-            DataContainer::Batch(datas) => {
-                for data in datas.iter_mut() {
-                    data.apply_inplace(func);
-                }
+            DataContainer::Batch(stacked) => {
+                stacked.apply_inplace(func);
             }
             DataContainer::Inference(data) => {
                 data.apply_inplace(func);
@@ -425,87 +703,72 @@ impl DataContainer {
         }
     }
 
+    // Reductions run over the batch axis, singular containers are treated as a batch of one
     pub fn sum_batch(&self) -> DataContainer {
         match self {
-            DataContainer::Batch(batch) => {
-                if batch.len() == 0 {
-                    return DataContainer::Empty;
-                }
-                let mut sum: Data = batch[0].clone();
-                for i in 1..batch.len() {
-                    let data: &Data = batch.get(i).unwrap();
-                    sum.sum_assign(data);
-                }
-
-                DataContainer::Parameter(sum)
-            }
-            _ => {
-                panic!("Invalid data format for operation [BATCH SUM], wasn't DataContainer::Batch")
-            }
+            DataContainer::Batch(stacked) => match stacked.precision() {
+                Precision::F32 => stacked
+                    .view_array_d::<f32>()
+                    .map(|view| Data::from_array_d(view.sum_axis(Axis(0)))),
+                Precision::F64 => stacked
+                    .view_array_d::<f64>()
+                    .map(|view| Data::from_array_d(view.sum_axis(Axis(0)))),
+            }
+            .map_or(DataContainer::Empty, DataContainer::Parameter),
+            DataContainer::Inference(data) => DataContainer::Parameter(data.clone()),
+            DataContainer::Parameter(data) => DataContainer::Parameter(data.clone()),
+            DataContainer::Empty => DataContainer::Empty,
         }
     }
 
     pub fn average_batch(&self) -> DataContainer {
         match self {
-            DataContainer::Batch(batch) => {
-                if batch.len() == 0 {
-                    return DataContainer::Empty;
-                }
-                let len_scale: f32 = 1.0 / batch.len() as f32;
-                let mut average: Data = batch[0].clone();
-                for i in 1..batch.len() {
-                    let data: &Data = batch.get(i).unwrap();
-                    average.sum_assign(data);
-                }
-                average.times_assign(&Data::ScalarF32(len_scale));
-                DataContainer::Parameter(average)
-            }
-            _ => panic!(
-                "Invalid data format for operation [BATCH MEAN], wasn't DataContainer::Batch"
-            ),
+            DataContainer::Batch(stacked) => match stacked.precision() {
+                Precision::F32 => stacked
+                    .view_array_d::<f32>()
+                    .and_then(|view| view.mean_axis(Axis(0)))
+                    .map(Data::from_array_d),
+                Precision::F64 => stacked
+                    .view_array_d::<f64>()
+                    .and_then(|view| view.mean_axis(Axis(0)))
+                    .map(Data::from_array_d),
+            }
+            .map_or(DataContainer::Empty, DataContainer::Parameter),
+            DataContainer::Inference(data) => DataContainer::Parameter(data.clone()),
+            DataContainer::Parameter(data) => DataContainer::Parameter(data.clone()),
+            DataContainer::Empty => DataContainer::Empty,
         }
     }
 
     pub fn variance_batch(&self) -> DataContainer {
         match self {
-            DataContainer::Batch(batch) => {
-                if batch.len() == 0 {
-                    return DataContainer::Empty;
-                }
-                let mean = self.average_batch();
-
-                if let DataContainer::Parameter(mean_data) = &mean {
-                    let len_scale: f32 = 1.0 / batch.len() as f32;
-                    let mut sum: Data = batch[0].clone();
-                    sum.minus_assign(&mean_data);
-                    sum.apply_inplace(|f| *f = f32::powi(*f, 2));
-                    for i in 1..batch.len() {
-                        let data: &Data = batch.get(i).unwrap();
-                        let mut diff: Data = data.minus(&mean_data);
-
-                        diff.apply_inplace(|f| *f = f32::powi(*f, 2));
-                        sum.sum_assign(&diff);
-                    }
-                    sum.times_assign(&Data::ScalarF32(len_scale));
-                    return DataContainer::Parameter(sum);
-                }
-
-                DataContainer::Empty
+            DataContainer::Batch(stacked) => match stacked.precision() {
+                Precision::F32 => stacked
+                    .view_array_d::<f32>()
+                    .filter(|view| !view.is_empty())
+                    .map(|view| Data::from_array_d(view.var_axis(Axis(0), 0.0))),
+                Precision::F64 => stacked
+                    .view_array_d::<f64>()
+                    .filter(|view| !view.is_empty())
+                    .map(|view| Data::from_array_d(view.var_axis(Axis(0), 0.0))),
+            }
+            .map_or(DataContainer::Empty, DataContainer::Parameter),
+            DataContainer::Inference(data) => {
+                DataContainer::Parameter(data.apply_elementwise(Fill(0.0)))
             }
-            _ => {
-                panic!("Invalid data format for operation [BATCH VAR], wasn't DataContainer::Batch")
+            DataContainer::Parameter(data) => {
+                DataContainer::Parameter(data.apply_elementwise(Fill(0.0)))
             }
+            DataContainer::Empty => DataContainer::Empty,
         }
     }
 
     pub fn dim(&self) -> (usize, &[usize]) {
         match self {
-            DataContainer::Batch(batch) => {
-                if batch.len() == 0 {
-                    return (0, &[]);
-                }
-                return (batch.len(), batch[0].dim());
-            }
+            DataContainer::Batch(stacked) => match stacked.dim().split_first() {
+                Some((batch_size, example_dim)) => (*batch_size, example_dim),
+                None => (0, &[]),
+            },
             DataContainer::Inference(data) => (1, data.dim()),
             DataContainer::Parameter(data) => (1, data.dim()),
             DataContainer::Empty => (0, &[]),
@@ -514,12 +777,14 @@ impl DataContainer {
 
     pub fn flatten_to_vec(&self) -> FlattenedData {
         match self {
-            DataContainer::Batch(batch) => {
-                let mut flattened: Vec<Vec<f32>> = Vec::new();
-
-                for data in batch {
-                    flattened.push(data.flatten_to_vec());
-                }
+            DataContainer::Batch(stacked) => {
+                let flattened: Vec<Vec<f32>> = match stacked.cast_array_d::<f32>() {
+                    Some(view) => view
+                        .outer_iter()
+                        .map(|example| example.iter().copied().collect())
+                        .collect(),
+                    None => Vec::new(),
+                };
 
                 FlattenedData::Batch(flattened)
             }
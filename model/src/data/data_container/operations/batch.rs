@@ -0,0 +1,222 @@
+// builtin
+
+// external
+use ndarray::{ArrayViewD, Axis, IxDyn};
+
+// internal
+use crate::data::{
+    data_container::DataContainer,
+    error::DataError,
+    operations::broadcast::{DataBroadcast, ElementwiseAssignOp, ElementwiseOp},
+    precision::{Element, Precision},
+    Data,
+};
+
+pub struct ContainerBatch;
+
+impl ContainerBatch {
+    pub fn wrap(stacked: Data) -> DataContainer {
+        match stacked {
+            Data::None => DataContainer::Empty,
+            _ => DataContainer::Batch(stacked),
+        }
+    }
+
+    // Inserts unit axes right after the batch axis so lower rank examples line up with the other operand
+    pub fn pad_examples<T: Element>(view: ArrayViewD<T>, rank: usize) -> ArrayViewD<T> {
+        let mut view = view;
+        while view.ndim() - 1 < rank {
+            view = view.insert_axis(Axis(1));
+        }
+        view
+    }
+
+    // Singular data is broadcast against each example, never against the batch axis
+    pub fn broadcast<O: ElementwiseOp>(
+        batch: &Data,
+        data: &Data,
+    ) -> Result<DataContainer, DataError> {
+        let res = match Precision::common(batch.precision(), data.precision()) {
+            Precision::F32 => ContainerBatch::broadcast_as::<f32, O>(batch, data, false),
+            Precision::F64 => ContainerBatch::broadcast_as::<f64, O>(batch, data, false),
+        };
+
+        match res {
+            Some(res) => Ok(DataContainer::Batch(res)),
+            None => Err(DataError::shape_mismatch(O::OPERATION, batch, data)),
+        }
+    }
+
+    // Same as `broadcast` with the singular data as the left operand
+    pub fn broadcast_reversed<O: ElementwiseOp>(
+        data: &Data,
+        batch: &Data,
+    ) -> Result<DataContainer, DataError> {
+        let res = match Precision::common(batch.precision(), data.precision()) {
+            Precision::F32 => ContainerBatch::broadcast_as::<f32, O>(batch, data, true),
+            Precision::F64 => ContainerBatch::broadcast_as::<f64, O>(batch, data, true),
+        };
+
+        match res {
+            Some(res) => Ok(DataContainer::Batch(res)),
+            None => Err(DataError::shape_mismatch(O::OPERATION, data, batch)),
+        }
+    }
+
+    fn broadcast_as<T: Element, O: ElementwiseOp>(
+        batch: &Data,
+        data: &Data,
+        reversed: bool,
+    ) -> Option<Data> {
+        let (batch_view, data_view) = (batch.cast_array_d::<T>()?, data.cast_array_d::<T>()?);
+
+        let batch_view = ContainerBatch::pad_examples(batch_view.view(), data_view.ndim());
+        let res = if reversed {
+            DataBroadcast::broadcast_views(&data_view.view(), &batch_view, O::apply)
+        } else {
+            DataBroadcast::broadcast_views(&batch_view, &data_view.view(), O::apply)
+        };
+
+        res.map(Data::from_array_d)
+    }
+
+    pub fn broadcast_batches<O: ElementwiseOp>(
+        batch1: &Data,
+        batch2: &Data,
+    ) -> Result<DataContainer, DataError> {
+        if batch1.dim().first() != batch2.dim().first() {
+            return Err(DataError::batch_mismatch(O::OPERATION, batch1, batch2));
+        }
+
+        let res = match Precision::common(batch1.precision(), batch2.precision()) {
+            Precision::F32 => ContainerBatch::broadcast_batches_as::<f32, O>(batch1, batch2),
+            Precision::F64 => ContainerBatch::broadcast_batches_as::<f64, O>(batch1, batch2),
+        };
+
+        match res {
+            Some(res) => Ok(DataContainer::Batch(res)),
+            None => Err(DataError::shape_mismatch(O::OPERATION, batch1, batch2)),
+        }
+    }
+
+    fn broadcast_batches_as<T: Element, O: ElementwiseOp>(
+        batch1: &Data,
+        batch2: &Data,
+    ) -> Option<Data> {
+        let (view1, view2) = (batch1.cast_array_d::<T>()?, batch2.cast_array_d::<T>()?);
+
+        let (rank1, rank2) = (view1.ndim() - 1, view2.ndim() - 1);
+        let view1 = ContainerBatch::pad_examples(view1.view(), rank2);
+        let view2 = ContainerBatch::pad_examples(view2.view(), rank1);
+
+        DataBroadcast::broadcast_views(&view1, &view2, O::apply).map(Data::from_array_d)
+    }
+
+    // In place updates keep the batch's shape and element type, so only the right operand is
+    // broadcast and converted
+    pub fn broadcast_assign<O: ElementwiseAssignOp>(
+        batch: &mut Data,
+        data: &Data,
+        batched: bool,
+    ) -> Result<(), DataError> {
+        if batched && batch.dim().first() != data.dim().first() {
+            return Err(DataError::batch_mismatch(O::OPERATION, batch, data));
+        }
+
+        let updated = match batch.precision() {
+            Precision::F32 => ContainerBatch::broadcast_assign_as::<f32, O>(batch, data, batched),
+            Precision::F64 => ContainerBatch::broadcast_assign_as::<f64, O>(batch, data, batched),
+        };
+
+        match updated {
+            Some(()) => Ok(()),
+            None => Err(DataError::shape_mismatch(O::OPERATION, batch, data)),
+        }
+    }
+
+    fn broadcast_assign_as<T: Element, O: ElementwiseAssignOp>(
+        batch: &mut Data,
+        data: &Data,
+        batched: bool,
+    ) -> Option<()> {
+        let shape: Vec<usize> = batch.shape()?.to_vec();
+        let data_view = data.cast_array_d::<T>()?;
+        if data_view.ndim() >= shape.len() + batched as usize {
+            return None;
+        }
+
+        let data_view = if batched {
+            ContainerBatch::pad_examples(data_view.view(), shape.len() - 1)
+        } else {
+            data_view.view()
+        };
+        let data_view = data_view.broadcast(IxDyn(&shape))?;
+
+        let mut batch_view = batch.view_array_d_mut::<T>()?;
+        O::apply(&mut batch_view, &data_view);
+
+        Some(())
+    }
+
+    // Reduces each example of a broadcast gradient back to the shape of one example of the target
+    pub fn reduce_examples(batch: &Data, example: &[usize]) -> Result<Data, DataError> {
+        if batch.shape().is_none() {
+            return Err(DataError::invalid_operand("REDUCE", batch));
+        }
+
+        match DataBroadcast::reduce_data(batch, example, 1) {
+            Some(res) => Ok(res),
+            None => Err(DataError::InvalidDimensions {
+                operation: "REDUCE",
+                dim: example.to_vec(),
+                len: batch.dim().iter().product(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{arr1, arr2};
+
+    use crate::data::{data_container::DataContainer, error::DataError, Data};
+
+    #[test]
+    fn batch_broadcast_test() {
+        let batch = DataContainer::batch(vec![
+            Data::MatrixF32(arr2(&[[1.0, 2.0], [3.0, 4.0]])),
+            Data::MatrixF32(arr2(&[[5.0, 6.0], [7.0, 8.0]])),
+        ]);
+        let bias = DataContainer::Parameter(Data::VectorF32(arr1(&[10.0, 20.0])));
+
+        let res = batch.try_plus(&bias).unwrap();
+        assert_eq!(res.dim(), (2, &[2, 2][..]));
+        assert_eq!(
+            res.batch_to_vec()[1].flatten_to_vec(),
+            vec![15.0, 26.0, 17.0, 28.0]
+        );
+
+        let columns = DataContainer::batch(vec![
+            Data::MatrixF32(arr2(&[[1.0], [2.0]])),
+            Data::MatrixF32(arr2(&[[3.0], [4.0]])),
+        ]);
+        let res = batch.try_times(&columns).unwrap();
+        assert_eq!(
+            res.batch_to_vec()[1].flatten_to_vec(),
+            vec![15.0, 18.0, 28.0, 32.0]
+        );
+
+        let reduced = res.try_reduce_like(&bias).unwrap();
+        assert_eq!(reduced.dim(), (2, &[2][..]));
+        let reduced = res.try_reduce_like(&columns).unwrap();
+        assert_eq!(reduced.dim(), (2, &[2, 1][..]));
+
+        let mut accumulated = batch.clone();
+        assert!(accumulated.try_sum_assign(&columns).is_ok());
+        assert!(columns.clone().try_sum_assign(&batch).is_err());
+
+        let short = DataContainer::batch(vec![Data::MatrixF32(arr2(&[[1.0], [2.0]]))]);
+        let err = batch.try_plus(&short).unwrap_err();
+        assert!(matches!(err, DataError::BatchMismatch { .. }));
+    }
+}
@@ -1,28 +1,51 @@
 // builtin
 
 // external
+use ndarray::{ArrayViewD, Axis};
 
 // internal
 use crate::data::{
-    data_container::{ContainerType, DataContainer},
+    data_container::{operations::batch::ContainerBatch, ContainerType, DataContainer},
+    error::DataError,
+    precision::{Element, Precision},
     Data,
 };
 
 pub struct ContainerElementSum;
 
 impl ContainerElementSum {
-    pub fn element_sum_batch(batch: &Vec<Data>) -> DataContainer {
-        let mut new_data: Vec<Data> = Vec::new();
-        for data in batch.iter() {
-            new_data.push(data.element_sum());
-        }
+    fn sum_examples<T: Element>(view: ArrayViewD<T>) -> Data {
+        let batch_size = view.shape()[0];
+        let example_size = view.len() / batch_size.max(1);
+        let flattened = view
+            .to_shape((batch_size, example_size))
+            .expect("Couldn't flatten batch examples");
+
+        Data::from_array_d(flattened.sum_axis(Axis(1)).into_dyn())
+    }
 
-        DataContainer::Batch(new_data)
+    pub fn element_sum_batch(batch: &Data) -> Result<DataContainer, DataError> {
+        let res = match batch.precision() {
+            Precision::F32 => batch
+                .view_array_d::<f32>()
+                .map(ContainerElementSum::sum_examples),
+            Precision::F64 => batch
+                .view_array_d::<f64>()
+                .map(ContainerElementSum::sum_examples),
+        };
+
+        match res {
+            Some(res) => Ok(ContainerBatch::wrap(res)),
+            None => Err(DataError::invalid_operand("ELEMENT_SUM", batch)),
+        }
     }
 
-    pub fn element_sum_data(data: &Data, result_type: ContainerType) -> DataContainer {
-        let new_data: Data = data.element_sum();
+    pub fn element_sum_data(
+        data: &Data,
+        result_type: ContainerType,
+    ) -> Result<DataContainer, DataError> {
+        let new_data: Data = data.try_element_sum()?;
 
-        DataContainer::data_with_type(new_data, result_type)
+        Ok(DataContainer::data_with_type(new_data, result_type))
     }
 }
@@ -1,59 +1,183 @@
 // builtin
 
 // external
+use ndarray::{ArrayD, ArrayViewD, Axis, Ix2};
 
 // internal
 use crate::data::{
-    data_container::{ContainerType, DataContainer},
+    data_container::{operations::batch::ContainerBatch, ContainerType, DataContainer},
+    error::DataError,
+    operations::matmul::DataMatMul,
+    precision::{Element, Precision},
     Data,
 };
 
 pub struct ContainerMatMul;
 
 impl ContainerMatMul {
-    fn warn_batch(batch1: &Vec<Data>, batch2: &Vec<Data>) {
-        println!(
-            "DataContainer::Empty returned on batch size mismatch for operation [MINUS]: {} and {}",
-            batch1.len(),
-            batch2.len()
-        );
+    // Pads a batched operand right after the batch axis so its examples line up with the other operand
+    fn pad_examples<T: Element>(view: ArrayViewD<T>, batched: bool, rank: usize) -> ArrayViewD<T> {
+        let mut view = view;
+        while batched && view.ndim() - 1 < rank {
+            view = view.insert_axis(Axis(1));
+        }
+        view
     }
 
-    pub fn matmul_batches(batch1: &Vec<Data>, batch2: &Vec<Data>) -> DataContainer {
-        if batch1.len() != batch2.len() {
-            ContainerMatMul::warn_batch(batch1, batch2);
-            return DataContainer::Empty;
+    // Same semantics as Data::matmul applied per example, batched operands carry a leading batch axis
+    fn matmul_examples<T: Element>(
+        lhs: ArrayViewD<T>,
+        lhs_batched: bool,
+        rhs: ArrayViewD<T>,
+        rhs_batched: bool,
+    ) -> Option<ArrayD<T>> {
+        let lhs_rank = lhs.ndim() - lhs_batched as usize;
+        let rhs_rank = rhs.ndim() - rhs_batched as usize;
+
+        if lhs_rank == 0 || rhs_rank == 0 {
+            return None;
         }
 
-        let mut new_data: Vec<Data> = Vec::new();
-        for (first, second) in batch1.iter().zip(batch2.iter()) {
-            new_data.push(first.matmul(second));
+        // Batches of vectors against a single matrix collapse into one GEMM
+        if lhs_batched && !rhs_batched && lhs_rank == 1 && rhs_rank == 2 {
+            let lhs_matrix = lhs.view().into_dimensionality::<Ix2>().ok()?;
+            let rhs_matrix = rhs.view().into_dimensionality::<Ix2>().ok()?;
+            if lhs_matrix.shape()[1] != rhs_matrix.shape()[0] {
+                return None;
+            }
+            return Some(lhs_matrix.dot(&rhs_matrix).into_dyn());
+        }
+        if !lhs_batched && rhs_batched && lhs_rank == 2 && rhs_rank == 1 {
+            let lhs_matrix = lhs.view().into_dimensionality::<Ix2>().ok()?;
+            let rhs_matrix = rhs.view().into_dimensionality::<Ix2>().ok()?;
+            if lhs_matrix.shape()[1] != rhs_matrix.shape()[1] {
+                return None;
+            }
+            return Some(rhs_matrix.dot(&lhs_matrix.t()).into_dyn());
         }
 
-        DataContainer::Batch(new_data)
-    }
+        // Vector pairs are outer products, which broadcasting covers without a matmul
+        if lhs_rank == 1 && rhs_rank == 1 {
+            let (lhs_end, rhs_end) = (lhs.ndim(), rhs.ndim());
+            let lhs_col = lhs.insert_axis(Axis(lhs_end));
+            let rhs_row = rhs.insert_axis(Axis(rhs_end - 1));
+            return Some(&lhs_col * &rhs_row);
+        }
+
+        let (lhs_end, rhs_end) = (lhs.ndim(), rhs.ndim());
+        let lhs_lifted = if lhs_rank == 1 {
+            lhs.insert_axis(Axis(lhs_end - 1))
+        } else {
+            lhs
+        };
+        let rhs_lifted = if rhs_rank == 1 {
+            rhs.insert_axis(Axis(rhs_end))
+        } else {
+            rhs
+        };
+
+        let lhs_example_rank = lhs_lifted.ndim() - lhs_batched as usize;
+        let rhs_example_rank = rhs_lifted.ndim() - rhs_batched as usize;
+        let lhs_padded = ContainerMatMul::pad_examples(lhs_lifted, lhs_batched, rhs_example_rank);
+        let rhs_padded = ContainerMatMul::pad_examples(rhs_lifted, rhs_batched, lhs_example_rank);
 
-    pub fn matmul_batch_data(batch: &Vec<Data>, data: &Data) -> DataContainer {
-        let mut new_data: Vec<Data> = Vec::new();
-        for batch_data in batch.iter() {
-            new_data.push(batch_data.matmul(data));
+        let mut res = DataMatMul::batched_matmul(&lhs_padded, &rhs_padded).ok()?;
+
+        let rank = res.ndim();
+        if lhs_rank == 1 {
+            res = res.remove_axis(Axis(rank - 2));
+        } else if rhs_rank == 1 {
+            res = res.remove_axis(Axis(rank - 1));
         }
+        Some(res)
+    }
 
-        DataContainer::Batch(new_data)
+    fn matmul_as<T: Element>(
+        lhs: &Data,
+        lhs_batched: bool,
+        rhs: &Data,
+        rhs_batched: bool,
+    ) -> Option<Data> {
+        let (lhs_view, rhs_view) = (lhs.cast_array_d::<T>()?, rhs.cast_array_d::<T>()?);
+
+        ContainerMatMul::matmul_examples(lhs_view.view(), lhs_batched, rhs_view.view(), rhs_batched)
+            .map(Data::from_array_d)
+    }
+
+    fn matmul_views(
+        lhs: &Data,
+        lhs_batched: bool,
+        rhs: &Data,
+        rhs_batched: bool,
+    ) -> Result<DataContainer, DataError> {
+        let res = match Precision::common(lhs.precision(), rhs.precision()) {
+            Precision::F32 => ContainerMatMul::matmul_as::<f32>(lhs, lhs_batched, rhs, rhs_batched),
+            Precision::F64 => ContainerMatMul::matmul_as::<f64>(lhs, lhs_batched, rhs, rhs_batched),
+        };
+
+        match res {
+            Some(res) => Ok(ContainerBatch::wrap(res)),
+            None => Err(DataError::shape_mismatch("MATMUL", lhs, rhs)),
+        }
     }
 
-    pub fn matmul_data_batch(data: &Data, batch: &Vec<Data>) -> DataContainer {
-        let mut new_data: Vec<Data> = Vec::new();
-        for batch_data in batch.iter() {
-            new_data.push(data.matmul(batch_data));
+    pub fn matmul_batches(batch1: &Data, batch2: &Data) -> Result<DataContainer, DataError> {
+        if batch1.dim().first() != batch2.dim().first() {
+            return Err(DataError::batch_mismatch("MATMUL", batch1, batch2));
         }
 
-        DataContainer::Batch(new_data)
+        ContainerMatMul::matmul_views(batch1, true, batch2, true)
     }
 
-    pub fn matmul_data(data1: &Data, data2: &Data, result_type: ContainerType) -> DataContainer {
-        let new_data: Data = data1.matmul(data2);
+    pub fn matmul_batch_data(batch: &Data, data: &Data) -> Result<DataContainer, DataError> {
+        ContainerMatMul::matmul_views(batch, true, data, false)
+    }
+
+    pub fn matmul_data_batch(data: &Data, batch: &Data) -> Result<DataContainer, DataError> {
+        ContainerMatMul::matmul_views(data, false, batch, true)
+    }
+
+    pub fn matmul_data(
+        data1: &Data,
+        data2: &Data,
+        result_type: ContainerType,
+    ) -> Result<DataContainer, DataError> {
+        let new_data: Data = data1.try_matmul(data2)?;
+
+        Ok(DataContainer::data_with_type(new_data, result_type))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{arr1, arr2};
+
+    use crate::data::{data_container::DataContainer, Data};
+
+    #[test]
+    fn batch_matmul_test() {
+        let weights = Data::MatrixF32(arr2(&[[1.0, 2.0, 0.0], [0.0, 1.0, -1.0]]));
+        let examples = vec![
+            Data::VectorF32(arr1(&[1.0, 0.0, 2.0])),
+            Data::VectorF32(arr1(&[0.5, 1.0, 1.0])),
+        ];
+
+        let batch = DataContainer::batch(examples.clone());
+        let forward = DataContainer::Parameter(weights.clone()).matmul(&batch);
+        let outer = forward.matmul(&batch.transpose());
+
+        for (i, example) in examples.iter().enumerate() {
+            let expected = weights.matmul(example);
+            let res = &forward.batch_to_vec()[i];
+            assert_eq!(res.flatten_to_vec(), expected.flatten_to_vec());
+
+            let expected_outer = expected.matmul(example);
+            let res_outer = &outer.batch_to_vec()[i];
+            assert_eq!(res_outer.dim(), &[2, 3]);
+            assert_eq!(res_outer.flatten_to_vec(), expected_outer.flatten_to_vec());
+        }
 
-        DataContainer::data_with_type(new_data, result_type)
+        let average = outer.average_batch();
+        assert_eq!(average.dim(), (1, &[2usize, 3][..]));
     }
 }
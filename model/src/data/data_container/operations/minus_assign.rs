@@ -3,37 +3,23 @@
 // external
 
 // internal
-use crate::data::Data;
+use crate::data::{
+    data_container::operations::batch::ContainerBatch, error::DataError,
+    operations::minus_assign::DataMinusAssign, Data,
+};
 
 pub struct ContainerMinusAssign;
 
 impl ContainerMinusAssign {
-    fn warn_batch(batch1: &Vec<Data>, batch2: &Vec<Data>) {
-        println!(
-            "Mutation failed on batch size mismatch for operation [PLUS_INPLACE]: {} and {}",
-            batch1.len(),
-            batch2.len()
-        );
+    pub fn minus_batches(batch1: &mut Data, batch2: &Data) -> Result<(), DataError> {
+        ContainerBatch::broadcast_assign::<DataMinusAssign>(batch1, batch2, true)
     }
 
-    pub fn minus_batches(batch1: &mut Vec<Data>, batch2: &Vec<Data>) {
-        if batch1.len() != batch2.len() {
-            Self::warn_batch(batch1, batch2);
-            return;
-        }
-
-        for (first, second) in batch1.into_iter().zip(batch2.iter()) {
-            first.minus_assign(second);
-        }
-    }
-
-    pub fn minus_batch_data(batch: &mut Vec<Data>, data: &Data) {
-        for batch_data in batch.into_iter() {
-            batch_data.minus_assign(data);
-        }
+    pub fn minus_batch_data(batch: &mut Data, data: &Data) -> Result<(), DataError> {
+        ContainerBatch::broadcast_assign::<DataMinusAssign>(batch, data, false)
     }
 
-    pub fn minus_data(l_data: &mut Data, r_data: &Data) {
-        l_data.minus_assign(r_data);
+    pub fn minus_data(l_data: &mut Data, r_data: &Data) -> Result<(), DataError> {
+        l_data.try_minus_assign(r_data)
     }
 }
@@ -3,49 +3,31 @@
 // external
 
 // internal
-
 use crate::data::{
-    data_container::{ContainerType, DataContainer},
+    data_container::{operations::batch::ContainerBatch, ContainerType, DataContainer},
+    error::DataError,
+    operations::plus::DataPlus,
     Data,
 };
 
 pub struct ContainerPlus;
 
 impl ContainerPlus {
-    fn warn_batch(batch1: &Vec<Data>, batch2: &Vec<Data>) {
-        println!(
-            "DataContainer::Empty returned on batch size mismatch for operation [MINUS]: {} and {}",
-            batch1.len(),
-            batch2.len()
-        );
-    }
-
-    pub fn sum_batches(batch1: &Vec<Data>, batch2: &Vec<Data>) -> DataContainer {
-        if batch1.len() != batch2.len() {
-            ContainerPlus::warn_batch(batch1, batch2);
-            return DataContainer::Empty;
-        }
-
-        let mut new_data: Vec<Data> = Vec::new();
-        for (first, second) in batch1.iter().zip(batch2.iter()) {
-            new_data.push(first.plus(second));
-        }
-
-        DataContainer::Batch(new_data)
+    pub fn sum_batches(batch1: &Data, batch2: &Data) -> Result<DataContainer, DataError> {
+        ContainerBatch::broadcast_batches::<DataPlus>(batch1, batch2)
     }
 
-    pub fn sum_batch_data(batch: &Vec<Data>, data: &Data) -> DataContainer {
-        let mut new_data: Vec<Data> = Vec::new();
-        for batch_data in batch.iter() {
-            new_data.push(batch_data.plus(data));
-        }
-
-        DataContainer::Batch(new_data)
+    pub fn sum_batch_data(batch: &Data, data: &Data) -> Result<DataContainer, DataError> {
+        ContainerBatch::broadcast::<DataPlus>(batch, data)
     }
 
-    pub fn sum_data(data1: &Data, data2: &Data, result_type: ContainerType) -> DataContainer {
-        let new_data: Data = data1.plus(data2);
+    pub fn sum_data(
+        data1: &Data,
+        data2: &Data,
+        result_type: ContainerType,
+    ) -> Result<DataContainer, DataError> {
+        let new_data: Data = data1.try_plus(data2)?;
 
-        DataContainer::data_with_type(new_data, result_type)
+        Ok(DataContainer::data_with_type(new_data, result_type))
     }
 }
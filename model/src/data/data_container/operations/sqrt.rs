@@ -4,25 +4,24 @@
 
 // internal
 use crate::data::{
-    data_container::{ContainerType, DataContainer},
+    data_container::{operations::batch::ContainerBatch, ContainerType, DataContainer},
+    error::DataError,
     Data,
 };
 
 pub struct ContainerSquareRoot;
 
 impl ContainerSquareRoot {
-    pub fn square_root_batch(batch: &Vec<Data>) -> DataContainer {
-        let mut new_data: Vec<Data> = Vec::new();
-        for data in batch.iter() {
-            new_data.push(data.sqrt());
-        }
-
-        DataContainer::Batch(new_data)
+    pub fn square_root_batch(batch: &Data) -> Result<DataContainer, DataError> {
+        Ok(ContainerBatch::wrap(batch.try_sqrt()?))
     }
 
-    pub fn square_root_data(data: &Data, result_type: ContainerType) -> DataContainer {
-        let new_data: Data = data.sqrt();
+    pub fn square_root_data(
+        data: &Data,
+        result_type: ContainerType,
+    ) -> Result<DataContainer, DataError> {
+        let new_data: Data = data.try_sqrt()?;
 
-        DataContainer::data_with_type(new_data, result_type)
+        Ok(DataContainer::data_with_type(new_data, result_type))
     }
 }
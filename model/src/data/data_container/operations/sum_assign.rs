@@ -3,37 +3,23 @@
 // external
 
 // internal
-use crate::data::Data;
+use crate::data::{
+    data_container::operations::batch::ContainerBatch, error::DataError,
+    operations::sum_assign::DataSumAssign, Data,
+};
 
 pub struct ContainerSumAssign;
 
 impl ContainerSumAssign {
-    fn warn_batch(batch1: &Vec<Data>, batch2: &Vec<Data>) {
-        println!(
-            "Mutation failed on batch size mismatch for operation [PLUS_INPLACE]: {} and {}",
-            batch1.len(),
-            batch2.len()
-        );
+    pub fn sum_batches(batch1: &mut Data, batch2: &Data) -> Result<(), DataError> {
+        ContainerBatch::broadcast_assign::<DataSumAssign>(batch1, batch2, true)
     }
 
-    pub fn sum_batches(batch1: &mut Vec<Data>, batch2: &Vec<Data>) {
-        if batch1.len() != batch2.len() {
-            Self::warn_batch(batch1, batch2);
-            return;
-        }
-
-        for (first, second) in batch1.into_iter().zip(batch2.iter()) {
-            first.sum_assign(second);
-        }
-    }
-
-    pub fn sum_batch_data(batch: &mut Vec<Data>, data: &Data) {
-        for batch_data in batch.into_iter() {
-            batch_data.sum_assign(data);
-        }
+    pub fn sum_batch_data(batch: &mut Data, data: &Data) -> Result<(), DataError> {
+        ContainerBatch::broadcast_assign::<DataSumAssign>(batch, data, false)
     }
 
-    pub fn sum_data(l_data: &mut Data, r_data: &Data) {
-        l_data.sum_assign(r_data);
+    pub fn sum_data(l_data: &mut Data, r_data: &Data) -> Result<(), DataError> {
+        l_data.try_sum_assign(r_data)
     }
 }
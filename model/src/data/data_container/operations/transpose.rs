@@ -4,25 +4,29 @@
 
 // internal
 use crate::data::{
-    data_container::{ContainerType, DataContainer},
+    data_container::{operations::batch::ContainerBatch, ContainerType, DataContainer},
+    error::DataError,
     Data,
 };
 
 pub struct ContainerTranspose;
 
 impl ContainerTranspose {
-    pub fn transpose_batch(batch: &Vec<Data>) -> DataContainer {
-        let mut new_data: Vec<Data> = Vec::new();
-        for data in batch.iter() {
-            new_data.push(data.transpose());
+    // Transposes each example, vectors are left as they are
+    pub fn transpose_batch(batch: &Data) -> Result<DataContainer, DataError> {
+        match batch.shape() {
+            Some(shape) if shape.len() > 2 => Ok(ContainerBatch::wrap(batch.try_transpose()?)),
+            Some(_) => Ok(ContainerBatch::wrap(batch.clone())),
+            None => Err(DataError::invalid_operand("TRANSPOSE", batch)),
         }
-
-        DataContainer::Batch(new_data)
     }
 
-    pub fn transpose_data(data: &Data, result_type: ContainerType) -> DataContainer {
-        let new_data: Data = data.transpose();
+    pub fn transpose_data(
+        data: &Data,
+        result_type: ContainerType,
+    ) -> Result<DataContainer, DataError> {
+        let new_data: Data = data.try_transpose()?;
 
-        DataContainer::data_with_type(new_data, result_type)
+        Ok(DataContainer::data_with_type(new_data, result_type))
     }
 }
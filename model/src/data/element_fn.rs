@@ -0,0 +1,136 @@
+// builtin
+
+// external
+
+// internal
+use crate::data::precision::Element;
+
+// Unary functions written once for every element type, so data is mapped in its own precision
+// instead of round tripping each element through f64
+pub trait ElementFn {
+    fn call<T: Element>(&self, value: T) -> T;
+}
+
+// f * factor
+pub struct Scale(pub f64);
+
+impl ElementFn for Scale {
+    fn call<T: Element>(&self, value: T) -> T {
+        value * T::from_f64(self.0)
+    }
+}
+
+// Replaces every element with the value
+pub struct Fill(pub f64);
+
+impl ElementFn for Fill {
+    fn call<T: Element>(&self, _value: T) -> T {
+        T::from_f64(self.0)
+    }
+}
+
+// factor * f^2
+pub struct ScaledSquare(pub f64);
+
+impl ElementFn for ScaledSquare {
+    fn call<T: Element>(&self, value: T) -> T {
+        T::from_f64(self.0) * value * value
+    }
+}
+
+// sqrt(f + offset)
+pub struct Sqrt {
+    pub offset: f64,
+}
+
+impl ElementFn for Sqrt {
+    fn call<T: Element>(&self, value: T) -> T {
+        (value + T::from_f64(self.offset)).sqrt()
+    }
+}
+
+// numerator / sqrt(f + offset)
+pub struct InverseSqrt {
+    pub numerator: f64,
+    pub offset: f64,
+}
+
+impl ElementFn for InverseSqrt {
+    fn call<T: Element>(&self, value: T) -> T {
+        T::from_f64(self.numerator) / (value + T::from_f64(self.offset)).sqrt()
+    }
+}
+
+// 1 / (f + offset)
+pub struct Inverse {
+    pub offset: f64,
+}
+
+impl ElementFn for Inverse {
+    fn call<T: Element>(&self, value: T) -> T {
+        T::one() / (value + T::from_f64(self.offset))
+    }
+}
+
+pub struct Abs;
+
+impl ElementFn for Abs {
+    fn call<T: Element>(&self, value: T) -> T {
+        value.abs()
+    }
+}
+
+// -1, 0 or 1, unlike `signum` zero keeps a zero derivative
+pub struct Sign;
+
+impl ElementFn for Sign {
+    fn call<T: Element>(&self, value: T) -> T {
+        if value > T::zero() {
+            T::one()
+        } else if value < T::zero() {
+            -T::one()
+        } else {
+            T::zero()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::arr1;
+
+    use crate::data::{
+        element_fn::{InverseSqrt, Scale},
+        precision::Precision,
+        Data,
+    };
+
+    #[test]
+    fn element_fn_keeps_precision() {
+        let vector: Data = Data::VectorF64(arr1(&[0.1, 4.0]));
+
+        let scaled: Data = vector.apply_elementwise(Scale(3.0));
+        assert_eq!(scaled.precision(), Precision::F64);
+        assert_eq!(scaled.flatten_as::<f64>(), vec![0.1 * 3.0, 12.0]);
+
+        let mut single: Data = Data::VectorF32(arr1(&[4.0]));
+        single.apply_inplace(InverseSqrt {
+            numerator: 2.0,
+            offset: 0.0,
+        });
+        assert_eq!(single.precision(), Precision::F32);
+        assert_eq!(single.flatten_as::<f32>(), vec![1.0]);
+
+        assert_eq!(
+            Data::zero_dim_as::<f64>(&[2, 3]).variant_name(),
+            "MatrixF64"
+        );
+        assert_eq!(Data::one_dim_as::<f64>(&[]).variant_name(), "ScalarF64");
+        let mask: Data = Data::bernoulli_as::<f64>(0.5, &[4]);
+        assert_eq!(mask.variant_name(), "VectorF64");
+        assert!(mask
+            .flatten_as::<f64>()
+            .iter()
+            .all(|f| *f == 0.0 || *f == 1.0));
+    }
+}
@@ -0,0 +1,272 @@
+// builtin
+use std::{error::Error, fmt::Display};
+
+// external
+
+// internal
+use crate::data::{data_container::DataContainer, Data};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Operand {
+    pub container: Option<&'static str>,
+    pub variant: &'static str,
+    pub shape: Box<[usize]>,
+}
+
+impl Operand {
+    pub fn new(variant: &'static str, shape: &[usize]) -> Operand {
+        Operand {
+            container: None,
+            variant,
+            shape: shape.into(),
+        }
+    }
+
+    pub fn of_data(data: &Data) -> Operand {
+        Operand::new(data.variant_name(), data.dim())
+    }
+
+    pub fn of_container(container: &DataContainer) -> Operand {
+        let mut operand = match container {
+            DataContainer::Batch(data) => Operand::of_data(data),
+            DataContainer::Inference(data) => Operand::of_data(data),
+            DataContainer::Parameter(data) => Operand::of_data(data),
+            DataContainer::Empty => Operand::new("None", &[]),
+        };
+        operand.container = Some(container.container_name());
+
+        operand
+    }
+}
+
+impl Display for Operand {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.container {
+            Some(container) => write!(f, "{container}<{}> {:?}", self.variant, self.shape),
+            None => write!(f, "{} {:?}", self.variant, self.shape),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataError {
+    ShapeMismatch {
+        operation: &'static str,
+        left: Operand,
+        right: Operand,
+    },
+    BatchMismatch {
+        operation: &'static str,
+        left: Operand,
+        right: Operand,
+    },
+    UnsupportedPair {
+        operation: &'static str,
+        left: Operand,
+        right: Operand,
+    },
+    InvalidOperand {
+        operation: &'static str,
+        operand: Operand,
+    },
+    InvalidDimensions {
+        operation: &'static str,
+        dim: Vec<usize>,
+        len: usize,
+    },
+    // Networks with named inputs or heads that weren't given data for one of them
+    MissingData {
+        operation: &'static str,
+        name: String,
+    },
+}
+
+impl DataError {
+    pub fn shape_mismatch(operation: &'static str, left: &Data, right: &Data) -> DataError {
+        DataError::ShapeMismatch {
+            operation,
+            left: Operand::of_data(left),
+            right: Operand::of_data(right),
+        }
+    }
+
+    pub fn unsupported_pair(operation: &'static str, left: &Data, right: &Data) -> DataError {
+        DataError::UnsupportedPair {
+            operation,
+            left: Operand::of_data(left),
+            right: Operand::of_data(right),
+        }
+    }
+
+    pub fn invalid_operand(operation: &'static str, operand: &Data) -> DataError {
+        DataError::InvalidOperand {
+            operation,
+            operand: Operand::of_data(operand),
+        }
+    }
+
+    pub fn unsupported_containers(
+        operation: &'static str,
+        left: &DataContainer,
+        right: &DataContainer,
+    ) -> DataError {
+        DataError::UnsupportedPair {
+            operation,
+            left: Operand::of_container(left),
+            right: Operand::of_container(right),
+        }
+    }
+
+    pub fn batch_mismatch(operation: &'static str, left: &Data, right: &Data) -> DataError {
+        DataError::BatchMismatch {
+            operation,
+            left: Operand::of_data(left),
+            right: Operand::of_data(right),
+        }
+    }
+
+    // Tags operands raised by the wrapped data with the kinds of the containers involved, operands
+    // that were already tagged keep their container
+    pub fn in_containers(self, left: &DataContainer, right: &DataContainer) -> DataError {
+        self.tagged(left.container_name(), right.container_name())
+    }
+
+    pub fn tagged(mut self, left: &'static str, right: &'static str) -> DataError {
+        match &mut self {
+            DataError::ShapeMismatch {
+                left: left_operand,
+                right: right_operand,
+                ..
+            }
+            | DataError::BatchMismatch {
+                left: left_operand,
+                right: right_operand,
+                ..
+            }
+            | DataError::UnsupportedPair {
+                left: left_operand,
+                right: right_operand,
+                ..
+            } => {
+                left_operand.container.get_or_insert(left);
+                right_operand.container.get_or_insert(right);
+            }
+            DataError::InvalidOperand { operand, .. } => {
+                operand.container.get_or_insert(left);
+            }
+            DataError::InvalidDimensions { .. } | DataError::MissingData { .. } => {}
+        }
+
+        self
+    }
+
+    pub fn operation(&self) -> &'static str {
+        match self {
+            DataError::ShapeMismatch { operation, .. } => operation,
+            DataError::BatchMismatch { operation, .. } => operation,
+            DataError::UnsupportedPair { operation, .. } => operation,
+            DataError::InvalidOperand { operation, .. } => operation,
+            DataError::InvalidDimensions { operation, .. } => operation,
+            DataError::MissingData { operation, .. } => operation,
+        }
+    }
+}
+
+impl Display for DataError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DataError::ShapeMismatch {
+                operation,
+                left,
+                right,
+            } => write!(f, "[{operation}] mismatched dimensions: {left} and {right}"),
+            DataError::BatchMismatch {
+                operation,
+                left,
+                right,
+            } => write!(
+                f,
+                "[{operation}] mismatched batch sizes: {left} and {right}"
+            ),
+            DataError::UnsupportedPair {
+                operation,
+                left,
+                right,
+            } => write!(
+                f,
+                "[{operation}] unsupported operand pair: {left} and {right}"
+            ),
+            DataError::InvalidOperand { operation, operand } => {
+                write!(f, "[{operation}] invalid operand: {operand}")
+            }
+            DataError::InvalidDimensions {
+                operation,
+                dim,
+                len,
+            } => write!(
+                f,
+                "[{operation}] {len} values can't fill dimensions {dim:?}"
+            ),
+            DataError::MissingData { operation, name } => {
+                write!(f, "[{operation}] no data given for `{name}`")
+            }
+        }
+    }
+}
+
+impl Error for DataError {}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{arr1, arr2};
+
+    use crate::data::{
+        data_container::DataContainer,
+        error::{DataError, Operand},
+        Data,
+    };
+
+    #[test]
+    fn container_error_test() {
+        let batch = DataContainer::batch(vec![
+            Data::VectorF32(arr1(&[1.0, 2.0])),
+            Data::VectorF32(arr1(&[3.0, 4.0])),
+        ]);
+        let weights = DataContainer::Parameter(Data::MatrixF32(arr2(&[[1.0, 0.0, 1.0]])));
+
+        let err = weights.try_plus(&batch).unwrap_err();
+        assert_eq!(
+            err,
+            DataError::ShapeMismatch {
+                operation: "PLUS",
+                left: Operand {
+                    container: Some("Batch"),
+                    variant: "MatrixF32",
+                    shape: Box::new([2, 2]),
+                },
+                right: Operand {
+                    container: Some("Parameter"),
+                    variant: "MatrixF32",
+                    shape: Box::new([1, 3]),
+                },
+            }
+        );
+        assert_eq!(
+            err.to_string(),
+            "[PLUS] mismatched dimensions: Batch<MatrixF32> [2, 2] and Parameter<MatrixF32> [1, 3]"
+        );
+
+        let err = weights.try_matmul(&batch).unwrap_err();
+        assert_eq!(err.operation(), "MATMUL");
+
+        let err = Data::try_from_dim(&[2, 2], vec![1.0, 2.0, 3.0]).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "[FROM_DIM] 3 values can't fill dimensions [2, 2]"
+        );
+
+        let mut inference = DataContainer::Inference(Data::VectorF32(arr1(&[1.0])));
+        let err = inference.try_sum_assign(&batch).unwrap_err();
+        assert!(matches!(err, DataError::UnsupportedPair { .. }));
+    }
+}
@@ -3,6 +3,8 @@
 // external
 
 // internal
+pub mod broadcast;
+pub mod concat;
 pub mod element_sum;
 pub mod matmul;
 pub mod minus;
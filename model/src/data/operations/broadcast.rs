@@ -0,0 +1,218 @@
+// builtin
+
+// external
+use ndarray::{ArrayD, ArrayViewD, ArrayViewMutD, Axis, IxDyn};
+
+// internal
+use crate::data::{
+    error::DataError,
+    precision::{Element, Precision},
+    Data,
+};
+
+// Implemented by the operation helpers so one broadcasting path serves every element type
+pub trait ElementwiseOp {
+    const OPERATION: &'static str;
+
+    fn apply<T: Element>(lhs: &ArrayViewD<T>, rhs: &ArrayViewD<T>) -> ArrayD<T>;
+}
+
+pub trait ElementwiseAssignOp {
+    const OPERATION: &'static str;
+
+    fn apply<T: Element>(lhs: &mut ArrayViewMutD<T>, rhs: &ArrayViewD<T>);
+}
+
+pub struct DataBroadcast;
+
+impl DataBroadcast {
+    // Aligns trailing axes, each pair must match or contain a 1 as in numpy
+    pub fn broadcast_shape(shape1: &[usize], shape2: &[usize]) -> Option<Vec<usize>> {
+        let rank = shape1.len().max(shape2.len());
+        let mut shape: Vec<usize> = vec![1; rank];
+
+        for i in 0..rank {
+            let dim1 = if i < shape1.len() {
+                shape1[shape1.len() - 1 - i]
+            } else {
+                1
+            };
+            let dim2 = if i < shape2.len() {
+                shape2[shape2.len() - 1 - i]
+            } else {
+                1
+            };
+
+            if dim1 != dim2 && dim1 != 1 && dim2 != 1 {
+                return None;
+            }
+            shape[rank - 1 - i] = dim1.max(dim2);
+        }
+
+        Some(shape)
+    }
+
+    pub fn broadcast_views<T: Element>(
+        view1: &ArrayViewD<T>,
+        view2: &ArrayViewD<T>,
+        func: impl Fn(&ArrayViewD<T>, &ArrayViewD<T>) -> ArrayD<T>,
+    ) -> Option<ArrayD<T>> {
+        let shape = DataBroadcast::broadcast_shape(view1.shape(), view2.shape())?;
+        let lhs = view1.broadcast(IxDyn(&shape))?;
+        let rhs = view2.broadcast(IxDyn(&shape))?;
+
+        Some(func(&lhs, &rhs))
+    }
+
+    pub fn elementwise<O: ElementwiseOp>(data1: &Data, data2: &Data) -> Result<Data, DataError> {
+        match Precision::common(data1.precision(), data2.precision()) {
+            Precision::F32 => DataBroadcast::elementwise_as::<f32, O>(data1, data2),
+            Precision::F64 => DataBroadcast::elementwise_as::<f64, O>(data1, data2),
+        }
+    }
+
+    fn elementwise_as<T: Element, O: ElementwiseOp>(
+        data1: &Data,
+        data2: &Data,
+    ) -> Result<Data, DataError> {
+        let (view1, view2) = match (data1.cast_array_d::<T>(), data2.cast_array_d::<T>()) {
+            (Some(view1), Some(view2)) => (view1, view2),
+            _ => return Err(DataError::unsupported_pair(O::OPERATION, data1, data2)),
+        };
+
+        match DataBroadcast::broadcast_views(&view1.view(), &view2.view(), O::apply) {
+            Some(res) => Ok(Data::from_array_d(res)),
+            None => Err(DataError::shape_mismatch(O::OPERATION, data1, data2)),
+        }
+    }
+
+    // In place updates can't grow or widen the left operand, so the right one is broadcast and
+    // converted to its element type
+    pub fn elementwise_assign<O: ElementwiseAssignOp>(
+        data1: &mut Data,
+        data2: &Data,
+    ) -> Result<(), DataError> {
+        match data1.precision() {
+            Precision::F32 => DataBroadcast::elementwise_assign_as::<f32, O>(data1, data2),
+            Precision::F64 => DataBroadcast::elementwise_assign_as::<f64, O>(data1, data2),
+        }
+    }
+
+    fn elementwise_assign_as<T: Element, O: ElementwiseAssignOp>(
+        data1: &mut Data,
+        data2: &Data,
+    ) -> Result<(), DataError> {
+        let rhs = match data2.cast_array_d::<T>() {
+            Some(view) => view,
+            None => return Err(DataError::unsupported_pair(O::OPERATION, data1, data2)),
+        };
+        let shape: Vec<usize> = match data1.shape() {
+            Some(shape) => shape.to_vec(),
+            None => return Err(DataError::unsupported_pair(O::OPERATION, data1, data2)),
+        };
+
+        let rhs = match rhs.broadcast(IxDyn(&shape)) {
+            Some(rhs) => rhs,
+            None => return Err(DataError::shape_mismatch(O::OPERATION, data1, data2)),
+        };
+        if let Some(mut lhs) = data1.view_array_d_mut::<T>() {
+            O::apply(&mut lhs, &rhs);
+        }
+
+        Ok(())
+    }
+
+    // Sums a broadcast result back down to the shape it was broadcast from, the first `keep` axes
+    // are left untouched so batches can reduce each example on its own
+    pub fn reduce_view<T: Element>(
+        view: &ArrayViewD<T>,
+        shape: &[usize],
+        keep: usize,
+    ) -> Option<ArrayD<T>> {
+        if view.ndim() < keep + shape.len() {
+            return None;
+        }
+
+        let mut res: ArrayD<T> = view.to_owned();
+        for _ in 0..(view.ndim() - keep - shape.len()) {
+            res = res.sum_axis(Axis(keep));
+        }
+
+        for (i, &dim) in shape.iter().enumerate() {
+            let axis = Axis(keep + i);
+            if res.len_of(axis) == dim {
+                continue;
+            }
+            if dim != 1 {
+                return None;
+            }
+            res = res.sum_axis(axis).insert_axis(axis);
+        }
+
+        Some(res)
+    }
+
+    pub fn reduce_data(data: &Data, shape: &[usize], keep: usize) -> Option<Data> {
+        match data.precision() {
+            Precision::F32 => DataBroadcast::reduce_view(&data.view_array_d::<f32>()?, shape, keep)
+                .map(Data::from_array_d),
+            Precision::F64 => DataBroadcast::reduce_view(&data.view_array_d::<f64>()?, shape, keep)
+                .map(Data::from_array_d),
+        }
+    }
+
+    pub fn reduce_like(data: &Data, target: &Data) -> Result<Data, DataError> {
+        let shape = match (data.shape(), target.shape()) {
+            (Some(_), Some(shape)) => shape,
+            _ => return Err(DataError::unsupported_pair("REDUCE", data, target)),
+        };
+
+        match DataBroadcast::reduce_data(data, shape, 0) {
+            Some(res) => Ok(res),
+            None => Err(DataError::shape_mismatch("REDUCE", data, target)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{arr1, arr2};
+
+    use crate::data::Data;
+
+    #[test]
+    fn broadcast_test() {
+        let matrix = Data::MatrixF32(arr2(&[[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]));
+        let row = Data::VectorF32(arr1(&[10.0, 20.0, 30.0]));
+        let column = Data::MatrixF32(arr2(&[[1.0], [2.0]]));
+
+        let res = matrix.try_plus(&row).unwrap();
+        assert_eq!(
+            res.flatten_to_vec(),
+            vec![11.0, 22.0, 33.0, 14.0, 25.0, 36.0]
+        );
+
+        let res = matrix.try_times(&column).unwrap();
+        assert_eq!(res.flatten_to_vec(), vec![1.0, 2.0, 3.0, 8.0, 10.0, 12.0]);
+
+        let res = row.try_minus(&Data::ScalarF32(10.0)).unwrap();
+        assert_eq!(res.flatten_to_vec(), vec![0.0, 10.0, 20.0]);
+
+        let outer = column.try_times(&row).unwrap();
+        assert_eq!(outer.dim(), &[2, 3]);
+
+        let mut accumulated = matrix.clone();
+        accumulated.try_sum_assign(&row).unwrap();
+        assert!(row.clone().try_sum_assign(&matrix).is_err());
+        assert!(matrix
+            .try_plus(&Data::VectorF32(arr1(&[1.0, 2.0])))
+            .is_err());
+
+        let reduced = accumulated.try_reduce_like(&row).unwrap();
+        assert_eq!(reduced.flatten_to_vec(), vec![25.0, 47.0, 69.0]);
+        let reduced = accumulated.try_reduce_like(&column).unwrap();
+        assert_eq!(reduced.dim(), &[2, 1]);
+        let reduced = accumulated.try_reduce_like(&Data::ScalarF32(0.0)).unwrap();
+        assert_eq!(reduced.variant_name(), "ScalarF32");
+    }
+}
@@ -0,0 +1,63 @@
+// builtin
+
+// external
+use ndarray::{concatenate, ArrayViewD, Axis, Slice};
+
+// internal
+use crate::data::{error::DataError, precision::Element, Data};
+
+pub struct DataConcat;
+
+impl DataConcat {
+    // Joins along the last axis, so batched examples keep their leading batch axis
+    pub fn concat_last<T: Element>(parts: &[Data]) -> Result<Data, DataError> {
+        let views: Vec<ArrayViewD<T>> = parts
+            .iter()
+            .map(|part| match part.view_array_d::<T>() {
+                Some(view) if view.ndim() > 0 => Ok(view),
+                _ => Err(DataError::invalid_operand("CONCAT", part)),
+            })
+            .collect::<Result<Vec<ArrayViewD<T>>, DataError>>()?;
+
+        let first: &Data = &parts[0];
+        let rank: usize = views[0].ndim();
+        for (part, view) in parts.iter().zip(&views) {
+            if view.ndim() != rank || view.shape()[..rank - 1] != views[0].shape()[..rank - 1] {
+                return Err(DataError::shape_mismatch("CONCAT", first, part));
+            }
+        }
+
+        match concatenate(Axis(rank - 1), &views) {
+            Ok(joined) => Ok(Data::from_array_d(joined)),
+            Err(_) => Err(DataError::shape_mismatch("CONCAT", first, &parts[1])),
+        }
+    }
+
+    // Inverse of concat_last, cuts the last axis into consecutive pieces of the given widths
+    pub fn split_last<T: Element>(data: &Data, widths: &[usize]) -> Result<Vec<Data>, DataError> {
+        let view: ArrayViewD<T> = match data.view_array_d::<T>() {
+            Some(view) if view.ndim() > 0 => view,
+            _ => return Err(DataError::invalid_operand("SPLIT", data)),
+        };
+
+        let axis: Axis = Axis(view.ndim() - 1);
+        let total: usize = widths.iter().sum();
+        if total != view.len_of(axis) {
+            return Err(DataError::InvalidDimensions {
+                operation: "SPLIT",
+                dim: view.shape().to_vec(),
+                len: total,
+            });
+        }
+
+        let mut start: usize = 0;
+        let mut pieces: Vec<Data> = Vec::new();
+        for width in widths {
+            let piece = view.slice_axis(axis, Slice::from(start..start + width));
+            pieces.push(Data::from_array_d(piece.to_owned()));
+            start += width;
+        }
+
+        Ok(pieces)
+    }
+}
@@ -1,23 +1,27 @@
 // builtin
 
 // external
-use ndarray::{Array1, Array2};
+use ndarray::{Array1, Array2, ArrayD};
 
 // internal
-use crate::data::Data;
+use crate::data::{precision::Element, Data};
 
 pub struct DataElementSum;
 
 impl DataElementSum {
-    pub fn element_sum_scalar(scalar: &f32) -> Data {
-        Data::ScalarF32(*scalar)
+    pub fn element_sum_scalar<T: Element>(scalar: &T) -> Data {
+        T::scalar(*scalar)
     }
 
-    pub fn element_sum_vector(vector: &Array1<f32>) -> Data {
-        Data::ScalarF32(vector.sum())
+    pub fn element_sum_vector<T: Element>(vector: &Array1<T>) -> Data {
+        T::scalar(vector.sum())
     }
 
-    pub fn element_sum_matrix(matrix: &Array2<f32>) -> Data {
-        Data::ScalarF32(matrix.sum())
+    pub fn element_sum_matrix<T: Element>(matrix: &Array2<T>) -> Data {
+        T::scalar(matrix.sum())
+    }
+
+    pub fn element_sum_tensor<T: Element>(tensor: &ArrayD<T>) -> Data {
+        T::scalar(tensor.sum())
     }
 }
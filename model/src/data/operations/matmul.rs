@@ -1,79 +1,232 @@
 // builtin
 
 // external
-use ndarray::{Array1, Array2, Axis};
+use ndarray::{linalg::general_mat_mul, Array1, Array2, Array3, ArrayD, ArrayViewD, Axis, IxDyn};
 
 // internal
-use crate::data::Data;
+use crate::data::{
+    error::{DataError, Operand},
+    operations::broadcast::DataBroadcast,
+    precision::Element,
+    Data,
+};
 
 pub struct DataMatMul;
 
 impl DataMatMul {
-    fn warn_matrices(matrix1: &Array2<f32>, matrix2: &Array2<f32>) {
-        println!(
-            "Data::None returned on mismatched dimensions for operation [MATMUL]: {:?} and {:?}",
-            matrix1.dim(),
-            matrix2.dim()
-        );
+    fn operand<T: Element>(shape: &[usize]) -> Operand {
+        Operand::new(T::variant_name(shape.len()), shape)
     }
 
-    fn warn_vector_matrix(vector: &Array1<f32>, matrix: &Array2<f32>) {
-        println!(
-            "Data::None returned on mismatched dimensions for operation [MATMUL]: {:?} and {:?}",
-            vector.dim(),
-            matrix.dim()
-        );
+    fn mismatch(left: Operand, right: Operand) -> DataError {
+        DataError::ShapeMismatch {
+            operation: "MATMUL",
+            left,
+            right,
+        }
     }
 
-    fn warn_matrix_vector(matrix: &Array2<f32>, vector: &Array1<f32>) {
-        println!(
-            "Data::None returned on mismatched dimensions for operation [MATMUL]: {:?} and {:?}",
-            matrix.dim(),
-            vector.dim()
-        );
+    // Treats the two trailing axes as matrices, leading axes broadcast as in numpy's matmul
+    pub fn batched_matmul<T: Element>(
+        tensor1: &ArrayViewD<T>,
+        tensor2: &ArrayViewD<T>,
+    ) -> Result<ArrayD<T>, DataError> {
+        let (shape1, shape2) = (tensor1.shape(), tensor2.shape());
+        let (rank1, rank2) = (shape1.len(), shape2.len());
+        if rank1 < 2 || rank2 < 2 {
+            let shape: &[usize] = if rank1 < 2 { shape1 } else { shape2 };
+            return Err(DataError::InvalidOperand {
+                operation: "MATMUL",
+                operand: DataMatMul::operand::<T>(shape),
+            });
+        }
+        let (rows, inner) = (shape1[rank1 - 2], shape1[rank1 - 1]);
+        let (other_inner, cols) = (shape2[rank2 - 2], shape2[rank2 - 1]);
+
+        let leading =
+            match DataBroadcast::broadcast_shape(&shape1[..rank1 - 2], &shape2[..rank2 - 2]) {
+                Some(leading) if inner == other_inner => leading,
+                _ => {
+                    return Err(DataMatMul::mismatch(
+                        DataMatMul::operand::<T>(shape1),
+                        DataMatMul::operand::<T>(shape2),
+                    ))
+                }
+            };
+        let batch: usize = leading.iter().product();
+
+        let lhs_shape: Vec<usize> = [leading.as_slice(), &[rows, inner]].concat();
+        let rhs_shape: Vec<usize> = [leading.as_slice(), &[inner, cols]].concat();
+        let lhs = tensor1
+            .broadcast(IxDyn(&lhs_shape))
+            .expect("Leading axes were checked for broadcasting");
+        let rhs = tensor2
+            .broadcast(IxDyn(&rhs_shape))
+            .expect("Leading axes were checked for broadcasting");
+        let lhs = lhs
+            .to_shape((batch, rows, inner))
+            .expect("Couldn't collapse leading axes");
+        let rhs = rhs
+            .to_shape((batch, inner, cols))
+            .expect("Couldn't collapse leading axes");
+
+        let mut res: Array3<T> = Array3::zeros((batch, rows, cols));
+        for (i, mut out) in res.outer_iter_mut().enumerate() {
+            let lhs_matrix = lhs.index_axis(Axis(0), i);
+            let rhs_matrix = rhs.index_axis(Axis(0), i);
+            general_mat_mul(T::one(), &lhs_matrix, &rhs_matrix, T::zero(), &mut out);
+        }
+
+        let res_shape: Vec<usize> = [leading.as_slice(), &[rows, cols]].concat();
+        let res = res
+            .into_shape_with_order(IxDyn(&res_shape))
+            .expect("Couldn't expand leading axes");
+        Ok(res)
     }
 
-    pub fn matmul_vectors(vector1: &Array1<f32>, vector2: &Array1<f32>) -> Data {
+    pub fn matmul_vectors<T: Element>(
+        vector1: &Array1<T>,
+        vector2: &Array1<T>,
+    ) -> Result<Data, DataError> {
         let matrix1 = vector1.view().insert_axis(Axis(1));
         let matrix2 = vector2.view().insert_axis(Axis(0));
 
         let res = matrix1.dot(&matrix2);
 
-        Data::MatrixF32(res)
+        Ok(Data::from_array_d(res.into_dyn()))
     }
 
-    pub fn matmul_matrices(matrix1: &Array2<f32>, matrix2: &Array2<f32>) -> Data {
+    pub fn matmul_matrices<T: Element>(
+        matrix1: &Array2<T>,
+        matrix2: &Array2<T>,
+    ) -> Result<Data, DataError> {
         if matrix1.shape()[1] != matrix2.shape()[0] {
-            DataMatMul::warn_matrices(matrix1, matrix2);
-            return Data::None;
+            return Err(DataMatMul::mismatch(
+                DataMatMul::operand::<T>(matrix1.shape()),
+                DataMatMul::operand::<T>(matrix2.shape()),
+            ));
         }
 
         let res = matrix1.dot(matrix2);
-        Data::MatrixF32(res)
+        Ok(Data::from_array_d(res.into_dyn()))
     }
 
     // Automatic transposition as row vector, use vector multiplication for [n x 1] x [1 x m] effect
-    pub fn matmul_vector_matrix(vector: &Array1<f32>, matrix: &Array2<f32>) -> Data {
+    pub fn matmul_vector_matrix<T: Element>(
+        vector: &Array1<T>,
+        matrix: &Array2<T>,
+    ) -> Result<Data, DataError> {
         if vector.shape()[0] != matrix.shape()[0] {
-            DataMatMul::warn_vector_matrix(vector, matrix);
-            return Data::None;
+            return Err(DataMatMul::mismatch(
+                DataMatMul::operand::<T>(vector.shape()),
+                DataMatMul::operand::<T>(matrix.shape()),
+            ));
         }
 
         let vector_row = vector.view().insert_axis(Axis(0));
         let res = vector_row.dot(matrix);
 
-        Data::VectorF32(res.remove_axis(Axis(0)))
+        Ok(Data::from_array_d(res.remove_axis(Axis(0)).into_dyn()))
     }
 
-    pub fn matmul_matrix_vector(matrix: &Array2<f32>, vector: &Array1<f32>) -> Data {
+    pub fn matmul_matrix_vector<T: Element>(
+        matrix: &Array2<T>,
+        vector: &Array1<T>,
+    ) -> Result<Data, DataError> {
         if matrix.shape()[1] != vector.shape()[0] {
-            DataMatMul::warn_matrix_vector(matrix, vector);
-            return Data::None;
+            return Err(DataMatMul::mismatch(
+                DataMatMul::operand::<T>(matrix.shape()),
+                DataMatMul::operand::<T>(vector.shape()),
+            ));
         }
 
         let vector_col = vector.view().insert_axis(Axis(1));
         let res = matrix.dot(&vector_col);
 
-        Data::VectorF32(res.remove_axis(Axis(1)))
+        Ok(Data::from_array_d(res.remove_axis(Axis(1)).into_dyn()))
+    }
+
+    pub fn matmul_tensors<T: Element>(
+        tensor1: &ArrayViewD<T>,
+        tensor2: &ArrayViewD<T>,
+    ) -> Result<Data, DataError> {
+        let res = DataMatMul::batched_matmul(tensor1, tensor2)?;
+        Ok(Data::from_array_d(res))
+    }
+
+    pub fn matmul_tensor_vector<T: Element>(
+        tensor: &ArrayD<T>,
+        vector: &Array1<T>,
+    ) -> Result<Data, DataError> {
+        let vector_col = vector.view().insert_axis(Axis(1)).into_dyn();
+
+        let res = DataMatMul::batched_matmul(&tensor.view(), &vector_col)?;
+        let rank = res.ndim();
+        Ok(Data::from_array_d(res.remove_axis(Axis(rank - 1))))
+    }
+
+    pub fn matmul_vector_tensor<T: Element>(
+        vector: &Array1<T>,
+        tensor: &ArrayD<T>,
+    ) -> Result<Data, DataError> {
+        let vector_row = vector.view().insert_axis(Axis(0)).into_dyn();
+
+        let res = DataMatMul::batched_matmul(&vector_row, &tensor.view())?;
+        let rank = res.ndim();
+        Ok(Data::from_array_d(res.remove_axis(Axis(rank - 2))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{arr0, arr1, arr2, Array, ArrayD, Axis, IxDyn};
+
+    use crate::data::{error::DataError, operations::matmul::DataMatMul, Data};
+
+    #[test]
+    fn batched_matmul_test() {
+        let tensor: ArrayD<f32> = Array::range(0.0, 12.0, 1.0)
+            .into_shape_with_order(IxDyn(&[2, 2, 3]))
+            .unwrap();
+        let matrix = arr2(&[[1.0, 0.0], [0.0, 1.0], [1.0, 1.0]]);
+
+        let res = Data::ArrayDF32(tensor.clone()).matmul(&Data::MatrixF32(matrix.clone()));
+
+        match res {
+            Data::ArrayDF32(res) => {
+                assert_eq!(res.shape(), &[2, 2, 2]);
+                for (i, slice) in tensor.outer_iter().enumerate() {
+                    let slice = slice.into_dimensionality::<ndarray::Ix2>().unwrap();
+                    let expected = slice.dot(&matrix).into_dyn();
+                    assert_eq!(res.index_axis(Axis(0), i), expected);
+                }
+            }
+            _ => panic!("Expected a batched tensor result"),
+        }
+
+        let transposed = Data::ArrayDF32(tensor).transpose();
+        assert_eq!(transposed.dim(), &[2, 3, 2]);
+
+        let squeezed = transposed.matmul(&Data::VectorF32(ndarray::arr1(&[1.0, 1.0])));
+        assert_eq!(squeezed.dim(), &[2, 3]);
+        assert_eq!(squeezed.variant_name(), "MatrixF32");
+    }
+
+    #[test]
+    fn batched_matmul_rank_test() {
+        let matrix: ArrayD<f32> = arr2(&[[1.0, 0.0], [0.0, 1.0]]).into_dyn();
+        let vector: ArrayD<f32> = arr1(&[1.0, 2.0]).into_dyn();
+        let scalar: ArrayD<f32> = arr0(1.0).into_dyn();
+
+        for operand in [&vector, &scalar] {
+            let errors = [
+                DataMatMul::batched_matmul(&matrix.view(), &operand.view()).unwrap_err(),
+                DataMatMul::batched_matmul(&operand.view(), &matrix.view()).unwrap_err(),
+            ];
+            for err in errors {
+                assert_eq!(err.operation(), "MATMUL");
+                assert!(matches!(err, DataError::InvalidOperand { .. }));
+            }
+        }
     }
 }
@@ -1,27 +1,31 @@
 // builtin
 
 // external
-use ndarray::{Array1, Array2};
+use ndarray::{Array1, Array2, ArrayD};
 
 // internal
-use crate::data::Data;
+use crate::data::{precision::Element, Data};
 
 pub struct DataSquareRoot;
 
 impl DataSquareRoot {
-    pub fn square_root_scalar(scalar: &f32) -> Data {
-        Data::ScalarF32(f32::sqrt(*scalar))
+    pub fn square_root_scalar<T: Element>(scalar: &T) -> Data {
+        T::scalar(scalar.sqrt())
     }
 
-    pub fn square_root_vector(vector: &Array1<f32>) -> Data {
+    pub fn square_root_vector<T: Element>(vector: &Array1<T>) -> Data {
         let mut vec_copy = vector.clone();
-        vec_copy.mapv_inplace(f32::sqrt);
-        Data::VectorF32(vec_copy)
+        vec_copy.mapv_inplace(T::sqrt);
+        Data::from_array_d(vec_copy.into_dyn())
     }
 
-    pub fn square_root_matrix(matrix: &Array2<f32>) -> Data {
+    pub fn square_root_matrix<T: Element>(matrix: &Array2<T>) -> Data {
         let mut matrix_copy = matrix.clone();
-        matrix_copy.mapv_inplace(f32::sqrt);
-        return Data::MatrixF32(matrix_copy);
+        matrix_copy.mapv_inplace(T::sqrt);
+        Data::from_array_d(matrix_copy.into_dyn())
+    }
+
+    pub fn square_root_tensor<T: Element>(tensor: &ArrayD<T>) -> Data {
+        Data::from_array_d(tensor.mapv(T::sqrt))
     }
 }
@@ -1,55 +1,32 @@
 // builtin
 
 // external
-use ndarray::{Array1, Array2};
+use ndarray::{ArrayD, ArrayViewD};
 
 // internal
-use crate::data::Data;
+use crate::data::{
+    error::DataError,
+    operations::broadcast::{DataBroadcast, ElementwiseOp},
+    precision::Element,
+    Data,
+};
 
 pub struct DataTimes;
 
 impl DataTimes {
-    fn warn_vectors(vector1: &Array1<f32>, vector2: &Array1<f32>) {
-        println!(
-            "Data::None returned on mismatched dimensions for operation [TIMES]: {:?} and {:?}",
-            vector1.dim(),
-            vector2.dim()
-        );
+    pub fn multiply_scalars<T: Element>(scalar1: &T, scalar2: &T) -> Result<Data, DataError> {
+        Ok(T::scalar(*scalar1 * *scalar2))
     }
 
-    fn warn_matrices(matrix1: &Array2<f32>, matrix2: &Array2<f32>) {
-        println!(
-            "Data::None returned on mismatched dimensions for operation [TIMES]: {:?} and {:?}",
-            matrix1.dim(),
-            matrix2.dim()
-        );
-    }
-
-    pub fn multiply_scalars(scalar1: &f32, scalar2: &f32) -> Data {
-        Data::ScalarF32(scalar1 * scalar2)
-    }
-
-    pub fn multiply_vectors(vector1: &Array1<f32>, vector2: &Array1<f32>) -> Data {
-        if vector1.dim() == vector2.dim() {
-            return Data::VectorF32(vector1 * vector2);
-        }
-        DataTimes::warn_vectors(vector1, vector2);
-        Data::None
-    }
-
-    pub fn multiply_matrices(matrix1: &Array2<f32>, matrix2: &Array2<f32>) -> Data {
-        if matrix1.dim() == matrix2.dim() {
-            return Data::MatrixF32(matrix1 * matrix2);
-        }
-        DataTimes::warn_matrices(matrix1, matrix2);
-        Data::None
+    pub fn multiply_broadcast(data1: &Data, data2: &Data) -> Result<Data, DataError> {
+        DataBroadcast::elementwise::<DataTimes>(data1, data2)
     }
+}
 
-    pub fn multiply_scalar_vector(scalar: &f32, vector: &Array1<f32>) -> Data {
-        Data::VectorF32(vector * *scalar)
-    }
+impl ElementwiseOp for DataTimes {
+    const OPERATION: &'static str = "TIMES";
 
-    pub fn multiply_scalar_matrix(scalar: &f32, matrix: &Array2<f32>) -> Data {
-        Data::MatrixF32(matrix * *scalar)
+    fn apply<T: Element>(lhs: &ArrayViewD<T>, rhs: &ArrayViewD<T>) -> ArrayD<T> {
+        lhs * rhs
     }
 }
@@ -1,54 +1,33 @@
 // builtin
 
 // external
-use ndarray::{Array1, Array2};
+use ndarray::{ArrayViewD, ArrayViewMutD};
 
 // internal
+use crate::data::{
+    error::DataError,
+    operations::broadcast::{DataBroadcast, ElementwiseAssignOp},
+    precision::Element,
+    Data,
+};
 
 pub struct DataTimesAssign;
 
 impl DataTimesAssign {
-    fn warn_vectors(vector1: &Array1<f32>, vector2: &Array1<f32>) {
-        println!(
-            "Mutation failed on mismatched dimensions for operation [TIMES_INPLACE]: {:?} and {:?}",
-            vector1.dim(),
-            vector2.dim()
-        );
+    pub fn multiply_scalars<T: Element>(l_scalar: &mut T, r_scalar: &T) -> Result<(), DataError> {
+        *l_scalar *= *r_scalar;
+        Ok(())
     }
 
-    fn warn_matrices(matrix1: &Array2<f32>, matrix2: &Array2<f32>) {
-        println!(
-            "Mutation failed on mismatched dimensions for operation [TIMES_INPLACE]: {:?} and {:?}",
-            matrix1.dim(),
-            matrix2.dim()
-        );
-    }
-
-    pub fn multiply_scalars(l_scalar: &mut f32, r_scalar: &f32) {
-        *l_scalar *= r_scalar;
-    }
-
-    pub fn multiply_vectors(l_vec: &mut Array1<f32>, r_vec: &Array1<f32>) {
-        if l_vec.dim() == r_vec.dim() {
-            *l_vec *= r_vec;
-        } else {
-            Self::warn_vectors(&l_vec, r_vec);
-        }
-    }
-
-    pub fn multiply_matrices(l_matrix: &mut Array2<f32>, r_matrix: &Array2<f32>) {
-        if l_matrix.dim() == r_matrix.dim() {
-            *l_matrix *= r_matrix;
-        } else {
-            Self::warn_matrices(&l_matrix, r_matrix);
-        }
+    pub fn multiply_broadcast(l_data: &mut Data, r_data: &Data) -> Result<(), DataError> {
+        DataBroadcast::elementwise_assign::<DataTimesAssign>(l_data, r_data)
     }
+}
 
-    pub fn multiply_vector_scalar(vector: &mut Array1<f32>, scalar: &f32) {
-        vector.map_inplace(|f| *f *= scalar);
-    }
+impl ElementwiseAssignOp for DataTimesAssign {
+    const OPERATION: &'static str = "TIMES_INPLACE";
 
-    pub fn multiply_matrix_scalar(matrix: &mut Array2<f32>, scalar: &f32) {
-        matrix.map_inplace(|f| *f *= scalar);
+    fn apply<T: Element>(lhs: &mut ArrayViewMutD<T>, rhs: &ArrayViewD<T>) {
+        *lhs *= rhs;
     }
 }
@@ -1,24 +1,33 @@
 // builtin
 
 // external
-use ndarray::{Array1, Array2};
+use ndarray::{Array1, Array2, ArrayD};
 
 // internal
-use crate::data::Data;
+use crate::data::{precision::Element, Data};
 
 pub struct DataTranspose;
 
 impl DataTranspose {
-    pub fn transpose_scalar(scalar: &f32) -> Data {
-        Data::ScalarF32(*scalar)
+    pub fn transpose_scalar<T: Element>(scalar: &T) -> Data {
+        T::scalar(*scalar)
     }
 
-    pub fn transpose_vector(vector: &Array1<f32>) -> Data {
-        Data::VectorF32(vector.clone())
+    pub fn transpose_vector<T: Element>(vector: &Array1<T>) -> Data {
+        Data::from_array_d(vector.clone().into_dyn())
     }
 
-    pub fn transpose_matrix(matrix: &Array2<f32>) -> Data {
+    pub fn transpose_matrix<T: Element>(matrix: &Array2<T>) -> Data {
         let transposed = matrix.t().to_owned();
-        Data::MatrixF32(transposed)
+        Data::from_array_d(transposed.into_dyn())
+    }
+
+    // Swaps the two trailing axes so batched tensors transpose each inner matrix
+    pub fn transpose_tensor<T: Element>(tensor: &ArrayD<T>) -> Data {
+        let mut transposed = tensor.view();
+        let rank = transposed.ndim();
+        transposed.swap_axes(rank - 2, rank - 1);
+
+        Data::from_array_d(transposed.to_owned())
     }
 }
@@ -0,0 +1,180 @@
+// builtin
+
+// external
+use ndarray::{arr0, aview0, ArrayD, ArrayViewD, ArrayViewMut, ArrayViewMutD, IxDyn, NdFloat};
+use serde::{Deserialize, Serialize};
+
+// internal
+use crate::data::Data;
+
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Precision {
+    #[default]
+    F32,
+    F64,
+}
+
+impl Precision {
+    // Mixed operands are promoted to the wider type, as in numpy
+    pub fn common(left: Precision, right: Precision) -> Precision {
+        if left == Precision::F64 || right == Precision::F64 {
+            return Precision::F64;
+        }
+
+        Precision::F32
+    }
+}
+
+fn view_scalar_mut<T: Element>(scalar: &mut T) -> ArrayViewMutD<'_, T> {
+    ArrayViewMut::from_shape(IxDyn(&[]), std::slice::from_mut(scalar))
+        .expect("Scalars always fit a zero dimensional view")
+}
+
+// Element types the tensor variants of `Data` can hold
+pub trait Element: NdFloat {
+    const PRECISION: Precision;
+
+    fn from_f64(value: f64) -> Self;
+
+    fn to_f64(self) -> f64;
+
+    fn variant_name(ndim: usize) -> &'static str;
+
+    fn view(data: &Data) -> Option<ArrayViewD<'_, Self>>;
+
+    fn view_mut(data: &mut Data) -> Option<ArrayViewMutD<'_, Self>>;
+
+    // Wraps a tensor in the variant matching its number of axes
+    fn wrap(tensor: ArrayD<Self>) -> Data;
+
+    fn scalar(value: Self) -> Data {
+        Self::wrap(arr0(value).into_dyn())
+    }
+
+    // Runs whichever of the two functions is written for this element type
+    fn select(value: Self, f32_fn: impl Fn(f32) -> f32, f64_fn: impl Fn(f64) -> f64) -> Self;
+}
+
+impl Element for f32 {
+    const PRECISION: Precision = Precision::F32;
+
+    fn from_f64(value: f64) -> f32 {
+        value as f32
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+
+    fn select(value: f32, f32_fn: impl Fn(f32) -> f32, _f64_fn: impl Fn(f64) -> f64) -> f32 {
+        f32_fn(value)
+    }
+
+    fn variant_name(ndim: usize) -> &'static str {
+        match ndim {
+            0 => "ScalarF32",
+            1 => "VectorF32",
+            2 => "MatrixF32",
+            _ => "ArrayDF32",
+        }
+    }
+
+    fn view(data: &Data) -> Option<ArrayViewD<'_, f32>> {
+        match data {
+            Data::ScalarF32(scalar) => Some(aview0(scalar).into_dyn()),
+            Data::VectorF32(vector) => Some(vector.view().into_dyn()),
+            Data::MatrixF32(matrix) => Some(matrix.view().into_dyn()),
+            Data::ArrayDF32(tensor) => Some(tensor.view()),
+            _ => None,
+        }
+    }
+
+    fn view_mut(data: &mut Data) -> Option<ArrayViewMutD<'_, f32>> {
+        match data {
+            Data::ScalarF32(scalar) => Some(view_scalar_mut(scalar)),
+            Data::VectorF32(vector) => Some(vector.view_mut().into_dyn()),
+            Data::MatrixF32(matrix) => Some(matrix.view_mut().into_dyn()),
+            Data::ArrayDF32(tensor) => Some(tensor.view_mut()),
+            _ => None,
+        }
+    }
+
+    fn wrap(tensor: ArrayD<f32>) -> Data {
+        match tensor.ndim() {
+            0 => Data::ScalarF32(tensor.into_iter().next().unwrap_or_default()),
+            1 => Data::VectorF32(
+                tensor
+                    .into_dimensionality()
+                    .expect("Couldn't coerce tensor to vector"),
+            ),
+            2 => Data::MatrixF32(
+                tensor
+                    .into_dimensionality()
+                    .expect("Couldn't coerce tensor to matrix"),
+            ),
+            _ => Data::ArrayDF32(tensor),
+        }
+    }
+}
+
+impl Element for f64 {
+    const PRECISION: Precision = Precision::F64;
+
+    fn from_f64(value: f64) -> f64 {
+        value
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+
+    fn select(value: f64, _f32_fn: impl Fn(f32) -> f32, f64_fn: impl Fn(f64) -> f64) -> f64 {
+        f64_fn(value)
+    }
+
+    fn variant_name(ndim: usize) -> &'static str {
+        match ndim {
+            0 => "ScalarF64",
+            1 => "VectorF64",
+            2 => "MatrixF64",
+            _ => "ArrayDF64",
+        }
+    }
+
+    fn view(data: &Data) -> Option<ArrayViewD<'_, f64>> {
+        match data {
+            Data::ScalarF64(scalar) => Some(aview0(scalar).into_dyn()),
+            Data::VectorF64(vector) => Some(vector.view().into_dyn()),
+            Data::MatrixF64(matrix) => Some(matrix.view().into_dyn()),
+            Data::ArrayDF64(tensor) => Some(tensor.view()),
+            _ => None,
+        }
+    }
+
+    fn view_mut(data: &mut Data) -> Option<ArrayViewMutD<'_, f64>> {
+        match data {
+            Data::ScalarF64(scalar) => Some(view_scalar_mut(scalar)),
+            Data::VectorF64(vector) => Some(vector.view_mut().into_dyn()),
+            Data::MatrixF64(matrix) => Some(matrix.view_mut().into_dyn()),
+            Data::ArrayDF64(tensor) => Some(tensor.view_mut()),
+            _ => None,
+        }
+    }
+
+    fn wrap(tensor: ArrayD<f64>) -> Data {
+        match tensor.ndim() {
+            0 => Data::ScalarF64(tensor.into_iter().next().unwrap_or_default()),
+            1 => Data::VectorF64(
+                tensor
+                    .into_dimensionality()
+                    .expect("Couldn't coerce tensor to vector"),
+            ),
+            2 => Data::MatrixF64(
+                tensor
+                    .into_dimensionality()
+                    .expect("Couldn't coerce tensor to matrix"),
+            ),
+            _ => Data::ArrayDF64(tensor),
+        }
+    }
+}
@@ -7,6 +7,7 @@ pub mod data;
 pub mod network;
 pub mod node;
 pub mod optimization;
+pub mod random;
 pub mod regularization;
 pub mod trainer;
 pub mod unit;
@@ -18,10 +19,13 @@ mod tests {
     use ndarray::{arr1, Array1};
 
     use crate::{
-        data::{data_container::DataContainer, Data},
-        network::{types::classifier::ClassifierNetwork, Network},
+        data::{data_container::DataContainer, precision::Precision, Data},
+        network::{
+            config_types::hyper_params::HyperParams, types::classifier::ClassifierNetwork, Network,
+        },
         optimization::{
-            batch_norm::NormalizationType, learning_decay::LearningDecayType, momentum::DescentType,
+            batch_norm::NormalizationType, learning_decay::LearningDecayType,
+            learning_schedule::LearningRateSchedule, momentum::DescentType,
         },
         regularization::{dropout::NetworkMaskType, penalty::PenaltyConfig},
     };
@@ -36,15 +40,19 @@ mod tests {
             vec![5],
             penalty_config,
             NetworkMaskType::none(),
-            LearningDecayType::constant(0.001),
-            DescentType::none(),
-            NormalizationType::none(),
+            HyperParams::new(
+                LearningDecayType::constant(0.001),
+                DescentType::none(),
+                NormalizationType::none(),
+                Precision::F32,
+                LearningRateSchedule::constant(),
+            ),
         );
 
         let input_arr1: Array1<f32> = arr1(&[0.4, 0.1, 1.0]);
         let input = DataContainer::Inference(Data::VectorF32(input_arr1));
 
-        let output = classifier.predict(input);
+        let output = classifier.predict(input).unwrap();
         println!("{:?}", output);
     }
 }
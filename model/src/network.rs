@@ -1,16 +1,59 @@
 // builtin
+use std::io;
 
 // external
 
 // internal
-use crate::{data::data_container::DataContainer, network::config_types::Config};
+use crate::{
+    data::{data_container::DataContainer, error::DataError},
+    network::{config_types::Config, inference_model::InferenceModel},
+    node::gradcheck::{GradCheckConfig, GradCheckReport},
+    optimization::learning_schedule::LearningRateSchedule,
+};
 pub mod config_types;
+pub mod inference_model;
 pub mod types;
 
 pub trait Network {
-    fn predict(&self, input: DataContainer) -> DataContainer;
+    fn predict(&self, input: DataContainer) -> Result<DataContainer, DataError>;
 
-    fn train(&mut self, input: DataContainer, response: DataContainer);
+    // Takes one optimization step on the batch and returns its loss, summed over the examples
+    fn train(&mut self, input: DataContainer, response: DataContainer) -> Result<f32, DataError>;
 
     fn create_config(&self) -> Config;
+
+    // Rebuilds a network of this type from a saved config, e.g. to resume training
+    fn load_config(config: Config) -> io::Result<Self>
+    where
+        Self: Sized;
+
+    // Rate the next train step uses: the decay type's rate at the current time step times the
+    // schedule multiplier and any change made through set_learning_rate
+    fn get_learning_rate(&self) -> f32;
+
+    // Rescales the rate so the next train step uses `learning_rate`, see get_learning_rate
+    fn set_learning_rate(&mut self, learning_rate: f32);
+
+    // Scales the learning rate of every parameter on top of the decay type, picking up from the
+    // current time step
+    fn set_learning_schedule(&mut self, schedule: LearningRateSchedule);
+
+    // Runs a finite difference check of the loss gradients on one batch without updating weights
+    fn check_gradients(
+        &mut self,
+        input: DataContainer,
+        response: DataContainer,
+        config: &GradCheckConfig,
+    ) -> Result<GradCheckReport, DataError>;
+
+    // Per label decision thresholds of networks that predict every label on its own, the trainer
+    // scores those predictions with SupervisedExample::get_label_error
+    fn label_thresholds(&self) -> Option<&[f32]> {
+        None
+    }
+
+    // Freezes the current parameters into a thread safe model for serving predictions
+    fn freeze(&self) -> InferenceModel {
+        InferenceModel::from_config(&self.create_config())
+    }
 }
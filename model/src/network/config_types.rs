@@ -1,12 +1,20 @@
 // builtin
-use std::io::Result;
+use std::{
+    fs::{rename, write},
+    io::Result,
+    sync::atomic::{AtomicUsize, Ordering},
+};
 
 // external
 use serde::{Deserialize, Serialize};
 
 // internal
 use crate::network::{
-    types::{classifier::config::ClassifierConfig, regressor::config::RegressorConfig},
+    types::{
+        binary_classifier::config::BinaryClassifierConfig, classifier::config::ClassifierConfig,
+        graph::config::GraphConfig, regressor::config::RegressorConfig,
+        sequential::config::SequentialConfig,
+    },
     Network,
 };
 pub mod batch_norm_params;
@@ -18,22 +26,63 @@ pub mod loss_params;
 pub mod regularization_params;
 pub mod unit_params;
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub enum Config {
     Classifier(ClassifierConfig),
     Regressor(RegressorConfig),
+    Sequential(SequentialConfig),
+    Graph(GraphConfig),
+    BinaryClassifier(BinaryClassifierConfig),
     None,
 }
 
+// Configs saved before the network level learning rate existed train at the decay type's rate
+pub(crate) fn default_rate_factor() -> f32 {
+    1.0
+}
+
+// Writes a temporary file next to `path` and renames it over the target, so a concurrent reader
+// sees either the old or the new contents but never a partial file
+pub(crate) fn write_atomic(path: &str, contents: &str) -> Result<()> {
+    static TEMP_COUNT: AtomicUsize = AtomicUsize::new(0);
+    let count: usize = TEMP_COUNT.fetch_add(1, Ordering::Relaxed);
+    let temp_path: String = format!("{path}.{}-{count}.tmp", std::process::id());
+
+    write(&temp_path, contents)?;
+    rename(&temp_path, path)
+}
+
 impl Config {
     pub fn save_to_file(self, path: &str) -> Result<()> {
         match self {
             Config::Classifier(classifier_config) => classifier_config.save_to_file(path),
             Config::Regressor(regressor_config) => regressor_config.save_to_file(path),
+            Config::Sequential(sequential_config) => sequential_config.save_to_file(path),
+            Config::Graph(graph_config) => graph_config.save_to_file(path),
+            Config::BinaryClassifier(binary_config) => binary_config.save_to_file(path),
             Config::None => Ok(()),
         }
     }
 
+    pub fn timestep(&self) -> usize {
+        match self {
+            Config::Classifier(classifier_config) => classifier_config.timestep(),
+            Config::Regressor(regressor_config) => regressor_config.timestep(),
+            Config::Sequential(sequential_config) => sequential_config.timestep(),
+            Config::Graph(graph_config) => graph_config.timestep(),
+            Config::BinaryClassifier(binary_config) => binary_config.timestep(),
+            Config::None => 0,
+        }
+    }
+
+    // See Network::label_thresholds
+    pub fn label_thresholds(&self) -> Option<&[f32]> {
+        match self {
+            Config::BinaryClassifier(binary_config) => Some(binary_config.thresholds()),
+            _ => None,
+        }
+    }
+
     pub fn from_network(network: &impl Network) -> Config {
         network.create_config()
     }
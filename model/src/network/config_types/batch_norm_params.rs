@@ -6,14 +6,14 @@ use serde::{Deserialize, Serialize};
 use crate::{
     data::{
         data_container::{ContainerType, DataContainer},
-        types::FlattenedData,
+        precision::Precision,
     },
     network::config_types::layer_params::LayerParams,
 };
 
 // internal
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct BatchNormParams {
     is_null: bool,
     normalization: NormParams,
@@ -38,13 +38,14 @@ impl BatchNormParams {
     pub fn new_from_parameters(
         dim: Vec<usize>,
         decay: f32,
-        scales: Vec<f32>,
-        shifts: Vec<f32>,
+        scales: Vec<f64>,
+        shifts: Vec<f64>,
+        precision: Precision,
     ) -> BatchNormParams {
-        let normalization = NormParams::new_from_parameters(dim.clone(), decay);
+        let normalization = NormParams::new_from_parameters(dim.clone(), decay, precision);
 
-        let scales = LayerParams::new_from_parameters(dim.clone(), scales);
-        let shifts = LayerParams::new_from_parameters(dim, shifts);
+        let scales = LayerParams::new_from_parameters(dim.clone(), scales, precision);
+        let shifts = LayerParams::new_from_parameters(dim, shifts, precision);
 
         BatchNormParams {
             is_null: false,
@@ -80,12 +81,14 @@ impl BatchNormParams {
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct NormParams {
     is_null: bool,
     dim: Vec<usize>,
-    mean: Vec<f32>,
-    variance: Vec<f32>,
+    mean: Vec<f64>,
+    variance: Vec<f64>,
+    #[serde(default)]
+    precision: Precision,
     decay: f32,
 }
 
@@ -93,16 +96,14 @@ impl NormParams {
     pub fn new(mean: &DataContainer, variance: &DataContainer, decay: f32) -> NormParams {
         let dim = mean.dim().1.to_vec();
 
-        let mean_flattened: FlattenedData = mean.flatten_to_vec();
-        let variance_flattened: FlattenedData = variance.flatten_to_vec();
-
-        if let FlattenedData::Singular(mean_vec) = mean_flattened {
-            if let FlattenedData::Singular(variance_vec) = variance_flattened {
+        if let DataContainer::Parameter(mean_data) = mean {
+            if let DataContainer::Parameter(variance_data) = variance {
                 return NormParams {
                     is_null: false,
                     dim,
-                    mean: mean_vec,
-                    variance: variance_vec,
+                    mean: mean_data.flatten_as(),
+                    variance: variance_data.flatten_as(),
+                    precision: mean_data.precision(),
                     decay,
                 };
             }
@@ -111,7 +112,7 @@ impl NormParams {
         panic!("Invalid DataContainer format to coerce to parameters! Expected DataContainer::Parameter");
     }
 
-    pub fn new_from_parameters(dim: Vec<usize>, decay: f32) -> NormParams {
+    pub fn new_from_parameters(dim: Vec<usize>, decay: f32, precision: Precision) -> NormParams {
         let total_size = dim.iter().product();
 
         NormParams {
@@ -119,6 +120,7 @@ impl NormParams {
             dim,
             mean: vec![0.0; total_size],
             variance: vec![1.0; total_size],
+            precision,
             decay,
         }
     }
@@ -129,6 +131,7 @@ impl NormParams {
             dim: Vec::new(),
             mean: Vec::new(),
             variance: Vec::new(),
+            precision: Precision::default(),
             decay: 0.0,
         }
     }
@@ -139,10 +142,12 @@ impl NormParams {
 
     pub fn get_mean(&self) -> DataContainer {
         DataContainer::from_dim(&self.dim, self.mean.clone(), ContainerType::Parameter)
+            .to_precision(self.precision)
     }
 
     pub fn get_variance(&self) -> DataContainer {
         DataContainer::from_dim(&self.dim, self.variance.clone(), ContainerType::Parameter)
+            .to_precision(self.precision)
     }
 
     pub fn get_decay(&self) -> f32 {
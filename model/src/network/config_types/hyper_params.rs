@@ -4,15 +4,23 @@
 use serde::{Deserialize, Serialize};
 
 // internal
-use crate::optimization::{
-    batch_norm::NormalizationType, learning_decay::LearningDecayType, momentum::DescentType,
+use crate::{
+    data::precision::Precision,
+    optimization::{
+        batch_norm::NormalizationType, learning_decay::LearningDecayType,
+        learning_schedule::LearningRateSchedule, momentum::DescentType,
+    },
 };
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct HyperParams {
     decay_type: LearningDecayType,
     descent_type: DescentType,
     normalization_type: NormalizationType,
+    #[serde(default)]
+    precision: Precision,
+    #[serde(default)]
+    schedule: LearningRateSchedule,
 }
 
 impl HyperParams {
@@ -20,11 +28,15 @@ impl HyperParams {
         decay_type: LearningDecayType,
         descent_type: DescentType,
         normalization_type: NormalizationType,
+        precision: Precision,
+        schedule: LearningRateSchedule,
     ) -> HyperParams {
         HyperParams {
             decay_type,
             descent_type,
             normalization_type,
+            precision,
+            schedule,
         }
     }
 
@@ -39,4 +51,12 @@ impl HyperParams {
     pub fn normalization_type(&self) -> &NormalizationType {
         &self.normalization_type
     }
+
+    pub fn precision(&self) -> Precision {
+        self.precision
+    }
+
+    pub fn schedule(&self) -> &LearningRateSchedule {
+        &self.schedule
+    }
 }
@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 // internal
 use crate::unit::{types::input_unit::InputUnit, UnitContainer};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct InputParams {
     input_size: Vec<usize>,
     keep_probability: f32,
@@ -4,17 +4,22 @@
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    data::data_container::{ContainerType, DataContainer},
+    data::{
+        data_container::{ContainerType, DataContainer},
+        precision::Precision,
+    },
     optimization::{learning_decay::LearningRateParams, momentum::MomentumParams},
 };
 
 // internal
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct LayerParams {
     is_null: bool,
     dim: Vec<usize>,
-    parameters: Vec<f32>,
+    parameters: Vec<f64>,
+    #[serde(default)]
+    precision: Precision,
     momentum: MomentumParams,
     learning_rate: LearningRateParams,
 }
@@ -25,6 +30,7 @@ impl LayerParams {
             is_null: true,
             dim: Vec::new(),
             parameters: Vec::new(),
+            precision: Precision::default(),
             momentum: MomentumParams::null(),
             learning_rate: LearningRateParams::null(),
         }
@@ -32,7 +38,8 @@ impl LayerParams {
 
     pub fn new(
         dim: Vec<usize>,
-        parameters: Vec<f32>,
+        parameters: Vec<f64>,
+        precision: Precision,
         momentum: MomentumParams,
         learning_rate: LearningRateParams,
     ) -> LayerParams {
@@ -40,16 +47,22 @@ impl LayerParams {
             is_null: false,
             dim,
             parameters,
+            precision,
             momentum,
             learning_rate,
         }
     }
 
-    pub fn new_from_parameters(dim: Vec<usize>, parameters: Vec<f32>) -> LayerParams {
+    pub fn new_from_parameters(
+        dim: Vec<usize>,
+        parameters: Vec<f64>,
+        precision: Precision,
+    ) -> LayerParams {
         LayerParams {
             is_null: false,
             dim,
             parameters,
+            precision,
             momentum: MomentumParams::null(),
             learning_rate: LearningRateParams::null(),
         }
@@ -61,25 +74,43 @@ impl LayerParams {
 
     pub fn get_parameters(&self) -> DataContainer {
         DataContainer::from_dim(&self.dim, self.parameters.clone(), ContainerType::Parameter)
+            .to_precision(self.precision)
+    }
+
+    pub fn get_precision(&self) -> Precision {
+        self.precision
     }
 
     pub fn get_momentum(&self) -> DataContainer {
-        let momentum_vec: Vec<f32> = self.momentum.get_momentum();
+        let momentum_vec: Vec<f64> = self.momentum.get_momentum();
 
         if momentum_vec.is_empty() {
             return DataContainer::Empty;
         }
 
         DataContainer::from_dim(&self.dim, momentum_vec, ContainerType::Parameter)
+            .to_precision(self.precision)
     }
 
     pub fn get_learning_rate(&self) -> DataContainer {
-        let learning_vec: Vec<f32> = self.learning_rate.get_adaptive_learning_rate();
+        let learning_vec: Vec<f64> = self.learning_rate.get_adaptive_learning_rate();
 
         if learning_vec.is_empty() {
             return DataContainer::Empty;
         }
 
         DataContainer::from_dim(&self.dim, learning_vec, ContainerType::Parameter)
+            .to_precision(self.precision)
+    }
+
+    pub fn get_update_accumulator(&self) -> DataContainer {
+        let accumulator_vec: Vec<f64> = self.learning_rate.get_update_accumulator();
+
+        if accumulator_vec.is_empty() {
+            return DataContainer::Empty;
+        }
+
+        DataContainer::from_dim(&self.dim, accumulator_vec, ContainerType::Parameter)
+            .to_precision(self.precision)
     }
 }
@@ -7,7 +7,7 @@ use crate::network::config_types::{batch_norm_params::NormParams, layer_params::
 
 // internal
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub enum LearnedParams {
     Layer { params: LayerParams },
     BatchNorm { params: NormParams },
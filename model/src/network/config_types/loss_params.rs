@@ -6,7 +6,7 @@ use serde::{Deserialize, Serialize};
 // internal
 use crate::unit::{types::loss_unit::LossUnit, UnitContainer};
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct LossParams {
     pub loss_type: String,
     pub output_size: Vec<usize>,
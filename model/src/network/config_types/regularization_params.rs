@@ -9,7 +9,7 @@ use crate::regularization::penalty::{
     PenaltyConfig, PenaltyType,
 };
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct RegularizationParams {
     norm_penalty: PenaltyType,
 }
@@ -21,7 +21,7 @@ impl RegularizationParams {
         }
     }
 
-    pub fn from_builder<'a>(builder: &Box<dyn PenaltyBuilder<'a> + 'a>) -> RegularizationParams {
+    pub fn from_builder<'a>(builder: &(dyn PenaltyBuilder<'a> + 'a)) -> RegularizationParams {
         let penalty_type: PenaltyType = builder.get_associated_type();
 
         RegularizationParams::new(penalty_type)
@@ -41,3 +41,9 @@ impl RegularizationParams {
         }
     }
 }
+
+impl Default for RegularizationParams {
+    fn default() -> Self {
+        RegularizationParams::new(PenaltyType::None)
+    }
+}
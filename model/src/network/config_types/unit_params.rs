@@ -7,9 +7,10 @@ use serde::{Deserialize, Serialize};
 
 // internal
 use crate::{
-    data::data_container::DataContainer,
+    data::{data_container::DataContainer, precision::Precision},
     network::config_types::{batch_norm_params::BatchNormParams, layer_params::LayerParams},
     optimization::batch_norm::NormalizationType,
+    random::with_rng,
     regularization::dropout::UnitMaskType,
     unit::{
         types::{linear_unit::LinearUnit, softmax_unit::SoftmaxUnit},
@@ -17,7 +18,7 @@ use crate::{
     },
 };
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 #[serde(tag = "unit_type")]
 pub enum UnitParams {
     Linear {
@@ -57,6 +58,51 @@ impl UnitParams {
         }
     }
 
+    // Batch norm is set per unit, a unit without saved norm params was built without it
+    pub fn normalization_type(&self) -> NormalizationType {
+        let norm_params: &BatchNormParams = match self {
+            UnitParams::Linear { norm_params, .. } => norm_params,
+            UnitParams::Softmax { norm_params, .. } => norm_params,
+        };
+
+        if norm_params.is_null() {
+            return NormalizationType::none();
+        }
+        NormalizationType::batch_norm(norm_params.get_normalization().get_decay())
+    }
+
+    pub fn get_input_size(&self) -> usize {
+        match self {
+            UnitParams::Linear { input_size, .. } => *input_size,
+            UnitParams::Softmax { input_size, .. } => *input_size,
+        }
+    }
+
+    pub fn get_output_size(&self) -> usize {
+        match self {
+            UnitParams::Linear { output_size, .. } => *output_size,
+            UnitParams::Softmax { output_size, .. } => *output_size,
+        }
+    }
+
+    pub fn get_activation(&self) -> &str {
+        match self {
+            UnitParams::Linear { activation, .. } => activation,
+            UnitParams::Softmax { activation, .. } => activation,
+        }
+    }
+
+    pub fn get_keep_probability(&self) -> f32 {
+        match self {
+            UnitParams::Linear {
+                keep_probability, ..
+            } => *keep_probability,
+            UnitParams::Softmax {
+                keep_probability, ..
+            } => *keep_probability,
+        }
+    }
+
     pub fn type_name(&self) -> &str {
         match self {
             UnitParams::Linear { .. } => "UnitParam::Linear",
@@ -121,12 +167,13 @@ impl UnitParams {
         mask_type: UnitMaskType,
         normalization_type: NormalizationType,
         is_last_layer: bool,
+        precision: Precision,
     ) -> UnitParams {
         let weights_dim: Vec<usize> = vec![output_size, input_size];
         let weights = UnitParams::generate_new_weights(input_size, output_size);
 
         let biases: LayerParams =
-            Self::generate_biases(&normalization_type, is_last_layer, output_size);
+            Self::generate_biases(&normalization_type, is_last_layer, output_size, precision);
 
         let activation = activation_function.to_string();
 
@@ -134,12 +181,13 @@ impl UnitParams {
             normalization_type,
             vec![output_size],
             output_size,
+            precision,
         );
 
         UnitParams::Linear {
             input_size,
             output_size,
-            weights: LayerParams::new_from_parameters(weights_dim, weights),
+            weights: LayerParams::new_from_parameters(weights_dim, weights, precision),
             biases,
             activation,
             keep_probability: mask_type.probability(),
@@ -155,12 +203,13 @@ impl UnitParams {
         mask_type: UnitMaskType,
         normalization_type: NormalizationType,
         is_last_layer: bool,
+        precision: Precision,
     ) -> UnitParams {
         let weights_dim: Vec<usize> = vec![output_size, input_size];
-        let weights: Vec<f32> = UnitParams::generate_new_weights(input_size, output_size);
+        let weights: Vec<f64> = UnitParams::generate_new_weights(input_size, output_size);
 
         let biases: LayerParams =
-            Self::generate_biases(&normalization_type, is_last_layer, output_size);
+            Self::generate_biases(&normalization_type, is_last_layer, output_size, precision);
 
         let activation: String = activation_function.to_string();
 
@@ -168,12 +217,13 @@ impl UnitParams {
             normalization_type,
             vec![output_size],
             output_size,
+            precision,
         );
 
         UnitParams::Softmax {
             input_size,
             output_size,
-            weights: LayerParams::new_from_parameters(weights_dim, weights),
+            weights: LayerParams::new_from_parameters(weights_dim, weights, precision),
             biases,
             activation,
             keep_probability: mask_type.probability(),
@@ -186,21 +236,23 @@ impl UnitParams {
         normalization_type: &NormalizationType,
         is_last_layer: bool,
         size: usize,
+        precision: Precision,
     ) -> LayerParams {
         let create_batch_norm = normalization_type.is_batch_norm_enabled() && !is_last_layer;
         if create_batch_norm {
             return LayerParams::null();
-        } else {
-            let biases_dim: Vec<usize> = vec![size];
-            let biases_params: Vec<f32> = vec![0.0; size];
-            return LayerParams::new_from_parameters(biases_dim, biases_params);
         }
+
+        let biases_dim: Vec<usize> = vec![size];
+        let biases_params: Vec<f64> = vec![0.0; size];
+        LayerParams::new_from_parameters(biases_dim, biases_params, precision)
     }
 
-    fn generate_new_weights(input_size: usize, output_size: usize) -> Vec<f32> {
-        let scale = f32::sqrt(6.0 / (input_size + output_size) as f32);
-        let initial_weights: Array1<f32> =
-            Array1::random(output_size * input_size, Uniform::new(-scale, scale));
+    fn generate_new_weights(input_size: usize, output_size: usize) -> Vec<f64> {
+        let scale = f64::sqrt(6.0 / (input_size + output_size) as f64);
+        let initial_weights: Array1<f64> = with_rng(|rng| {
+            Array1::random_using(output_size * input_size, Uniform::new(-scale, scale), rng)
+        });
 
         initial_weights.to_vec()
     }
@@ -209,13 +261,14 @@ impl UnitParams {
         normalization_type: NormalizationType,
         dim: Vec<usize>,
         size: usize,
+        precision: Precision,
     ) -> BatchNormParams {
         match normalization_type {
             NormalizationType::BatchNorm { decay } => {
-                let scales: Vec<f32> = vec![1.0; size];
-                let shifts: Vec<f32> = vec![0.0; size];
+                let scales: Vec<f64> = vec![1.0; size];
+                let shifts: Vec<f64> = vec![0.0; size];
 
-                BatchNormParams::new_from_parameters(dim, decay, scales, shifts)
+                BatchNormParams::new_from_parameters(dim, decay, scales, shifts, precision)
             }
             NormalizationType::None => BatchNormParams::null(),
         }
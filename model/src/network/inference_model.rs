@@ -0,0 +1,503 @@
+// builtin
+use std::collections::HashMap;
+
+// external
+
+// internal
+use crate::{
+    data::{data_container::DataContainer, error::DataError, precision::Precision, Data},
+    network::{
+        config_types::{
+            batch_norm_params::BatchNormParams, hyper_params::HyperParams,
+            layer_params::LayerParams, unit_params::UnitParams, Config,
+        },
+        types::graph::{
+            config::{GraphConfig, GraphUnitConfig, GraphUnitParams},
+            validation::GraphLayout,
+        },
+    },
+    node::{
+        activation::activation_function::ActivationFunction,
+        types::{normalization_node::NormalizationNode, softmax_node::SoftmaxNode},
+    },
+    optimization::momentum::DescentType,
+};
+
+// Immutable copy of a trained network that can be shared across threads. Dropout is inverted
+// while training, so inference masks are all ones and are left out of the frozen layers
+pub struct InferenceModel {
+    inputs: Vec<InferenceInput>,
+    precision: Precision,
+    steps: Vec<InferenceStep>,
+    outputs: Vec<usize>,
+}
+
+struct InferenceInput {
+    name: String,
+    size: Vec<usize>,
+}
+
+// Sources index the values of the inputs followed by the results of the earlier steps
+struct InferenceStep {
+    sources: Vec<usize>,
+    operation: InferenceOperation,
+}
+
+enum InferenceOperation {
+    Layer(Box<InferenceLayer>),
+    Concat,
+    Add,
+}
+
+struct InferenceLayer {
+    weights: DataContainer,
+    biases: Option<DataContainer>,
+    normalization: Option<InferenceNorm>,
+    activation: ActivationFunction,
+    is_softmax: bool,
+}
+
+struct InferenceNorm {
+    running_mean: DataContainer,
+    running_var: DataContainer,
+    scales: DataContainer,
+    shifts: DataContainer,
+}
+
+impl InferenceModel {
+    pub fn from_config(config: &Config) -> InferenceModel {
+        match config {
+            Config::Classifier(classifier) => InferenceModel::from_params(
+                classifier.input().get_input_size(),
+                classifier.units(),
+                classifier.params(),
+            ),
+            Config::Regressor(regressor) => InferenceModel::from_params(
+                regressor.input().get_input_size(),
+                regressor.units(),
+                regressor.params(),
+            ),
+            Config::Sequential(sequential) => InferenceModel::from_params(
+                sequential.input().get_input_size(),
+                sequential.units(),
+                sequential.params(),
+            ),
+            Config::BinaryClassifier(binary_classifier) => InferenceModel::from_params(
+                binary_classifier.network().input().get_input_size(),
+                binary_classifier.network().units(),
+                binary_classifier.network().params(),
+            ),
+            Config::Graph(graph) => InferenceModel::from_graph(graph),
+            Config::None => panic!("[INFERENCE] Cannot freeze an empty network config"),
+        }
+    }
+
+    fn from_params(
+        input_size: &[usize],
+        units: &[UnitParams],
+        params: &HyperParams,
+    ) -> InferenceModel {
+        let steps: Vec<InferenceStep> = units
+            .iter()
+            .enumerate()
+            .map(|(position, unit)| InferenceStep {
+                sources: vec![position],
+                operation: InferenceOperation::Layer(Box::new(InferenceLayer::from_params(
+                    unit, params,
+                ))),
+            })
+            .collect();
+
+        InferenceModel {
+            inputs: vec![InferenceInput {
+                name: "input".to_string(),
+                size: input_size.to_vec(),
+            }],
+            precision: params.precision(),
+            outputs: vec![steps.len()],
+            steps,
+        }
+    }
+
+    fn from_graph(config: &GraphConfig) -> InferenceModel {
+        let layout: GraphLayout = match config.validate() {
+            Ok(layout) => layout,
+            Err(error) => panic!("[INFERENCE] Cannot freeze an invalid graph config: {error}"),
+        };
+
+        let inputs: Vec<InferenceInput> = config
+            .inputs()
+            .iter()
+            .map(|input| InferenceInput {
+                name: input.name.clone(),
+                size: input.input_size.clone(),
+            })
+            .collect();
+        let mut slots: HashMap<&str, usize> = config
+            .inputs()
+            .iter()
+            .enumerate()
+            .map(|(slot, input)| (input.name.as_str(), slot))
+            .collect();
+
+        let mut steps: Vec<InferenceStep> = Vec::new();
+        for &position in &layout.order {
+            let unit: &GraphUnitConfig = &config.units()[position];
+
+            let operation: InferenceOperation = match &unit.unit {
+                GraphUnitParams::Linear(spec) | GraphUnitParams::Softmax(spec) => {
+                    match &spec.params {
+                        Some(unit_params) => InferenceOperation::Layer(Box::new(
+                            InferenceLayer::from_params(unit_params, config.params()),
+                        )),
+                        None => panic!(
+                            "[INFERENCE] Cannot freeze `{}` without trained params",
+                            unit.name
+                        ),
+                    }
+                }
+                GraphUnitParams::Concat => InferenceOperation::Concat,
+                GraphUnitParams::Add => InferenceOperation::Add,
+            };
+
+            steps.push(InferenceStep {
+                sources: unit
+                    .inputs
+                    .iter()
+                    .map(|name| slots[name.as_str()])
+                    .collect(),
+                operation,
+            });
+            slots.insert(&unit.name, inputs.len() + steps.len() - 1);
+        }
+
+        InferenceModel {
+            outputs: config
+                .heads()
+                .iter()
+                .map(|head| slots[head.unit.as_str()])
+                .collect(),
+            inputs,
+            precision: config.params().precision(),
+            steps,
+        }
+    }
+
+    // Size of the first input, the only one of models frozen from a sequential network
+    pub fn input_size(&self) -> &[usize] {
+        &self.inputs[0].size
+    }
+
+    pub fn input_names(&self) -> Vec<&str> {
+        self.inputs
+            .iter()
+            .map(|input| input.name.as_str())
+            .collect()
+    }
+
+    pub fn precision(&self) -> Precision {
+        self.precision
+    }
+
+    pub fn predict(&self, input: &Data) -> Data {
+        self.try_predict(input).unwrap_or_else(|err| {
+            println!("[INFERENCE] Data::None returned on {err}");
+            Data::None
+        })
+    }
+
+    // Output of the first head
+    pub fn try_predict(&self, input: &Data) -> Result<Data, DataError> {
+        let mut outputs: Vec<Data> = self.try_predict_all(std::slice::from_ref(input))?;
+        Ok(outputs.swap_remove(0))
+    }
+
+    // Takes the inputs in the order of input_names and returns the output of every head
+    pub fn try_predict_all(&self, inputs: &[Data]) -> Result<Vec<Data>, DataError> {
+        if let Some(missing) = self.inputs.get(inputs.len()) {
+            return Err(DataError::MissingData {
+                operation: "INFERENCE",
+                name: missing.name.clone(),
+            });
+        }
+
+        let mut values: Vec<DataContainer> = inputs
+            .iter()
+            .map(|input| DataContainer::Inference(input.clone()).to_precision(self.precision))
+            .collect();
+
+        for step in &self.steps {
+            let value: DataContainer = step.apply(&values)?;
+            values.push(value);
+        }
+
+        self.outputs
+            .iter()
+            .map(|&slot| match &values[slot] {
+                DataContainer::Inference(output) => Ok(output.clone()),
+                other => Err(DataError::unsupported_containers(
+                    "INFERENCE",
+                    other,
+                    &DataContainer::Empty,
+                )),
+            })
+            .collect()
+    }
+}
+
+impl InferenceStep {
+    fn apply(&self, values: &[DataContainer]) -> Result<DataContainer, DataError> {
+        let first: DataContainer = values[self.sources[0]].clone();
+
+        match &self.operation {
+            InferenceOperation::Layer(layer) => layer.apply(first),
+            InferenceOperation::Concat => {
+                let parts: Vec<DataContainer> = self
+                    .sources
+                    .iter()
+                    .map(|&slot| values[slot].clone())
+                    .collect();
+                DataContainer::try_concat(&parts)
+            }
+            InferenceOperation::Add => self.sources[1..]
+                .iter()
+                .try_fold(first, |sum, &slot| sum.try_plus(&values[slot])),
+        }
+    }
+}
+
+impl InferenceLayer {
+    fn from_params(unit: &UnitParams, params: &HyperParams) -> InferenceLayer {
+        let (weights, biases, activation, is_last_layer, norm_params) = match unit {
+            UnitParams::Linear {
+                weights,
+                biases,
+                activation,
+                is_last_layer,
+                norm_params,
+                ..
+            }
+            | UnitParams::Softmax {
+                weights,
+                biases,
+                activation,
+                is_last_layer,
+                norm_params,
+                ..
+            } => (weights, biases, activation, is_last_layer, norm_params),
+        };
+
+        let descent_type: &DescentType = params.descent_type();
+        let batch_norm_enabled: bool =
+            unit.normalization_type().is_batch_norm_enabled() && !is_last_layer;
+
+        let (biases, normalization) = if batch_norm_enabled {
+            (
+                None,
+                Some(InferenceNorm::from_params(norm_params, descent_type)),
+            )
+        } else {
+            (Some(InferenceLayer::lookahead(biases, descent_type)), None)
+        };
+
+        InferenceLayer {
+            weights: InferenceLayer::lookahead(weights, descent_type),
+            biases,
+            normalization,
+            activation: ActivationFunction::new(activation),
+            is_softmax: matches!(unit, UnitParams::Softmax { .. }),
+        }
+    }
+
+    // Weight and bias nodes read through the nesterov lookahead, so it is baked into the parameters
+    fn lookahead(layer: &LayerParams, descent_type: &DescentType) -> DataContainer {
+        let mut parameters: DataContainer = layer.get_parameters();
+        let momentum: DataContainer = layer.get_momentum();
+
+        if let (DescentType::Nesterov { .. }, DataContainer::Parameter(_)) =
+            (descent_type, &momentum)
+        {
+            if let Err(err) = parameters.try_sum_assign(&momentum) {
+                println!("[INFERENCE] Skipping nesterov lookahead on {err}");
+            }
+        }
+
+        parameters
+    }
+
+    // Mirrors the node order of the linear and softmax units
+    fn apply(&self, input: DataContainer) -> Result<DataContainer, DataError> {
+        let mut data: DataContainer = self.weights.try_matmul(&input)?;
+
+        if let Some(biases) = &self.biases {
+            data = data.try_plus(biases)?;
+        }
+
+        if let Some(normalization) = &self.normalization {
+            data = normalization.apply(data)?;
+        }
+
+        data = data.apply_elementwise(self.activation.applied());
+
+        if self.is_softmax {
+            data = data.apply_function(SoftmaxNode::softmax);
+        }
+
+        Ok(data)
+    }
+}
+
+impl InferenceNorm {
+    fn from_params(params: &BatchNormParams, descent_type: &DescentType) -> InferenceNorm {
+        let normalization = params.get_normalization();
+        let (running_mean, running_var) = if normalization.is_null() {
+            (DataContainer::zero(), DataContainer::one())
+        } else {
+            (normalization.get_mean(), normalization.get_variance())
+        };
+
+        InferenceNorm {
+            running_mean,
+            running_var,
+            scales: InferenceLayer::lookahead(params.get_scales(), descent_type),
+            shifts: InferenceLayer::lookahead(params.get_shifts(), descent_type),
+        }
+    }
+
+    fn apply(&self, data: DataContainer) -> Result<DataContainer, DataError> {
+        let normalized =
+            NormalizationNode::normalize_running(data, &self.running_mean, &self.running_var)?;
+
+        normalized.try_times(&self.scales)?.try_plus(&self.shifts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, thread};
+
+    use ndarray::arr1;
+    use rand::{distributions::Uniform, prelude::Distribution};
+
+    use crate::{
+        data::{data_container::DataContainer, precision::Precision, Data},
+        network::{
+            config_types::{hyper_params::HyperParams, Config},
+            inference_model::InferenceModel,
+            types::classifier::ClassifierNetwork,
+            types::regressor::RegressorNetwork,
+            Network,
+        },
+        optimization::{
+            batch_norm::NormalizationType, learning_decay::LearningDecayType,
+            learning_schedule::LearningRateSchedule, momentum::DescentType,
+        },
+        regularization::{
+            dropout::NetworkMaskType,
+            penalty::{l2_penalty::builder::L2PenaltyBuilder, PenaltyConfig},
+        },
+    };
+
+    fn assert_send_sync<T: Send + Sync>() {}
+
+    fn assert_matches(network: &impl Network, model: &InferenceModel, inputs: &[f32]) {
+        for x in inputs {
+            let input: Data = Data::VectorF32(arr1(&[*x]));
+            let expected = network
+                .predict(DataContainer::Inference(input.clone()))
+                .unwrap();
+            let frozen: Data = model.try_predict(&input).unwrap();
+
+            if let DataContainer::Inference(expected) = expected {
+                assert_eq!(expected.precision(), frozen.precision());
+                assert_eq!(expected.flatten_as::<f64>(), frozen.flatten_as::<f64>());
+            } else {
+                panic!("Expected an inference output from the network");
+            }
+        }
+    }
+
+    #[test]
+    fn frozen_classifier_matches_network() {
+        assert_send_sync::<InferenceModel>();
+
+        let mut classifier: ClassifierNetwork = ClassifierNetwork::new(
+            vec![1],
+            vec![2],
+            vec![4],
+            PenaltyConfig::new(L2PenaltyBuilder::new(0.001)),
+            NetworkMaskType::from_probabilities(0.9, 0.8),
+            HyperParams::new(
+                LearningDecayType::rms_prop(0.05, 0.95),
+                DescentType::nesterov(0.4),
+                NormalizationType::batch_norm(0.95),
+                Precision::F32,
+                LearningRateSchedule::constant(),
+            ),
+        );
+
+        let mut rng = rand::thread_rng();
+        let distribution = Uniform::new(-1.0, 1.0);
+
+        for _i in 0..50 {
+            let mut inputs = Vec::new();
+            let mut responses = Vec::new();
+
+            for _j in 0..8 {
+                let x: f32 = distribution.sample(&mut rng);
+                let response = if x < 0.0 { [1.0, 0.0] } else { [0.0, 1.0] };
+
+                inputs.push(Data::VectorF32(arr1(&[x])));
+                responses.push(Data::VectorF32(arr1(&response)));
+            }
+
+            classifier
+                .train(
+                    DataContainer::batch(inputs),
+                    DataContainer::batch(responses),
+                )
+                .unwrap();
+        }
+
+        let inputs: [f32; 4] = [-0.7, -0.1, 0.3, 0.9];
+        let model: InferenceModel = classifier.freeze();
+        assert_matches(&classifier, &model, &inputs);
+
+        let model: InferenceModel = InferenceModel::from_config(&Config::from_network(&classifier));
+        assert_matches(&classifier, &model, &inputs);
+    }
+
+    #[test]
+    fn frozen_regressor_across_threads() {
+        let regressor: RegressorNetwork = RegressorNetwork::new(
+            vec![1],
+            vec![1],
+            vec![6, 3],
+            PenaltyConfig::new(L2PenaltyBuilder::new(0.2)),
+            NetworkMaskType::None,
+            HyperParams::new(
+                LearningDecayType::constant(0.005),
+                DescentType::Base,
+                NormalizationType::none(),
+                Precision::F64,
+                LearningRateSchedule::constant(),
+            ),
+        );
+
+        let model: Arc<InferenceModel> = Arc::new(regressor.freeze());
+        assert_matches(&regressor, &model, &[1.0, 2.5]);
+
+        let expected: Data = model.predict(&Data::VectorF32(arr1(&[2.0])));
+        let handles: Vec<thread::JoinHandle<Data>> = (0..4)
+            .map(|_i| {
+                let model = Arc::clone(&model);
+                thread::spawn(move || model.predict(&Data::VectorF32(arr1(&[2.0]))))
+            })
+            .collect();
+
+        for handle in handles {
+            let output: Data = handle.join().unwrap();
+            assert_eq!(output.flatten_as::<f64>(), expected.flatten_as::<f64>());
+        }
+    }
+}
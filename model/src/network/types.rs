@@ -3,6 +3,10 @@
 // external
 
 // internal
+pub mod binary_classifier;
 pub mod classifier;
+pub mod graph;
+pub mod layer_builder;
 pub mod regressor;
+pub mod sequential;
 pub mod test_network;
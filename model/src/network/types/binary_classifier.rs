@@ -0,0 +1,270 @@
+// builtin
+use std::io;
+
+// external
+
+// internal
+use crate::{
+    data::{data_container::DataContainer, error::DataError, Data},
+    network::{
+        config_types::Config,
+        types::{
+            binary_classifier::config::BinaryClassifierConfig,
+            sequential::{sequential_builder::SequentialBuilder, SequentialNetwork},
+        },
+        Network,
+    },
+    node::gradcheck::{GradCheckConfig, GradCheckReport},
+    optimization::learning_schedule::LearningRateSchedule,
+};
+pub mod config;
+
+// A label is predicted once its probability reaches the threshold
+pub fn apply_thresholds(probabilities: &[f32], thresholds: &[f32]) -> Vec<bool> {
+    probabilities
+        .iter()
+        .zip(thresholds)
+        .map(|(probability, threshold)| probability >= threshold)
+        .collect()
+}
+
+// Independent yes / no decision per label, so any number of labels can be present at once. A
+// single label gives a plain binary classifier
+pub struct BinaryClassifierNetwork<'a> {
+    network: SequentialNetwork<'a>,
+    thresholds: Vec<f32>,
+}
+
+impl<'a> BinaryClassifierNetwork<'a> {
+    // See BinaryClassifierConfig::new, e.g.
+    // `BinaryClassifierNetwork::new(SequentialNetwork::builder().input(vec![4]).linear(8, "relu"), 3)`
+    pub fn new(hidden: SequentialBuilder, label_count: usize) -> BinaryClassifierNetwork<'a> {
+        let config: BinaryClassifierConfig = BinaryClassifierConfig::new(hidden, label_count);
+
+        BinaryClassifierNetwork {
+            network: SequentialNetwork::from_config(config.network().clone()),
+            thresholds: config.thresholds().clone(),
+        }
+    }
+
+    // Fails on a config with a different number of thresholds than labels, e.g. a hand edited file
+    pub fn from_config(config: BinaryClassifierConfig) -> io::Result<BinaryClassifierNetwork<'a>> {
+        let label_count: usize = config.label_count();
+        if config.thresholds().len() != label_count {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "[BINARY_CLASSIFIER] Expected {label_count} thresholds but got {}",
+                    config.thresholds().len()
+                ),
+            ));
+        }
+
+        Ok(BinaryClassifierNetwork {
+            network: SequentialNetwork::from_config(config.network().clone()),
+            thresholds: config.thresholds().clone(),
+        })
+    }
+
+    pub fn load_from_file(path: &str) -> io::Result<BinaryClassifierNetwork<'a>> {
+        let config: BinaryClassifierConfig = BinaryClassifierConfig::load_from_file(path)?;
+        BinaryClassifierNetwork::from_config(config)
+    }
+
+    pub fn save_to_file(&self, path: &str) -> io::Result<()> {
+        let config: BinaryClassifierConfig = BinaryClassifierConfig::from_network(self);
+        config.save_to_file(path)
+    }
+
+    pub fn label_count(&self) -> usize {
+        self.thresholds.len()
+    }
+
+    pub fn thresholds(&self) -> &[f32] {
+        &self.thresholds
+    }
+
+    // One threshold per label, e.g. tuned on validation data to trade precision for recall
+    pub fn set_thresholds(&mut self, thresholds: Vec<f32>) {
+        if thresholds.len() != self.label_count() {
+            panic!(
+                "[BINARY_CLASSIFIER] Expected {} thresholds but got {}",
+                self.label_count(),
+                thresholds.len()
+            );
+        }
+
+        self.thresholds = thresholds;
+    }
+
+    pub fn apply_thresholds(&self, probabilities: &[f32]) -> Vec<bool> {
+        apply_thresholds(probabilities, &self.thresholds)
+    }
+
+    // Thresholded labels of every example, a single example for an inference input
+    pub fn predict_labels(&self, input: DataContainer) -> Result<Vec<Vec<bool>>, DataError> {
+        let probabilities: Vec<Data> = match self.network.predict(input)? {
+            DataContainer::Inference(data) => vec![data],
+            batch @ DataContainer::Batch(_) => batch.batch_to_vec(),
+            other => {
+                return Err(DataError::unsupported_containers(
+                    "BINARY_CLASSIFIER",
+                    &other,
+                    &DataContainer::Empty,
+                ))
+            }
+        };
+
+        Ok(probabilities
+            .iter()
+            .map(|data| self.apply_thresholds(&data.flatten_as::<f32>()))
+            .collect())
+    }
+}
+
+impl Network for BinaryClassifierNetwork<'_> {
+    // Probability of every label, see predict_labels for the thresholded labels
+    fn predict(&self, input: DataContainer) -> Result<DataContainer, DataError> {
+        self.network.predict(input)
+    }
+
+    fn train(&mut self, input: DataContainer, response: DataContainer) -> Result<f32, DataError> {
+        self.network.train(input, response)
+    }
+
+    fn create_config(&self) -> Config {
+        Config::BinaryClassifier(BinaryClassifierConfig::from_network(self))
+    }
+
+    fn load_config(config: Config) -> io::Result<Self> {
+        match config {
+            Config::BinaryClassifier(binary_config) => {
+                BinaryClassifierNetwork::from_config(binary_config)
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Network config did not match the requested network type",
+            )),
+        }
+    }
+
+    fn get_learning_rate(&self) -> f32 {
+        self.network.get_learning_rate()
+    }
+
+    fn set_learning_rate(&mut self, learning_rate: f32) {
+        self.network.set_learning_rate(learning_rate);
+    }
+
+    fn set_learning_schedule(&mut self, schedule: LearningRateSchedule) {
+        self.network.set_learning_schedule(schedule);
+    }
+
+    fn label_thresholds(&self) -> Option<&[f32]> {
+        Some(&self.thresholds)
+    }
+
+    fn check_gradients(
+        &mut self,
+        input: DataContainer,
+        response: DataContainer,
+        config: &GradCheckConfig,
+    ) -> Result<GradCheckReport, DataError> {
+        self.network.check_gradients(input, response, config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+
+    use ndarray::arr1;
+    use rand::{distributions::Uniform, prelude::Distribution};
+
+    use crate::{
+        data::{data_container::DataContainer, Data},
+        network::{
+            config_types::Config,
+            types::{
+                binary_classifier::BinaryClassifierNetwork, layer_builder::LayerBuilder,
+                sequential::SequentialNetwork,
+            },
+            Network,
+        },
+        optimization::learning_decay::LearningDecayType,
+        random::{set_seed, with_rng},
+    };
+
+    // Label i is present when x_i is positive, so both labels can be on at once
+    fn labels(x: &[f32; 2]) -> [f32; 2] {
+        x.map(|value| if value > 0.0 { 1.0 } else { 0.0 })
+    }
+
+    #[test]
+    fn multi_label_classifier() {
+        set_seed(23);
+
+        let hidden = SequentialNetwork::builder()
+            .input(vec![2])
+            .linear(8, "relu")
+            .decay(LearningDecayType::rms_prop(0.02, 0.9));
+        let mut network: BinaryClassifierNetwork = BinaryClassifierNetwork::new(hidden, 2);
+        assert_eq!(network.label_count(), 2);
+        assert_eq!(network.thresholds(), &[0.5, 0.5]);
+
+        let distribution = Uniform::new(-1.0, 1.0);
+        for _i in 0..300 {
+            let xs: Vec<[f32; 2]> = with_rng(|rng| {
+                (0..8)
+                    .map(|_j| [distribution.sample(rng), distribution.sample(rng)])
+                    .collect()
+            });
+            let inputs = xs.iter().map(|x| Data::VectorF32(arr1(x))).collect();
+            let responses = xs
+                .iter()
+                .map(|x| Data::VectorF32(arr1(&labels(x))))
+                .collect();
+
+            network
+                .train(
+                    DataContainer::batch(inputs),
+                    DataContainer::batch(responses),
+                )
+                .unwrap();
+        }
+
+        let tests: [[f32; 2]; 4] = [[-0.7, -0.6], [-0.8, 0.7], [0.6, -0.9], [0.8, 0.7]];
+        let inputs = tests.iter().map(|x| Data::VectorF32(arr1(x))).collect();
+        let predicted: Vec<Vec<bool>> = network
+            .predict_labels(DataContainer::batch(inputs))
+            .unwrap();
+
+        let expected: Vec<Vec<bool>> = tests
+            .iter()
+            .map(|x| labels(x).iter().map(|label| *label > 0.5).collect())
+            .collect();
+        assert_eq!(predicted, expected);
+
+        // An unreachable threshold switches the first label off everywhere
+        let mut loaded: BinaryClassifierNetwork =
+            BinaryClassifierNetwork::load_config(network.create_config()).unwrap();
+        loaded.set_thresholds(vec![1.1, 0.5]);
+        let loaded: BinaryClassifierNetwork =
+            BinaryClassifierNetwork::load_config(loaded.create_config()).unwrap();
+        assert_eq!(loaded.thresholds(), &[1.1, 0.5]);
+
+        let single = DataContainer::Inference(Data::VectorF32(arr1(&[0.8, 0.7])));
+        assert_eq!(
+            loaded.predict_labels(single).unwrap(),
+            vec![vec![false, true]]
+        );
+
+        let mut json = serde_json::to_value(loaded.create_config()).unwrap();
+        json["BinaryClassifier"]["thresholds"] = serde_json::json!([0.5]);
+        let mismatched: Config = serde_json::from_value(json).unwrap();
+        let err = BinaryClassifierNetwork::load_config(mismatched)
+            .err()
+            .unwrap();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,92 @@
+// builtin
+use std::{
+    fs::read_to_string,
+    io::{Error, ErrorKind, Result},
+};
+
+// external
+use serde::{Deserialize, Serialize};
+
+// internal
+use crate::network::{
+    config_types::{write_atomic, Config},
+    types::{
+        binary_classifier::BinaryClassifierNetwork,
+        sequential::{config::SequentialConfig, sequential_builder::SequentialBuilder},
+    },
+};
+
+const DEFAULT_THRESHOLD: f32 = 0.5;
+
+// A sequential stack ending in one sigmoid output per label, with the threshold each label's
+// probability has to reach to be predicted
+#[derive(Serialize, Deserialize, Clone)]
+pub struct BinaryClassifierConfig {
+    network: SequentialConfig,
+    thresholds: Vec<f32>,
+}
+
+impl BinaryClassifierConfig {
+    // `hidden` sets the input, the hidden units and the training settings, the sigmoid output
+    // unit with one value per label and its loss are added here
+    pub fn new(hidden: SequentialBuilder, label_count: usize) -> BinaryClassifierConfig {
+        let network: SequentialConfig = hidden
+            .linear(label_count, "sigmoid")
+            .loss("binary_cross_entropy")
+            .config();
+
+        BinaryClassifierConfig {
+            network,
+            thresholds: vec![DEFAULT_THRESHOLD; label_count],
+        }
+    }
+
+    pub fn from_network(network: &BinaryClassifierNetwork) -> BinaryClassifierConfig {
+        BinaryClassifierConfig {
+            network: SequentialConfig::from_network(&network.network),
+            thresholds: network.thresholds.clone(),
+        }
+    }
+
+    pub fn save_to_file(self, path: &str) -> Result<()> {
+        let config: Config = Config::BinaryClassifier(self);
+        let json_string = serde_json::to_string_pretty(&config).unwrap();
+        write_atomic(path, &json_string)
+    }
+
+    pub fn load_from_file(path: &str) -> Result<BinaryClassifierConfig> {
+        let data = read_to_string(path)?;
+        let config: Config =
+            serde_json::from_str(&data).expect("Invalid JSON data for network configuration");
+
+        if let Config::BinaryClassifier(binary_config) = config {
+            return Ok(binary_config);
+        }
+
+        Err(Error::new(
+            ErrorKind::InvalidData,
+            "JSON network data did not match the requested network type",
+        ))
+    }
+
+    pub fn network(&self) -> &SequentialConfig {
+        &self.network
+    }
+
+    pub fn thresholds(&self) -> &Vec<f32> {
+        &self.thresholds
+    }
+
+    // Width of the sigmoid output unit
+    pub fn label_count(&self) -> usize {
+        self.network
+            .units()
+            .last()
+            .map(|unit| unit.get_output_size())
+            .unwrap_or(0)
+    }
+
+    pub fn timestep(&self) -> usize {
+        self.network.timestep()
+    }
+}
@@ -1,45 +1,25 @@
 // builtin
+use std::io;
 
 // external
 
-use std::io::Result;
-
 // internal
 use crate::{
-    data::data_container::DataContainer,
+    data::{data_container::DataContainer, error::DataError},
     network::{
-        config_types::Config,
-        types::classifier::{builder::build_from_config, config::ClassifierConfig},
+        config_types::{hyper_params::HyperParams, Config},
+        types::{classifier::config::ClassifierConfig, sequential::SequentialNetwork},
         Network,
     },
-    optimization::{
-        batch_norm::NormalizationType, learning_decay::LearningDecayType, momentum::DescentType,
-    },
-    regularization::{
-        dropout::{NetworkMaskType, NetworkMode},
-        penalty::{PenaltyConfig, PenaltyType},
-    },
-    unit::{
-        types::{
-            input_unit::InputUnit, linear_unit::LinearUnit, loss_unit::LossUnit,
-            softmax_unit::SoftmaxUnit,
-        },
-        Unit, UnitContainer,
-    },
+    node::gradcheck::{GradCheckConfig, GradCheckReport},
+    optimization::learning_schedule::LearningRateSchedule,
+    regularization::{dropout::NetworkMaskType, penalty::PenaltyConfig},
 };
-pub mod builder;
 pub mod config;
 
+// A sequential network of relu units and a softmax output, kept for its config format
 pub struct ClassifierNetwork<'a> {
-    input: UnitContainer<'a, InputUnit<'a>>,
-    hidden: Vec<UnitContainer<'a, LinearUnit<'a>>>,
-    inference: UnitContainer<'a, SoftmaxUnit<'a>>,
-    loss: UnitContainer<'a, LossUnit<'a>>,
-    penalty_type: PenaltyType,
-    decay_type: LearningDecayType,
-    descent_type: DescentType,
-    normalization_type: NormalizationType,
-    time_step: usize,
+    network: SequentialNetwork<'a>,
 }
 
 impl<'a> ClassifierNetwork<'a> {
@@ -49,9 +29,7 @@ impl<'a> ClassifierNetwork<'a> {
         hidden_sizes: Vec<usize>,
         penalty_config: PenaltyConfig,
         mask_type: NetworkMaskType,
-        decay_type: LearningDecayType,
-        descent_type: DescentType,
-        normalization_type: NormalizationType,
+        hyperparams: HyperParams,
     ) -> ClassifierNetwork<'a> {
         let config: ClassifierConfig = ClassifierConfig::new(
             input_size,
@@ -59,9 +37,7 @@ impl<'a> ClassifierNetwork<'a> {
             hidden_sizes,
             penalty_config,
             mask_type,
-            decay_type,
-            descent_type,
-            normalization_type,
+            hyperparams,
         );
 
         ClassifierNetwork::from_config(config)
@@ -72,54 +48,62 @@ impl<'a> ClassifierNetwork<'a> {
         ClassifierNetwork::from_config(config)
     }
 
-    pub fn save_to_file(&self, path: &str) -> Result<()> {
-        let config: ClassifierConfig = ClassifierConfig::from_network(&self);
+    pub fn save_to_file(&self, path: &str) -> io::Result<()> {
+        let config: ClassifierConfig = ClassifierConfig::from_network(self);
         config.save_to_file(path)
     }
 
     fn from_config(config: ClassifierConfig) -> ClassifierNetwork<'a> {
-        build_from_config(config)
+        ClassifierNetwork {
+            network: SequentialNetwork::from_config(config.into()),
+        }
     }
 }
 
 impl Network for ClassifierNetwork<'_> {
-    fn predict(&self, input: DataContainer) -> DataContainer {
-        self.input.update_mode(NetworkMode::Inference);
-
-        self.input.borrow_mut().set_input_data(input);
-
-        let inference_ref = self.inference.borrow();
-        let inference_node = inference_ref.get_output_node();
-
-        inference_node.borrow_mut().apply_operation();
-
-        let output = inference_node.borrow_mut().get_data();
-
-        output
+    fn predict(&self, input: DataContainer) -> Result<DataContainer, DataError> {
+        self.network.predict(input)
     }
 
-    fn train(&mut self, input: DataContainer, response: DataContainer) {
-        self.input.update_mode(NetworkMode::Train);
-
-        self.time_step += 1;
+    fn train(&mut self, input: DataContainer, response: DataContainer) -> Result<f32, DataError> {
+        self.network.train(input, response)
+    }
 
-        self.input.borrow().set_input_data(input);
-        self.loss.borrow().set_expected_response(response);
+    fn create_config(&self) -> Config {
+        Config::Classifier(ClassifierConfig::from_network(self))
+    }
 
-        let loss_ref = self.loss.borrow();
-        let loss_node = loss_ref.get_output_node();
+    fn load_config(config: Config) -> io::Result<Self> {
+        match config {
+            Config::Classifier(classifier_config) => {
+                Ok(ClassifierNetwork::from_config(classifier_config))
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Network config did not match the requested network type",
+            )),
+        }
+    }
 
-        loss_node.borrow_mut().apply_operation();
+    fn get_learning_rate(&self) -> f32 {
+        self.network.get_learning_rate()
+    }
 
-        loss_node.borrow_mut().add_gradient(&DataContainer::one());
-        loss_node.borrow_mut().apply_jacobian();
+    fn set_learning_rate(&mut self, learning_rate: f32) {
+        self.network.set_learning_rate(learning_rate);
+    }
 
-        self.decay_type.update_timestep(self.time_step);
+    fn set_learning_schedule(&mut self, schedule: LearningRateSchedule) {
+        self.network.set_learning_schedule(schedule);
     }
 
-    fn create_config(&self) -> Config {
-        let classifier_config = ClassifierConfig::from_network(self);
-        Config::Classifier(classifier_config)
+    fn check_gradients(
+        &mut self,
+        input: DataContainer,
+        response: DataContainer,
+        config: &GradCheckConfig,
+    ) -> Result<GradCheckReport, DataError> {
+        self.network.check_gradients(input, response, config)
     }
 }
 
@@ -129,10 +113,13 @@ mod tests {
     use rand::{distributions::Uniform, prelude::Distribution};
 
     use crate::{
-        data::{data_container::DataContainer, Data},
-        network::{types::classifier::ClassifierNetwork, Network},
+        data::{data_container::DataContainer, precision::Precision, Data},
+        network::{
+            config_types::hyper_params::HyperParams, types::classifier::ClassifierNetwork, Network,
+        },
         optimization::{
-            batch_norm::NormalizationType, learning_decay::LearningDecayType, momentum::DescentType,
+            batch_norm::NormalizationType, learning_decay::LearningDecayType,
+            learning_schedule::LearningRateSchedule, momentum::DescentType,
         },
         regularization::{
             dropout::NetworkMaskType,
@@ -151,14 +138,18 @@ mod tests {
             vec![2],
             penalty_config,
             NetworkMaskType::from_probabilities(0.9, 0.9),
-            LearningDecayType::rms_prop(0.05, 0.95),
-            DescentType::nesterov(0.4),
-            NormalizationType::batch_norm(0.95),
+            HyperParams::new(
+                LearningDecayType::rms_prop(0.05, 0.95),
+                DescentType::nesterov(0.4),
+                NormalizationType::batch_norm(0.95),
+                Precision::F32,
+                LearningRateSchedule::constant(),
+            ),
         );
 
         let test_arr: Array1<f32> = arr1(&[-0.7]);
         let before_data = DataContainer::Inference(Data::VectorF32(test_arr.clone()));
-        let before_output = classifier.predict(before_data);
+        let before_output = classifier.predict(before_data).unwrap();
         println!("Before: {:?}", before_output);
 
         let mut rng = rand::thread_rng();
@@ -186,19 +177,19 @@ mod tests {
                 }
             }
 
-            let input = DataContainer::Batch(inputs);
-            let response = DataContainer::Batch(responses);
+            let input = DataContainer::batch(inputs);
+            let response = DataContainer::batch(responses);
 
-            classifier.train(input, response);
+            classifier.train(input, response).unwrap();
         }
 
         let after_data = DataContainer::Inference(Data::VectorF32(test_arr.clone()));
-        let after_output = classifier.predict(after_data);
+        let after_output = classifier.predict(after_data).unwrap();
         println!("After: {:?}", after_output);
 
         let test_arr2: Array1<f32> = arr1(&[0.6]);
         let after_data2 = DataContainer::Inference(Data::VectorF32(test_arr2.clone()));
-        let after_output2 = classifier.predict(after_data2);
+        let after_output2 = classifier.predict(after_data2).unwrap();
         println!("After 2: {:?}", after_output2);
 
         classifier
@@ -213,12 +204,12 @@ mod tests {
 
         let test_arr: Array1<f32> = arr1(&[-0.7]);
         let after_data = DataContainer::Inference(Data::VectorF32(test_arr.clone()));
-        let after_output = classifier.predict(after_data);
+        let after_output = classifier.predict(after_data).unwrap();
         println!("Loaded output 1: {:?}", after_output);
 
         let test_arr2: Array1<f32> = arr1(&[0.6]);
         let after_data2 = DataContainer::Inference(Data::VectorF32(test_arr2.clone()));
-        let after_output2 = classifier.predict(after_data2);
+        let after_output2 = classifier.predict(after_data2).unwrap();
         println!("Loaded output 2: {:?}", after_output2);
 
         let mut inputs = Vec::new();
@@ -245,9 +236,9 @@ mod tests {
             }
         }
 
-        let input = DataContainer::Batch(inputs);
-        let response = DataContainer::Batch(responses);
+        let input = DataContainer::batch(inputs);
+        let response = DataContainer::batch(responses);
 
-        classifier.train(input, response);
+        classifier.train(input, response).unwrap();
     }
 }
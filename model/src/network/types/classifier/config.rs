@@ -1,6 +1,6 @@
 // builtin
 use std::{
-    fs::{read_to_string, write},
+    fs::read_to_string,
     io::{Error, ErrorKind, Result},
 };
 
@@ -9,23 +9,23 @@ use serde::{Deserialize, Serialize};
 
 // internal
 use crate::{
+    data::precision::Precision,
     network::{
         config_types::{
-            hyper_params::HyperParams, input_params::InputParams, loss_params::LossParams,
-            regularization_params::RegularizationParams, unit_params::UnitParams, Config,
+            default_rate_factor, hyper_params::HyperParams, input_params::InputParams,
+            loss_params::LossParams, regularization_params::RegularizationParams,
+            unit_params::UnitParams, write_atomic, Config,
         },
-        types::classifier::ClassifierNetwork,
-    },
-    optimization::{
-        batch_norm::NormalizationType, learning_decay::LearningDecayType, momentum::DescentType,
+        types::{classifier::ClassifierNetwork, sequential::config::SequentialConfig},
     },
+    optimization::batch_norm::NormalizationType,
     regularization::{
         dropout::{NetworkMaskType, UnitMaskType},
         penalty::PenaltyConfig,
     },
 };
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct ClassifierConfig {
     input: InputParams,
     units: Vec<UnitParams>,
@@ -33,6 +33,8 @@ pub struct ClassifierConfig {
     params: HyperParams,
     regularization: RegularizationParams,
     time_step: usize,
+    #[serde(default = "default_rate_factor")]
+    rate_factor: f32,
 }
 
 impl ClassifierConfig {
@@ -42,9 +44,7 @@ impl ClassifierConfig {
         hidden_sizes: Vec<usize>,
         penalty_config: PenaltyConfig,
         mask_type: NetworkMaskType,
-        decay_type: LearningDecayType,
-        descent_type: DescentType,
-        normalization_type: NormalizationType,
+        params: HyperParams,
     ) -> ClassifierConfig {
         if input_size.len() != 1 || output_size.len() != 1 {
             panic!("[SIMPLE_CLASSIFIER] Invalid input / output dimensions for network type, expected 1 and 1 but got {} and {}.", input_size.len(), output_size.len());
@@ -58,8 +58,8 @@ impl ClassifierConfig {
             output_size,
         };
 
-        let params: HyperParams =
-            HyperParams::new(decay_type, descent_type, normalization_type.clone());
+        let normalization_type: NormalizationType = params.normalization_type().clone();
+        let precision: Precision = params.precision();
 
         let mut units: Vec<UnitParams> = Vec::new();
         let mut prev_width: usize = input_usize;
@@ -73,6 +73,7 @@ impl ClassifierConfig {
                 UnitMaskType::from_keep_probability(keep_probability),
                 normalization_type.clone(),
                 false,
+                precision,
             );
             units.push(unit);
             prev_width = unit_size;
@@ -85,11 +86,12 @@ impl ClassifierConfig {
             UnitMaskType::from_keep_probability(keep_probability),
             normalization_type,
             true,
+            precision,
         );
         units.push(inference_unit);
 
         let regularization: RegularizationParams =
-            RegularizationParams::from_builder(penalty_config.get_builder());
+            RegularizationParams::from_builder(penalty_config.get_builder().as_ref());
 
         ClassifierConfig {
             input,
@@ -98,44 +100,18 @@ impl ClassifierConfig {
             params,
             regularization,
             time_step: 0,
+            rate_factor: default_rate_factor(),
         }
     }
 
     pub fn from_network(network: &ClassifierNetwork) -> ClassifierConfig {
-        let input: InputParams = InputParams::from_unit(&network.input);
-
-        let loss: LossParams = LossParams::from_unit(&network.loss);
-
-        let mut units: Vec<UnitParams> = Vec::new();
-
-        for unit in &network.hidden {
-            units.push(UnitParams::from_linear_unit(unit));
-        }
-        units.push(UnitParams::from_softmax_unit(&network.inference));
-
-        let params: HyperParams = HyperParams::new(
-            network.decay_type.clone(),
-            network.descent_type.clone(),
-            network.normalization_type.clone(),
-        );
-
-        let regularization: RegularizationParams =
-            RegularizationParams::new(network.penalty_type.clone());
-
-        ClassifierConfig {
-            input,
-            units,
-            loss,
-            params,
-            regularization,
-            time_step: network.time_step,
-        }
+        ClassifierConfig::from(SequentialConfig::from_network(&network.network))
     }
 
     pub fn save_to_file(self, path: &str) -> Result<()> {
         let config: Config = Config::Classifier(self);
         let json_string = serde_json::to_string_pretty(&config).unwrap();
-        write(path, json_string)
+        write_atomic(path, &json_string)
     }
 
     pub fn load_from_file(path: &str) -> Result<ClassifierConfig> {
@@ -176,4 +152,22 @@ impl ClassifierConfig {
     pub fn timestep(&self) -> usize {
         self.time_step
     }
+
+    pub fn rate_factor(&self) -> f32 {
+        self.rate_factor
+    }
+}
+
+impl From<SequentialConfig> for ClassifierConfig {
+    fn from(config: SequentialConfig) -> ClassifierConfig {
+        ClassifierConfig {
+            input: config.input().clone(),
+            units: config.units().clone(),
+            loss: config.loss().clone(),
+            params: config.params().clone(),
+            regularization: config.regularization().clone(),
+            time_step: config.timestep(),
+            rate_factor: config.rate_factor(),
+        }
+    }
 }
@@ -0,0 +1,676 @@
+// builtin
+use std::{collections::HashMap, io};
+
+// external
+
+// internal
+use crate::{
+    data::{data_container::DataContainer, error::DataError, precision::Precision},
+    network::{
+        config_types::Config,
+        types::graph::{
+            builder::build_from_config,
+            config::{
+                GraphConfig, GraphHeadParams, GraphInputParams, GraphUnitConfig, GraphUnitParams,
+                LayerSpec,
+            },
+            multi_task_builder::MultiTaskBuilder,
+            validation::GraphConfigError,
+        },
+        Network,
+    },
+    node::{
+        gradcheck::{GradCheck, GradCheckConfig, GradCheckReport},
+        graph::Graph,
+        NodeRef,
+    },
+    optimization::{
+        batch_norm::NormalizationType,
+        learning_schedule::{LearningRateSchedule, NetworkRate},
+        momentum::DescentType,
+    },
+    regularization::{dropout::NetworkMode, penalty::PenaltyType},
+    unit::{
+        types::{
+            input_unit::InputUnit,
+            layer_unit::LayerUnit,
+            loss_unit::LossUnit,
+            merge_unit::{MergeType, MergeUnit},
+        },
+        Unit, UnitContainer, UnitRef,
+    },
+};
+pub mod builder;
+pub mod config;
+pub mod multi_task_builder;
+pub mod validation;
+
+struct GraphInput<'a> {
+    name: String,
+    unit: UnitContainer<'a, InputUnit<'a>>,
+}
+
+enum GraphUnit<'a> {
+    Layer(LayerUnit<'a>),
+    Merge(UnitContainer<'a, MergeUnit<'a>>),
+}
+
+impl<'a> GraphUnit<'a> {
+    fn get_ref(&self) -> UnitRef<'a> {
+        match self {
+            GraphUnit::Layer(unit) => unit.get_ref(),
+            GraphUnit::Merge(unit) => unit.get_ref(),
+        }
+    }
+}
+
+struct NamedUnit<'a> {
+    name: String,
+    inputs: Vec<String>,
+    unit: GraphUnit<'a>,
+}
+
+struct GraphHead<'a> {
+    unit: String,
+    output: NodeRef<'a>,
+    loss: UnitContainer<'a, LossUnit<'a>>,
+    loss_weight: f32,
+}
+
+// Named units wired into any acyclic graph, with one loss per head. The weighted losses of every
+// head and the penalty are summed into a single training objective
+pub struct GraphNetwork<'a> {
+    inputs: Vec<GraphInput<'a>>,
+    units: Vec<NamedUnit<'a>>,
+    heads: Vec<GraphHead<'a>>,
+    train_graph: Graph<'a>,
+    inference_graph: Graph<'a>,
+    penalty_type: PenaltyType,
+    rate: NetworkRate,
+    descent_type: DescentType,
+    normalization_type: NormalizationType,
+    precision: Precision,
+}
+
+impl<'a> GraphNetwork<'a> {
+    // Shared trunk with one branch per head, e.g. a classification head next to a regression head
+    pub fn multi_task() -> MultiTaskBuilder {
+        MultiTaskBuilder::new()
+    }
+
+    pub fn try_from_config(config: GraphConfig) -> Result<GraphNetwork<'a>, GraphConfigError> {
+        let layout = config.validate()?;
+        Ok(build_from_config(config, layout))
+    }
+
+    pub fn from_config(config: GraphConfig) -> GraphNetwork<'a> {
+        match GraphNetwork::try_from_config(config) {
+            Ok(network) => network,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    pub fn load_from_file(path: &str) -> GraphNetwork<'a> {
+        let config: GraphConfig = GraphConfig::load_from_file(path).unwrap();
+        GraphNetwork::from_config(config)
+    }
+
+    pub fn save_to_file(&self, path: &str) -> io::Result<()> {
+        let config: GraphConfig = GraphConfig::from_network(self);
+        config.save_to_file(path)
+    }
+
+    pub fn input_names(&self) -> Vec<&str> {
+        self.inputs
+            .iter()
+            .map(|input| input.name.as_str())
+            .collect()
+    }
+
+    pub fn head_names(&self) -> Vec<&str> {
+        self.heads.iter().map(|head| head.unit.as_str()).collect()
+    }
+
+    // Runs every head in one pass, keyed by the name of the head unit
+    pub fn predict_named(
+        &self,
+        inputs: &HashMap<String, DataContainer>,
+    ) -> Result<HashMap<String, DataContainer>, DataError> {
+        self.update_mode(NetworkMode::Inference);
+        self.set_inputs(inputs)?;
+
+        self.inference_graph.forward()?;
+
+        Ok(self
+            .heads
+            .iter()
+            .map(|head| (head.unit.clone(), head.output.borrow_mut().get_data()))
+            .collect())
+    }
+
+    // Expects a response for every head, keyed by the name of the head unit
+    pub fn train_named(
+        &mut self,
+        inputs: &HashMap<String, DataContainer>,
+        responses: &HashMap<String, DataContainer>,
+    ) -> Result<f32, DataError> {
+        self.update_mode(NetworkMode::Train);
+        self.set_inputs(inputs)?;
+        self.set_responses(responses)?;
+
+        self.train_graph.set_rate_scale(self.rate.next_scale());
+
+        self.train_graph.forward()?;
+        let loss: f32 = self.train_graph.output_sum();
+        self.train_graph.backward(&DataContainer::one())?;
+
+        Ok(loss)
+    }
+
+    // Unweighted loss of every head from the last train step, summed over the batch
+    pub fn head_losses(&self) -> HashMap<String, f32> {
+        self.heads
+            .iter()
+            .map(|head| {
+                let loss: f32 = Graph::node_sum(head.loss.borrow().get_output_node());
+                (head.unit.clone(), loss)
+            })
+            .collect()
+    }
+
+    pub(crate) fn input_params(&self) -> Vec<GraphInputParams> {
+        self.inputs
+            .iter()
+            .map(|input| GraphInputParams {
+                name: input.name.clone(),
+                input_size: input.unit.borrow().get_input_size().to_vec(),
+                keep_probability: input.unit.borrow().get_mask_type().probability(),
+            })
+            .collect()
+    }
+
+    pub(crate) fn unit_params(&self) -> Vec<GraphUnitConfig> {
+        self.units
+            .iter()
+            .map(|unit| {
+                let params: GraphUnitParams = match &unit.unit {
+                    GraphUnit::Layer(layer @ LayerUnit::Linear(_)) => {
+                        GraphUnitParams::Linear(LayerSpec::from_params(layer.to_params()))
+                    }
+                    GraphUnit::Layer(layer @ LayerUnit::Softmax(_)) => {
+                        GraphUnitParams::Softmax(LayerSpec::from_params(layer.to_params()))
+                    }
+                    GraphUnit::Merge(merge) => match merge.borrow().get_merge_type() {
+                        MergeType::Concat => GraphUnitParams::Concat,
+                        MergeType::Add => GraphUnitParams::Add,
+                    },
+                };
+
+                GraphUnitConfig {
+                    name: unit.name.clone(),
+                    inputs: unit.inputs.clone(),
+                    unit: params,
+                }
+            })
+            .collect()
+    }
+
+    pub(crate) fn head_params(&self) -> Vec<GraphHeadParams> {
+        self.heads
+            .iter()
+            .map(|head| {
+                GraphHeadParams::with_weight(
+                    &head.unit,
+                    head.loss.borrow().get_loss_type(),
+                    head.loss_weight,
+                )
+            })
+            .collect()
+    }
+
+    fn update_mode(&self, new_mode: NetworkMode) {
+        for input in &self.inputs {
+            input.unit.update_mode(new_mode);
+        }
+    }
+
+    fn set_inputs(&self, inputs: &HashMap<String, DataContainer>) -> Result<(), DataError> {
+        for input in &self.inputs {
+            let data: &DataContainer =
+                inputs
+                    .get(&input.name)
+                    .ok_or_else(|| DataError::MissingData {
+                        operation: "GRAPH_NETWORK",
+                        name: input.name.clone(),
+                    })?;
+
+            input
+                .unit
+                .borrow()
+                .set_input_data(data.to_precision(self.precision));
+        }
+
+        Ok(())
+    }
+
+    fn set_responses(&self, responses: &HashMap<String, DataContainer>) -> Result<(), DataError> {
+        for head in &self.heads {
+            let response: &DataContainer =
+                responses
+                    .get(&head.unit)
+                    .ok_or_else(|| DataError::MissingData {
+                        operation: "GRAPH_NETWORK",
+                        name: head.unit.clone(),
+                    })?;
+
+            head.loss
+                .borrow()
+                .set_expected_response(response.to_precision(self.precision));
+        }
+
+        Ok(())
+    }
+
+    // Keys a single input and response by the first input and head, for the Network trait
+    fn first_named(
+        &self,
+        input: DataContainer,
+        response: Option<DataContainer>,
+    ) -> (
+        HashMap<String, DataContainer>,
+        HashMap<String, DataContainer>,
+    ) {
+        let inputs = HashMap::from([(self.inputs[0].name.clone(), input)]);
+        let responses = response
+            .map(|response| HashMap::from([(self.heads[0].unit.clone(), response)]))
+            .unwrap_or_default();
+
+        (inputs, responses)
+    }
+}
+
+// Networks with several inputs or heads need predict_named and train_named, these only cover the
+// first input and head
+impl Network for GraphNetwork<'_> {
+    fn predict(&self, input: DataContainer) -> Result<DataContainer, DataError> {
+        let (inputs, _responses) = self.first_named(input, None);
+        let mut outputs: HashMap<String, DataContainer> = self.predict_named(&inputs)?;
+
+        Ok(outputs
+            .remove(&self.heads[0].unit)
+            .unwrap_or(DataContainer::Empty))
+    }
+
+    fn train(&mut self, input: DataContainer, response: DataContainer) -> Result<f32, DataError> {
+        let (inputs, responses) = self.first_named(input, Some(response));
+        self.train_named(&inputs, &responses)
+    }
+
+    fn create_config(&self) -> Config {
+        Config::Graph(GraphConfig::from_network(self))
+    }
+
+    fn load_config(config: Config) -> io::Result<Self> {
+        match config {
+            Config::Graph(graph_config) => GraphNetwork::try_from_config(graph_config)
+                .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err)),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "JSON network data did not match the requested network type",
+            )),
+        }
+    }
+
+    fn get_learning_rate(&self) -> f32 {
+        self.rate.get_rate()
+    }
+
+    fn set_learning_rate(&mut self, learning_rate: f32) {
+        self.rate.set_rate(learning_rate);
+    }
+
+    fn set_learning_schedule(&mut self, schedule: LearningRateSchedule) {
+        self.rate.set_schedule(schedule);
+    }
+
+    fn check_gradients(
+        &mut self,
+        input: DataContainer,
+        response: DataContainer,
+        config: &GradCheckConfig,
+    ) -> Result<GradCheckReport, DataError> {
+        let (inputs, responses) = self.first_named(input, Some(response));
+
+        self.set_inputs(&inputs)?;
+        self.set_responses(&responses)?;
+
+        let units: Vec<UnitRef> = self
+            .inputs
+            .iter()
+            .map(|input| input.unit.get_ref())
+            .collect();
+        GradCheck::check_network(&self.train_graph, &units, config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use ndarray::arr1;
+    use rand::{distributions::Uniform, prelude::Distribution};
+
+    use crate::{
+        data::{data_container::DataContainer, error::DataError, precision::Precision, Data},
+        network::{
+            config_types::{
+                hyper_params::HyperParams, regularization_params::RegularizationParams, Config,
+            },
+            types::{
+                graph::{
+                    config::{
+                        GraphConfig, GraphHeadParams, GraphInputParams, GraphUnitConfig,
+                        GraphUnitParams, LayerSpec,
+                    },
+                    validation::GraphConfigError,
+                    GraphNetwork,
+                },
+                layer_builder::LayerBuilder,
+            },
+            Network,
+        },
+        optimization::{
+            batch_norm::NormalizationType, learning_decay::LearningDecayType,
+            learning_schedule::LearningRateSchedule, momentum::DescentType,
+        },
+        random::{set_seed, with_rng},
+    };
+
+    // Units are listed out of order on purpose, the skip connection adds `hidden` back onto the
+    // output of `block`
+    const RESIDUAL_MLP: &str = r#"{
+        "Graph": {
+            "inputs": [{ "name": "x", "input_size": [1] }],
+            "units": [
+                { "name": "out", "inputs": ["skip"],
+                  "unit": { "unit_type": "Linear", "output_size": 1, "activation": "none" } },
+                { "name": "skip", "inputs": ["hidden", "block"], "unit": { "unit_type": "Add" } },
+                { "name": "hidden", "inputs": ["x"],
+                  "unit": { "unit_type": "Linear", "output_size": 8, "activation": "relu" } },
+                { "name": "block", "inputs": ["hidden"],
+                  "unit": { "unit_type": "Linear", "output_size": 8, "activation": "relu",
+                            "batch_norm": 0.9 } }
+            ],
+            "heads": [{ "unit": "out", "loss_type": "mean_squared_error" }],
+            "hyperparams": {
+                "decay_type": { "None": { "rate": 0.01 } },
+                "descent_type": "Base",
+                "normalization_type": "None"
+            }
+        }
+    }"#;
+
+    fn vectors(values: &[f32]) -> DataContainer {
+        DataContainer::batch(
+            values
+                .iter()
+                .map(|value| Data::VectorF32(arr1(&[*value])))
+                .collect(),
+        )
+    }
+
+    fn values(container: &DataContainer) -> Vec<f64> {
+        match container {
+            DataContainer::Inference(output) => output.flatten_as(),
+            _ => panic!("Expected an inference output from the network"),
+        }
+    }
+
+    fn hyperparams() -> HyperParams {
+        HyperParams::new(
+            LearningDecayType::constant(0.01),
+            DescentType::Base,
+            NormalizationType::none(),
+            Precision::F32,
+            LearningRateSchedule::Constant,
+        )
+    }
+
+    fn unit(name: &str, inputs: &[&str], unit: GraphUnitParams) -> GraphUnitConfig {
+        GraphUnitConfig {
+            name: name.to_string(),
+            inputs: inputs.iter().map(|input| input.to_string()).collect(),
+            unit,
+        }
+    }
+
+    fn input(name: &str, size: usize) -> GraphInputParams {
+        GraphInputParams {
+            name: name.to_string(),
+            input_size: vec![size],
+            keep_probability: 1.0,
+        }
+    }
+
+    fn head(unit: &str) -> GraphHeadParams {
+        GraphHeadParams::new(unit, "mean_squared_error")
+    }
+
+    #[test]
+    fn residual_graph_from_json() {
+        set_seed(5);
+
+        let config: Config = serde_json::from_str(RESIDUAL_MLP).unwrap();
+        let mut network: GraphNetwork = GraphNetwork::load_config(config).unwrap();
+
+        let distribution = Uniform::new(-1.0, 1.0);
+        let mut losses: Vec<f32> = Vec::new();
+        for _i in 0..300 {
+            let xs: Vec<f32> = with_rng(|rng| (0..8).map(|_j| distribution.sample(rng)).collect());
+            let ys: Vec<f32> = xs.iter().map(|x| 2.0 * x + 1.0).collect();
+
+            losses.push(network.train(vectors(&xs), vectors(&ys)).unwrap());
+        }
+        assert!(losses[290..].iter().sum::<f32>() < losses[..10].iter().sum::<f32>() / 10.0);
+
+        let json: String = serde_json::to_string(&network.create_config()).unwrap();
+        let loaded: GraphNetwork =
+            GraphNetwork::load_config(serde_json::from_str(&json).unwrap()).unwrap();
+        let frozen = network.freeze();
+
+        for x in [-0.5, 0.0, 0.7] {
+            let input = Data::VectorF32(arr1(&[x]));
+            let expected = network
+                .predict(DataContainer::Inference(input.clone()))
+                .unwrap();
+            let reloaded = loaded
+                .predict(DataContainer::Inference(input.clone()))
+                .unwrap();
+
+            let expected: Vec<f64> = values(&expected);
+            assert_eq!(values(&reloaded), expected);
+            assert_eq!(
+                frozen.try_predict(&input).unwrap().flatten_as::<f64>(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn multi_input_graph_with_two_heads() {
+        set_seed(3);
+
+        let config: GraphConfig = GraphConfig::new(
+            vec![input("a", 1), input("b", 2)],
+            vec![
+                unit("joined", &["a", "b"], GraphUnitParams::Concat),
+                unit(
+                    "sum",
+                    &["joined"],
+                    GraphUnitParams::Linear(LayerSpec::new(1, "none")),
+                ),
+                unit(
+                    "features",
+                    &["b"],
+                    GraphUnitParams::Linear(LayerSpec::new(2, "none")),
+                ),
+            ],
+            vec![head("sum"), head("features")],
+            hyperparams(),
+            RegularizationParams::default(),
+        );
+        let mut network: GraphNetwork = GraphNetwork::from_config(config);
+        assert_eq!(network.input_names(), vec!["a", "b"]);
+        assert_eq!(network.head_names(), vec!["sum", "features"]);
+
+        let inputs = HashMap::from([
+            ("a".to_string(), vectors(&[0.5, -1.0])),
+            (
+                "b".to_string(),
+                DataContainer::batch(vec![
+                    Data::VectorF32(arr1(&[1.0, 2.0])),
+                    Data::VectorF32(arr1(&[0.0, -1.0])),
+                ]),
+            ),
+        ]);
+        let responses = HashMap::from([
+            ("sum".to_string(), vectors(&[3.5, -2.0])),
+            (
+                "features".to_string(),
+                DataContainer::batch(vec![
+                    Data::VectorF32(arr1(&[1.0, 2.0])),
+                    Data::VectorF32(arr1(&[0.0, -1.0])),
+                ]),
+            ),
+        ]);
+        network.train_named(&inputs, &responses).unwrap();
+
+        let missing = network.train_named(&inputs, &HashMap::new());
+        assert!(matches!(missing, Err(DataError::MissingData { .. })));
+
+        let a = Data::VectorF32(arr1(&[0.25]));
+        let b = Data::VectorF32(arr1(&[-0.5, 1.5]));
+        let single = HashMap::from([
+            ("a".to_string(), DataContainer::Inference(a.clone())),
+            ("b".to_string(), DataContainer::Inference(b.clone())),
+        ]);
+        let outputs = network.predict_named(&single).unwrap();
+
+        let frozen: Vec<Data> = network.freeze().try_predict_all(&[a, b]).unwrap();
+        for (name, output) in ["sum", "features"].iter().zip(frozen) {
+            assert_eq!(values(&outputs[*name]), output.flatten_as::<f64>());
+        }
+    }
+
+    #[test]
+    fn invalid_graph_configs() {
+        let linear = |size: usize| GraphUnitParams::Linear(LayerSpec::new(size, "relu"));
+        let validate = |units: Vec<GraphUnitConfig>| {
+            GraphConfig::new(
+                vec![input("x", 3)],
+                units,
+                vec![head("out")],
+                hyperparams(),
+                RegularizationParams::default(),
+            )
+            .validate()
+            .err()
+        };
+
+        let cycle = validate(vec![
+            unit("first", &["x", "out"], GraphUnitParams::Add),
+            unit("out", &["first"], linear(3)),
+        ]);
+        assert!(matches!(cycle, Some(GraphConfigError::Cycle { .. })));
+
+        let mismatch = validate(vec![
+            unit("hidden", &["x"], linear(4)),
+            unit("out", &["x", "hidden"], GraphUnitParams::Add),
+        ]);
+        assert_eq!(
+            mismatch,
+            Some(GraphConfigError::ShapeMismatch {
+                unit: "out".to_string(),
+                expected: 3,
+                found: 4,
+            })
+        );
+
+        let unknown = validate(vec![unit("out", &["y"], linear(1))]);
+        assert!(matches!(
+            unknown,
+            Some(GraphConfigError::UnknownName { .. })
+        ));
+
+        let unused = validate(vec![
+            unit("out", &["x"], linear(1)),
+            unit("dangling", &["x"], linear(2)),
+        ]);
+        assert!(matches!(unused, Some(GraphConfigError::Unused { .. })));
+    }
+
+    #[test]
+    fn multi_task_heads() {
+        set_seed(17);
+
+        let mut network: GraphNetwork = GraphNetwork::multi_task()
+            .input(vec![1])
+            .linear(8, "relu")
+            .head("class", "base_cross_entropy")
+            .linear(2, "none")
+            .softmax()
+            .weighted_head("value", "mean_squared_error", 0.5)
+            .linear(4, "relu")
+            .linear(1, "none")
+            .decay(LearningDecayType::rms_prop(0.02, 0.9))
+            .build();
+        assert_eq!(network.head_names(), vec!["class", "value"]);
+
+        let distribution = Uniform::new(-1.0, 1.0);
+        let mut value_losses: Vec<f32> = Vec::new();
+        for _i in 0..300 {
+            let xs: Vec<f32> = with_rng(|rng| (0..8).map(|_j| distribution.sample(rng)).collect());
+            let classes = xs
+                .iter()
+                .map(|x| Data::VectorF32(arr1(if *x < 0.0 { &[1.0, 0.0] } else { &[0.0, 1.0] })))
+                .collect();
+            let values: Vec<f32> = xs.iter().map(|x| 3.0 * x).collect();
+
+            let inputs = HashMap::from([("input".to_string(), vectors(&xs))]);
+            let responses = HashMap::from([
+                ("class".to_string(), DataContainer::batch(classes)),
+                ("value".to_string(), vectors(&values)),
+            ]);
+            let total: f32 = network.train_named(&inputs, &responses).unwrap();
+
+            let losses: HashMap<String, f32> = network.head_losses();
+            assert!((losses["class"] + 0.5 * losses["value"] - total).abs() < 1e-3);
+            value_losses.push(losses["value"]);
+        }
+        assert!(value_losses[290..].iter().sum::<f32>() < value_losses[..10].iter().sum::<f32>());
+
+        let predict = |network: &GraphNetwork, x: f32| {
+            let inputs = HashMap::from([(
+                "input".to_string(),
+                DataContainer::Inference(Data::VectorF32(arr1(&[x]))),
+            )]);
+            let outputs = network.predict_named(&inputs).unwrap();
+            (values(&outputs["class"]), values(&outputs["value"]))
+        };
+        assert!(predict(&network, -0.8).0[0] > 0.5);
+        assert!(predict(&network, 0.8).0[1] > 0.5);
+
+        let config: Config = network.create_config();
+        if let Config::Graph(graph) = &config {
+            assert_eq!(graph.heads()[1].loss_weight, 0.5);
+        } else {
+            panic!("Expected a graph config");
+        }
+        let json: String = serde_json::to_string(&config).unwrap();
+        let loaded: GraphNetwork =
+            GraphNetwork::load_config(serde_json::from_str(&json).unwrap()).unwrap();
+        for x in [-0.6, 0.3] {
+            assert_eq!(predict(&loaded, x), predict(&network, x));
+        }
+    }
+}
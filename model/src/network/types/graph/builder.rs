@@ -0,0 +1,267 @@
+// builtin
+
+// external
+
+// internal
+use crate::{
+    data::Data,
+    network::{
+        config_types::unit_params::UnitParams,
+        types::graph::{
+            config::{GraphConfig, GraphUnitConfig, GraphUnitParams, LayerSpec},
+            validation::GraphLayout,
+            GraphHead, GraphInput, GraphNetwork, GraphUnit, NamedUnit,
+        },
+    },
+    node::{
+        graph::Graph,
+        types::{add_node::AddNode, constant_node::ConstantNode, multiply_node::MultiplyNode},
+        NodeRef,
+    },
+    optimization::{batch_norm::NormalizationType, learning_schedule::NetworkRate},
+    regularization::{
+        dropout::UnitMaskType,
+        penalty::{PenaltyConfig, PenaltyContainer},
+    },
+    unit::{
+        types::{
+            input_unit::InputUnit,
+            layer_unit::LayerUnit,
+            loss_unit::LossUnit,
+            merge_unit::{MergeType, MergeUnit},
+        },
+        Unit, UnitContainer, UnitRef,
+    },
+};
+
+// Expects a layout from validating the same config, so every name and width is known
+pub fn build_from_config<'a>(config: GraphConfig, layout: GraphLayout) -> GraphNetwork<'a> {
+    let penalty_config: PenaltyConfig = config.regularization().get_config();
+
+    let inputs: Vec<GraphInput> = config
+        .inputs()
+        .iter()
+        .map(|params| GraphInput {
+            name: params.name.clone(),
+            unit: UnitContainer::new(InputUnit::new(
+                params.input_size.clone(),
+                UnitMaskType::from_keep_probability(params.keep_probability),
+                config.params().precision(),
+            )),
+        })
+        .collect();
+
+    let (units, penalty) = build_units(&config, &layout, &inputs, &penalty_config);
+
+    let heads: Vec<GraphHead> = build_heads(&config, &layout, &units);
+
+    let (train_graph, inference_graph) = build_graphs(&heads, penalty.as_ref());
+
+    GraphNetwork {
+        inputs,
+        units,
+        heads,
+        train_graph,
+        inference_graph,
+        penalty_type: penalty_config.get_type(),
+        rate: NetworkRate::new(
+            config.params().decay_type().clone(),
+            config.params().schedule().clone(),
+            config.timestep(),
+            config.rate_factor(),
+        ),
+        descent_type: config.params().descent_type().clone(),
+        normalization_type: config.params().normalization_type().clone(),
+        precision: config.params().precision(),
+    }
+}
+
+// Units are built and stored in dependency order, so each one's inputs already exist
+fn build_units<'a>(
+    config: &GraphConfig,
+    layout: &GraphLayout,
+    inputs: &[GraphInput<'a>],
+    penalty_config: &PenaltyConfig<'a>,
+) -> (Vec<NamedUnit<'a>>, Option<PenaltyContainer<'a>>) {
+    let mut units: Vec<NamedUnit> = Vec::new();
+    let mut prev_penalty: Option<PenaltyContainer> = None;
+
+    for &position in &layout.order {
+        let unit_config: &GraphUnitConfig = &config.units()[position];
+        let sources: Vec<UnitRef> = unit_config
+            .inputs
+            .iter()
+            .map(|name| find_ref(name, inputs, &units))
+            .collect();
+
+        let unit: GraphUnit = match &unit_config.unit {
+            GraphUnitParams::Linear(spec) | GraphUnitParams::Softmax(spec) => {
+                let params: UnitParams = layer_params(config, layout, unit_config, spec);
+                let layer: LayerUnit = LayerUnit::from_config(
+                    &params,
+                    config.params().decay_type(),
+                    config.params().descent_type(),
+                );
+
+                let weights_ref: NodeRef = layer.get_weights_ref();
+                prev_penalty = Some(match &prev_penalty {
+                    Some(prev) => penalty_config.create_new(&prev.get_ref(), &weights_ref),
+                    None => penalty_config.create_first(&weights_ref),
+                });
+
+                GraphUnit::Layer(layer)
+            }
+            GraphUnitParams::Concat => {
+                GraphUnit::Merge(UnitContainer::new(MergeUnit::new(MergeType::Concat)))
+            }
+            GraphUnitParams::Add => {
+                GraphUnit::Merge(UnitContainer::new(MergeUnit::new(MergeType::Add)))
+            }
+        };
+
+        let unit_ref: UnitRef = unit.get_ref();
+        for source in &sources {
+            unit_ref.borrow_mut().add_input(&unit_ref, source);
+        }
+
+        units.push(NamedUnit {
+            name: unit_config.name.clone(),
+            inputs: unit_config.inputs.clone(),
+            unit,
+        });
+    }
+
+    (units, prev_penalty)
+}
+
+// Saved params take precedence, otherwise new weights are drawn for the resolved input width
+fn layer_params(
+    config: &GraphConfig,
+    layout: &GraphLayout,
+    unit_config: &GraphUnitConfig,
+    spec: &LayerSpec,
+) -> UnitParams {
+    if let Some(params) = &spec.params {
+        return params.clone();
+    }
+
+    let input_size: usize = layout.widths[&unit_config.inputs[0]];
+    let mask_type: UnitMaskType = UnitMaskType::from_keep_probability(spec.keep_probability);
+    let normalization_type: NormalizationType = spec.normalization_type();
+    let is_head: bool = config
+        .heads()
+        .iter()
+        .any(|head| head.unit == unit_config.name);
+
+    match &unit_config.unit {
+        GraphUnitParams::Softmax(_) => UnitParams::new_softmax(
+            input_size,
+            spec.output_size,
+            &spec.activation,
+            mask_type,
+            normalization_type,
+            is_head,
+            config.params().precision(),
+        ),
+        _ => UnitParams::new_linear(
+            input_size,
+            spec.output_size,
+            &spec.activation,
+            mask_type,
+            normalization_type,
+            is_head,
+            config.params().precision(),
+        ),
+    }
+}
+
+fn find_ref<'a>(name: &str, inputs: &[GraphInput<'a>], units: &[NamedUnit<'a>]) -> UnitRef<'a> {
+    if let Some(input) = inputs.iter().find(|input| input.name == name) {
+        return input.unit.get_ref();
+    }
+
+    match units.iter().find(|unit| unit.name == name) {
+        Some(unit) => unit.unit.get_ref(),
+        None => panic!("[GRAPH_NETWORK] `{name}` is used before it was built"),
+    }
+}
+
+fn build_heads<'a>(
+    config: &GraphConfig,
+    layout: &GraphLayout,
+    units: &[NamedUnit<'a>],
+) -> Vec<GraphHead<'a>> {
+    config
+        .heads()
+        .iter()
+        .map(|head| {
+            let unit_ref: UnitRef = find_ref(&head.unit, &[], units);
+            let width: usize = layout.widths[&head.unit];
+
+            let loss: UnitContainer<LossUnit> =
+                UnitContainer::new(LossUnit::new(vec![width], &head.loss_type));
+            loss.add_input_ref(&unit_ref);
+
+            let output: NodeRef = NodeRef::clone(unit_ref.borrow().get_output_node());
+
+            GraphHead {
+                unit: head.unit.clone(),
+                output,
+                loss,
+                loss_weight: head.loss_weight,
+            }
+        })
+        .collect()
+}
+
+// Heads with a weight other than 1 are scaled before the sum, the penalty is added once
+fn build_graphs<'a>(
+    heads: &[GraphHead<'a>],
+    penalty: Option<&PenaltyContainer<'a>>,
+) -> (Graph<'a>, Graph<'a>) {
+    let total_ref: NodeRef = NodeRef::new(AddNode::new());
+    for head in heads {
+        let loss_ref: NodeRef = NodeRef::clone(head.loss.borrow().get_output_node());
+        let term_ref: NodeRef = if head.loss_weight == 1.0 {
+            loss_ref
+        } else {
+            let weight_ref: NodeRef =
+                NodeRef::new(ConstantNode::new(Data::ScalarF32(head.loss_weight)));
+            let multiply_ref: NodeRef = NodeRef::new(MultiplyNode::new());
+            multiply_ref
+                .borrow_mut()
+                .add_input(&multiply_ref, &loss_ref);
+            multiply_ref
+                .borrow_mut()
+                .add_input(&multiply_ref, &weight_ref);
+            multiply_ref
+        };
+
+        total_ref.borrow_mut().add_input(&total_ref, &term_ref);
+    }
+
+    if let Some(penalty) = penalty {
+        let penalty_ref = penalty.get_ref();
+        if !penalty_ref.borrow().is_null() {
+            total_ref
+                .borrow_mut()
+                .add_input(&total_ref, penalty_ref.borrow().get_output_ref());
+        }
+    }
+
+    let train_graph: Graph = match Graph::new(&total_ref) {
+        Ok(graph) => graph,
+        Err(error) => panic!("[GRAPH_NETWORK] Invalid network graph: {error}"),
+    };
+
+    let outputs: Vec<NodeRef> = heads
+        .iter()
+        .map(|head| NodeRef::clone(&head.output))
+        .collect();
+    let inference_graph: Graph = match train_graph.subgraph_all(&outputs) {
+        Ok(graph) => graph,
+        Err(error) => panic!("[GRAPH_NETWORK] Invalid inference graph: {error}"),
+    };
+
+    (train_graph, inference_graph)
+}
@@ -0,0 +1,245 @@
+// builtin
+use std::{
+    fs::read_to_string,
+    io::{Error, ErrorKind, Result},
+};
+
+// external
+use serde::{Deserialize, Serialize};
+
+// internal
+use crate::{
+    network::{
+        config_types::{
+            default_rate_factor, hyper_params::HyperParams,
+            regularization_params::RegularizationParams, unit_params::UnitParams, write_atomic,
+            Config,
+        },
+        types::graph::{
+            validation::{GraphConfigError, GraphLayout},
+            GraphNetwork,
+        },
+    },
+    optimization::batch_norm::NormalizationType,
+};
+
+fn default_keep_probability() -> f32 {
+    1.0
+}
+
+fn default_loss_weight() -> f32 {
+    1.0
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GraphInputParams {
+    pub name: String,
+    pub input_size: Vec<usize>,
+    #[serde(default = "default_keep_probability")]
+    pub keep_probability: f32,
+}
+
+// Weights are generated when `params` is left out, which is how hand written configs declare
+// new units. Saved networks fill it in and it takes precedence over the other settings
+#[derive(Serialize, Deserialize, Clone)]
+pub struct LayerSpec {
+    pub output_size: usize,
+    pub activation: String,
+    #[serde(default = "default_keep_probability")]
+    pub keep_probability: f32,
+    // Decay of the batch norm running averages, no batch norm when left out
+    #[serde(default)]
+    pub batch_norm: Option<f32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub params: Option<UnitParams>,
+}
+
+impl LayerSpec {
+    pub fn new(output_size: usize, activation: &str) -> LayerSpec {
+        LayerSpec {
+            output_size,
+            activation: activation.to_string(),
+            keep_probability: default_keep_probability(),
+            batch_norm: None,
+            params: None,
+        }
+    }
+
+    pub fn from_params(params: UnitParams) -> LayerSpec {
+        let batch_norm: Option<f32> = match params.normalization_type() {
+            NormalizationType::BatchNorm { decay } => Some(decay),
+            NormalizationType::None => None,
+        };
+
+        LayerSpec {
+            output_size: params.get_output_size(),
+            activation: params.get_activation().to_string(),
+            keep_probability: params.get_keep_probability(),
+            batch_norm,
+            params: Some(params),
+        }
+    }
+
+    pub fn normalization_type(&self) -> NormalizationType {
+        match self.batch_norm {
+            Some(decay) => NormalizationType::batch_norm(decay),
+            None => NormalizationType::none(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+#[serde(tag = "unit_type")]
+pub enum GraphUnitParams {
+    Linear(LayerSpec),
+    Softmax(LayerSpec),
+    // Joins the features of every input in the listed order
+    Concat,
+    // Sums inputs of the same width, e.g. for a skip connection
+    Add,
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GraphUnitConfig {
+    pub name: String,
+    pub inputs: Vec<String>,
+    pub unit: GraphUnitParams,
+}
+
+// The training objective is the sum of every head's loss times its weight
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GraphHeadParams {
+    pub unit: String,
+    pub loss_type: String,
+    #[serde(default = "default_loss_weight")]
+    pub loss_weight: f32,
+}
+
+impl GraphHeadParams {
+    pub fn new(unit: &str, loss_type: &str) -> GraphHeadParams {
+        GraphHeadParams {
+            unit: unit.to_string(),
+            loss_type: loss_type.to_string(),
+            loss_weight: default_loss_weight(),
+        }
+    }
+
+    pub fn with_weight(unit: &str, loss_type: &str, loss_weight: f32) -> GraphHeadParams {
+        GraphHeadParams {
+            unit: unit.to_string(),
+            loss_type: loss_type.to_string(),
+            loss_weight,
+        }
+    }
+}
+
+// Named inputs and units wired up by name. Units may be listed in any order, they are built in
+// dependency order and saved back in that order
+#[derive(Serialize, Deserialize, Clone)]
+pub struct GraphConfig {
+    inputs: Vec<GraphInputParams>,
+    units: Vec<GraphUnitConfig>,
+    heads: Vec<GraphHeadParams>,
+    hyperparams: HyperParams,
+    #[serde(default)]
+    regularization: RegularizationParams,
+    #[serde(default)]
+    time_step: usize,
+    #[serde(default = "default_rate_factor")]
+    rate_factor: f32,
+}
+
+impl GraphConfig {
+    pub fn new(
+        inputs: Vec<GraphInputParams>,
+        units: Vec<GraphUnitConfig>,
+        heads: Vec<GraphHeadParams>,
+        hyperparams: HyperParams,
+        regularization: RegularizationParams,
+    ) -> GraphConfig {
+        GraphConfig {
+            inputs,
+            units,
+            heads,
+            hyperparams,
+            regularization,
+            time_step: 0,
+            rate_factor: default_rate_factor(),
+        }
+    }
+
+    pub fn from_network(network: &GraphNetwork) -> GraphConfig {
+        GraphConfig {
+            inputs: network.input_params(),
+            units: network.unit_params(),
+            heads: network.head_params(),
+            hyperparams: HyperParams::new(
+                network.rate.decay_type().clone(),
+                network.descent_type.clone(),
+                network.normalization_type.clone(),
+                network.precision,
+                network.rate.schedule().clone(),
+            ),
+            regularization: RegularizationParams::new(network.penalty_type.clone()),
+            time_step: network.rate.time_step(),
+            rate_factor: network.rate.factor(),
+        }
+    }
+
+    // Checks names, edges, acyclicity and widths, and returns the build order of the units
+    pub fn validate(&self) -> std::result::Result<GraphLayout, GraphConfigError> {
+        GraphLayout::resolve(self)
+    }
+
+    pub fn save_to_file(self, path: &str) -> Result<()> {
+        let config: Config = Config::Graph(self);
+        let json_string = serde_json::to_string_pretty(&config).unwrap();
+        write_atomic(path, &json_string)
+    }
+
+    pub fn load_from_file(path: &str) -> Result<GraphConfig> {
+        let data = read_to_string(path)?;
+        let config: Config =
+            serde_json::from_str(&data).expect("Invalid JSON data for network configuration");
+
+        if let Config::Graph(graph_config) = config {
+            graph_config
+                .validate()
+                .map_err(|err| Error::new(ErrorKind::InvalidData, err))?;
+            return Ok(graph_config);
+        }
+
+        Err(Error::new(
+            ErrorKind::InvalidData,
+            "JSON network data did not match the requested network type",
+        ))
+    }
+
+    pub fn inputs(&self) -> &Vec<GraphInputParams> {
+        &self.inputs
+    }
+
+    pub fn units(&self) -> &Vec<GraphUnitConfig> {
+        &self.units
+    }
+
+    pub fn heads(&self) -> &Vec<GraphHeadParams> {
+        &self.heads
+    }
+
+    pub fn params(&self) -> &HyperParams {
+        &self.hyperparams
+    }
+
+    pub fn regularization(&self) -> &RegularizationParams {
+        &self.regularization
+    }
+
+    pub fn timestep(&self) -> usize {
+        self.time_step
+    }
+
+    pub fn rate_factor(&self) -> f32 {
+        self.rate_factor
+    }
+}
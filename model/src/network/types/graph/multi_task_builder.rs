@@ -0,0 +1,180 @@
+// builtin
+
+// external
+
+// internal
+use crate::network::types::{
+    graph::{
+        config::{
+            GraphConfig, GraphHeadParams, GraphInputParams, GraphUnitConfig, GraphUnitParams,
+        },
+        GraphNetwork,
+    },
+    layer_builder::{BuilderParams, LayerBuilder, LayerEntry},
+};
+
+const INPUT_NAME: &str = "input";
+
+struct HeadEntry {
+    name: String,
+    loss_type: String,
+    loss_weight: f32,
+    layers: Vec<LayerEntry>,
+}
+
+// Units added before the first `head` form the shared trunk, later ones belong to the head added
+// last, see LayerBuilder for the shared settings. The last unit of a head is named after it, so
+// responses and predictions are keyed by the head name
+pub struct MultiTaskBuilder {
+    params: BuilderParams,
+    trunk: Vec<LayerEntry>,
+    heads: Vec<HeadEntry>,
+}
+
+impl Default for MultiTaskBuilder {
+    fn default() -> Self {
+        MultiTaskBuilder::new()
+    }
+}
+
+impl LayerBuilder for MultiTaskBuilder {
+    fn params_mut(&mut self) -> &mut BuilderParams {
+        &mut self.params
+    }
+
+    fn last_layer(&mut self) -> Option<&mut LayerEntry> {
+        match self.heads.last_mut() {
+            Some(head) => head.layers.last_mut(),
+            None => self.trunk.last_mut(),
+        }
+    }
+
+    fn has_layers(&self) -> bool {
+        !self.trunk.is_empty() || !self.heads.is_empty()
+    }
+}
+
+impl MultiTaskBuilder {
+    pub fn new() -> MultiTaskBuilder {
+        MultiTaskBuilder {
+            params: BuilderParams::new("MULTI_TASK"),
+            trunk: Vec::new(),
+            heads: Vec::new(),
+        }
+    }
+
+    pub fn linear(mut self, output_size: usize, activation: &str) -> MultiTaskBuilder {
+        if !self.params.has_input() {
+            panic!("[MULTI_TASK] Units can only be added after the input");
+        }
+
+        let layer: LayerEntry = LayerEntry::new(output_size, activation);
+        match self.heads.last_mut() {
+            Some(head) => head.layers.push(layer),
+            None => self.trunk.push(layer),
+        }
+        self
+    }
+
+    // Starts a new head, the units added after it are stacked on top of the shared trunk
+    pub fn head(self, name: &str, loss_type: &str) -> MultiTaskBuilder {
+        self.weighted_head(name, loss_type, 1.0)
+    }
+
+    pub fn weighted_head(
+        mut self,
+        name: &str,
+        loss_type: &str,
+        loss_weight: f32,
+    ) -> MultiTaskBuilder {
+        if self.trunk.is_empty() {
+            panic!("[MULTI_TASK] Heads can only be added after the shared units");
+        }
+
+        self.heads.push(HeadEntry {
+            name: name.to_string(),
+            loss_type: loss_type.to_string(),
+            loss_weight,
+            layers: Vec::new(),
+        });
+        self
+    }
+
+    pub fn config(self) -> GraphConfig {
+        let (input_size, input_keep_probability, hyperparams, regularization) =
+            self.params.into_parts();
+        if self.heads.is_empty() {
+            panic!("[MULTI_TASK] Cannot build a network without any heads");
+        }
+
+        let mut units: Vec<GraphUnitConfig> = Vec::new();
+        let mut trunk_end: String = INPUT_NAME.to_string();
+        for (i, layer) in self.trunk.into_iter().enumerate() {
+            let name: String = format!("shared_{i}");
+            units.push(layer.into_unit(&name, &trunk_end));
+            trunk_end = name;
+        }
+
+        let mut heads: Vec<GraphHeadParams> = Vec::new();
+        for head in self.heads {
+            if head.layers.is_empty() {
+                panic!("[MULTI_TASK] Head `{}` needs at least one unit", head.name);
+            }
+
+            let last: usize = head.layers.len() - 1;
+            let mut prev: String = trunk_end.clone();
+            for (i, mut layer) in head.layers.into_iter().enumerate() {
+                let name: String = if i == last {
+                    head.name.clone()
+                } else {
+                    format!("{}_{i}", head.name)
+                };
+                if i == last {
+                    if layer.spec.keep_probability < 1.0 || layer.spec.batch_norm.is_some() {
+                        println!(
+                            "[MULTI_TASK] Dropout and batch norm are skipped on the output unit of `{}`",
+                            head.name
+                        );
+                    }
+                    layer.spec.keep_probability = 1.0;
+                    layer.spec.batch_norm = None;
+                }
+
+                units.push(layer.into_unit(&name, &prev));
+                prev = name;
+            }
+
+            heads.push(GraphHeadParams::with_weight(
+                &head.name,
+                &head.loss_type,
+                head.loss_weight,
+            ));
+        }
+
+        let input: GraphInputParams = GraphInputParams {
+            name: INPUT_NAME.to_string(),
+            input_size,
+            keep_probability: input_keep_probability,
+        };
+
+        GraphConfig::new(vec![input], units, heads, hyperparams, regularization)
+    }
+
+    pub fn build<'a>(self) -> GraphNetwork<'a> {
+        GraphNetwork::from_config(self.config())
+    }
+}
+
+impl LayerEntry {
+    fn into_unit(self, name: &str, input: &str) -> GraphUnitConfig {
+        GraphUnitConfig {
+            name: name.to_string(),
+            inputs: vec![input.to_string()],
+            unit: if self.is_softmax {
+                GraphUnitParams::Softmax(self.spec)
+            } else {
+                GraphUnitParams::Linear(self.spec)
+            },
+        }
+    }
+}
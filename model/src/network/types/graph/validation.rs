@@ -0,0 +1,319 @@
+// builtin
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    error::Error,
+    fmt::Display,
+};
+
+// external
+
+// internal
+use crate::network::types::graph::config::{GraphConfig, GraphUnitConfig, GraphUnitParams};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphConfigError {
+    Empty {
+        part: &'static str,
+    },
+    DuplicateName {
+        name: String,
+    },
+    InvalidInputSize {
+        input: String,
+        dims: Vec<usize>,
+    },
+    UnknownName {
+        unit: String,
+        name: String,
+    },
+    InputCount {
+        unit: String,
+        count: usize,
+    },
+    RepeatedInput {
+        unit: String,
+        name: String,
+    },
+    Cycle {
+        unit: String,
+    },
+    ShapeMismatch {
+        unit: String,
+        expected: usize,
+        found: usize,
+    },
+    Unused {
+        name: String,
+    },
+    InvalidLossWeight {
+        unit: String,
+        weight: f32,
+    },
+}
+
+impl Display for GraphConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphConfigError::Empty { part } => {
+                write!(f, "[GRAPH_CONFIG] expected at least one entry in `{part}`")
+            }
+            GraphConfigError::DuplicateName { name } => {
+                write!(f, "[GRAPH_CONFIG] `{name}` is declared more than once")
+            }
+            GraphConfigError::InvalidInputSize { input, dims } => write!(
+                f,
+                "[GRAPH_CONFIG] input `{input}` has size {dims:?}, only flat inputs are supported"
+            ),
+            GraphConfigError::UnknownName { unit, name } => {
+                write!(f, "[GRAPH_CONFIG] `{unit}` refers to unknown unit `{name}`")
+            }
+            GraphConfigError::InputCount { unit, count } => write!(
+                f,
+                "[GRAPH_CONFIG] `{unit}` has {count} input(s), layers take exactly one and merges at least two"
+            ),
+            GraphConfigError::RepeatedInput { unit, name } => {
+                write!(f, "[GRAPH_CONFIG] `{unit}` lists `{name}` as an input more than once")
+            }
+            GraphConfigError::Cycle { unit } => {
+                write!(f, "[GRAPH_CONFIG] cycle detected through `{unit}`")
+            }
+            GraphConfigError::ShapeMismatch {
+                unit,
+                expected,
+                found,
+            } => write!(
+                f,
+                "[GRAPH_CONFIG] `{unit}` expected an input of width {expected} but found {found}"
+            ),
+            GraphConfigError::Unused { name } => {
+                write!(f, "[GRAPH_CONFIG] `{name}` does not lead to any head")
+            }
+            GraphConfigError::InvalidLossWeight { unit, weight } => write!(
+                f,
+                "[GRAPH_CONFIG] head `{unit}` has loss weight {weight}, expected a finite value of at least 0"
+            ),
+        }
+    }
+}
+
+impl Error for GraphConfigError {}
+
+// Units in the order they can be built in, with the output width of every input and unit
+pub struct GraphLayout {
+    pub order: Vec<usize>,
+    pub widths: HashMap<String, usize>,
+}
+
+impl GraphLayout {
+    pub fn resolve(config: &GraphConfig) -> Result<GraphLayout, GraphConfigError> {
+        if config.inputs().is_empty() {
+            return Err(GraphConfigError::Empty { part: "inputs" });
+        }
+        if config.heads().is_empty() {
+            return Err(GraphConfigError::Empty { part: "heads" });
+        }
+
+        let mut widths: HashMap<String, usize> = HashMap::new();
+        for input in config.inputs() {
+            if input.input_size.len() != 1 {
+                return Err(GraphConfigError::InvalidInputSize {
+                    input: input.name.clone(),
+                    dims: input.input_size.clone(),
+                });
+            }
+            if widths
+                .insert(input.name.clone(), input.input_size[0])
+                .is_some()
+            {
+                return Err(GraphConfigError::DuplicateName {
+                    name: input.name.clone(),
+                });
+            }
+        }
+
+        let mut positions: HashMap<&str, usize> = HashMap::new();
+        for (position, unit) in config.units().iter().enumerate() {
+            if widths.contains_key(&unit.name) || positions.insert(&unit.name, position).is_some() {
+                return Err(GraphConfigError::DuplicateName {
+                    name: unit.name.clone(),
+                });
+            }
+        }
+
+        for unit in config.units() {
+            GraphLayout::check_edges(unit, &widths, &positions)?;
+        }
+
+        let order: Vec<usize> = GraphLayout::build_order(config.units(), &positions)?;
+
+        for &position in &order {
+            let unit: &GraphUnitConfig = &config.units()[position];
+            let width: usize = GraphLayout::unit_width(unit, &widths)?;
+            widths.insert(unit.name.clone(), width);
+        }
+
+        let mut heads: HashSet<&str> = HashSet::new();
+        for head in config.heads() {
+            if !positions.contains_key(head.unit.as_str()) {
+                return Err(GraphConfigError::UnknownName {
+                    unit: "heads".to_string(),
+                    name: head.unit.clone(),
+                });
+            }
+            if !heads.insert(&head.unit) {
+                return Err(GraphConfigError::DuplicateName {
+                    name: head.unit.clone(),
+                });
+            }
+            if !head.loss_weight.is_finite() || head.loss_weight < 0.0 {
+                return Err(GraphConfigError::InvalidLossWeight {
+                    unit: head.unit.clone(),
+                    weight: head.loss_weight,
+                });
+            }
+        }
+
+        GraphLayout::check_used(config, &heads)?;
+
+        Ok(GraphLayout { order, widths })
+    }
+
+    fn check_edges(
+        unit: &GraphUnitConfig,
+        widths: &HashMap<String, usize>,
+        positions: &HashMap<&str, usize>,
+    ) -> Result<(), GraphConfigError> {
+        let count: usize = unit.inputs.len();
+        let valid_count: bool = match unit.unit {
+            GraphUnitParams::Linear(_) | GraphUnitParams::Softmax(_) => count == 1,
+            GraphUnitParams::Concat | GraphUnitParams::Add => count >= 2,
+        };
+        if !valid_count {
+            return Err(GraphConfigError::InputCount {
+                unit: unit.name.clone(),
+                count,
+            });
+        }
+
+        let mut seen: HashSet<&str> = HashSet::new();
+        for name in &unit.inputs {
+            if !widths.contains_key(name) && !positions.contains_key(name.as_str()) {
+                return Err(GraphConfigError::UnknownName {
+                    unit: unit.name.clone(),
+                    name: name.clone(),
+                });
+            }
+            if !seen.insert(name) {
+                return Err(GraphConfigError::RepeatedInput {
+                    unit: unit.name.clone(),
+                    name: name.clone(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    // Kahn's algorithm over the unit edges, inputs are available from the start
+    fn build_order(
+        units: &[GraphUnitConfig],
+        positions: &HashMap<&str, usize>,
+    ) -> Result<Vec<usize>, GraphConfigError> {
+        let mut pending: Vec<usize> = vec![0; units.len()];
+        let mut consumers: Vec<Vec<usize>> = vec![Vec::new(); units.len()];
+        for (position, unit) in units.iter().enumerate() {
+            for name in &unit.inputs {
+                if let Some(&source) = positions.get(name.as_str()) {
+                    pending[position] += 1;
+                    consumers[source].push(position);
+                }
+            }
+        }
+
+        let mut ready: VecDeque<usize> = (0..units.len())
+            .filter(|&position| pending[position] == 0)
+            .collect();
+        let mut order: Vec<usize> = Vec::new();
+        while let Some(position) = ready.pop_front() {
+            order.push(position);
+            for &consumer in &consumers[position] {
+                pending[consumer] -= 1;
+                if pending[consumer] == 0 {
+                    ready.push_back(consumer);
+                }
+            }
+        }
+
+        match (0..units.len()).find(|&position| pending[position] > 0) {
+            Some(position) => Err(GraphConfigError::Cycle {
+                unit: units[position].name.clone(),
+            }),
+            None => Ok(order),
+        }
+    }
+
+    fn unit_width(
+        unit: &GraphUnitConfig,
+        widths: &HashMap<String, usize>,
+    ) -> Result<usize, GraphConfigError> {
+        let input_widths: Vec<usize> = unit.inputs.iter().map(|name| widths[name]).collect();
+
+        match &unit.unit {
+            GraphUnitParams::Linear(spec) | GraphUnitParams::Softmax(spec) => match &spec.params {
+                Some(params) if params.get_input_size() != input_widths[0] => {
+                    Err(GraphConfigError::ShapeMismatch {
+                        unit: unit.name.clone(),
+                        expected: params.get_input_size(),
+                        found: input_widths[0],
+                    })
+                }
+                Some(params) => Ok(params.get_output_size()),
+                None => Ok(spec.output_size),
+            },
+            GraphUnitParams::Concat => Ok(input_widths.iter().sum()),
+            GraphUnitParams::Add => {
+                match input_widths.iter().find(|&&width| width != input_widths[0]) {
+                    Some(&found) => Err(GraphConfigError::ShapeMismatch {
+                        unit: unit.name.clone(),
+                        expected: input_widths[0],
+                        found,
+                    }),
+                    None => Ok(input_widths[0]),
+                }
+            }
+        }
+    }
+
+    // Anything not feeding a head would never receive a gradient
+    fn check_used(config: &GraphConfig, heads: &HashSet<&str>) -> Result<(), GraphConfigError> {
+        let sources: HashMap<&str, &Vec<String>> = config
+            .units()
+            .iter()
+            .map(|unit| (unit.name.as_str(), &unit.inputs))
+            .collect();
+
+        let mut used: HashSet<&str> = HashSet::new();
+        let mut stack: Vec<&str> = heads.iter().copied().collect();
+        while let Some(name) = stack.pop() {
+            if !used.insert(name) {
+                continue;
+            }
+            if let Some(inputs) = sources.get(name) {
+                stack.extend(inputs.iter().map(|input| input.as_str()));
+            }
+        }
+
+        let names = config
+            .inputs()
+            .iter()
+            .map(|input| &input.name)
+            .chain(config.units().iter().map(|unit| &unit.name));
+        for name in names {
+            if !used.contains(name.as_str()) {
+                return Err(GraphConfigError::Unused { name: name.clone() });
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,194 @@
+// builtin
+
+// external
+
+// internal
+use crate::{
+    data::precision::Precision,
+    network::{
+        config_types::{hyper_params::HyperParams, regularization_params::RegularizationParams},
+        types::graph::config::LayerSpec,
+    },
+    optimization::{
+        batch_norm::NormalizationType, learning_decay::LearningDecayType,
+        learning_schedule::LearningRateSchedule, momentum::DescentType,
+    },
+    regularization::penalty::{PenaltyConfig, PenaltyType},
+};
+
+pub const DEFAULT_BATCH_NORM_DECAY: f32 = 0.9;
+
+// A unit added to a builder, wired up once the network is built
+pub struct LayerEntry {
+    pub spec: LayerSpec,
+    pub is_softmax: bool,
+}
+
+impl LayerEntry {
+    pub fn new(output_size: usize, activation: &str) -> LayerEntry {
+        LayerEntry {
+            spec: LayerSpec::new(output_size, activation),
+            is_softmax: false,
+        }
+    }
+}
+
+// The input and the network wide settings every builder keeps, `tag` prefixes its panics
+pub struct BuilderParams {
+    tag: &'static str,
+    input_size: Option<Vec<usize>>,
+    input_keep_probability: f32,
+    decay_type: LearningDecayType,
+    descent_type: DescentType,
+    regularization: RegularizationParams,
+    precision: Precision,
+    schedule: LearningRateSchedule,
+}
+
+impl BuilderParams {
+    pub fn new(tag: &'static str) -> BuilderParams {
+        BuilderParams {
+            tag,
+            input_size: None,
+            input_keep_probability: 1.0,
+            decay_type: LearningDecayType::constant(0.01),
+            descent_type: DescentType::none(),
+            regularization: RegularizationParams::new(PenaltyType::None),
+            precision: Precision::default(),
+            schedule: LearningRateSchedule::constant(),
+        }
+    }
+
+    pub fn tag(&self) -> &'static str {
+        self.tag
+    }
+
+    pub fn has_input(&self) -> bool {
+        self.input_size.is_some()
+    }
+
+    pub fn precision(&self) -> Precision {
+        self.precision
+    }
+
+    // Returns the input size, its keep probability, the hyper parameters and the regularization
+    pub fn into_parts(self) -> (Vec<usize>, f32, HyperParams, RegularizationParams) {
+        let input_size: Vec<usize> = match self.input_size {
+            Some(input_size) => input_size,
+            None => panic!("[{}] Cannot build a network without an input", self.tag),
+        };
+        let hyperparams: HyperParams = HyperParams::new(
+            self.decay_type,
+            self.descent_type,
+            NormalizationType::none(),
+            self.precision,
+            self.schedule,
+        );
+
+        (
+            input_size,
+            self.input_keep_probability,
+            hyperparams,
+            self.regularization,
+        )
+    }
+}
+
+// Setters shared by the network builders. `dropout`, `batch_norm` and `softmax` configure the unit
+// added last, dropout before the first unit masks the input itself
+pub trait LayerBuilder: Sized {
+    fn params_mut(&mut self) -> &mut BuilderParams;
+
+    // None before the first unit, or when the unit added last belongs to another part of the
+    // network than the one being built, e.g. a multi task head without units yet
+    fn last_layer(&mut self) -> Option<&mut LayerEntry>;
+
+    fn has_layers(&self) -> bool;
+
+    // A single input with one dimension
+    fn input(mut self, input_size: Vec<usize>) -> Self {
+        let params: &mut BuilderParams = self.params_mut();
+        if input_size.len() != 1 {
+            panic!(
+                "[{}] Invalid input dimensions for network type, expected 1 but got {}.",
+                params.tag,
+                input_size.len()
+            );
+        }
+        if params.input_size.is_some() {
+            panic!(
+                "[{}] Input was already set, the network takes a single input",
+                params.tag
+            );
+        }
+
+        params.input_size = Some(input_size);
+        self
+    }
+
+    // Keep probability of the mask applied to the output of the last added unit or input
+    fn dropout(mut self, keep_probability: f32) -> Self {
+        if !self.has_layers() && self.params_mut().has_input() {
+            self.params_mut().input_keep_probability = keep_probability;
+            return self;
+        }
+
+        last_layer_or_panic(&mut self, "Dropout")
+            .spec
+            .keep_probability = keep_probability;
+        self
+    }
+
+    fn batch_norm(self) -> Self {
+        self.batch_norm_with_decay(DEFAULT_BATCH_NORM_DECAY)
+    }
+
+    // Replaces the biases of the last added unit with a normalization layer
+    fn batch_norm_with_decay(mut self, decay: f32) -> Self {
+        last_layer_or_panic(&mut self, "Batch norm").spec.batch_norm = Some(decay);
+        self
+    }
+
+    // Turns the last added unit into a softmax unit over its activation
+    fn softmax(mut self) -> Self {
+        last_layer_or_panic(&mut self, "Softmax").is_softmax = true;
+        self
+    }
+
+    fn decay(mut self, decay_type: LearningDecayType) -> Self {
+        self.params_mut().decay_type = decay_type;
+        self
+    }
+
+    fn descent(mut self, descent_type: DescentType) -> Self {
+        self.params_mut().descent_type = descent_type;
+        self
+    }
+
+    fn penalty(mut self, penalty_config: PenaltyConfig) -> Self {
+        self.params_mut().regularization =
+            RegularizationParams::from_builder(penalty_config.get_builder().as_ref());
+        self
+    }
+
+    fn precision(mut self, precision: Precision) -> Self {
+        self.params_mut().precision = precision;
+        self
+    }
+
+    fn schedule(mut self, schedule: LearningRateSchedule) -> Self {
+        self.params_mut().schedule = schedule;
+        self
+    }
+}
+
+fn last_layer_or_panic<'b>(
+    builder: &'b mut impl LayerBuilder,
+    setting: &str,
+) -> &'b mut LayerEntry {
+    let tag: &'static str = builder.params_mut().tag();
+    match builder.last_layer() {
+        Some(layer) => layer,
+        None => panic!("[{tag}] {setting} needs a unit to apply to"),
+    }
+}
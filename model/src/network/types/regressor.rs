@@ -1,41 +1,25 @@
 // builtin
-use std::io::Result;
+use std::io;
 
 // external
 
 // internal
 use crate::{
-    data::data_container::DataContainer,
+    data::{data_container::DataContainer, error::DataError},
     network::{
-        config_types::Config,
-        types::regressor::{builder::build_from_config, config::RegressorConfig},
+        config_types::{hyper_params::HyperParams, Config},
+        types::{regressor::config::RegressorConfig, sequential::SequentialNetwork},
         Network,
     },
-    optimization::{
-        batch_norm::NormalizationType, learning_decay::LearningDecayType, momentum::DescentType,
-    },
-    regularization::{
-        dropout::{NetworkMaskType, NetworkMode},
-        penalty::{PenaltyConfig, PenaltyType},
-    },
-    unit::{
-        types::{input_unit::InputUnit, linear_unit::LinearUnit, loss_unit::LossUnit},
-        Unit, UnitContainer,
-    },
+    node::gradcheck::{GradCheckConfig, GradCheckReport},
+    optimization::learning_schedule::LearningRateSchedule,
+    regularization::{dropout::NetworkMaskType, penalty::PenaltyConfig},
 };
-pub mod builder;
 pub mod config;
 
+// A sequential network of relu units and a linear output, kept for its config format
 pub struct RegressorNetwork<'a> {
-    input: UnitContainer<'a, InputUnit<'a>>,
-    hidden: Vec<UnitContainer<'a, LinearUnit<'a>>>,
-    inference: UnitContainer<'a, LinearUnit<'a>>,
-    loss: UnitContainer<'a, LossUnit<'a>>,
-    penalty_type: PenaltyType,
-    decay_type: LearningDecayType,
-    descent_type: DescentType,
-    normalization_type: NormalizationType,
-    time_step: usize,
+    network: SequentialNetwork<'a>,
 }
 
 impl<'a> RegressorNetwork<'a> {
@@ -45,9 +29,7 @@ impl<'a> RegressorNetwork<'a> {
         hidden_sizes: Vec<usize>,
         penalty_config: PenaltyConfig,
         mask_type: NetworkMaskType,
-        decay_type: LearningDecayType,
-        descent_type: DescentType,
-        normalization_type: NormalizationType,
+        hyperparams: HyperParams,
     ) -> RegressorNetwork<'a> {
         let config: RegressorConfig = RegressorConfig::new(
             input_size,
@@ -55,10 +37,9 @@ impl<'a> RegressorNetwork<'a> {
             hidden_sizes,
             penalty_config,
             mask_type,
-            decay_type,
-            descent_type,
-            normalization_type,
+            hyperparams,
         );
+
         RegressorNetwork::from_config(config)
     }
 
@@ -67,50 +48,62 @@ impl<'a> RegressorNetwork<'a> {
         RegressorNetwork::from_config(config)
     }
 
-    pub fn save_to_file(&self, path: &str) -> Result<()> {
+    pub fn save_to_file(&self, path: &str) -> io::Result<()> {
         let config: RegressorConfig = RegressorConfig::to_config(self);
         config.save_to_file(path)
     }
 
     fn from_config(config: RegressorConfig) -> RegressorNetwork<'a> {
-        build_from_config(config)
+        RegressorNetwork {
+            network: SequentialNetwork::from_config(config.into()),
+        }
     }
 }
 
 impl Network for RegressorNetwork<'_> {
-    fn predict(&self, input: DataContainer) -> DataContainer {
-        self.input.update_mode(NetworkMode::Inference);
-
-        self.input.borrow_mut().set_input_data(input);
-
-        let inference_ref = self.inference.borrow();
-        let inference_node = inference_ref.get_output_node();
-
-        inference_node.borrow_mut().apply_operation();
-
-        let output = inference_node.borrow_mut().get_data();
+    fn predict(&self, input: DataContainer) -> Result<DataContainer, DataError> {
+        self.network.predict(input)
+    }
 
-        output
+    fn train(&mut self, input: DataContainer, response: DataContainer) -> Result<f32, DataError> {
+        self.network.train(input, response)
     }
 
-    fn train(&mut self, input: DataContainer, response: DataContainer) {
-        self.input.update_mode(NetworkMode::Train);
+    fn create_config(&self) -> Config {
+        Config::Regressor(RegressorConfig::to_config(self))
+    }
 
-        self.input.borrow().set_input_data(input);
-        self.loss.borrow().set_expected_response(response);
+    fn load_config(config: Config) -> io::Result<Self> {
+        match config {
+            Config::Regressor(regressor_config) => {
+                Ok(RegressorNetwork::from_config(regressor_config))
+            }
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "Network config did not match the requested network type",
+            )),
+        }
+    }
 
-        let loss_ref = self.loss.borrow();
-        let loss_node = loss_ref.get_output_node();
+    fn get_learning_rate(&self) -> f32 {
+        self.network.get_learning_rate()
+    }
 
-        loss_node.borrow_mut().apply_operation();
+    fn set_learning_rate(&mut self, learning_rate: f32) {
+        self.network.set_learning_rate(learning_rate);
+    }
 
-        loss_node.borrow_mut().add_gradient(&DataContainer::one());
-        loss_node.borrow_mut().apply_jacobian();
+    fn set_learning_schedule(&mut self, schedule: LearningRateSchedule) {
+        self.network.set_learning_schedule(schedule);
     }
 
-    fn create_config(&self) -> Config {
-        let regressor_config = RegressorConfig::to_config(self);
-        Config::Regressor(regressor_config)
+    fn check_gradients(
+        &mut self,
+        input: DataContainer,
+        response: DataContainer,
+        config: &GradCheckConfig,
+    ) -> Result<GradCheckReport, DataError> {
+        self.network.check_gradients(input, response, config)
     }
 }
 
@@ -120,10 +113,15 @@ mod tests {
     use rand::{distributions::Uniform, prelude::Distribution};
 
     use crate::{
-        data::{data_container::DataContainer, Data},
-        network::{types::regressor::RegressorNetwork, Network},
+        data::{data_container::DataContainer, precision::Precision, Data},
+        network::{
+            config_types::hyper_params::HyperParams,
+            types::regressor::{config::RegressorConfig, RegressorNetwork},
+            Network,
+        },
         optimization::{
-            batch_norm::NormalizationType, learning_decay::LearningDecayType, momentum::DescentType,
+            batch_norm::NormalizationType, learning_decay::LearningDecayType,
+            learning_schedule::LearningRateSchedule, momentum::DescentType,
         },
         regularization::{
             dropout::NetworkMaskType,
@@ -138,17 +136,17 @@ mod tests {
 
         let test_arr: Array1<f32> = arr1(&[2.0]);
         let after_data = DataContainer::Inference(Data::VectorF32(test_arr.clone()));
-        let after_output = regressor.predict(after_data);
+        let after_output = regressor.predict(after_data).unwrap();
         println!("Loaded output 1: {:?}", after_output);
 
         let test_arr2: Array1<f32> = arr1(&[3.0]);
         let after_data2 = DataContainer::Inference(Data::VectorF32(test_arr2.clone()));
-        let after_output2 = regressor.predict(after_data2);
+        let after_output2 = regressor.predict(after_data2).unwrap();
         println!("Loaded output 2: {:?}", after_output2);
 
         let test_arr3: Array1<f32> = arr1(&[4.0]);
         let after_data3 = DataContainer::Inference(Data::VectorF32(test_arr3.clone()));
-        let after_output3 = regressor.predict(after_data3);
+        let after_output3 = regressor.predict(after_data3).unwrap();
         println!("Loaded output 3: {:?}", after_output3);
     }
 
@@ -162,9 +160,13 @@ mod tests {
             vec![6, 3],
             config,
             NetworkMaskType::None,
-            LearningDecayType::constant(0.005),
-            DescentType::Base,
-            NormalizationType::none(),
+            HyperParams::new(
+                LearningDecayType::constant(0.005),
+                DescentType::Base,
+                NormalizationType::none(),
+                Precision::F32,
+                LearningRateSchedule::constant(),
+            ),
         );
 
         let mut rng = rand::thread_rng();
@@ -181,24 +183,226 @@ mod tests {
                 responses.push(Data::VectorF32(arr1(&[x * x])));
             }
 
-            let input = DataContainer::Batch(inputs);
-            let response = DataContainer::Batch(responses);
+            let input = DataContainer::batch(inputs);
+            let response = DataContainer::batch(responses);
 
-            regressor.train(input, response);
+            regressor.train(input, response).unwrap();
         }
 
         let test_arr: Array1<f32> = arr1(&[2.0]);
         let after_data = DataContainer::Inference(Data::VectorF32(test_arr.clone()));
-        let after_output = regressor.predict(after_data);
+        let after_output = regressor.predict(after_data).unwrap();
         println!("Loaded output 1: {:?}", after_output);
 
         let test_arr2: Array1<f32> = arr1(&[3.0]);
         let after_data2 = DataContainer::Inference(Data::VectorF32(test_arr2.clone()));
-        let after_output2 = regressor.predict(after_data2);
+        let after_output2 = regressor.predict(after_data2).unwrap();
         println!("Loaded output 2: {:?}", after_output2);
 
         regressor
             .save_to_file("test/regressor_test.json")
             .expect("Save Failed");
     }
+
+    #[test]
+    fn regressor_precision_test() {
+        let config: PenaltyConfig = PenaltyConfig::new(L2PenaltyBuilder::new(0.2));
+
+        let mut regressor: RegressorNetwork = RegressorNetwork::new(
+            vec![1],
+            vec![1],
+            vec![6, 3],
+            config,
+            NetworkMaskType::None,
+            HyperParams::new(
+                LearningDecayType::constant(0.005),
+                DescentType::Base,
+                NormalizationType::batch_norm(0.95),
+                Precision::F64,
+                LearningRateSchedule::constant(),
+            ),
+        );
+
+        let mut rng = rand::thread_rng();
+        let distribution = Uniform::new(1.0, 4.0);
+
+        for _i in 0..20 {
+            let mut inputs = Vec::new();
+            let mut responses = Vec::new();
+
+            for _j in 0..8 {
+                let x: f32 = distribution.sample(&mut rng);
+
+                inputs.push(Data::VectorF32(arr1(&[x])));
+                responses.push(Data::VectorF32(arr1(&[x * x])));
+            }
+
+            regressor
+                .train(
+                    DataContainer::batch(inputs),
+                    DataContainer::batch(responses),
+                )
+                .unwrap();
+        }
+
+        let test_data = DataContainer::Inference(Data::VectorF32(arr1(&[2.0])));
+        let output = regressor.predict(test_data.clone()).unwrap();
+        assert_eq!(output.precision(), Precision::F64);
+
+        let path = std::env::temp_dir().join(format!(
+            "regressor_precision_test_{}.json",
+            std::process::id()
+        ));
+        let path: &str = path.to_str().unwrap();
+        regressor.save_to_file(path).expect("Save Failed");
+        let loaded: RegressorNetwork = RegressorNetwork::load_from_file(path);
+        let loaded_output = loaded.predict(test_data).unwrap();
+
+        assert_eq!(loaded_output.precision(), Precision::F64);
+        if let (DataContainer::Inference(loaded), DataContainer::Inference(saved)) =
+            (&loaded_output, &output)
+        {
+            let loaded: Vec<f64> = loaded.flatten_as();
+            let saved: Vec<f64> = saved.flatten_as();
+            assert!((loaded[0] - saved[0]).abs() < 1e-9);
+        }
+    }
+
+    // Trains a network, resumes a copy from its saved config and checks both continue alike,
+    // returns the saved config for checks on the optimizer state
+    fn assert_resume_matches(
+        decay_type: LearningDecayType,
+        descent_type: DescentType,
+    ) -> serde_json::Value {
+        let mut regressor: RegressorNetwork = RegressorNetwork::new(
+            vec![1],
+            vec![1],
+            vec![6, 3],
+            PenaltyConfig::new(L2PenaltyBuilder::new(0.01)),
+            NetworkMaskType::None,
+            HyperParams::new(
+                decay_type,
+                descent_type,
+                NormalizationType::none(),
+                Precision::F32,
+                LearningRateSchedule::constant(),
+            ),
+        );
+
+        let batch = |xs: &[f32]| {
+            let inputs = xs.iter().map(|x| Data::VectorF32(arr1(&[*x]))).collect();
+            let responses = xs.iter().map(|x| Data::VectorF32(arr1(&[x * x]))).collect();
+            (
+                DataContainer::batch(inputs),
+                DataContainer::batch(responses),
+            )
+        };
+
+        for i in 0..20 {
+            let offset = (i % 4) as f32 * 0.5;
+            let (input, response) = batch(&[1.0 + offset, 1.5 + offset, 2.0 + offset]);
+            regressor.train(input, response).unwrap();
+        }
+
+        let json = serde_json::to_string(&RegressorConfig::to_config(&regressor)).unwrap();
+        let mut resumed: RegressorNetwork =
+            RegressorNetwork::from_config(serde_json::from_str::<RegressorConfig>(&json).unwrap());
+
+        for network in [&mut regressor, &mut resumed] {
+            let (input, response) = batch(&[1.2, 2.4, 3.6]);
+            network.train(input, response).unwrap();
+        }
+
+        let test_data = DataContainer::Inference(Data::VectorF32(arr1(&[2.0])));
+        let original = regressor.predict(test_data.clone()).unwrap();
+        let loaded = resumed.predict(test_data).unwrap();
+
+        if let (DataContainer::Inference(original), DataContainer::Inference(loaded)) =
+            (original, loaded)
+        {
+            assert_eq!(original.flatten_as::<f64>(), loaded.flatten_as::<f64>());
+        } else {
+            panic!("Expected inference outputs from both networks");
+        }
+
+        serde_json::from_str(&json).unwrap()
+    }
+
+    #[test]
+    fn regressor_adam_resume_test() {
+        assert_resume_matches(
+            LearningDecayType::adam_w(0.01, 0.999, 0.01),
+            DescentType::adam(0.9),
+        );
+    }
+
+    #[test]
+    fn regressor_ada_delta_resume_test() {
+        let saved = assert_resume_matches(LearningDecayType::ada_delta(0.95), DescentType::Base);
+
+        // AdaDelta saves its squared update accumulator next to the gradient accumulator
+        let learning_rate = &saved["units"][0]["weights"]["learning_rate"];
+        let accumulator = learning_rate["update_accumulator"].as_array().unwrap();
+        assert_eq!(accumulator.len(), 6);
+        assert!(accumulator.iter().any(|f| f.as_f64() != Some(0.0)));
+    }
+
+    #[test]
+    fn regressor_schedule_test() {
+        let mut regressor: RegressorNetwork = RegressorNetwork::new(
+            vec![1],
+            vec![1],
+            vec![4],
+            PenaltyConfig::new(L2PenaltyBuilder::new(0.01)),
+            NetworkMaskType::None,
+            HyperParams::new(
+                LearningDecayType::rms_prop(0.01, 0.9),
+                DescentType::Base,
+                NormalizationType::none(),
+                Precision::F32,
+                LearningRateSchedule::constant(),
+            ),
+        );
+        // Warms up over two steps, then drops the rate to zero after three more
+        regressor.set_learning_schedule(LearningRateSchedule::warmup(
+            2,
+            LearningRateSchedule::step(3, 0.0),
+        ));
+        regressor.set_learning_rate(0.02);
+
+        let batch = || {
+            let inputs = vec![Data::VectorF32(arr1(&[1.0])), Data::VectorF32(arr1(&[2.0]))];
+            let responses = vec![Data::VectorF32(arr1(&[1.0])), Data::VectorF32(arr1(&[4.0]))];
+            (
+                DataContainer::batch(inputs),
+                DataContainer::batch(responses),
+            )
+        };
+        let output = |network: &RegressorNetwork| {
+            let test_data = DataContainer::Inference(Data::VectorF32(arr1(&[1.5])));
+            match network.predict(test_data).unwrap() {
+                DataContainer::Inference(output) => output.flatten_as::<f64>(),
+                _ => panic!("Expected an inference output from the network"),
+            }
+        };
+
+        let initial = output(&regressor);
+        for _i in 0..5 {
+            let (input, response) = batch();
+            regressor.train(input, response).unwrap();
+        }
+        let trained = output(&regressor);
+        assert_ne!(initial, trained);
+
+        let json = serde_json::to_string(&RegressorConfig::to_config(&regressor)).unwrap();
+        let mut resumed: RegressorNetwork =
+            RegressorNetwork::from_config(serde_json::from_str::<RegressorConfig>(&json).unwrap());
+        assert_eq!(resumed.get_learning_rate(), regressor.get_learning_rate());
+
+        for network in [&mut regressor, &mut resumed] {
+            let (input, response) = batch();
+            network.train(input, response).unwrap();
+            assert_eq!(output(network), trained);
+        }
+    }
 }
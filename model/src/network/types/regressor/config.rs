@@ -1,6 +1,6 @@
 // builtin
 use std::{
-    fs::{read_to_string, write},
+    fs::read_to_string,
     io::{Error, ErrorKind, Result},
 };
 
@@ -9,23 +9,23 @@ use serde::{Deserialize, Serialize};
 
 // internal
 use crate::{
+    data::precision::Precision,
     network::{
         config_types::{
-            hyper_params::HyperParams, input_params::InputParams, loss_params::LossParams,
-            regularization_params::RegularizationParams, unit_params::UnitParams, Config,
+            default_rate_factor, hyper_params::HyperParams, input_params::InputParams,
+            loss_params::LossParams, regularization_params::RegularizationParams,
+            unit_params::UnitParams, write_atomic, Config,
         },
-        types::regressor::RegressorNetwork,
-    },
-    optimization::{
-        batch_norm::NormalizationType, learning_decay::LearningDecayType, momentum::DescentType,
+        types::{regressor::RegressorNetwork, sequential::config::SequentialConfig},
     },
+    optimization::batch_norm::NormalizationType,
     regularization::{
         dropout::{NetworkMaskType, UnitMaskType},
         penalty::PenaltyConfig,
     },
 };
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct RegressorConfig {
     input: InputParams,
     units: Vec<UnitParams>,
@@ -33,6 +33,8 @@ pub struct RegressorConfig {
     hyperparams: HyperParams,
     regularization: RegularizationParams,
     time_step: usize,
+    #[serde(default = "default_rate_factor")]
+    rate_factor: f32,
 }
 
 impl RegressorConfig {
@@ -42,9 +44,7 @@ impl RegressorConfig {
         hidden_sizes: Vec<usize>,
         penalty_config: PenaltyConfig,
         mask_type: NetworkMaskType,
-        decay_type: LearningDecayType,
-        descent_type: DescentType,
-        normalization_type: NormalizationType,
+        hyperparams: HyperParams,
     ) -> RegressorConfig {
         if input_size.len() != 1 || output_size.len() != 1 {
             panic!("[SIMPLE_REGRESSOR] Invalid input / output dimensions for network type, expected 1 and 1 but got {} and {}.", input_size.len(), output_size.len());
@@ -57,8 +57,8 @@ impl RegressorConfig {
             loss_type: String::from("mean_squared_error"),
             output_size: output_size,
         };
-        let hyperparams: HyperParams =
-            HyperParams::new(decay_type, descent_type, normalization_type.clone());
+        let normalization_type: NormalizationType = hyperparams.normalization_type().clone();
+        let precision: Precision = hyperparams.precision();
 
         let mut units: Vec<UnitParams> = Vec::new();
         let mut prev_width: usize = input_usize;
@@ -72,6 +72,7 @@ impl RegressorConfig {
                 UnitMaskType::from_keep_probability(hidden_keep_p),
                 normalization_type.clone(),
                 false,
+                precision,
             );
             units.push(unit);
             prev_width = unit_size;
@@ -84,11 +85,12 @@ impl RegressorConfig {
             UnitMaskType::from_keep_probability(hidden_keep_p),
             normalization_type,
             true,
+            precision,
         );
         units.push(inference_unit);
 
         let regularization: RegularizationParams =
-            RegularizationParams::from_builder(penalty_config.get_builder());
+            RegularizationParams::from_builder(penalty_config.get_builder().as_ref());
 
         RegressorConfig {
             input,
@@ -97,44 +99,18 @@ impl RegressorConfig {
             hyperparams,
             regularization,
             time_step: 0,
+            rate_factor: default_rate_factor(),
         }
     }
 
     pub fn to_config(network: &RegressorNetwork) -> RegressorConfig {
-        let input = InputParams::from_unit(&network.input);
-
-        let loss = LossParams::from_unit(&network.loss);
-
-        let mut units: Vec<UnitParams> = Vec::new();
-
-        for unit in &network.hidden {
-            units.push(UnitParams::from_linear_unit(unit));
-        }
-        units.push(UnitParams::from_linear_unit(&network.inference));
-
-        let hyperparams: HyperParams = HyperParams::new(
-            network.decay_type.clone(),
-            network.descent_type.clone(),
-            network.normalization_type.clone(),
-        );
-
-        let regularization: RegularizationParams =
-            RegularizationParams::new(network.penalty_type.clone());
-
-        RegressorConfig {
-            input,
-            units,
-            loss,
-            hyperparams,
-            regularization,
-            time_step: network.time_step,
-        }
+        RegressorConfig::from(SequentialConfig::from_network(&network.network))
     }
 
     pub fn save_to_file(self, path: &str) -> Result<()> {
         let config: Config = Config::Regressor(self);
         let json_string = serde_json::to_string_pretty(&config).unwrap();
-        write(path, json_string)
+        write_atomic(path, &json_string)
     }
 
     pub fn load_from_file(path: &str) -> Result<RegressorConfig> {
@@ -175,4 +151,22 @@ impl RegressorConfig {
     pub fn timestep(&self) -> usize {
         self.time_step
     }
+
+    pub fn rate_factor(&self) -> f32 {
+        self.rate_factor
+    }
+}
+
+impl From<SequentialConfig> for RegressorConfig {
+    fn from(config: SequentialConfig) -> RegressorConfig {
+        RegressorConfig {
+            input: config.input().clone(),
+            units: config.units().clone(),
+            loss: config.loss().clone(),
+            hyperparams: config.params().clone(),
+            regularization: config.regularization().clone(),
+            time_step: config.timestep(),
+            rate_factor: config.rate_factor(),
+        }
+    }
 }
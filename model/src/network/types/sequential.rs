@@ -0,0 +1,264 @@
+// builtin
+use std::io;
+
+// external
+
+// internal
+use crate::{
+    data::{data_container::DataContainer, error::DataError, precision::Precision},
+    network::{
+        config_types::Config,
+        types::sequential::{
+            builder::build_from_config, config::SequentialConfig,
+            sequential_builder::SequentialBuilder,
+        },
+        Network,
+    },
+    node::{
+        gradcheck::{GradCheck, GradCheckConfig, GradCheckReport},
+        graph::Graph,
+    },
+    optimization::{
+        batch_norm::NormalizationType,
+        learning_schedule::{LearningRateSchedule, NetworkRate},
+        momentum::DescentType,
+    },
+    regularization::{dropout::NetworkMode, penalty::PenaltyType},
+    unit::{
+        types::{input_unit::InputUnit, layer_unit::LayerUnit, loss_unit::LossUnit},
+        UnitContainer,
+    },
+};
+pub mod builder;
+pub mod config;
+pub mod sequential_builder;
+
+pub struct SequentialNetwork<'a> {
+    input: UnitContainer<'a, InputUnit<'a>>,
+    units: Vec<LayerUnit<'a>>,
+    loss: UnitContainer<'a, LossUnit<'a>>,
+    train_graph: Graph<'a>,
+    inference_graph: Graph<'a>,
+    penalty_type: PenaltyType,
+    rate: NetworkRate,
+    descent_type: DescentType,
+    normalization_type: NormalizationType,
+    precision: Precision,
+}
+
+impl<'a> SequentialNetwork<'a> {
+    pub fn builder() -> SequentialBuilder {
+        SequentialBuilder::new()
+    }
+
+    pub fn from_config(config: SequentialConfig) -> SequentialNetwork<'a> {
+        build_from_config(config)
+    }
+
+    pub fn load_from_file(path: &str) -> SequentialNetwork<'a> {
+        let config: SequentialConfig = SequentialConfig::load_from_file(path).unwrap();
+        SequentialNetwork::from_config(config)
+    }
+
+    pub fn save_to_file(&self, path: &str) -> io::Result<()> {
+        let config: SequentialConfig = SequentialConfig::from_network(self);
+        config.save_to_file(path)
+    }
+}
+
+impl Network for SequentialNetwork<'_> {
+    fn predict(&self, input: DataContainer) -> Result<DataContainer, DataError> {
+        self.input.update_mode(NetworkMode::Inference);
+
+        self.input
+            .borrow_mut()
+            .set_input_data(input.to_precision(self.precision));
+
+        self.inference_graph.forward()?;
+
+        let output = self.inference_graph.get_output().borrow_mut().get_data();
+
+        Ok(output)
+    }
+
+    fn train(&mut self, input: DataContainer, response: DataContainer) -> Result<f32, DataError> {
+        self.input.update_mode(NetworkMode::Train);
+
+        self.train_graph.set_rate_scale(self.rate.next_scale());
+
+        self.input
+            .borrow()
+            .set_input_data(input.to_precision(self.precision));
+        self.loss
+            .borrow()
+            .set_expected_response(response.to_precision(self.precision));
+
+        self.train_graph.forward()?;
+        let loss: f32 = self.train_graph.output_sum();
+        self.train_graph.backward(&DataContainer::one())?;
+
+        Ok(loss)
+    }
+
+    fn create_config(&self) -> Config {
+        Config::Sequential(SequentialConfig::from_network(self))
+    }
+
+    fn load_config(config: Config) -> io::Result<Self> {
+        let sequential_config: SequentialConfig = SequentialConfig::from_config(config)?;
+        Ok(SequentialNetwork::from_config(sequential_config))
+    }
+
+    fn get_learning_rate(&self) -> f32 {
+        self.rate.get_rate()
+    }
+
+    fn set_learning_rate(&mut self, learning_rate: f32) {
+        self.rate.set_rate(learning_rate);
+    }
+
+    fn set_learning_schedule(&mut self, schedule: LearningRateSchedule) {
+        self.rate.set_schedule(schedule);
+    }
+
+    fn check_gradients(
+        &mut self,
+        input: DataContainer,
+        response: DataContainer,
+        config: &GradCheckConfig,
+    ) -> Result<GradCheckReport, DataError> {
+        self.input
+            .borrow()
+            .set_input_data(input.to_precision(self.precision));
+        self.loss
+            .borrow()
+            .set_expected_response(response.to_precision(self.precision));
+
+        GradCheck::check_network(&self.train_graph, &[self.input.get_ref()], config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::arr1;
+    use rand::{distributions::Uniform, prelude::Distribution};
+
+    use crate::{
+        data::{data_container::DataContainer, precision::Precision, Data},
+        network::{
+            config_types::{hyper_params::HyperParams, unit_params::UnitParams, Config},
+            types::{
+                classifier::ClassifierNetwork, layer_builder::LayerBuilder,
+                sequential::SequentialNetwork,
+            },
+            Network,
+        },
+        optimization::{
+            batch_norm::NormalizationType, learning_decay::LearningDecayType,
+            learning_schedule::LearningRateSchedule, momentum::DescentType,
+        },
+        random::{set_seed, with_rng},
+        regularization::{
+            dropout::NetworkMaskType,
+            penalty::{l2_penalty::builder::L2PenaltyBuilder, PenaltyConfig},
+        },
+    };
+
+    fn predict_values(network: &impl Network, x: f32) -> Vec<f64> {
+        let input = DataContainer::Inference(Data::VectorF32(arr1(&[x])));
+        match network.predict(input).unwrap() {
+            DataContainer::Inference(output) => output.flatten_as(),
+            _ => panic!("Expected an inference output from the network"),
+        }
+    }
+
+    #[test]
+    fn sequential_builder_test() {
+        set_seed(11);
+
+        let mut network: SequentialNetwork = SequentialNetwork::builder()
+            .input(vec![1])
+            .linear(8, "relu")
+            .dropout(0.9)
+            .batch_norm()
+            .linear(4, "sigmoid")
+            .linear(2, "none")
+            .softmax()
+            .loss("base_cross_entropy")
+            .decay(LearningDecayType::rms_prop(0.05, 0.95))
+            .descent(DescentType::nesterov(0.4))
+            .penalty(PenaltyConfig::new(L2PenaltyBuilder::new(0.001)))
+            .build();
+
+        let config: Config = network.create_config();
+        if let Config::Sequential(sequential) = &config {
+            let units: &Vec<UnitParams> = sequential.units();
+            assert!(units[0].normalization_type().is_batch_norm_enabled());
+            assert!(!units[1].normalization_type().is_batch_norm_enabled());
+            assert!(matches!(units[2], UnitParams::Softmax { .. }));
+        } else {
+            panic!("Expected a sequential config");
+        }
+
+        let distribution = Uniform::new(-1.0, 1.0);
+        for _i in 0..200 {
+            let xs: Vec<f32> = with_rng(|rng| (0..8).map(|_j| distribution.sample(rng)).collect());
+            let inputs = xs.iter().map(|x| Data::VectorF32(arr1(&[*x]))).collect();
+            let responses = xs
+                .iter()
+                .map(|x| Data::VectorF32(arr1(if *x < 0.0 { &[1.0, 0.0] } else { &[0.0, 1.0] })))
+                .collect();
+
+            network
+                .train(
+                    DataContainer::batch(inputs),
+                    DataContainer::batch(responses),
+                )
+                .unwrap();
+        }
+
+        assert!(predict_values(&network, -0.8)[0] > 0.5);
+        assert!(predict_values(&network, 0.8)[1] > 0.5);
+
+        let json: String = serde_json::to_string(&network.create_config()).unwrap();
+        let loaded: SequentialNetwork =
+            SequentialNetwork::load_config(serde_json::from_str(&json).unwrap()).unwrap();
+        let frozen = network.freeze();
+
+        for x in [-0.8, -0.1, 0.4] {
+            let expected: Vec<f64> = predict_values(&network, x);
+            assert_eq!(predict_values(&loaded, x), expected);
+
+            let frozen_output: Data = frozen.try_predict(&Data::VectorF32(arr1(&[x]))).unwrap();
+            assert_eq!(frozen_output.flatten_as::<f64>(), expected);
+        }
+    }
+
+    #[test]
+    fn classifier_config_loads_as_sequential() {
+        let classifier: ClassifierNetwork = ClassifierNetwork::new(
+            vec![1],
+            vec![2],
+            vec![4, 3],
+            PenaltyConfig::new(L2PenaltyBuilder::new(0.001)),
+            NetworkMaskType::from_probabilities(0.9, 0.8),
+            HyperParams::new(
+                LearningDecayType::constant(0.01),
+                DescentType::Base,
+                NormalizationType::batch_norm(0.95),
+                Precision::F64,
+                LearningRateSchedule::constant(),
+            ),
+        );
+
+        let sequential: SequentialNetwork =
+            SequentialNetwork::load_config(classifier.create_config()).unwrap();
+
+        for x in [-0.7, 0.2, 0.9] {
+            assert_eq!(
+                predict_values(&sequential, x),
+                predict_values(&classifier, x)
+            );
+        }
+    }
+}
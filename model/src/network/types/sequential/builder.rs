@@ -0,0 +1,130 @@
+// builtin
+
+// external
+
+// internal
+use crate::{
+    network::types::sequential::{config::SequentialConfig, SequentialNetwork},
+    node::{graph::Graph, NodeRef},
+    optimization::{
+        learning_decay::LearningDecayType, learning_schedule::NetworkRate, momentum::DescentType,
+    },
+    regularization::penalty::{PenaltyConfig, PenaltyContainer},
+    unit::{
+        types::{input_unit::InputUnit, layer_unit::LayerUnit, loss_unit::LossUnit},
+        Unit, UnitContainer, UnitRef,
+    },
+};
+
+pub fn build_from_config<'a>(config: SequentialConfig) -> SequentialNetwork<'a> {
+    let decay_type: &LearningDecayType = config.params().decay_type();
+    let descent_type: &DescentType = config.params().descent_type();
+    let penalty_config: PenaltyConfig = config.regularization().get_config();
+
+    let input: UnitContainer<InputUnit> = UnitContainer::new(InputUnit::from_config(
+        config.input(),
+        config.params().precision(),
+    ));
+
+    let (units, last_ref, last_penalty) = build_units(&config, input.get_ref(), &penalty_config);
+
+    let loss: UnitContainer<LossUnit> = build_loss(&config, &last_ref, &last_penalty);
+
+    let (train_graph, inference_graph) = build_graphs(&last_ref, &loss);
+
+    SequentialNetwork {
+        input,
+        units,
+        loss,
+        train_graph,
+        inference_graph,
+        penalty_type: penalty_config.get_type(),
+        rate: NetworkRate::new(
+            decay_type.clone(),
+            config.params().schedule().clone(),
+            config.timestep(),
+            config.rate_factor(),
+        ),
+        descent_type: descent_type.clone(),
+        normalization_type: config.params().normalization_type().clone(),
+        precision: config.params().precision(),
+    }
+}
+
+fn build_units<'a>(
+    config: &SequentialConfig,
+    input_ref: UnitRef<'a>,
+    penalty_config: &PenaltyConfig<'a>,
+) -> (Vec<LayerUnit<'a>>, UnitRef<'a>, PenaltyContainer<'a>) {
+    let decay_type: &LearningDecayType = config.params().decay_type();
+    let descent_type: &DescentType = config.params().descent_type();
+
+    let mut prev_ref: UnitRef = input_ref;
+    let mut units: Vec<LayerUnit> = Vec::new();
+    let mut prev_penalty: Option<PenaltyContainer> = None;
+
+    for unit_config in config.units() {
+        let unit: LayerUnit = LayerUnit::from_config(unit_config, decay_type, descent_type);
+
+        let penalty: PenaltyContainer =
+            build_penalty(penalty_config, prev_penalty, &unit.get_weights_ref());
+
+        let unit_ref: UnitRef = unit.get_ref();
+        unit_ref.borrow_mut().add_input(&unit_ref, &prev_ref);
+
+        prev_penalty = Option::Some(penalty);
+        prev_ref = unit_ref;
+        units.push(unit);
+    }
+
+    match prev_penalty {
+        Option::Some(penalty) => (units, prev_ref, penalty),
+        Option::None => {
+            panic!(
+                "[SEQUENTIAL] Invalid network config, expected at least one unit after the input"
+            )
+        }
+    }
+}
+
+fn build_penalty<'a>(
+    penalty_config: &PenaltyConfig<'a>,
+    prev_penalty: Option<PenaltyContainer<'a>>,
+    parameter: &NodeRef<'a>,
+) -> PenaltyContainer<'a> {
+    if let Option::Some(prev) = &prev_penalty {
+        penalty_config.create_new(&prev.get_ref(), parameter)
+    } else {
+        penalty_config.create_first(parameter)
+    }
+}
+
+fn build_loss<'a>(
+    config: &SequentialConfig,
+    last_ref: &UnitRef<'a>,
+    last_penalty: &PenaltyContainer<'a>,
+) -> UnitContainer<'a, LossUnit<'a>> {
+    let loss: UnitContainer<LossUnit> = UnitContainer::new(LossUnit::from_config(config.loss()));
+    loss.add_input_ref(last_ref);
+    loss.borrow()
+        .add_regularization_node(&last_penalty.get_ref());
+
+    loss
+}
+
+fn build_graphs<'a>(
+    last_ref: &UnitRef<'a>,
+    loss: &UnitContainer<'a, LossUnit<'a>>,
+) -> (Graph<'a>, Graph<'a>) {
+    let train_graph: Graph = match Graph::new(loss.borrow().get_output_node()) {
+        Ok(graph) => graph,
+        Err(error) => panic!("[SEQUENTIAL] Invalid network graph: {error}"),
+    };
+
+    let inference_graph: Graph = match train_graph.subgraph(last_ref.borrow().get_output_node()) {
+        Ok(graph) => graph,
+        Err(error) => panic!("[SEQUENTIAL] Invalid inference graph: {error}"),
+    };
+
+    (train_graph, inference_graph)
+}
@@ -0,0 +1,179 @@
+// builtin
+use std::{
+    fs::read_to_string,
+    io::{Error, ErrorKind, Result},
+};
+
+// external
+use serde::{Deserialize, Serialize};
+
+// internal
+use crate::network::{
+    config_types::{
+        default_rate_factor, hyper_params::HyperParams, input_params::InputParams,
+        loss_params::LossParams, regularization_params::RegularizationParams,
+        unit_params::UnitParams, write_atomic, Config,
+    },
+    types::{
+        classifier::config::ClassifierConfig, regressor::config::RegressorConfig,
+        sequential::SequentialNetwork,
+    },
+};
+
+// Every unit carries its own activation, dropout and batch norm settings, the normalization type
+// in the hyperparams is unused
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SequentialConfig {
+    input: InputParams,
+    units: Vec<UnitParams>,
+    loss: LossParams,
+    hyperparams: HyperParams,
+    regularization: RegularizationParams,
+    time_step: usize,
+    #[serde(default = "default_rate_factor")]
+    rate_factor: f32,
+}
+
+impl SequentialConfig {
+    pub fn new(
+        input: InputParams,
+        units: Vec<UnitParams>,
+        loss: LossParams,
+        hyperparams: HyperParams,
+        regularization: RegularizationParams,
+    ) -> SequentialConfig {
+        if units.is_empty() {
+            panic!(
+                "[SEQUENTIAL] Invalid network config, expected at least one unit after the input"
+            );
+        }
+
+        SequentialConfig {
+            input,
+            units,
+            loss,
+            hyperparams,
+            regularization,
+            time_step: 0,
+            rate_factor: default_rate_factor(),
+        }
+    }
+
+    pub fn from_network(network: &SequentialNetwork) -> SequentialConfig {
+        let input: InputParams = InputParams::from_unit(&network.input);
+
+        let loss: LossParams = LossParams::from_unit(&network.loss);
+
+        let units: Vec<UnitParams> = network.units.iter().map(|unit| unit.to_params()).collect();
+
+        let hyperparams: HyperParams = HyperParams::new(
+            network.rate.decay_type().clone(),
+            network.descent_type.clone(),
+            network.normalization_type.clone(),
+            network.precision,
+            network.rate.schedule().clone(),
+        );
+
+        let regularization: RegularizationParams =
+            RegularizationParams::new(network.penalty_type.clone());
+
+        SequentialConfig {
+            input,
+            units,
+            loss,
+            hyperparams,
+            regularization,
+            time_step: network.rate.time_step(),
+            rate_factor: network.rate.factor(),
+        }
+    }
+
+    // Classifier, regressor and binary classifier configs are sequential stacks, so saved ones load
+    // as either type
+    pub fn from_config(config: Config) -> Result<SequentialConfig> {
+        match config {
+            Config::Sequential(sequential_config) => Ok(sequential_config),
+            Config::Classifier(classifier_config) => Ok(SequentialConfig::from(classifier_config)),
+            Config::Regressor(regressor_config) => Ok(SequentialConfig::from(regressor_config)),
+            Config::BinaryClassifier(binary_config) => Ok(binary_config.network().clone()),
+            Config::Graph(_) => Err(Error::new(
+                ErrorKind::InvalidData,
+                "Cannot build a sequential network from a graph config",
+            )),
+            Config::None => Err(Error::new(
+                ErrorKind::InvalidData,
+                "Cannot build a sequential network from an empty config",
+            )),
+        }
+    }
+
+    pub fn save_to_file(self, path: &str) -> Result<()> {
+        let config: Config = Config::Sequential(self);
+        let json_string = serde_json::to_string_pretty(&config).unwrap();
+        write_atomic(path, &json_string)
+    }
+
+    pub fn load_from_file(path: &str) -> Result<SequentialConfig> {
+        let data = read_to_string(path)?;
+        let config: Config =
+            serde_json::from_str(&data).expect("Invalid JSON data for network configuration");
+
+        SequentialConfig::from_config(config)
+    }
+
+    pub fn input(&self) -> &InputParams {
+        &self.input
+    }
+
+    pub fn units(&self) -> &Vec<UnitParams> {
+        &self.units
+    }
+
+    pub fn loss(&self) -> &LossParams {
+        &self.loss
+    }
+
+    pub fn params(&self) -> &HyperParams {
+        &self.hyperparams
+    }
+
+    pub fn regularization(&self) -> &RegularizationParams {
+        &self.regularization
+    }
+
+    pub fn timestep(&self) -> usize {
+        self.time_step
+    }
+
+    pub fn rate_factor(&self) -> f32 {
+        self.rate_factor
+    }
+}
+
+impl From<ClassifierConfig> for SequentialConfig {
+    fn from(config: ClassifierConfig) -> SequentialConfig {
+        SequentialConfig {
+            input: config.input().clone(),
+            units: config.units().clone(),
+            loss: config.loss().clone(),
+            hyperparams: config.params().clone(),
+            regularization: config.regularization().clone(),
+            time_step: config.timestep(),
+            rate_factor: config.rate_factor(),
+        }
+    }
+}
+
+impl From<RegressorConfig> for SequentialConfig {
+    fn from(config: RegressorConfig) -> SequentialConfig {
+        SequentialConfig {
+            input: config.input().clone(),
+            units: config.units().clone(),
+            loss: config.loss().clone(),
+            hyperparams: config.params().clone(),
+            regularization: config.regularization().clone(),
+            time_step: config.timestep(),
+            rate_factor: config.rate_factor(),
+        }
+    }
+}
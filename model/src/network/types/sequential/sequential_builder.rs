@@ -0,0 +1,132 @@
+// builtin
+
+// external
+
+// internal
+use crate::{
+    data::precision::Precision,
+    network::{
+        config_types::{
+            input_params::InputParams, loss_params::LossParams, unit_params::UnitParams,
+        },
+        types::{
+            graph::config::LayerSpec,
+            layer_builder::{BuilderParams, LayerBuilder, LayerEntry},
+            sequential::{config::SequentialConfig, SequentialNetwork},
+        },
+    },
+    optimization::batch_norm::NormalizationType,
+    regularization::dropout::UnitMaskType,
+};
+
+// Chains units in the order they are added, see LayerBuilder for the shared settings. The output
+// unit never gets dropout or batch norm, matching the other network types
+pub struct SequentialBuilder {
+    params: BuilderParams,
+    units: Vec<LayerEntry>,
+    loss_type: Option<String>,
+}
+
+impl Default for SequentialBuilder {
+    fn default() -> Self {
+        SequentialBuilder::new()
+    }
+}
+
+impl LayerBuilder for SequentialBuilder {
+    fn params_mut(&mut self) -> &mut BuilderParams {
+        &mut self.params
+    }
+
+    fn last_layer(&mut self) -> Option<&mut LayerEntry> {
+        self.units.last_mut()
+    }
+
+    fn has_layers(&self) -> bool {
+        !self.units.is_empty()
+    }
+}
+
+impl SequentialBuilder {
+    pub fn new() -> SequentialBuilder {
+        SequentialBuilder {
+            params: BuilderParams::new("SEQUENTIAL"),
+            units: Vec::new(),
+            loss_type: None,
+        }
+    }
+
+    pub fn linear(mut self, output_size: usize, activation: &str) -> SequentialBuilder {
+        if !self.params.has_input() {
+            panic!("[SEQUENTIAL] Units can only be added after the input");
+        }
+
+        self.units.push(LayerEntry::new(output_size, activation));
+        self
+    }
+
+    pub fn loss(mut self, loss_type: &str) -> SequentialBuilder {
+        self.loss_type = Some(loss_type.to_string());
+        self
+    }
+
+    pub fn config(self) -> SequentialConfig {
+        let precision: Precision = self.params.precision();
+        let (input_size, input_keep_probability, hyperparams, regularization) =
+            self.params.into_parts();
+        let loss_type: String = match self.loss_type {
+            Some(loss_type) => loss_type,
+            None => panic!("[SEQUENTIAL] Cannot build a network without a loss"),
+        };
+        if self.units.is_empty() {
+            panic!("[SEQUENTIAL] Cannot build a network without any units after the input");
+        }
+
+        let last: usize = self.units.len() - 1;
+        let last_spec: &LayerSpec = &self.units[last].spec;
+        if last_spec.keep_probability < 1.0 || last_spec.batch_norm.is_some() {
+            println!("[SEQUENTIAL] Dropout and batch norm are skipped on the output unit");
+        }
+
+        let mut units: Vec<UnitParams> = Vec::new();
+        let mut prev_width: usize = input_size[0];
+        for (i, unit) in self.units.iter().enumerate() {
+            let is_last_layer: bool = i == last;
+            let mask_type: UnitMaskType =
+                UnitMaskType::from_keep_probability(unit.spec.keep_probability);
+            let normalization_type: NormalizationType = if is_last_layer {
+                NormalizationType::none()
+            } else {
+                unit.spec.normalization_type()
+            };
+
+            let new_unit = if unit.is_softmax {
+                UnitParams::new_softmax
+            } else {
+                UnitParams::new_linear
+            };
+            units.push(new_unit(
+                prev_width,
+                unit.spec.output_size,
+                &unit.spec.activation,
+                mask_type,
+                normalization_type,
+                is_last_layer,
+                precision,
+            ));
+            prev_width = unit.spec.output_size;
+        }
+
+        let input: InputParams = InputParams::new(input_size, input_keep_probability);
+        let loss: LossParams = LossParams {
+            loss_type,
+            output_size: vec![prev_width],
+        };
+
+        SequentialConfig::new(input, units, loss, hyperparams, regularization)
+    }
+
+    pub fn build<'a>(self) -> SequentialNetwork<'a> {
+        SequentialNetwork::from_config(self.config())
+    }
+}
@@ -4,6 +4,7 @@
 
 // internal
 use crate::{
+    data::precision::Precision,
     network::config_types::unit_params::UnitParams,
     optimization::{
         batch_norm::NormalizationType, learning_decay::LearningDecayType, momentum::DescentType,
@@ -28,8 +29,11 @@ impl<'a> TestNetwork<'a> {
         decay_type: LearningDecayType,
         penalty: PenaltyConfig<'a>,
     ) -> TestNetwork<'a> {
-        let input: UnitContainer<InputUnit> =
-            UnitContainer::new(InputUnit::new(vec![input_size], UnitMaskType::None));
+        let input: UnitContainer<InputUnit> = UnitContainer::new(InputUnit::new(
+            vec![input_size],
+            UnitMaskType::None,
+            Precision::default(),
+        ));
 
         let config = &UnitParams::new_linear(
             input_size,
@@ -38,6 +42,7 @@ impl<'a> TestNetwork<'a> {
             UnitMaskType::None,
             NormalizationType::none(),
             true,
+            Precision::F32,
         );
         let inference: UnitContainer<LinearUnit> = UnitContainer::new(LinearUnit::from_config(
             config,
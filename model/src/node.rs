@@ -9,10 +9,13 @@ use std::{
 
 // internal
 use crate::{
-    data::data_container::DataContainer, network::config_types::learned_params::LearnedParams,
+    data::{data_container::DataContainer, error::DataError},
+    network::config_types::learned_params::LearnedParams,
     regularization::dropout::NetworkMode,
 };
 pub mod activation;
+pub mod gradcheck;
+pub mod graph;
 pub mod loss;
 pub mod node_base;
 pub mod types;
@@ -47,6 +50,11 @@ impl<'a> NodeRef<'a> {
         self.node_type
     }
 
+    // Address of the shared node, identical for every clone of the same reference
+    pub fn id(&self) -> usize {
+        Rc::as_ptr(&self.reference) as *const () as usize
+    }
+
     pub fn borrow(&self) -> Ref<'_, dyn Node<'a> + 'a> {
         self.reference.borrow()
     }
@@ -56,7 +64,7 @@ impl<'a> NodeRef<'a> {
     }
 }
 
-#[derive(Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NodeType {
     Parameter,
     Input,
@@ -101,11 +109,20 @@ pub trait Node<'a> {
 
     fn set_learning_rate(&mut self, learning_rate: DataContainer);
 
-    fn apply_operation(&mut self);
+    // Only AdaDelta parameters keep an update accumulator, every other node ignores it
+    fn set_update_accumulator(&mut self, _update_accumulator: DataContainer) {}
+
+    // Only learnable parameters use the scale, every other node ignores it
+    fn set_rate_scale(&mut self, scale: f32);
+
+    fn apply_operation(&mut self) -> Result<(), DataError>;
+
+    fn add_gradient(&mut self, grad: &DataContainer) -> Result<(), DataError>;
 
-    fn add_gradient(&mut self, grad: &DataContainer);
+    fn apply_jacobian(&mut self) -> Result<(), DataError>;
 
-    fn apply_jacobian(&mut self);
+    // Only parameter and input nodes hold on to their gradient, every other node returns Empty
+    fn get_gradient(&self) -> DataContainer;
 
-    fn should_process_backprop(&self) -> bool;
+    fn set_retain_gradient(&mut self, retain: bool);
 }
@@ -5,7 +5,7 @@
 use std::fmt::Debug;
 
 // internal
-use crate::data::Data;
+use crate::data::{element_fn::ElementFn, precision::Element, Data};
 use crate::node::activation::registry::{init_activation_registry, ActivationRegistry};
 
 pub trait ActivationType: Send + Sync + Debug {
@@ -13,6 +13,10 @@ pub trait ActivationType: Send + Sync + Debug {
 
     fn diff(&self, input: f32) -> f32;
 
+    fn apply_f64(&self, input: f64) -> f64;
+
+    fn diff_f64(&self, input: f64) -> f64;
+
     fn name(&self) -> &str;
 
     fn copy(&self) -> Box<dyn ActivationType>;
@@ -31,31 +35,52 @@ impl ActivationFunction {
         }
     }
 
-    pub fn apply_all(&self, data: Data) -> Data {
-        match data {
-            Data::VectorF32(mut vec) => {
-                vec.mapv_inplace(|f| self.activation_type.apply(f));
-                Data::VectorF32(vec)
-            }
-            Data::MatrixF32(mut matrix) => {
-                matrix.mapv_inplace(|f| self.activation_type.apply(f));
-                Data::MatrixF32(matrix)
-            }
-            _ => Data::None,
+    // Elementwise function for `apply_elementwise`, evaluated in the data's own precision
+    pub fn applied(&self) -> Activate<'_> {
+        Activate {
+            activation_type: self.activation_type.as_ref(),
         }
     }
 
-    pub fn diff_all(&self, data: Data) -> Data {
-        match data {
-            Data::VectorF32(mut vec) => {
-                vec.mapv_inplace(|f| self.activation_type.diff(f));
-                Data::VectorF32(vec)
-            }
-            Data::MatrixF32(mut matrix) => {
-                matrix.mapv_inplace(|f| self.activation_type.diff(f));
-                Data::MatrixF32(matrix)
-            }
-            _ => Data::None,
+    pub fn derived(&self) -> Derive<'_> {
+        Derive {
+            activation_type: self.activation_type.as_ref(),
         }
     }
+
+    pub fn apply_all(&self, data: Data) -> Data {
+        data.apply_elementwise(self.applied())
+    }
+
+    pub fn diff_all(&self, data: Data) -> Data {
+        data.apply_elementwise(self.derived())
+    }
+}
+
+pub struct Activate<'f> {
+    activation_type: &'f dyn ActivationType,
+}
+
+impl ElementFn for Activate<'_> {
+    fn call<T: Element>(&self, value: T) -> T {
+        T::select(
+            value,
+            |f| self.activation_type.apply(f),
+            |f| self.activation_type.apply_f64(f),
+        )
+    }
+}
+
+pub struct Derive<'f> {
+    activation_type: &'f dyn ActivationType,
+}
+
+impl ElementFn for Derive<'_> {
+    fn call<T: Element>(&self, value: T) -> T {
+        T::select(
+            value,
+            |f| self.activation_type.diff(f),
+            |f| self.activation_type.diff_f64(f),
+        )
+    }
 }
@@ -18,6 +18,14 @@ impl ActivationType for LinearActivation {
         1.0
     }
 
+    fn apply_f64(&self, input: f64) -> f64 {
+        input
+    }
+
+    fn diff_f64(&self, _input: f64) -> f64 {
+        1.0
+    }
+
     fn name(&self) -> &str {
         "none"
     }
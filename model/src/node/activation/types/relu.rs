@@ -3,24 +3,26 @@
 // external
 
 // internal
-use crate::node::activation::activation_function::ActivationType;
+use crate::{data::precision::Element, node::activation::activation_function::ActivationType};
 
 #[derive(Debug)]
 pub struct ReLUActivation;
 
 impl ActivationType for ReLUActivation {
     fn apply(&self, input: f32) -> f32 {
-        if input < 0.0 {
-            return 0.0;
-        }
-        input
+        relu(input)
     }
 
     fn diff(&self, input: f32) -> f32 {
-        if input < 0.0 {
-            return 0.0;
-        }
-        1.0
+        relu_diff(input)
+    }
+
+    fn apply_f64(&self, input: f64) -> f64 {
+        relu(input)
+    }
+
+    fn diff_f64(&self, input: f64) -> f64 {
+        relu_diff(input)
     }
 
     fn name(&self) -> &str {
@@ -31,3 +33,17 @@ impl ActivationType for ReLUActivation {
         Box::new(ReLUActivation)
     }
 }
+
+fn relu<T: Element>(input: T) -> T {
+    if input < T::zero() {
+        return T::zero();
+    }
+    input
+}
+
+fn relu_diff<T: Element>(input: T) -> T {
+    if input < T::zero() {
+        return T::zero();
+    }
+    T::one()
+}
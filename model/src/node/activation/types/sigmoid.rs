@@ -3,20 +3,26 @@
 // external
 
 // internal
-use crate::node::activation::activation_function::ActivationType;
+use crate::{data::precision::Element, node::activation::activation_function::ActivationType};
 
 #[derive(Debug)]
 pub struct SigmoidActivation;
 
 impl ActivationType for SigmoidActivation {
     fn apply(&self, input: f32) -> f32 {
-        1.0 / (1.0 + f32::exp(-input))
+        sigmoid(input)
     }
 
     fn diff(&self, input: f32) -> f32 {
-        let sigmoid: f32 = self.apply(input);
+        sigmoid_diff(input)
+    }
+
+    fn apply_f64(&self, input: f64) -> f64 {
+        sigmoid(input)
+    }
 
-        sigmoid * (1.0 - sigmoid)
+    fn diff_f64(&self, input: f64) -> f64 {
+        sigmoid_diff(input)
     }
 
     fn name(&self) -> &str {
@@ -27,3 +33,13 @@ impl ActivationType for SigmoidActivation {
         Box::new(SigmoidActivation)
     }
 }
+
+fn sigmoid<T: Element>(input: T) -> T {
+    T::one() / (T::one() + (-input).exp())
+}
+
+fn sigmoid_diff<T: Element>(input: T) -> T {
+    let sigmoid: T = sigmoid(input);
+
+    sigmoid * (T::one() - sigmoid)
+}
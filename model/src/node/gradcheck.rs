@@ -0,0 +1,675 @@
+// builtin
+use std::fmt::Display;
+
+// external
+
+// internal
+use crate::{
+    data::{data_container::DataContainer, element_fn::Fill, error::DataError, Data},
+    node::{graph::Graph, NodeRef, NodeType},
+    regularization::dropout::NetworkMode,
+    unit::UnitRef,
+};
+
+// Below this magnitude errors are measured absolutely, otherwise rounding noise in the numeric
+// estimate of a zero gradient would dominate the report
+const MIN_SCALE: f64 = 1e-2;
+
+#[derive(Clone, Copy)]
+pub struct GradCheckConfig {
+    epsilon: f64,
+    check_inputs: bool,
+}
+
+impl GradCheckConfig {
+    pub fn new(epsilon: f64, check_inputs: bool) -> GradCheckConfig {
+        GradCheckConfig {
+            epsilon,
+            check_inputs,
+        }
+    }
+
+    pub fn epsilon(&self) -> f64 {
+        self.epsilon
+    }
+
+    pub fn check_inputs(&self) -> bool {
+        self.check_inputs
+    }
+}
+
+impl Default for GradCheckConfig {
+    fn default() -> Self {
+        GradCheckConfig::new(1e-6, false)
+    }
+}
+
+pub struct NodeGradCheck {
+    index: usize,
+    node_type: NodeType,
+    size: usize,
+    max_relative_error: f64,
+}
+
+impl NodeGradCheck {
+    // Position of the node among the checked nodes, in the topological order of the graph
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn node_type(&self) -> NodeType {
+        self.node_type
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn max_relative_error(&self) -> f64 {
+        self.max_relative_error
+    }
+}
+
+pub struct GradCheckReport {
+    nodes: Vec<NodeGradCheck>,
+}
+
+impl GradCheckReport {
+    pub fn nodes(&self) -> &Vec<NodeGradCheck> {
+        &self.nodes
+    }
+
+    pub fn max_relative_error(&self) -> f64 {
+        self.nodes
+            .iter()
+            .map(NodeGradCheck::max_relative_error)
+            .fold(0.0, f64::max)
+    }
+
+    pub fn passed(&self, tolerance: f64) -> bool {
+        self.max_relative_error() <= tolerance
+    }
+}
+
+impl Display for GradCheckReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for node in &self.nodes {
+            writeln!(
+                f,
+                "[GRADCHECK] node {} ({}, {} values): max relative error {:e}",
+                node.index, node.node_type, node.size, node.max_relative_error
+            )?;
+        }
+        Ok(())
+    }
+}
+
+pub struct GradCheck;
+
+impl GradCheck {
+    // Checks the training graph of a network whose data is already set, `inputs` are the units
+    // the mode is propagated from. Running in NetworkMode::GradCheck holds the dropout masks for
+    // the whole check and keeps batch norm statistics untouched, so the network is left as it was
+    pub fn check_network<'a>(
+        graph: &Graph<'a>,
+        inputs: &[UnitRef<'a>],
+        config: &GradCheckConfig,
+    ) -> Result<GradCheckReport, DataError> {
+        for input in inputs {
+            input.borrow_mut().update_mode(NetworkMode::GradCheck);
+        }
+
+        GradCheck::check_graph(graph, config)
+    }
+
+    // Compares backprop gradients against central differences of the summed output of `graph`,
+    // every weight and bias in it is perturbed and, if configured, every input as well.
+    // The graph should be deterministic, so masks and batch norm have to be in inference or
+    // gradcheck mode beforehand
+    pub fn check_graph<'a>(
+        graph: &Graph<'a>,
+        config: &GradCheckConfig,
+    ) -> Result<GradCheckReport, DataError> {
+        let nodes: Vec<NodeRef<'a>> = graph
+            .get_nodes()
+            .iter()
+            .filter(|node| match node.get_type() {
+                NodeType::Parameter => true,
+                NodeType::Input => config.check_inputs,
+                _ => false,
+            })
+            .cloned()
+            .collect();
+
+        for node in &nodes {
+            node.borrow_mut().set_retain_gradient(true);
+        }
+
+        let report = GradCheck::compare_gradients(graph, &nodes, config);
+
+        for node in &nodes {
+            node.borrow_mut().set_retain_gradient(false);
+        }
+
+        report
+    }
+
+    fn compare_gradients<'a>(
+        graph: &Graph<'a>,
+        nodes: &[NodeRef<'a>],
+        config: &GradCheckConfig,
+    ) -> Result<GradCheckReport, DataError> {
+        graph.forward()?;
+        let seed: DataContainer = graph
+            .get_output()
+            .borrow_mut()
+            .get_data()
+            .apply_elementwise(Fill(1.0));
+        graph.backward(&seed)?;
+
+        let mut checks: Vec<NodeGradCheck> = Vec::new();
+
+        for (index, node) in nodes.iter().enumerate() {
+            let gradient: DataContainer = node.borrow().get_gradient();
+            // Masks and constants are parameters that never learn, they don't keep a gradient
+            let analytic: Vec<f64> = match (&gradient, node.get_type()) {
+                (DataContainer::Empty, _) => continue,
+                (_, NodeType::Input) => GradCheck::values(&gradient),
+                _ => GradCheck::values(&gradient.sum_batch()),
+            };
+
+            let original: DataContainer = node.borrow_mut().get_data();
+            let data: &Data = GradCheck::inner(&original)?;
+            let size = data.flatten_as::<f64>().len();
+            if analytic.len() != size {
+                return Err(DataError::shape_mismatch(
+                    "GRADCHECK",
+                    data,
+                    GradCheck::inner(&gradient)?,
+                ));
+            }
+
+            let max_relative_error =
+                GradCheck::max_relative_error(graph, node, &original, &analytic, config);
+            node.borrow_mut().set_data(original);
+
+            checks.push(NodeGradCheck {
+                index,
+                node_type: node.get_type(),
+                size,
+                max_relative_error: max_relative_error?,
+            });
+        }
+
+        Ok(GradCheckReport { nodes: checks })
+    }
+
+    fn max_relative_error<'a>(
+        graph: &Graph<'a>,
+        node: &NodeRef<'a>,
+        original: &DataContainer,
+        analytic: &[f64],
+        config: &GradCheckConfig,
+    ) -> Result<f64, DataError> {
+        let mut max_relative_error: f64 = 0.0;
+
+        for (i, &analytic_value) in analytic.iter().enumerate() {
+            let plus = GradCheck::perturbed_objective(graph, node, original, i, config.epsilon)?;
+            let minus = GradCheck::perturbed_objective(graph, node, original, i, -config.epsilon)?;
+            let numeric = (plus - minus) / (2.0 * config.epsilon);
+
+            let scale = f64::max(analytic_value.abs().max(numeric.abs()), MIN_SCALE);
+            let relative_error = (analytic_value - numeric).abs() / scale;
+            max_relative_error = max_relative_error.max(relative_error);
+        }
+
+        Ok(max_relative_error)
+    }
+
+    fn perturbed_objective<'a>(
+        graph: &Graph<'a>,
+        node: &NodeRef<'a>,
+        original: &DataContainer,
+        index: usize,
+        delta: f64,
+    ) -> Result<f64, DataError> {
+        node.borrow_mut()
+            .set_data(GradCheck::perturb(original, index, delta)?);
+        graph.forward()?;
+
+        let res = graph.get_output().borrow_mut().get_data();
+        Ok(GradCheck::values(&res).iter().sum())
+    }
+
+    fn perturb(
+        container: &DataContainer,
+        index: usize,
+        delta: f64,
+    ) -> Result<DataContainer, DataError> {
+        let data: &Data = GradCheck::inner(container)?;
+        let shape: Vec<usize> = data.dim().to_vec();
+
+        let mut values: Vec<f64> = data.flatten_as();
+        values[index] += delta;
+        let perturbed: Data = Data::try_from_dim(&shape, values)?.to_precision(data.precision());
+
+        Ok(match container {
+            DataContainer::Batch(_) => DataContainer::Batch(perturbed),
+            DataContainer::Inference(_) => DataContainer::Inference(perturbed),
+            _ => DataContainer::Parameter(perturbed),
+        })
+    }
+
+    fn inner(container: &DataContainer) -> Result<&Data, DataError> {
+        match container {
+            DataContainer::Batch(data)
+            | DataContainer::Inference(data)
+            | DataContainer::Parameter(data) => Ok(data),
+            DataContainer::Empty => Err(DataError::invalid_operand("GRADCHECK", &Data::None)),
+        }
+    }
+
+    fn values(container: &DataContainer) -> Vec<f64> {
+        match GradCheck::inner(container) {
+            Ok(data) => data.flatten_as(),
+            Err(_) => Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{arr1, arr2};
+
+    use crate::{
+        data::{data_container::DataContainer, precision::Precision, Data},
+        network::{
+            config_types::hyper_params::HyperParams, types::regressor::RegressorNetwork, Network,
+        },
+        node::{
+            gradcheck::{GradCheck, GradCheckConfig, GradCheckReport},
+            graph::Graph,
+            types::{
+                abs_value_node::AbsoluteValueNode, activation_node::ActivationNode,
+                add_node::AddNode, bias_node::BiasNode, concat_node::ConcatNode,
+                constant_node::ConstantNode, element_sum_node::ElementSumNode,
+                expected_response_node::ExpectedResponseNode, input_node::InputNode,
+                loss_node::LossNode, mask_node::MaskNode, matrix_multiply_node::MatrixMultiplyNode,
+                multiply_node::MultiplyNode, normalization_node::NormalizationNode,
+                softmax_node::SoftmaxNode, square_node::SquareNode, weight_node::WeightNode,
+            },
+            NodeRef,
+        },
+        optimization::{
+            batch_norm::NormalizationType, learning_decay::LearningDecayType,
+            learning_schedule::LearningRateSchedule, momentum::DescentType,
+        },
+        regularization::{
+            dropout::{NetworkMaskType, NetworkMode},
+            penalty::{l2_penalty::builder::L2PenaltyBuilder, PenaltyConfig},
+        },
+    };
+
+    const TOLERANCE: f64 = 1e-5;
+
+    fn batch(examples: &[&[f64]]) -> DataContainer {
+        DataContainer::batch(
+            examples
+                .iter()
+                .map(|example| Data::VectorF64(arr1(example)))
+                .collect(),
+        )
+    }
+
+    fn connect<'a>(node: &NodeRef<'a>, inputs: &[&NodeRef<'a>]) {
+        for input in inputs {
+            node.borrow_mut().add_input(node, input);
+        }
+    }
+
+    fn check(output: &NodeRef) -> GradCheckReport {
+        let graph: Graph = Graph::new(output).unwrap();
+        let report = GradCheck::check_graph(&graph, &GradCheckConfig::new(1e-6, true)).unwrap();
+        println!("{}", report);
+
+        report
+    }
+
+    #[test]
+    fn dense_gradcheck() {
+        let input: NodeRef = NodeRef::new(InputNode::new(vec![3]));
+        let response: NodeRef = NodeRef::new(ExpectedResponseNode::new(vec![2]));
+        let weights: NodeRef = NodeRef::new(WeightNode::new_matrix(
+            3,
+            2,
+            LearningDecayType::constant(0.1),
+            DescentType::none(),
+        ));
+        let biases: NodeRef = NodeRef::new(BiasNode::new(
+            2,
+            LearningDecayType::constant(0.1),
+            DescentType::none(),
+        ));
+        weights
+            .borrow_mut()
+            .set_data(DataContainer::Parameter(Data::MatrixF64(arr2(&[
+                [0.3, -0.2, 0.5],
+                [-0.4, 0.1, 0.2],
+            ]))));
+        biases
+            .borrow_mut()
+            .set_data(DataContainer::Parameter(Data::VectorF64(arr1(&[
+                0.1, -0.3,
+            ]))));
+
+        let matmul: NodeRef = NodeRef::new(MatrixMultiplyNode::new());
+        let add: NodeRef = NodeRef::new(AddNode::new());
+        let sigmoid: NodeRef = NodeRef::new(ActivationNode::new("sigmoid"));
+        let loss: NodeRef = NodeRef::new(LossNode::new("mean_squared_error"));
+        connect(&matmul, &[&weights, &input]);
+        connect(&add, &[&matmul, &biases]);
+        connect(&sigmoid, &[&add]);
+        connect(&loss, &[&response, &sigmoid]);
+
+        input
+            .borrow_mut()
+            .set_data(batch(&[&[1.0, 2.0, -1.0], &[0.5, -1.5, 2.0]]));
+        response
+            .borrow_mut()
+            .set_data(batch(&[&[0.2, 0.9], &[0.7, 0.1]]));
+
+        let report = check(&loss);
+        assert_eq!(report.nodes().len(), 3);
+        assert!(report.passed(TOLERANCE));
+
+        // Retained gradients must not have been applied to the weights
+        let weights_data = weights.borrow_mut().get_data();
+        if let DataContainer::Parameter(data) = weights_data {
+            assert_eq!(
+                data.flatten_as::<f64>(),
+                vec![0.3, -0.2, 0.5, -0.4, 0.1, 0.2]
+            );
+        }
+    }
+
+    #[test]
+    fn elementwise_gradcheck() {
+        let input: NodeRef = NodeRef::new(InputNode::new(vec![3]));
+        let scales: NodeRef = NodeRef::new(WeightNode::new_vec(
+            3,
+            LearningDecayType::constant(0.1),
+            DescentType::none(),
+        ));
+        scales
+            .borrow_mut()
+            .set_data(DataContainer::Parameter(Data::VectorF64(arr1(&[
+                0.5, -1.2, 0.8,
+            ]))));
+        let constant: NodeRef =
+            NodeRef::new(ConstantNode::new(Data::VectorF64(arr1(&[1.5, -0.5, 2.0]))));
+        let mask: NodeRef = NodeRef::new(MaskNode::new(vec![3], 0.5, Precision::F64));
+        mask.borrow_mut().set_mode(NetworkMode::Inference);
+
+        let multiply: NodeRef = NodeRef::new(MultiplyNode::new());
+        let shift: NodeRef = NodeRef::new(AddNode::new());
+        let masked: NodeRef = NodeRef::new(MultiplyNode::new());
+        let relu: NodeRef = NodeRef::new(ActivationNode::new("relu"));
+        let linear: NodeRef = NodeRef::new(ActivationNode::new("none"));
+        let abs: NodeRef = NodeRef::new(AbsoluteValueNode::new());
+        let square: NodeRef = NodeRef::new(SquareNode::new());
+        let sum: NodeRef = NodeRef::new(ElementSumNode::new());
+        connect(&multiply, &[&input, &scales]);
+        connect(&shift, &[&multiply, &constant]);
+        connect(&masked, &[&shift, &mask]);
+        connect(&relu, &[&masked]);
+        connect(&linear, &[&relu]);
+        connect(&abs, &[&linear]);
+        connect(&square, &[&abs]);
+        connect(&sum, &[&square]);
+
+        input
+            .borrow_mut()
+            .set_data(batch(&[&[1.0, -2.0, 0.5], &[2.0, 0.5, -1.0]]));
+
+        let report = check(&sum);
+        assert_eq!(report.nodes().len(), 2);
+        assert!(report.passed(TOLERANCE));
+    }
+
+    #[test]
+    fn concat_gradcheck() {
+        let left: NodeRef = NodeRef::new(InputNode::new(vec![2]));
+        let right: NodeRef = NodeRef::new(InputNode::new(vec![3]));
+        let response: NodeRef = NodeRef::new(ExpectedResponseNode::new(vec![5]));
+        let left_scales: NodeRef = NodeRef::new(WeightNode::new_vec(
+            2,
+            LearningDecayType::constant(0.1),
+            DescentType::none(),
+        ));
+        let right_scales: NodeRef = NodeRef::new(WeightNode::new_vec(
+            3,
+            LearningDecayType::constant(0.1),
+            DescentType::none(),
+        ));
+        left_scales
+            .borrow_mut()
+            .set_data(DataContainer::Parameter(Data::VectorF64(arr1(&[
+                0.7, -0.4,
+            ]))));
+        right_scales
+            .borrow_mut()
+            .set_data(DataContainer::Parameter(Data::VectorF64(arr1(&[
+                -0.3, 0.9, 0.2,
+            ]))));
+
+        let left_multiply: NodeRef = NodeRef::new(MultiplyNode::new());
+        let right_multiply: NodeRef = NodeRef::new(MultiplyNode::new());
+        let concat: NodeRef = NodeRef::new(ConcatNode::new());
+        let sigmoid: NodeRef = NodeRef::new(ActivationNode::new("sigmoid"));
+        let loss: NodeRef = NodeRef::new(LossNode::new("mean_squared_error"));
+        connect(&left_multiply, &[&left, &left_scales]);
+        connect(&right_multiply, &[&right, &right_scales]);
+        connect(&concat, &[&left_multiply, &right_multiply]);
+        connect(&sigmoid, &[&concat]);
+        connect(&loss, &[&response, &sigmoid]);
+
+        left.borrow_mut()
+            .set_data(batch(&[&[1.0, -0.5], &[0.3, 2.0]]));
+        right
+            .borrow_mut()
+            .set_data(batch(&[&[0.5, 1.5, -1.0], &[-2.0, 0.4, 1.0]]));
+        response.borrow_mut().set_data(batch(&[
+            &[0.2, 0.9, 0.4, 0.8, 0.1],
+            &[0.5, 0.3, 0.7, 0.1, 0.9],
+        ]));
+
+        let report = check(&loss);
+        assert_eq!(report.nodes().len(), 4);
+        assert!(report.passed(TOLERANCE));
+    }
+
+    #[test]
+    fn softmax_gradcheck() {
+        let input: NodeRef = NodeRef::new(InputNode::new(vec![2]));
+        let response: NodeRef = NodeRef::new(ExpectedResponseNode::new(vec![3]));
+        let weights: NodeRef = NodeRef::new(WeightNode::new_matrix(
+            2,
+            3,
+            LearningDecayType::constant(0.1),
+            DescentType::none(),
+        ));
+        weights
+            .borrow_mut()
+            .set_data(DataContainer::Parameter(Data::MatrixF64(arr2(&[
+                [0.3, -0.2],
+                [-0.4, 0.1],
+                [0.6, 0.2],
+            ]))));
+
+        let matmul: NodeRef = NodeRef::new(MatrixMultiplyNode::new());
+        let softmax: NodeRef = NodeRef::new(SoftmaxNode::new());
+        let loss: NodeRef = NodeRef::new(LossNode::new("base_cross_entropy"));
+        connect(&matmul, &[&weights, &input]);
+        connect(&softmax, &[&matmul]);
+        connect(&loss, &[&response, &softmax]);
+
+        input
+            .borrow_mut()
+            .set_data(batch(&[&[1.0, 2.0], &[-0.5, 1.5]]));
+        response
+            .borrow_mut()
+            .set_data(batch(&[&[0.0, 1.0, 0.0], &[1.0, 0.0, 0.0]]));
+
+        assert!(check(&loss).passed(TOLERANCE));
+    }
+
+    #[test]
+    fn binary_entropy_gradcheck() {
+        let input: NodeRef = NodeRef::new(InputNode::new(vec![2]));
+        let response: NodeRef = NodeRef::new(ExpectedResponseNode::new(vec![1]));
+        let weights: NodeRef = NodeRef::new(WeightNode::new_matrix(
+            2,
+            1,
+            LearningDecayType::constant(0.1),
+            DescentType::none(),
+        ));
+        weights
+            .borrow_mut()
+            .set_data(DataContainer::Parameter(Data::MatrixF64(arr2(&[[
+                0.4, -0.7,
+            ]]))));
+
+        let matmul: NodeRef = NodeRef::new(MatrixMultiplyNode::new());
+        let sigmoid: NodeRef = NodeRef::new(ActivationNode::new("sigmoid"));
+        let loss: NodeRef = NodeRef::new(LossNode::new("binary_cross_entropy"));
+        connect(&matmul, &[&weights, &input]);
+        connect(&sigmoid, &[&matmul]);
+        connect(&loss, &[&response, &sigmoid]);
+
+        input
+            .borrow_mut()
+            .set_data(batch(&[&[1.0, 2.0], &[-0.5, 1.5]]));
+        response.borrow_mut().set_data(batch(&[&[1.0], &[0.0]]));
+
+        assert!(check(&loss).passed(TOLERANCE));
+    }
+
+    #[test]
+    fn multi_label_entropy_gradcheck() {
+        let input: NodeRef = NodeRef::new(InputNode::new(vec![2]));
+        let response: NodeRef = NodeRef::new(ExpectedResponseNode::new(vec![3]));
+        let weights: NodeRef = NodeRef::new(WeightNode::new_matrix(
+            2,
+            3,
+            LearningDecayType::constant(0.1),
+            DescentType::none(),
+        ));
+        weights
+            .borrow_mut()
+            .set_data(DataContainer::Parameter(Data::MatrixF64(arr2(&[
+                [0.4, -0.7],
+                [0.1, 0.3],
+                [-0.2, 0.5],
+            ]))));
+
+        let matmul: NodeRef = NodeRef::new(MatrixMultiplyNode::new());
+        let sigmoid: NodeRef = NodeRef::new(ActivationNode::new("sigmoid"));
+        let loss: NodeRef = NodeRef::new(LossNode::new("binary_cross_entropy"));
+        connect(&matmul, &[&weights, &input]);
+        connect(&sigmoid, &[&matmul]);
+        connect(&loss, &[&response, &sigmoid]);
+
+        input
+            .borrow_mut()
+            .set_data(batch(&[&[1.0, 2.0], &[-0.5, 1.5]]));
+        response
+            .borrow_mut()
+            .set_data(batch(&[&[1.0, 0.0, 1.0], &[0.0, 0.0, 1.0]]));
+
+        assert!(check(&loss).passed(TOLERANCE));
+    }
+
+    #[test]
+    fn normalization_gradcheck() {
+        let input: NodeRef = NodeRef::new(InputNode::new(vec![2]));
+        let response: NodeRef = NodeRef::new(ExpectedResponseNode::new(vec![2]));
+        let norm: NodeRef = NodeRef::new(NormalizationNode::new(0.9));
+        let scales: NodeRef = NodeRef::new(WeightNode::new_vec(
+            2,
+            LearningDecayType::constant(0.1),
+            DescentType::none(),
+        ));
+        let shifts: NodeRef = NodeRef::new(BiasNode::new(
+            2,
+            LearningDecayType::constant(0.1),
+            DescentType::none(),
+        ));
+        scales
+            .borrow_mut()
+            .set_data(DataContainer::Parameter(Data::VectorF64(arr1(&[1.2, 0.7]))));
+        shifts
+            .borrow_mut()
+            .set_data(DataContainer::Parameter(Data::VectorF64(arr1(&[
+                0.1, -0.2,
+            ]))));
+
+        let multiply: NodeRef = NodeRef::new(MultiplyNode::new());
+        let add: NodeRef = NodeRef::new(AddNode::new());
+        let loss: NodeRef = NodeRef::new(LossNode::new("mean_squared_error"));
+        connect(&norm, &[&input]);
+        connect(&multiply, &[&norm, &scales]);
+        connect(&add, &[&multiply, &shifts]);
+        connect(&loss, &[&response, &add]);
+        norm.borrow_mut().set_mode(NetworkMode::Train);
+
+        input.borrow_mut().set_data(batch(&[
+            &[1.0, 3.0],
+            &[2.0, -1.0],
+            &[0.5, 2.0],
+            &[-1.0, 0.5],
+        ]));
+        response.borrow_mut().set_data(batch(&[
+            &[0.5, 1.0],
+            &[1.5, -0.5],
+            &[0.0, 0.5],
+            &[-1.0, 0.0],
+        ]));
+
+        assert!(check(&loss).passed(TOLERANCE));
+    }
+
+    #[test]
+    fn network_gradcheck() {
+        let mut regressor: RegressorNetwork = RegressorNetwork::new(
+            vec![2],
+            vec![1],
+            vec![4],
+            PenaltyConfig::new(L2PenaltyBuilder::new(0.1)),
+            NetworkMaskType::from_probabilities(0.8, 0.5),
+            HyperParams::new(
+                LearningDecayType::constant(0.01),
+                DescentType::Base,
+                NormalizationType::batch_norm(0.9),
+                Precision::F64,
+                LearningRateSchedule::constant(),
+            ),
+        );
+
+        let input = batch(&[&[1.0, 2.0], &[-0.5, 1.5], &[2.0, -1.0]]);
+        let response = batch(&[&[1.0], &[0.5], &[-1.0]]);
+        let before: String = serde_json::to_string(&regressor.create_config()).unwrap();
+
+        // Dropout masks are held for the whole check, so it passes with dropout enabled
+        let report = regressor
+            .check_gradients(input, response, &GradCheckConfig::default())
+            .unwrap();
+        println!("{}", report);
+
+        assert!(!report.nodes().is_empty());
+        assert!(report.passed(1e-4));
+
+        // Weights and batch norm running statistics are left as they were
+        let after: String = serde_json::to_string(&regressor.create_config()).unwrap();
+        assert_eq!(before, after);
+    }
+}
@@ -0,0 +1,352 @@
+// builtin
+use std::{collections::HashMap, error::Error, fmt::Display};
+
+// external
+
+// internal
+use crate::{
+    data::{data_container::DataContainer, error::DataError},
+    node::{NodeRef, NodeType},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum GraphError {
+    Cycle { node_type: NodeType },
+    Unreachable { node_type: NodeType, count: usize },
+    NotInGraph { node_type: NodeType },
+}
+
+impl Display for GraphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GraphError::Cycle { node_type } => {
+                write!(f, "[GRAPH] cycle detected through a {node_type} node")
+            }
+            GraphError::Unreachable { node_type, count } => write!(
+                f,
+                "[GRAPH] {count} node(s) consume graph values without reaching the output, first one is a {node_type} node"
+            ),
+            GraphError::NotInGraph { node_type } => {
+                write!(f, "[GRAPH] {node_type} node is not part of the graph")
+            }
+        }
+    }
+}
+
+impl Error for GraphError {}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Visit {
+    InProgress,
+    Done,
+}
+
+// Owns every node feeding into `output` in topological order, so forward and backward passes are
+// flat loops instead of recursion through each node's inputs
+pub struct Graph<'a> {
+    order: Vec<NodeRef<'a>>,
+    output: NodeRef<'a>,
+}
+
+impl<'a> Graph<'a> {
+    // Every node consuming a value of the graph has to lead to `output`, otherwise its gradient
+    // could never be complete
+    pub fn new(output: &NodeRef<'a>) -> Result<Graph<'a>, GraphError> {
+        let order: Vec<NodeRef<'a>> = Graph::topological_order(output)?;
+
+        let positions: HashMap<usize, usize> = Graph::positions(&order);
+        let unreachable: Vec<NodeType> = order
+            .iter()
+            .flat_map(|node| node.borrow().get_outputs().to_vec())
+            .filter(|consumer| !positions.contains_key(&consumer.id()))
+            .map(|consumer| consumer.get_type())
+            .collect();
+
+        if let Some(node_type) = unreachable.first() {
+            return Err(GraphError::Unreachable {
+                node_type: *node_type,
+                count: unreachable.len(),
+            });
+        }
+
+        Ok(Graph {
+            order,
+            output: NodeRef::clone(output),
+        })
+    }
+
+    // Restricts the graph to the nodes `output` depends on, e.g. to run inference without the loss
+    pub fn subgraph(&self, output: &NodeRef<'a>) -> Result<Graph<'a>, GraphError> {
+        self.subgraph_all(std::slice::from_ref(output))
+    }
+
+    // Keeps the nodes any of `outputs` depends on, e.g. to run every head of a network in one
+    // pass. The first one becomes the output of the graph
+    pub fn subgraph_all(&self, outputs: &[NodeRef<'a>]) -> Result<Graph<'a>, GraphError> {
+        let positions: HashMap<usize, usize> = Graph::positions(&self.order);
+        let mut ends: Vec<usize> = Vec::new();
+        for output in outputs {
+            match positions.get(&output.id()) {
+                Some(&position) => ends.push(position),
+                None => {
+                    return Err(GraphError::NotInGraph {
+                        node_type: output.get_type(),
+                    })
+                }
+            }
+        }
+        let end: usize = match ends.iter().max() {
+            Some(&end) => end,
+            None => {
+                return Err(GraphError::NotInGraph {
+                    node_type: NodeType::None,
+                })
+            }
+        };
+
+        let mut needed: Vec<bool> = vec![false; end + 1];
+        for position in ends {
+            needed[position] = true;
+        }
+        for i in (0..=end).rev() {
+            if !needed[i] {
+                continue;
+            }
+            for input in self.order[i].borrow().get_inputs() {
+                needed[positions[&input.id()]] = true;
+            }
+        }
+
+        let order: Vec<NodeRef<'a>> = self.order[..=end]
+            .iter()
+            .zip(needed)
+            .filter(|(_node, needed)| *needed)
+            .map(|(node, _needed)| NodeRef::clone(node))
+            .collect();
+
+        Ok(Graph {
+            order,
+            output: NodeRef::clone(&outputs[0]),
+        })
+    }
+
+    pub fn get_output(&self) -> &NodeRef<'a> {
+        &self.output
+    }
+
+    pub fn get_nodes(&self) -> &Vec<NodeRef<'a>> {
+        &self.order
+    }
+
+    pub fn forward(&self) -> Result<(), DataError> {
+        for node in &self.order {
+            node.borrow_mut().apply_operation()?;
+        }
+
+        Ok(())
+    }
+
+    // Nodes are visited after all of their consumers, so each one has its complete gradient
+    pub fn backward(&self, seed: &DataContainer) -> Result<(), DataError> {
+        self.output.borrow_mut().add_gradient(seed)?;
+
+        for node in self.order.iter().rev() {
+            node.borrow_mut().apply_jacobian()?;
+        }
+
+        Ok(())
+    }
+
+    // Sum of the output values after a forward pass, e.g. the loss of every example in a batch
+    pub fn output_sum(&self) -> f32 {
+        Graph::node_sum(&self.output)
+    }
+
+    // Sum of the values a node computed in the last forward pass
+    pub fn node_sum(node: &NodeRef<'a>) -> f32 {
+        match node.borrow_mut().get_data() {
+            DataContainer::Batch(data)
+            | DataContainer::Inference(data)
+            | DataContainer::Parameter(data) => data.flatten_as::<f64>().iter().sum::<f64>() as f32,
+            DataContainer::Empty => 0.0,
+        }
+    }
+
+    // Global learning rate multiplier, e.g. from a LearningRateSchedule
+    pub fn set_rate_scale(&self, scale: f32) {
+        for node in &self.order {
+            node.borrow_mut().set_rate_scale(scale);
+        }
+    }
+
+    fn positions(order: &[NodeRef<'a>]) -> HashMap<usize, usize> {
+        order
+            .iter()
+            .enumerate()
+            .map(|(position, node)| (node.id(), position))
+            .collect()
+    }
+
+    // Iterative depth first search so deep networks can't overflow the stack
+    fn topological_order(output: &NodeRef<'a>) -> Result<Vec<NodeRef<'a>>, GraphError> {
+        let mut visits: HashMap<usize, Visit> = HashMap::new();
+        let mut order: Vec<NodeRef<'a>> = Vec::new();
+        let mut stack: Vec<(NodeRef<'a>, usize)> = vec![(NodeRef::clone(output), 0)];
+        visits.insert(output.id(), Visit::InProgress);
+
+        while let Some((node, next)) = stack.pop() {
+            let input: Option<NodeRef<'a>> = node.borrow().get_inputs().get(next).cloned();
+
+            match input {
+                Some(input) => {
+                    stack.push((node, next + 1));
+
+                    match visits.get(&input.id()) {
+                        Some(Visit::InProgress) => {
+                            return Err(GraphError::Cycle {
+                                node_type: input.get_type(),
+                            })
+                        }
+                        Some(Visit::Done) => {}
+                        None => {
+                            visits.insert(input.id(), Visit::InProgress);
+                            stack.push((input, 0));
+                        }
+                    }
+                }
+                None => {
+                    visits.insert(node.id(), Visit::Done);
+                    order.push(node);
+                }
+            }
+        }
+
+        Ok(order)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::arr1;
+
+    use crate::{
+        data::{data_container::DataContainer, types::FlattenedData, Data},
+        node::{
+            graph::{Graph, GraphError},
+            types::{
+                abs_value_node::AbsoluteValueNode, add_node::AddNode,
+                element_sum_node::ElementSumNode, input_node::InputNode, weight_node::WeightNode,
+            },
+            NodeRef, NodeType,
+        },
+        optimization::{learning_decay::LearningDecayType, momentum::DescentType},
+    };
+
+    fn values(container: DataContainer) -> Vec<f32> {
+        match container.flatten_to_vec() {
+            FlattenedData::Singular(values) => values,
+            _ => Vec::new(),
+        }
+    }
+
+    #[test]
+    fn shared_input_gradient() {
+        let weight: NodeRef = NodeRef::new(WeightNode::new_vec(
+            3,
+            LearningDecayType::constant(0.1),
+            DescentType::Base,
+        ));
+        let first: NodeRef = NodeRef::new(AbsoluteValueNode::new());
+        let second: NodeRef = NodeRef::new(AbsoluteValueNode::new());
+        let add: NodeRef = NodeRef::new(AddNode::new());
+        let sum: NodeRef = NodeRef::new(ElementSumNode::new());
+
+        first.borrow_mut().add_input(&first, &weight);
+        second.borrow_mut().add_input(&second, &weight);
+        add.borrow_mut().add_input(&add, &first);
+        add.borrow_mut().add_input(&add, &second);
+        sum.borrow_mut().add_input(&sum, &add);
+
+        let graph: Graph = Graph::new(&sum).unwrap();
+        assert_eq!(graph.get_nodes().len(), 5);
+
+        weight.borrow_mut().set_retain_gradient(true);
+        graph.forward().unwrap();
+        graph.backward(&DataContainer::one()).unwrap();
+
+        let weights: Vec<f32> = values(weight.borrow_mut().get_data());
+        let gradient: Vec<f32> = values(weight.borrow().get_gradient());
+        assert_eq!(gradient.len(), 3);
+        for (value, grad) in weights.iter().zip(gradient) {
+            assert!((grad - 2.0 * value.signum()).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn cycle_detection() {
+        let input: NodeRef = NodeRef::new(InputNode::new(vec![2]));
+        let first: NodeRef = NodeRef::new(AddNode::new());
+        let second: NodeRef = NodeRef::new(AddNode::new());
+
+        first.borrow_mut().add_input(&first, &input);
+        first.borrow_mut().add_input(&first, &second);
+        second.borrow_mut().add_input(&second, &first);
+
+        assert!(matches!(
+            Graph::new(&second),
+            Err(GraphError::Cycle {
+                node_type: NodeType::Operation
+            })
+        ));
+    }
+
+    #[test]
+    fn unreachable_detection() {
+        let input: NodeRef = NodeRef::new(InputNode::new(vec![2]));
+        let output: NodeRef = NodeRef::new(AbsoluteValueNode::new());
+        let dangling: NodeRef = NodeRef::new(AbsoluteValueNode::new());
+
+        output.borrow_mut().add_input(&output, &input);
+        dangling.borrow_mut().add_input(&dangling, &input);
+
+        assert_eq!(
+            Graph::new(&output).err(),
+            Some(GraphError::Unreachable {
+                node_type: NodeType::Operation,
+                count: 1
+            })
+        );
+
+        let other: NodeRef = NodeRef::new(InputNode::new(vec![2]));
+        let graph: Graph = Graph::new(&other).unwrap();
+        assert!(matches!(
+            graph.subgraph(&output),
+            Err(GraphError::NotInGraph { .. })
+        ));
+    }
+
+    #[test]
+    fn deep_chain() {
+        let input: NodeRef = NodeRef::new(InputNode::new(vec![2]));
+        let mut prev: NodeRef = NodeRef::clone(&input);
+
+        for _i in 0..20000 {
+            let node: NodeRef = NodeRef::new(AbsoluteValueNode::new());
+            node.borrow_mut().add_input(&node, &prev);
+            prev = node;
+        }
+
+        let graph: Graph = Graph::new(&prev).unwrap();
+        assert_eq!(graph.get_nodes().len(), 20001);
+
+        input
+            .borrow_mut()
+            .set_data(DataContainer::Inference(Data::VectorF32(arr1(&[
+                -1.0, 2.0,
+            ]))));
+        graph.forward().unwrap();
+        graph.backward(&DataContainer::one()).unwrap();
+
+        assert_eq!(values(prev.borrow_mut().get_data()), vec![1.0, 2.0]);
+    }
+}
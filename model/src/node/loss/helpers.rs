@@ -1,222 +1,177 @@
 // builtin
 
 // external
+use ndarray::{Array1, ArrayD, ArrayView1, CowArray, IxDyn};
 
 // internal
-
-use ndarray::{Array1, Array2, ArrayView1};
-
-use crate::data::{data_container::DataContainer, Data};
-
-fn warn_containers(first: &DataContainer, second: &DataContainer, operation: &str) {
-    let first_variant = first.container_name();
-    let second_variant = second.container_name();
-    println!(
-        "DataContainer::Empty returned for unsupported data container type pair for operation [{operation}]: {first_variant} and {second_variant}"
-    );
-}
-
-fn warn_data(first: &Data, second: &Data, operation: &str) {
-    let first_variant = first.variant_name();
-    let second_variant = second.variant_name();
-    println!(
-        "Data::None returned for unsupported data type pair for operation [{operation}]: {first_variant} and {second_variant}"
-    );
-}
-
-fn warn_dim(operation: &str) {
-    println!("Data::None returned for mismatched dimensions for operation [{operation}]")
-}
+use crate::data::{data_container::DataContainer, error::DataError, precision::Precision, Data};
 
 pub fn container_apply(
     expected: &DataContainer,
     actual: &DataContainer,
-    func: impl Fn(&Data, &Data) -> Data,
-    operation: &str,
-) -> DataContainer {
-    match (expected, actual) {
-        (DataContainer::Batch(ans_batch), DataContainer::Batch(pred_batch)) => {
-            if ans_batch.len() != pred_batch.len() {
-                println!(
-                    "DataContainer::Empty returned due to mismatch in batch sizes: {} and {}",
-                    ans_batch.len(),
-                    pred_batch.len()
-                );
-                return DataContainer::Empty;
-            }
+    func: impl Fn(&Data, &Data) -> Result<Data, DataError>,
+    operation: &'static str,
+) -> Result<DataContainer, DataError> {
+    let res = match (expected, actual) {
+        (DataContainer::Batch(ans_stacked), DataContainer::Batch(pred_stacked)) => {
+            let ans_batch: Vec<Data> = expected.batch_to_vec();
+            let pred_batch: Vec<Data> = actual.batch_to_vec();
 
-            let mut new_batch: Vec<Data> = Vec::new();
-            for (ans, pred) in ans_batch.iter().zip(pred_batch.iter()) {
-                new_batch.push(func(ans, pred));
+            if ans_batch.len() != pred_batch.len() {
+                Err(DataError::batch_mismatch(
+                    operation,
+                    ans_stacked,
+                    pred_stacked,
+                ))
+            } else {
+                ans_batch
+                    .iter()
+                    .zip(pred_batch.iter())
+                    .map(|(ans, pred)| func(ans, pred))
+                    .collect::<Result<Vec<Data>, DataError>>()
+                    .and_then(DataContainer::try_batch)
             }
-
-            DataContainer::Batch(new_batch)
         }
         (DataContainer::Inference(ans), DataContainer::Inference(pred)) => {
-            let new_data = func(ans, pred);
-
-            DataContainer::Inference(new_data)
-        }
-        _ => {
-            warn_containers(expected, actual, operation);
-            DataContainer::Empty
+            func(ans, pred).map(DataContainer::Inference)
         }
+        _ => Err(DataError::unsupported_containers(
+            operation, expected, actual,
+        )),
+    };
+
+    res.map_err(|err| err.in_containers(expected, actual))
+}
+
+type LossOperands<'d> = (
+    CowArray<'d, f64, IxDyn>,
+    CowArray<'d, f64, IxDyn>,
+    Precision,
+);
+
+// Loss math runs in f64, results are handed back in the precision the operands share
+fn loss_operands<'d>(
+    expected: &'d Data,
+    actual: &'d Data,
+    operation: &'static str,
+) -> Result<LossOperands<'d>, DataError> {
+    let precision = Precision::common(expected.precision(), actual.precision());
+
+    match (expected.cast_array_d::<f64>(), actual.cast_array_d::<f64>()) {
+        (Some(ans), Some(pred)) => Ok((ans, pred, precision)),
+        _ => Err(DataError::unsupported_pair(operation, expected, actual)),
     }
 }
 
 pub fn data_apply_vector(
     expected: &Data,
     actual: &Data,
-    func: impl Fn(ArrayView1<f32>, ArrayView1<f32>) -> f32,
-    check: impl Fn(&ArrayView1<f32>, &ArrayView1<f32>) -> bool,
-    operation: &str,
-) -> Data {
-    match (expected, actual) {
-        (Data::VectorF32(ans), Data::VectorF32(pred)) => {
-            let ans_view = ans.view();
-            let pred_view = pred.view();
-
-            if check(&ans_view, &pred_view) {
-                let result = func(ans_view, pred_view);
-                Data::ScalarF32(result)
-            } else {
-                warn_dim(operation);
-                Data::None
-            }
-        }
-        (Data::MatrixF32(ans), Data::MatrixF32(pred)) => {
-            let ans_flat = ans.flatten();
-            let pred_flat = pred.flatten();
+    func: impl Fn(ArrayView1<f64>, ArrayView1<f64>) -> f64,
+    check: impl Fn(&ArrayView1<f64>, &ArrayView1<f64>) -> bool,
+    operation: &'static str,
+) -> Result<Data, DataError> {
+    let (ans, pred, precision) = loss_operands(expected, actual, operation)?;
+    if ans.ndim() == 0 || ans.ndim() != pred.ndim() {
+        return Err(DataError::unsupported_pair(operation, expected, actual));
+    }
 
-            let ans_view = ans_flat.view();
-            let pred_view = pred_flat.view();
+    let ans_flat = ans.flatten();
+    let pred_flat = pred.flatten();
 
-            if check(&ans_view, &pred_view) {
-                let result = func(ans_view, pred_view);
-                Data::ScalarF32(result)
-            } else {
-                warn_dim(operation);
-                Data::None
-            }
-        }
-        _ => {
-            warn_data(expected, actual, operation);
-            Data::None
-        }
+    let ans_view = ans_flat.view();
+    let pred_view = pred_flat.view();
+
+    if check(&ans_view, &pred_view) {
+        let result = func(ans_view, pred_view);
+        Ok(Data::ScalarF64(result).to_precision(precision))
+    } else {
+        Err(DataError::shape_mismatch(operation, expected, actual))
     }
 }
 
 pub fn data_diff_vector(
     expected: &Data,
     actual: &Data,
-    func: impl Fn(ArrayView1<f32>, ArrayView1<f32>) -> Vec<f32>,
-    check: impl Fn(&ArrayView1<f32>, &ArrayView1<f32>) -> bool,
-    operation: &str,
-) -> Data {
-    match (expected, actual) {
-        (Data::VectorF32(ans), Data::VectorF32(pred)) => {
-            let ans_view = ans.view();
-            let pred_view = pred.view();
-
-            if check(&ans_view, &pred_view) {
-                let result = func(ans_view, pred_view);
-                Data::VectorF32(Array1::from_vec(result))
-            } else {
-                warn_dim(operation);
-                Data::None
-            }
-        }
-        (Data::MatrixF32(ans), Data::MatrixF32(pred)) => {
-            let dim = ans.dim();
+    func: impl Fn(ArrayView1<f64>, ArrayView1<f64>) -> Vec<f64>,
+    check: impl Fn(&ArrayView1<f64>, &ArrayView1<f64>) -> bool,
+    operation: &'static str,
+) -> Result<Data, DataError> {
+    let (ans, pred, precision) = loss_operands(expected, actual, operation)?;
+    if ans.ndim() == 0 || ans.ndim() != pred.ndim() {
+        return Err(DataError::unsupported_pair(operation, expected, actual));
+    }
 
-            let ans_flat = ans.flatten();
-            let pred_flat = pred.flatten();
+    let dim = ans.raw_dim();
 
-            let ans_view = ans_flat.view();
-            let pred_view = pred_flat.view();
+    let ans_flat = ans.flatten();
+    let pred_flat = pred.flatten();
 
-            if check(&ans_view, &pred_view) {
-                let result = func(ans_view, pred_view);
-                let matrix = Array2::from_shape_vec(dim, result)
-                    .expect("Failed to coerce Jacobian into matrix format");
-                Data::MatrixF32(matrix)
-            } else {
-                warn_dim(operation);
-                Data::None
-            }
-        }
-        _ => {
-            warn_data(expected, actual, operation);
-            Data::None
-        }
+    let ans_view = ans_flat.view();
+    let pred_view = pred_flat.view();
+
+    if check(&ans_view, &pred_view) {
+        let result = func(ans_view, pred_view);
+        let tensor = ArrayD::from_shape_vec(dim, result)
+            .expect("Failed to coerce Jacobian into tensor format");
+        Ok(Data::from_array_d(tensor).to_precision(precision))
+    } else {
+        Err(DataError::shape_mismatch(operation, expected, actual))
     }
 }
 
+// Scalar losses accept a bare scalar or a single element vector as the expected value
+fn scalar_operands(
+    ans: &CowArray<f64, IxDyn>,
+    pred: &CowArray<f64, IxDyn>,
+) -> Option<(Array1<f64>, Array1<f64>)> {
+    if ans.ndim() > 1 || pred.ndim() != 1 {
+        return None;
+    }
+
+    let ans_wrapped: Array1<f64> = ans.iter().cloned().collect();
+    let pred_wrapped: Array1<f64> = pred.iter().cloned().collect();
+
+    Some((ans_wrapped, pred_wrapped))
+}
+
 pub fn data_apply_scalar(
     expected: &Data,
     actual: &Data,
-    func: impl Fn(f32, f32) -> f32,
-    check: impl Fn(&Array1<f32>, &Array1<f32>) -> bool,
-    operation: &str,
-) -> Data {
-    match (expected, actual) {
-        (Data::ScalarF32(ans), Data::VectorF32(pred)) => {
-            let ans_wrapped: Array1<f32> = Array1::from_elem(1, *ans);
-
-            if check(&ans_wrapped, pred) {
-                let result = func(*ans, pred[0]);
-                Data::ScalarF32(result)
-            } else {
-                warn_dim(operation);
-                Data::None
-            }
-        }
-        (Data::VectorF32(ans), Data::VectorF32(pred)) => {
-            if check(ans, pred) {
-                let result = func(ans[0], pred[0]);
-                Data::ScalarF32(result)
-            } else {
-                warn_dim(operation);
-                Data::None
-            }
-        }
-        _ => {
-            warn_data(expected, actual, operation);
-            Data::None
-        }
+    func: impl Fn(f64, f64) -> f64,
+    check: impl Fn(&Array1<f64>, &Array1<f64>) -> bool,
+    operation: &'static str,
+) -> Result<Data, DataError> {
+    let (ans, pred, precision) = loss_operands(expected, actual, operation)?;
+    let (ans, pred) = match scalar_operands(&ans, &pred) {
+        Some(operands) => operands,
+        None => return Err(DataError::unsupported_pair(operation, expected, actual)),
+    };
+
+    if check(&ans, &pred) {
+        let result = func(ans[0], pred[0]);
+        Ok(Data::ScalarF64(result).to_precision(precision))
+    } else {
+        Err(DataError::shape_mismatch(operation, expected, actual))
     }
 }
 
 pub fn data_diff_scalar(
     expected: &Data,
     actual: &Data,
-    func: impl Fn(f32, f32) -> Vec<f32>,
-    check: impl Fn(&Array1<f32>, &Array1<f32>) -> bool,
-    operation: &str,
-) -> Data {
-    match (expected, actual) {
-        (Data::VectorF32(ans), Data::VectorF32(pred)) => {
-            if check(ans, pred) {
-                let result = func(ans[0], pred[0]);
-                Data::VectorF32(Array1::from_vec(result))
-            } else {
-                warn_dim(operation);
-                Data::None
-            }
-        }
-        (Data::ScalarF32(ans), Data::VectorF32(pred)) => {
-            let ans_wrapped: Array1<f32> = Array1::from_elem(1, *ans);
-            if check(&ans_wrapped, pred) {
-                let result = func(*ans, pred[0]);
-                Data::VectorF32(Array1::from_vec(result))
-            } else {
-                warn_dim(operation);
-                Data::None
-            }
-        }
-        _ => {
-            warn_data(expected, actual, operation);
-            Data::None
-        }
+    func: impl Fn(f64, f64) -> Vec<f64>,
+    check: impl Fn(&Array1<f64>, &Array1<f64>) -> bool,
+    operation: &'static str,
+) -> Result<Data, DataError> {
+    let (ans, pred, precision) = loss_operands(expected, actual, operation)?;
+    let (ans, pred) = match scalar_operands(&ans, &pred) {
+        Some(operands) => operands,
+        None => return Err(DataError::unsupported_pair(operation, expected, actual)),
+    };
+
+    if check(&ans, &pred) {
+        let result = func(ans[0], pred[0]);
+        Ok(Data::VectorF64(Array1::from_vec(result)).to_precision(precision))
+    } else {
+        Err(DataError::shape_mismatch(operation, expected, actual))
     }
 }
@@ -5,19 +5,23 @@ use std::fmt::Debug;
 
 // internal
 use crate::{
-    data::data_container::DataContainer,
+    data::{data_container::DataContainer, error::DataError},
     node::loss::registry::{init_loss_registry, LossRegistry},
 };
 
 pub trait LossType: Send + Sync + Debug {
-    fn apply(&self, expected: &DataContainer, actual: &DataContainer) -> DataContainer;
+    fn apply(
+        &self,
+        expected: &DataContainer,
+        actual: &DataContainer,
+    ) -> Result<DataContainer, DataError>;
 
     fn diff(
         &self,
         expected: &DataContainer,
         actual: &DataContainer,
         wrt_expected: bool,
-    ) -> DataContainer;
+    ) -> Result<DataContainer, DataError>;
 
     fn name(&self) -> &str;
 
@@ -37,7 +41,11 @@ impl LossFunction {
         }
     }
 
-    pub fn apply(&self, expected: &DataContainer, actual: &DataContainer) -> DataContainer {
+    pub fn apply(
+        &self,
+        expected: &DataContainer,
+        actual: &DataContainer,
+    ) -> Result<DataContainer, DataError> {
         self.loss_type.apply(expected, actual)
     }
 
@@ -46,7 +54,7 @@ impl LossFunction {
         expected: &DataContainer,
         actual: &DataContainer,
         wrt_expected: bool,
-    ) -> DataContainer {
+    ) -> Result<DataContainer, DataError> {
         self.loss_type.diff(expected, actual, wrt_expected)
     }
 }
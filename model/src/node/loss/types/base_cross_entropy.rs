@@ -5,7 +5,7 @@ use ndarray::ArrayView1;
 
 // internal
 use crate::{
-    data::{data_container::DataContainer, Data},
+    data::{data_container::DataContainer, error::DataError, Data},
     node::loss::{
         helpers::{container_apply, data_apply_vector, data_diff_vector},
         loss_function::LossType,
@@ -16,11 +16,11 @@ use crate::{
 pub struct BaseCrossEntropy;
 
 impl BaseCrossEntropy {
-    fn epsilon() -> f32 {
+    fn epsilon() -> f64 {
         1e-7
     }
 
-    fn error(expected: &Data, actual: &Data) -> Data {
+    fn error(expected: &Data, actual: &Data) -> Result<Data, DataError> {
         data_apply_vector(
             expected,
             actual,
@@ -30,8 +30,8 @@ impl BaseCrossEntropy {
         )
     }
 
-    fn error_calc(expected: ArrayView1<f32>, actual: ArrayView1<f32>) -> f32 {
-        let mut sum: f32 = 0.0;
+    fn error_calc(expected: ArrayView1<f64>, actual: ArrayView1<f64>) -> f64 {
+        let mut sum: f64 = 0.0;
 
         for (i, ans) in expected.iter().enumerate() {
             let epsilon = BaseCrossEntropy::epsilon();
@@ -43,14 +43,14 @@ impl BaseCrossEntropy {
                 pred = &epsilon;
             }
 
-            let val = -ans * f32::ln(*pred);
+            let val = -ans * f64::ln(*pred);
             sum += val;
         }
 
         sum
     }
 
-    fn diff(expected: &Data, actual: &Data, wrt_expected: bool) -> Data {
+    fn diff(expected: &Data, actual: &Data, wrt_expected: bool) -> Result<Data, DataError> {
         data_diff_vector(
             expected,
             actual,
@@ -61,16 +61,16 @@ impl BaseCrossEntropy {
     }
 
     fn diff_calc(
-        expected: ArrayView1<f32>,
-        actual: ArrayView1<f32>,
+        expected: ArrayView1<f64>,
+        actual: ArrayView1<f64>,
         wrt_expected: bool,
-    ) -> Vec<f32> {
+    ) -> Vec<f64> {
         let epsilon = BaseCrossEntropy::epsilon();
         let mut result = Vec::with_capacity(expected.len());
         for (&ans, &pred) in expected.iter().zip(actual.iter()) {
             let pred_safe = if pred <= epsilon { epsilon } else { pred };
             if wrt_expected {
-                result.push(-f32::ln(pred_safe));
+                result.push(-f64::ln(pred_safe));
             } else {
                 result.push(-ans / pred_safe);
             }
@@ -80,7 +80,11 @@ impl BaseCrossEntropy {
 }
 
 impl LossType for BaseCrossEntropy {
-    fn apply(&self, expected: &DataContainer, actual: &DataContainer) -> DataContainer {
+    fn apply(
+        &self,
+        expected: &DataContainer,
+        actual: &DataContainer,
+    ) -> Result<DataContainer, DataError> {
         container_apply(expected, actual, BaseCrossEntropy::error, "CROSS_ENTROPY")
     }
 
@@ -89,7 +93,7 @@ impl LossType for BaseCrossEntropy {
         expected: &DataContainer,
         actual: &DataContainer,
         wrt_expected: bool,
-    ) -> DataContainer {
+    ) -> Result<DataContainer, DataError> {
         container_apply(
             expected,
             actual,
@@ -4,9 +4,12 @@
 
 // internal
 use crate::{
-    data::{data_container::DataContainer, Data},
+    data::{data_container::DataContainer, error::DataError, Data},
     node::loss::{
-        helpers::{container_apply, data_apply_scalar, data_diff_scalar},
+        helpers::{
+            container_apply, data_apply_scalar, data_apply_vector, data_diff_scalar,
+            data_diff_vector,
+        },
         loss_function::LossType,
     },
 };
@@ -15,46 +18,80 @@ use crate::{
 pub struct BinaryCrossEntropy;
 
 impl BinaryCrossEntropy {
-    fn epsilon() -> f32 {
+    fn epsilon() -> f64 {
         0.0000001
     }
 
-    fn error(expected: &Data, actual: &Data) -> Data {
-        data_apply_scalar(
+    // A vector of labels is scored independently per label and summed, e.g. for multi-label
+    // outputs. A bare scalar expected value is compared against a single element prediction
+    fn error(expected: &Data, actual: &Data) -> Result<Data, DataError> {
+        if expected.dim().is_empty() {
+            return data_apply_scalar(
+                expected,
+                actual,
+                BinaryCrossEntropy::error_calc,
+                |ans, pred| ans.dim() == 1 && pred.dim() == 1,
+                "BINARY_ENTROPY",
+            );
+        }
+
+        data_apply_vector(
             expected,
             actual,
-            BinaryCrossEntropy::error_calc,
-            |ans, pred| ans.dim() == 1 && pred.dim() == 1,
+            |ans, pred| {
+                ans.iter()
+                    .zip(pred.iter())
+                    .map(|(ans, pred)| BinaryCrossEntropy::error_calc(*ans, *pred))
+                    .sum()
+            },
+            |ans, pred| ans.len() == pred.len(),
             "BINARY_ENTROPY",
         )
     }
 
-    fn error_calc(expected: f32, actual: f32) -> f32 {
-        let epsilon: f32 = BinaryCrossEntropy::epsilon();
-        let safe_actual: f32 = f32::clamp(actual, epsilon, 1.0 - epsilon);
+    fn error_calc(expected: f64, actual: f64) -> f64 {
+        let epsilon: f64 = BinaryCrossEntropy::epsilon();
+        let safe_actual: f64 = f64::clamp(actual, epsilon, 1.0 - epsilon);
 
-        let exp_term: f32 = -expected * f32::ln(safe_actual);
-        let neg_term: f32 = -(1.0 - expected) * f32::ln(1.0 - safe_actual);
+        let exp_term: f64 = -expected * f64::ln(safe_actual);
+        let neg_term: f64 = -(1.0 - expected) * f64::ln(1.0 - safe_actual);
 
         exp_term + neg_term
     }
 
-    fn diff(expected: &Data, actual: &Data, wrt_expected: bool) -> Data {
-        data_diff_scalar(
+    fn diff(expected: &Data, actual: &Data, wrt_expected: bool) -> Result<Data, DataError> {
+        if expected.dim().is_empty() {
+            return data_diff_scalar(
+                expected,
+                actual,
+                |ans, pred| BinaryCrossEntropy::diff_calc(ans, pred, wrt_expected),
+                |ans, pred| ans.dim() == 1 && pred.dim() == 1,
+                "BINARY_ENTROPY",
+            );
+        }
+
+        data_diff_vector(
             expected,
             actual,
-            |ans, pred| BinaryCrossEntropy::diff_calc(ans, pred, wrt_expected),
-            |ans, pred| ans.dim() == 1 && pred.dim() == 1,
+            |ans, pred| {
+                ans.iter()
+                    .zip(pred.iter())
+                    .flat_map(|(ans, pred)| {
+                        BinaryCrossEntropy::diff_calc(*ans, *pred, wrt_expected)
+                    })
+                    .collect()
+            },
+            |ans, pred| ans.len() == pred.len(),
             "BINARY_ENTROPY",
         )
     }
 
-    fn diff_calc(expected: f32, actual: f32, wrt_expected: bool) -> Vec<f32> {
+    fn diff_calc(expected: f64, actual: f64, wrt_expected: bool) -> Vec<f64> {
         let epsilon = BinaryCrossEntropy::epsilon();
-        let safe_actual = f32::clamp(actual, epsilon, 1.0 - epsilon);
+        let safe_actual = f64::clamp(actual, epsilon, 1.0 - epsilon);
 
-        let result: f32 = if wrt_expected {
-            -f32::ln(safe_actual) + f32::ln(1.0 - safe_actual)
+        let result: f64 = if wrt_expected {
+            -f64::ln(safe_actual) + f64::ln(1.0 - safe_actual)
         } else {
             -expected / safe_actual + (1.0 - expected) / (1.0 - safe_actual)
         };
@@ -64,7 +101,11 @@ impl BinaryCrossEntropy {
 }
 
 impl LossType for BinaryCrossEntropy {
-    fn apply(&self, expected: &DataContainer, actual: &DataContainer) -> DataContainer {
+    fn apply(
+        &self,
+        expected: &DataContainer,
+        actual: &DataContainer,
+    ) -> Result<DataContainer, DataError> {
         container_apply(
             expected,
             actual,
@@ -78,7 +119,7 @@ impl LossType for BinaryCrossEntropy {
         expected: &DataContainer,
         actual: &DataContainer,
         wrt_expected: bool,
-    ) -> DataContainer {
+    ) -> Result<DataContainer, DataError> {
         container_apply(
             expected,
             actual,
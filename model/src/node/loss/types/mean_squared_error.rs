@@ -5,7 +5,7 @@ use ndarray::ArrayView1;
 
 // internal
 use crate::{
-    data::{data_container::DataContainer, Data},
+    data::{data_container::DataContainer, error::DataError, Data},
     node::loss::{
         helpers::{container_apply, data_apply_vector, data_diff_vector},
         loss_function::LossType,
@@ -16,7 +16,7 @@ use crate::{
 pub struct MeanSquaredError;
 
 impl MeanSquaredError {
-    fn error(expected: &Data, actual: &Data) -> Data {
+    fn error(expected: &Data, actual: &Data) -> Result<Data, DataError> {
         data_apply_vector(
             expected,
             actual,
@@ -26,23 +26,23 @@ impl MeanSquaredError {
         )
     }
 
-    fn error_calc(expected: ArrayView1<f32>, actual: ArrayView1<f32>) -> f32 {
-        let mut sum: f32 = 0.0;
-        let length: f32 = expected.dim() as f32;
+    fn error_calc(expected: ArrayView1<f64>, actual: ArrayView1<f64>) -> f64 {
+        let mut sum: f64 = 0.0;
+        let length: f64 = expected.dim() as f64;
 
         for (ans, pred) in expected.iter().zip(actual.iter()) {
-            let val = f32::powi(ans - pred, 2);
+            let val = f64::powi(ans - pred, 2);
             sum += val;
         }
 
         sum / (2.0 * length)
     }
 
-    fn diff(expected: &Data, actual: &Data, wrt_expected: bool) -> Data {
+    fn diff(expected: &Data, actual: &Data, wrt_expected: bool) -> Result<Data, DataError> {
         data_diff_vector(
             expected,
             actual,
-            |ans: ArrayView1<f32>, pred: ArrayView1<f32>| {
+            |ans: ArrayView1<f64>, pred: ArrayView1<f64>| {
                 MeanSquaredError::diff_calc(ans, pred, wrt_expected)
             },
             |ans, pred| ans.dim() == pred.dim(),
@@ -51,12 +51,12 @@ impl MeanSquaredError {
     }
 
     fn diff_calc(
-        expected: ArrayView1<f32>,
-        actual: ArrayView1<f32>,
+        expected: ArrayView1<f64>,
+        actual: ArrayView1<f64>,
         wrt_expected: bool,
-    ) -> Vec<f32> {
-        let mut result: Vec<f32> = Vec::with_capacity(expected.len());
-        let length: f32 = expected.dim() as f32;
+    ) -> Vec<f64> {
+        let mut result: Vec<f64> = Vec::with_capacity(expected.len());
+        let length: f64 = expected.dim() as f64;
         for (ans, pred) in expected.iter().zip(actual.iter()) {
             if wrt_expected {
                 result.push((ans - pred) / length);
@@ -69,7 +69,11 @@ impl MeanSquaredError {
 }
 
 impl LossType for MeanSquaredError {
-    fn apply(&self, expected: &DataContainer, actual: &DataContainer) -> DataContainer {
+    fn apply(
+        &self,
+        expected: &DataContainer,
+        actual: &DataContainer,
+    ) -> Result<DataContainer, DataError> {
         container_apply(expected, actual, MeanSquaredError::error, "MSE")
     }
 
@@ -78,7 +82,7 @@ impl LossType for MeanSquaredError {
         expected: &DataContainer,
         actual: &DataContainer,
         wrt_expected: bool,
-    ) -> DataContainer {
+    ) -> Result<DataContainer, DataError> {
         container_apply(
             expected,
             actual,
@@ -3,7 +3,7 @@
 // external
 
 // internal
-use crate::data::data_container::DataContainer;
+use crate::data::{data_container::DataContainer, error::DataError};
 use crate::node::NodeRef;
 pub mod adaptive_learning_base;
 pub mod momentum_base;
@@ -12,11 +12,11 @@ pub struct NodeBase<'a> {
     inputs: Vec<NodeRef<'a>>,
     outputs: Vec<NodeRef<'a>>,
     data: DataContainer,
-    grad_count: usize,
     grad: DataContainer,
     momentum: DataContainer,
     is_grad_null: bool,
     is_momentum_null: bool,
+    retain_grad: bool,
 }
 
 impl<'a> NodeBase<'a> {
@@ -25,11 +25,11 @@ impl<'a> NodeBase<'a> {
             inputs: Vec::new(),
             outputs: Vec::new(),
             data: DataContainer::zero(),
-            grad_count: 0,
             grad: DataContainer::zero(),
             momentum: DataContainer::zero(),
             is_grad_null: true,
             is_momentum_null: true,
+            retain_grad: false,
         }
     }
 }
@@ -64,36 +64,51 @@ impl<'a> NodeBase<'a> {
         self.data = data;
     }
 
-    pub fn reset_grad_count(&mut self) {
-        self.grad_count = 0;
-    }
-
-    pub fn increment_grad_count(&mut self) {
-        self.grad_count += 1;
-    }
-
-    pub fn should_process_backprop(&self) -> bool {
-        self.grad_count == self.outputs.len()
-    }
-
     pub fn reset_gradient(&mut self) {
         self.grad = DataContainer::zero();
         self.is_grad_null = true;
     }
 
-    pub fn add_to_gradient(&mut self, component: &DataContainer) {
-        if self.is_grad_null {
-            self.grad = self.grad.plus(component);
+    pub fn add_to_gradient(&mut self, component: &DataContainer) -> Result<(), DataError> {
+        // A batched gradient can arrive after a parameter shaped one, e.g. a weight reached by its
+        // penalty first, so the accumulated gradient has to widen to the batch
+        let widens = matches!(
+            (&self.grad, component),
+            (DataContainer::Parameter(_), DataContainer::Batch(_))
+        );
+
+        if self.is_grad_null || widens {
+            self.grad = self.grad.try_plus(component)?;
             self.is_grad_null = false;
         } else {
-            self.grad.sum_assign(component);
+            self.grad.try_sum_assign(component)?;
         }
+
+        Ok(())
     }
 
     pub fn get_gradient(&self) -> &DataContainer {
         &self.grad
     }
 
+    // While retained, the gradient is kept after backprop instead of being applied and cleared
+    pub fn set_retain_gradient(&mut self, retain: bool) {
+        self.retain_grad = retain;
+        self.reset_gradient();
+    }
+
+    pub fn is_gradient_retained(&self) -> bool {
+        self.retain_grad
+    }
+
+    pub fn get_retained_gradient(&self) -> DataContainer {
+        if self.retain_grad {
+            return self.grad.clone();
+        }
+
+        DataContainer::Empty
+    }
+
     pub fn process_gradient(&mut self, learning_rate: &DataContainer) {
         let mut update = self.grad.average_batch();
         update.times_assign(learning_rate);
@@ -115,7 +130,7 @@ impl<'a> NodeBase<'a> {
         self.data.sum_assign(&self.momentum);
     }
 
-    pub fn update_gradient(&mut self, update: &DataContainer) {
-        self.data.minus_assign(update);
+    pub fn update_gradient(&mut self, update: &DataContainer) -> Result<(), DataError> {
+        self.data.try_minus_assign(update)
     }
 }
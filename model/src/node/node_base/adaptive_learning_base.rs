@@ -4,12 +4,14 @@
 
 // internal
 use crate::{
-    data::{data_container::DataContainer, Data},
+    data::{data_container::DataContainer, element_fn::Scale, precision::Precision},
     optimization::learning_decay::{LearningDecayType, LearningRateParams},
 };
 
 pub struct NodeLearningDecay {
     learning_rate: DataContainer,
+    update_accumulator: DataContainer,
+    rate_scale: f64,
     decay_type: LearningDecayType,
     time_step: usize,
     matches_dim: bool,
@@ -20,6 +22,8 @@ impl NodeLearningDecay {
     pub fn new(decay_type: LearningDecayType) -> NodeLearningDecay {
         NodeLearningDecay {
             learning_rate: decay_type.get_initial_rate(),
+            update_accumulator: DataContainer::zero(),
+            rate_scale: 1.0,
             is_adaptive: decay_type.is_adaptive(),
             time_step: decay_type.get_initial_timestep(),
             decay_type: decay_type,
@@ -27,12 +31,21 @@ impl NodeLearningDecay {
         }
     }
 
-    pub fn scale_update(&self, update: &mut DataContainer) {
+    pub fn scale_update(&mut self, update: &mut DataContainer) {
         if self.is_adaptive {
-            self.decay_type.scale_adaptive(update, &self.learning_rate);
+            self.decay_type.scale_adaptive(
+                update,
+                &self.learning_rate,
+                &mut self.update_accumulator,
+                self.time_step,
+            );
         } else {
             update.times_assign(&self.learning_rate);
         }
+
+        if self.rate_scale != 1.0 {
+            update.apply_inplace(Scale(self.rate_scale));
+        }
     }
 
     // Assumes gradient has already been averaged (not a batch)
@@ -41,9 +54,13 @@ impl NodeLearningDecay {
 
         if self.is_adaptive {
             if !self.matches_dim {
-                self.learning_rate = DataContainer::zero_dim(gradient.dim().1);
+                self.learning_rate = NodeLearningDecay::zero_like(gradient);
                 self.matches_dim = true;
             }
+            let dim: &[usize] = gradient.dim().1;
+            if self.decay_type.has_update_accumulator() && self.update_accumulator.dim().1 != dim {
+                self.update_accumulator = NodeLearningDecay::zero_like(gradient);
+            }
             self.decay_type
                 .update_adaptive(&mut self.learning_rate, gradient, self.time_step);
         } else {
@@ -52,19 +69,142 @@ impl NodeLearningDecay {
         }
     }
 
+    pub fn get_time_step(&self) -> usize {
+        self.time_step
+    }
+
+    pub fn get_decoupled_decay(&self, weights: &DataContainer) -> Option<DataContainer> {
+        let rate_scale: f64 = self.rate_scale;
+        self.decay_type
+            .decoupled_decay(weights)
+            .map(|decay| decay.apply_elementwise(Scale(rate_scale)))
+    }
+
     pub fn get_learning_rate_save(&self) -> LearningRateParams {
-        if let DataContainer::Parameter(data) = &self.learning_rate {
-            return match data {
-                Data::VectorF32(vec) => LearningRateParams::new(vec.to_vec()),
-                Data::MatrixF32(matrix) => LearningRateParams::new(matrix.flatten().to_vec()),
-                _ => LearningRateParams::null(),
+        let learning_rate: Vec<f64> = NodeLearningDecay::flatten_save(&self.learning_rate);
+
+        if learning_rate.is_empty() {
+            return LearningRateParams::null();
+        }
+
+        LearningRateParams::new(
+            learning_rate,
+            NodeLearningDecay::flatten_save(&self.update_accumulator),
+        )
+    }
+
+    // Accumulators take the parameter's precision, so updates don't convert every step
+    fn zero_like(gradient: &DataContainer) -> DataContainer {
+        let dim: &[usize] = gradient.dim().1;
+        match gradient.precision() {
+            Precision::F32 => DataContainer::zero_dim_as::<f32>(dim),
+            Precision::F64 => DataContainer::zero_dim_as::<f64>(dim),
+        }
+    }
+
+    // Scalars are only placeholders until the first update, so they aren't worth saving
+    fn flatten_save(container: &DataContainer) -> Vec<f64> {
+        if let DataContainer::Parameter(data) = container {
+            return match data.shape() {
+                Some(shape) if !shape.is_empty() => data.flatten_as(),
+                _ => Vec::new(),
             };
         }
 
-        LearningRateParams::null()
+        Vec::new()
     }
 
+    // A loaded adaptive accumulator already has the parameter's shape and must not be reset
     pub fn set_learning_rate(&mut self, learning_rate: DataContainer) {
         self.learning_rate = learning_rate;
+        self.matches_dim = self.is_adaptive;
+    }
+
+    pub fn set_update_accumulator(&mut self, update_accumulator: DataContainer) {
+        self.update_accumulator = update_accumulator;
+    }
+
+    pub fn set_rate_scale(&mut self, rate_scale: f32) {
+        self.rate_scale = f64::from(rate_scale);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::arr1;
+
+    use crate::{
+        data::{data_container::DataContainer, Data},
+        node::node_base::adaptive_learning_base::NodeLearningDecay,
+        optimization::learning_decay::LearningDecayType,
+    };
+
+    fn vector(values: &[f64]) -> DataContainer {
+        DataContainer::Parameter(Data::VectorF64(arr1(values)))
+    }
+
+    fn step(decay: &mut NodeLearningDecay, gradient: &[f64]) -> Vec<f64> {
+        let mut update: DataContainer = vector(gradient);
+        decay.update_learning_rate(&update);
+        decay.scale_update(&mut update);
+
+        match update {
+            DataContainer::Parameter(data) => data.flatten_as(),
+            _ => panic!("Expected a parameter update"),
+        }
+    }
+
+    // Accumulators are offset by DELTA before taking the root, so updates are only close
+    fn assert_close(actual: &[f64], expected: &[f64]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-6, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn ada_grad_update_test() {
+        let mut decay = NodeLearningDecay::new(LearningDecayType::ada_grad(0.1));
+
+        // The accumulator sums the squared gradients, the update divides by its root
+        let update = step(&mut decay, &[1.0, 2.0]);
+        assert_close(
+            &decay.get_learning_rate_save().get_adaptive_learning_rate(),
+            &[1.0, 4.0],
+        );
+        assert_close(&update, &[0.1, 0.1]);
+
+        let update = step(&mut decay, &[3.0, -1.0]);
+        assert_close(
+            &decay.get_learning_rate_save().get_adaptive_learning_rate(),
+            &[10.0, 5.0],
+        );
+        assert_close(&update, &[0.3 / f64::sqrt(10.0), -0.1 / f64::sqrt(5.0)]);
+        assert!(decay
+            .get_learning_rate_save()
+            .get_update_accumulator()
+            .is_empty());
+    }
+
+    #[test]
+    fn ada_grad_resume_test() {
+        let mut decay = NodeLearningDecay::new(LearningDecayType::ada_grad(0.1));
+        step(&mut decay, &[1.0, 2.0]);
+        step(&mut decay, &[3.0, -1.0]);
+
+        let saved: Vec<f64> = decay.get_learning_rate_save().get_adaptive_learning_rate();
+        let mut resumed = NodeLearningDecay::new(LearningDecayType::ada_grad(0.1));
+        resumed.set_learning_rate(vector(&saved));
+
+        // A loaded accumulator keeps growing instead of being reset on the first update
+        let original = step(&mut decay, &[-2.0, 1.0]);
+        let loaded = step(&mut resumed, &[-2.0, 1.0]);
+        assert_close(&loaded, &original);
+        assert_close(
+            &resumed
+                .get_learning_rate_save()
+                .get_adaptive_learning_rate(),
+            &[14.0, 6.0],
+        );
     }
 }
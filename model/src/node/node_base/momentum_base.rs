@@ -4,7 +4,7 @@
 
 // internal
 use crate::{
-    data::{data_container::DataContainer, Data},
+    data::{data_container::DataContainer, element_fn::Scale},
     optimization::momentum::{DescentType, MomentumParams},
 };
 
@@ -39,9 +39,40 @@ impl NodeMomentum {
             DescentType::Base => false,
             DescentType::Momentum { .. } => true,
             DescentType::Nesterov { .. } => true,
+            DescentType::Adam { .. } => true,
         }
     }
 
+    // Adam averages the raw gradient, so the learning rate is applied to the estimate afterwards
+    pub fn is_moment_estimate(&self) -> bool {
+        matches!(self.descent_type, DescentType::Adam { .. })
+    }
+
+    // Bias corrected first moment, `time_step` counts updates starting at 1
+    pub fn get_moment_estimate(
+        &mut self,
+        gradient: &DataContainer,
+        time_step: usize,
+    ) -> DataContainer {
+        if let DescentType::Adam { beta1 } = self.descent_type {
+            let beta1 = f64::from(beta1);
+            let update: DataContainer = gradient.apply_elementwise(Scale(1.0 - beta1));
+
+            if self.is_momentum_null {
+                self.momentum = update;
+                self.is_momentum_null = false;
+            } else {
+                self.momentum.apply_inplace(Scale(beta1));
+                self.momentum.sum_assign(&update);
+            }
+
+            let correction = 1.0 - f64::powi(beta1, time_step as i32);
+            return self.momentum.apply_elementwise(Scale(1.0 / correction));
+        }
+
+        panic!("Tried to get a moment estimate without an Adam descent type");
+    }
+
     pub fn get_momentum_update(&mut self, update: &DataContainer) -> &DataContainer {
         if self.is_momentum_null {
             self.momentum = update.clone();
@@ -51,11 +82,11 @@ impl NodeMomentum {
 
         match &self.descent_type {
             DescentType::Momentum { decay } => {
-                self.momentum.apply_inplace(|f| *f *= decay);
+                self.momentum.apply_inplace(Scale(f64::from(*decay)));
                 self.momentum.sum_assign(&update);
             }
             DescentType::Nesterov { decay } => {
-                self.momentum.apply_inplace(|f| *f *= decay);
+                self.momentum.apply_inplace(Scale(f64::from(*decay)));
                 self.momentum.sum_assign(&update);
             }
             _ => panic!("Tried to get invalid momentum update"),
@@ -70,9 +101,8 @@ impl NodeMomentum {
         }
 
         if let DataContainer::Parameter(data) = &self.momentum {
-            return match data {
-                Data::VectorF32(vec) => MomentumParams::new(vec.to_vec()),
-                Data::MatrixF32(matrix) => MomentumParams::new(matrix.flatten().to_vec()),
+            return match data.shape() {
+                Some(shape) if !shape.is_empty() => MomentumParams::new(data.flatten_as()),
                 _ => MomentumParams::null(),
             };
         }
@@ -82,5 +112,6 @@ impl NodeMomentum {
 
     pub fn set_momentum(&mut self, momentum: DataContainer) {
         self.momentum = momentum;
+        self.is_momentum_null = false;
     }
 }
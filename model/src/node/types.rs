@@ -7,6 +7,7 @@ pub mod abs_value_node;
 pub mod activation_node;
 pub mod add_node;
 pub mod bias_node;
+pub mod concat_node;
 pub mod constant_node;
 pub mod element_sum_node;
 pub mod expected_response_node;
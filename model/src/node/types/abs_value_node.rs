@@ -4,7 +4,11 @@
 
 // internal
 use crate::{
-    data::data_container::DataContainer,
+    data::{
+        data_container::DataContainer,
+        element_fn::{Abs, Sign},
+        error::DataError,
+    },
     network::config_types::learned_params::LearnedParams,
     node::{node_base::NodeBase, Node, NodeRef, NodeType},
     regularization::dropout::NetworkMode,
@@ -55,62 +59,50 @@ impl<'a> Node<'a> for AbsoluteValueNode<'a> {
         self.base.get_data()
     }
 
-    fn apply_operation(&mut self) {
+    fn apply_operation(&mut self) -> Result<(), DataError> {
         let inputs = self.base.get_inputs();
 
-        for input in inputs {
-            input.borrow_mut().apply_operation();
-        }
-
         let input_ref = inputs.get(0).unwrap();
         let input_data = input_ref.borrow_mut().get_data();
 
-        let res = input_data.apply_elementwise(f32::abs);
+        let res = input_data.apply_elementwise(Abs);
 
         self.base.set_data(res);
+
+        Ok(())
     }
 
-    fn add_gradient(&mut self, grad: &DataContainer) {
-        self.base.increment_grad_count();
-        self.base.add_to_gradient(grad);
+    fn add_gradient(&mut self, grad: &DataContainer) -> Result<(), DataError> {
+        self.base.add_to_gradient(grad)
     }
 
-    fn apply_jacobian(&mut self) {
-        self.base.reset_grad_count();
+    fn apply_jacobian(&mut self) -> Result<(), DataError> {
         if self.base.get_inputs().len() == 0 {
             self.base.reset_gradient();
-            return;
+            return Ok(());
         }
 
         let input = self.base.get_inputs().get(0).unwrap();
         let input_data = input.borrow_mut().get_data();
 
-        let scale = input_data.apply_elementwise(|f| {
-            if f > 0.0 {
-                1.0
-            } else if f < 0.0 {
-                -1.0
-            } else {
-                0.0
-            }
-        });
+        let scale = input_data.apply_elementwise(Sign);
         let prev_grad = self.base.get_gradient();
 
-        let update = prev_grad.times(&scale);
-
-        input.borrow_mut().add_gradient(&update);
+        let update = prev_grad.try_times(&scale)?;
 
-        if input.borrow().should_process_backprop() {
-            input.borrow_mut().apply_jacobian();
-        }
+        input.borrow_mut().add_gradient(&update)?;
 
         self.base.reset_gradient();
+
+        Ok(())
     }
 
-    fn should_process_backprop(&self) -> bool {
-        self.base.should_process_backprop()
+    fn get_gradient(&self) -> DataContainer {
+        DataContainer::Empty
     }
 
+    fn set_retain_gradient(&mut self, _retain: bool) {}
+
     fn set_momentum(&mut self, _momentum: DataContainer) {
         println!(
             "[ABSOLUTE_VALUE] Unsupported Operation: Cannot set momentum of an operation node"
@@ -123,6 +115,8 @@ impl<'a> Node<'a> for AbsoluteValueNode<'a> {
         );
     }
 
+    fn set_rate_scale(&mut self, _scale: f32) {}
+
     fn save_parameters(&self) -> LearnedParams {
         println!(
             "[ABSOLUTE_VALUE] Unsupported Operation: Cannot save parameters of an operation node"
@@ -4,6 +4,7 @@
 
 // internal
 use crate::data::data_container::DataContainer;
+use crate::data::error::DataError;
 use crate::network::config_types::learned_params::LearnedParams;
 use crate::node::NodeType;
 use crate::node::{
@@ -54,56 +55,52 @@ impl<'a> Node<'a> for ActivationNode<'a> {
         self.base.get_data()
     }
 
-    fn apply_operation(&mut self) {
+    fn apply_operation(&mut self) -> Result<(), DataError> {
         if self.get_inputs().len() == 0 {
             println!("[ACTIVATION] Tried to apply operation on no inputs");
-            return;
+            return Ok(());
         }
 
         let inputs: Vec<NodeRef<'a>> = self.get_inputs().iter().cloned().collect();
 
-        for input in &inputs {
-            input.borrow_mut().apply_operation();
-        }
-
         let mut input_ref = inputs.get(0).unwrap().borrow_mut();
 
         let data = input_ref.get_data();
-        let result = data.apply_function(|data| self.function.apply_all(data));
+        let result = data.apply_elementwise(self.function.applied());
 
         self.base.set_data(result);
+
+        Ok(())
     }
 
     fn set_data(&mut self, _data: DataContainer) {
         panic!("[ACTIVATION] Unsupported Operation: Cannot set data of an operation node");
     }
 
-    fn add_gradient(&mut self, grad: &DataContainer) {
-        self.base.increment_grad_count();
-        self.base.add_to_gradient(grad);
+    fn add_gradient(&mut self, grad: &DataContainer) -> Result<(), DataError> {
+        self.base.add_to_gradient(grad)
     }
 
-    fn apply_jacobian(&mut self) {
-        self.base.reset_grad_count();
-
+    fn apply_jacobian(&mut self) -> Result<(), DataError> {
         for node in self.get_inputs() {
             let data = node.borrow_mut().get_data();
-            let mut grad = data.apply_function(|data| self.function.diff_all(data));
-            grad.times_assign(self.base.get_gradient());
+            let mut grad = data.apply_elementwise(self.function.derived());
+            grad.try_times_assign(self.base.get_gradient())?;
 
-            node.borrow_mut().add_gradient(&grad);
-            if node.borrow().should_process_backprop() {
-                node.borrow_mut().apply_jacobian();
-            }
+            node.borrow_mut().add_gradient(&grad)?;
         }
 
         self.base.reset_gradient();
+
+        Ok(())
     }
 
-    fn should_process_backprop(&self) -> bool {
-        self.base.should_process_backprop()
+    fn get_gradient(&self) -> DataContainer {
+        DataContainer::Empty
     }
 
+    fn set_retain_gradient(&mut self, _retain: bool) {}
+
     fn set_momentum(&mut self, _momentum: DataContainer) {
         println!("[ACTIVATION] Unsupported Operation: Cannot set momentum of an operation node");
     }
@@ -114,6 +111,8 @@ impl<'a> Node<'a> for ActivationNode<'a> {
         );
     }
 
+    fn set_rate_scale(&mut self, _scale: f32) {}
+
     fn save_parameters(&self) -> LearnedParams {
         println!("[ACTIVATION] Unsupported Operation: Cannot save parameters of an operation node");
         LearnedParams::null()
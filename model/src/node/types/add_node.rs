@@ -4,6 +4,7 @@
 
 // internal
 use crate::data::data_container::DataContainer;
+use crate::data::error::DataError;
 use crate::network::config_types::learned_params::LearnedParams;
 use crate::node::NodeType;
 use crate::node::{node_base::NodeBase, Node, NodeRef};
@@ -46,55 +47,54 @@ impl<'a> Node<'a> for AddNode<'a> {
         self.base.get_data()
     }
 
-    fn apply_operation(&mut self) {
+    fn apply_operation(&mut self) -> Result<(), DataError> {
         if self.get_inputs().len() == 0 {
-            return;
+            return Ok(());
         }
 
         let inputs: Vec<NodeRef<'a>> = self.get_inputs().iter().cloned().collect();
 
-        for input in &inputs {
-            input.borrow_mut().apply_operation();
-        }
-
         let mut first_ref = inputs.get(0).unwrap().borrow_mut();
         let mut sum = first_ref.get_data();
 
         for i in 1..inputs.len() {
             let mut node_ref = inputs[i].borrow_mut();
             let data = node_ref.get_data();
-            sum = sum.plus(&data);
+            sum = sum.try_plus(&data)?;
         }
 
         self.base.set_data(sum);
+
+        Ok(())
     }
 
     fn set_data(&mut self, _data: DataContainer) {
         panic!("[ADD] Unsupported Operation: Cannot set data of an operation node");
     }
 
-    fn add_gradient(&mut self, grad: &DataContainer) {
-        self.base.increment_grad_count();
-        self.base.add_to_gradient(grad);
+    fn add_gradient(&mut self, grad: &DataContainer) -> Result<(), DataError> {
+        self.base.add_to_gradient(grad)
     }
 
-    fn apply_jacobian(&mut self) {
-        self.base.reset_grad_count();
-
+    fn apply_jacobian(&mut self) -> Result<(), DataError> {
         for node in self.get_inputs() {
-            node.borrow_mut().add_gradient(self.base.get_gradient());
-            if node.borrow().should_process_backprop() {
-                node.borrow_mut().apply_jacobian();
-            }
+            let input_data = node.borrow_mut().get_data();
+            let grad = self.base.get_gradient().try_reduce_like(&input_data)?;
+
+            node.borrow_mut().add_gradient(&grad)?;
         }
 
         self.base.reset_gradient();
+
+        Ok(())
     }
 
-    fn should_process_backprop(&self) -> bool {
-        self.base.should_process_backprop()
+    fn get_gradient(&self) -> DataContainer {
+        DataContainer::Empty
     }
 
+    fn set_retain_gradient(&mut self, _retain: bool) {}
+
     fn set_momentum(&mut self, _momentum: DataContainer) {
         println!("[ADD] Unsupported Operation: Cannot set momentum of an operation node");
     }
@@ -103,6 +103,8 @@ impl<'a> Node<'a> for AddNode<'a> {
         println!("[ADD] Unsupported Operation: Cannot set learning rate of an operation node");
     }
 
+    fn set_rate_scale(&mut self, _scale: f32) {}
+
     fn save_parameters(&self) -> LearnedParams {
         println!("[ADD] Unsupported Operation: Cannot save parameters of an operation node");
         LearnedParams::null()
@@ -5,6 +5,7 @@ use ndarray::Array1;
 
 // internal
 use crate::data::data_container::DataContainer;
+use crate::data::error::DataError;
 use crate::data::Data;
 use crate::network::config_types::layer_params::LayerParams;
 use crate::network::config_types::learned_params::LearnedParams;
@@ -66,10 +67,9 @@ impl<'a> Node<'a> for BiasNode<'a> {
     }
 
     fn set_data(&mut self, input: DataContainer) {
-        if let DataContainer::Parameter(Data::VectorF32(vec)) = input {
-            if vec.dim() == self.dim {
-                let container = DataContainer::Parameter(Data::VectorF32(vec));
-                self.base.set_data(container);
+        if let DataContainer::Parameter(data) = &input {
+            if data.shape() == Some(&[self.dim]) {
+                self.base.set_data(input);
                 return;
             }
         }
@@ -78,37 +78,56 @@ impl<'a> Node<'a> for BiasNode<'a> {
 
     fn get_data(&mut self) -> DataContainer {
         let mut data = self.base.get_data();
-        self.momentum_base.alter_data(&mut data);
+        // Gradient checks perturb the stored parameters, so the nesterov lookahead is skipped
+        if !self.base.is_gradient_retained() {
+            self.momentum_base.alter_data(&mut data);
+        }
 
         data
     }
 
-    fn apply_operation(&mut self) {}
+    fn apply_operation(&mut self) -> Result<(), DataError> {
+        Ok(())
+    }
 
-    fn add_gradient(&mut self, grad: &DataContainer) {
-        self.base.increment_grad_count();
-        self.base.add_to_gradient(grad);
+    fn add_gradient(&mut self, grad: &DataContainer) -> Result<(), DataError> {
+        self.base.add_to_gradient(grad)
     }
 
-    fn apply_jacobian(&mut self) {
-        self.base.reset_grad_count();
+    fn apply_jacobian(&mut self) -> Result<(), DataError> {
+        if self.base.is_gradient_retained() {
+            return Ok(());
+        }
 
         let mut update: DataContainer = self.base.get_gradient().average_batch();
         self.learning_base.update_learning_rate(&update);
-        self.learning_base.scale_update(&mut update);
 
-        if self.momentum_base.is_momentum_update() {
+        if self.momentum_base.is_moment_estimate() {
+            let time_step: usize = self.learning_base.get_time_step();
+            let mut moment: DataContainer =
+                self.momentum_base.get_moment_estimate(&update, time_step);
+            self.learning_base.scale_update(&mut moment);
+            self.base.update_gradient(&moment)?;
+        } else if self.momentum_base.is_momentum_update() {
+            self.learning_base.scale_update(&mut update);
             let momentum_update: &DataContainer = self.momentum_base.get_momentum_update(&update);
-            self.base.update_gradient(momentum_update);
+            self.base.update_gradient(momentum_update)?;
         } else {
-            self.base.update_gradient(&update);
+            self.learning_base.scale_update(&mut update);
+            self.base.update_gradient(&update)?;
         }
 
         self.base.reset_gradient();
+
+        Ok(())
+    }
+
+    fn get_gradient(&self) -> DataContainer {
+        self.base.get_retained_gradient()
     }
 
-    fn should_process_backprop(&self) -> bool {
-        self.base.should_process_backprop()
+    fn set_retain_gradient(&mut self, retain: bool) {
+        self.base.set_retain_gradient(retain);
     }
 
     fn set_momentum(&mut self, momentum: DataContainer) {
@@ -119,17 +138,27 @@ impl<'a> Node<'a> for BiasNode<'a> {
         self.learning_base.set_learning_rate(learning_rate);
     }
 
+    fn set_update_accumulator(&mut self, update_accumulator: DataContainer) {
+        self.learning_base
+            .set_update_accumulator(update_accumulator);
+    }
+
+    fn set_rate_scale(&mut self, scale: f32) {
+        self.learning_base.set_rate_scale(scale);
+    }
+
     fn save_parameters(&self) -> LearnedParams {
         let data = self.base.get_data();
 
-        if let DataContainer::Parameter(Data::VectorF32(vec)) = data {
-            let parameters = vec.to_vec();
+        if let DataContainer::Parameter(data) = &data {
+            let parameters: Vec<f64> = data.flatten_as();
 
             let dim: Vec<usize> = vec![self.dim];
             let momentum = self.momentum_base.get_momentum_save();
             let learning_rate = self.learning_base.get_learning_rate_save();
 
-            let params = LayerParams::new(dim, parameters, momentum, learning_rate);
+            let params =
+                LayerParams::new(dim, parameters, data.precision(), momentum, learning_rate);
             return LearnedParams::new_layer(params);
         }
 
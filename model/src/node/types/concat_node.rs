@@ -0,0 +1,174 @@
+// builtin
+
+// external
+
+// internal
+use crate::data::data_container::DataContainer;
+use crate::data::error::DataError;
+use crate::network::config_types::learned_params::LearnedParams;
+use crate::node::NodeType;
+use crate::node::{node_base::NodeBase, Node, NodeRef};
+use crate::regularization::dropout::NetworkMode;
+
+// Joins the features of its inputs in the order they were added
+pub struct ConcatNode<'a> {
+    base: NodeBase<'a>,
+    widths: Vec<usize>,
+}
+
+impl<'a> ConcatNode<'a> {
+    pub fn new() -> ConcatNode<'a> {
+        ConcatNode {
+            base: NodeBase::new(),
+            widths: Vec::new(),
+        }
+    }
+}
+
+impl Default for ConcatNode<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Node<'a> for ConcatNode<'a> {
+    fn get_type(&self) -> NodeType {
+        NodeType::Operation
+    }
+
+    fn add_input(&mut self, this: &NodeRef<'a>, input: &NodeRef<'a>) {
+        self.base.add_input(this, input);
+    }
+
+    fn add_output(&mut self, output: &NodeRef<'a>) {
+        self.base.add_output(output);
+    }
+
+    fn get_inputs(&self) -> &Vec<NodeRef<'a>> {
+        self.base.get_inputs()
+    }
+
+    fn get_outputs(&self) -> &Vec<NodeRef<'a>> {
+        self.base.get_outputs()
+    }
+
+    fn get_data(&mut self) -> DataContainer {
+        self.base.get_data()
+    }
+
+    fn apply_operation(&mut self) -> Result<(), DataError> {
+        if self.get_inputs().is_empty() {
+            return Ok(());
+        }
+
+        let parts: Vec<DataContainer> = self
+            .get_inputs()
+            .iter()
+            .map(|input| input.borrow_mut().get_data())
+            .collect();
+
+        let joined: DataContainer = DataContainer::try_concat(&parts)?;
+
+        self.widths = parts
+            .iter()
+            .map(|part| part.dim().1.last().copied().unwrap_or(0))
+            .collect();
+        self.base.set_data(joined);
+
+        Ok(())
+    }
+
+    fn set_data(&mut self, _data: DataContainer) {
+        panic!("[CONCAT] Unsupported Operation: Cannot set data of an operation node");
+    }
+
+    fn add_gradient(&mut self, grad: &DataContainer) -> Result<(), DataError> {
+        self.base.add_to_gradient(grad)
+    }
+
+    fn apply_jacobian(&mut self) -> Result<(), DataError> {
+        let grads: Vec<DataContainer> = self.base.get_gradient().try_split(&self.widths)?;
+
+        for (node, grad) in self.get_inputs().iter().zip(grads) {
+            node.borrow_mut().add_gradient(&grad)?;
+        }
+
+        self.base.reset_gradient();
+
+        Ok(())
+    }
+
+    fn get_gradient(&self) -> DataContainer {
+        DataContainer::Empty
+    }
+
+    fn set_retain_gradient(&mut self, _retain: bool) {}
+
+    fn set_momentum(&mut self, _momentum: DataContainer) {
+        println!("[CONCAT] Unsupported Operation: Cannot set momentum of an operation node");
+    }
+
+    fn set_learning_rate(&mut self, _learning_rate: DataContainer) {
+        println!("[CONCAT] Unsupported Operation: Cannot set learning rate of an operation node");
+    }
+
+    fn set_rate_scale(&mut self, _scale: f32) {}
+
+    fn save_parameters(&self) -> LearnedParams {
+        println!("[CONCAT] Unsupported Operation: Cannot save parameters of an operation node");
+        LearnedParams::null()
+    }
+
+    fn set_mode(&mut self, _new_mode: NetworkMode) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::arr1;
+
+    use crate::{
+        data::{data_container::DataContainer, Data},
+        node::{
+            graph::Graph,
+            types::{concat_node::ConcatNode, input_node::InputNode},
+            NodeRef,
+        },
+    };
+
+    #[test]
+    fn concat_splits_gradient() {
+        let first: NodeRef = NodeRef::new(InputNode::new(vec![2]));
+        let second: NodeRef = NodeRef::new(InputNode::new(vec![1]));
+        let concat: NodeRef = NodeRef::new(ConcatNode::new());
+        concat.borrow_mut().add_input(&concat, &first);
+        concat.borrow_mut().add_input(&concat, &second);
+
+        first.borrow_mut().set_data(DataContainer::batch(vec![
+            Data::VectorF32(arr1(&[1.0, 2.0])),
+            Data::VectorF32(arr1(&[3.0, 4.0])),
+        ]));
+        second.borrow_mut().set_data(DataContainer::batch(vec![
+            Data::VectorF32(arr1(&[5.0])),
+            Data::VectorF32(arr1(&[6.0])),
+        ]));
+        first.borrow_mut().set_retain_gradient(true);
+        second.borrow_mut().set_retain_gradient(true);
+
+        let graph: Graph = Graph::new(&concat).unwrap();
+        graph.forward().unwrap();
+
+        let joined: Vec<Data> = concat.borrow_mut().get_data().batch_to_vec();
+        assert_eq!(joined[1].flatten_as::<f32>(), vec![3.0, 4.0, 6.0]);
+
+        let seed = DataContainer::batch(vec![
+            Data::VectorF32(arr1(&[0.1, 0.2, 0.3])),
+            Data::VectorF32(arr1(&[0.4, 0.5, 0.6])),
+        ]);
+        graph.backward(&seed).unwrap();
+
+        let first_grad: Vec<Data> = first.borrow().get_gradient().batch_to_vec();
+        let second_grad: Vec<Data> = second.borrow().get_gradient().batch_to_vec();
+        assert_eq!(first_grad[1].flatten_as::<f32>(), vec![0.4, 0.5]);
+        assert_eq!(second_grad[0].flatten_as::<f32>(), vec![0.3]);
+    }
+}
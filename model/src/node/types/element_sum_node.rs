@@ -4,7 +4,7 @@
 
 // internal
 use crate::{
-    data::data_container::DataContainer,
+    data::{data_container::DataContainer, element_fn::Fill, error::DataError},
     network::config_types::learned_params::LearnedParams,
     node::{node_base::NodeBase, Node, NodeRef, NodeType},
     regularization::dropout::NetworkMode,
@@ -55,54 +55,50 @@ impl<'a> Node<'a> for ElementSumNode<'a> {
         self.base.get_data()
     }
 
-    fn apply_operation(&mut self) {
+    fn apply_operation(&mut self) -> Result<(), DataError> {
         let inputs = self.base.get_inputs();
 
-        for input in inputs {
-            input.borrow_mut().apply_operation();
-        }
-
         let input_ref = inputs.get(0).unwrap();
         let input_data = input_ref.borrow_mut().get_data();
 
-        let res = input_data.element_sum();
+        let res = input_data.try_element_sum()?;
 
         self.base.set_data(res);
+
+        Ok(())
     }
 
-    fn add_gradient(&mut self, grad: &DataContainer) {
-        self.base.increment_grad_count();
-        self.base.add_to_gradient(grad);
+    fn add_gradient(&mut self, grad: &DataContainer) -> Result<(), DataError> {
+        self.base.add_to_gradient(grad)
     }
 
-    fn apply_jacobian(&mut self) {
-        self.base.reset_grad_count();
+    fn apply_jacobian(&mut self) -> Result<(), DataError> {
         if self.base.get_inputs().len() == 0 {
             self.base.reset_gradient();
-            return;
+            return Ok(());
         }
 
         let input = self.base.get_inputs().get(0).unwrap();
         let input_data = input.borrow_mut().get_data();
 
-        let scale = input_data.apply_elementwise(|_f| 1.0);
+        let scale = input_data.apply_elementwise(Fill(1.0));
         let prev_grad = self.base.get_gradient();
 
-        let update = prev_grad.times(&scale);
-
-        input.borrow_mut().add_gradient(&update);
+        let update = prev_grad.try_times(&scale)?;
 
-        if input.borrow().should_process_backprop() {
-            input.borrow_mut().apply_jacobian();
-        }
+        input.borrow_mut().add_gradient(&update)?;
 
         self.base.reset_gradient();
+
+        Ok(())
     }
 
-    fn should_process_backprop(&self) -> bool {
-        self.base.should_process_backprop()
+    fn get_gradient(&self) -> DataContainer {
+        DataContainer::Empty
     }
 
+    fn set_retain_gradient(&mut self, _retain: bool) {}
+
     fn set_momentum(&mut self, _momentum: DataContainer) {
         println!("[ELEMENT_SUM] Unsupported Operation: Cannot set momentum of an operation node");
     }
@@ -113,6 +109,8 @@ impl<'a> Node<'a> for ElementSumNode<'a> {
         );
     }
 
+    fn set_rate_scale(&mut self, _scale: f32) {}
+
     fn save_parameters(&self) -> LearnedParams {
         println!(
             "[ELEMENT_SUM] Unsupported Operation: Cannot save parameters of an operation node"
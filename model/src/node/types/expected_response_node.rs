@@ -4,6 +4,7 @@
 
 // internal
 use crate::data::data_container::DataContainer;
+use crate::data::error::DataError;
 use crate::network::config_types::learned_params::LearnedParams;
 use crate::node::NodeType;
 use crate::node::{node_base::NodeBase, Node, NodeRef};
@@ -59,20 +60,24 @@ impl<'a> Node<'a> for ExpectedResponseNode<'a> {
         self.base.get_data()
     }
 
-    fn apply_operation(&mut self) {}
+    fn apply_operation(&mut self) -> Result<(), DataError> {
+        Ok(())
+    }
 
-    fn add_gradient(&mut self, _grad: &DataContainer) {
-        self.base.increment_grad_count();
+    fn add_gradient(&mut self, _grad: &DataContainer) -> Result<(), DataError> {
+        Ok(())
     }
 
-    fn apply_jacobian(&mut self) {
-        self.base.reset_grad_count();
+    fn apply_jacobian(&mut self) -> Result<(), DataError> {
+        Ok(())
     }
 
-    fn should_process_backprop(&self) -> bool {
-        self.base.should_process_backprop()
+    fn get_gradient(&self) -> DataContainer {
+        DataContainer::Empty
     }
 
+    fn set_retain_gradient(&mut self, _retain: bool) {}
+
     fn set_momentum(&mut self, _momentum: DataContainer) {
         println!("[RESPONSE] Unsupported Operation: Cannot set momentum of a response node");
     }
@@ -81,6 +86,8 @@ impl<'a> Node<'a> for ExpectedResponseNode<'a> {
         println!("[RESPONSE] Unsupported Operation: Cannot set learning rate of a response node");
     }
 
+    fn set_rate_scale(&mut self, _scale: f32) {}
+
     fn save_parameters(&self) -> LearnedParams {
         println!("[RESPONSE] Unsupported Operation: Cannot save parameters of a response node");
         LearnedParams::null()
@@ -4,6 +4,7 @@
 
 // internal
 use crate::data::data_container::DataContainer;
+use crate::data::error::DataError;
 use crate::network::config_types::learned_params::LearnedParams;
 use crate::node::NodeType;
 use crate::node::{node_base::NodeBase, Node, NodeRef};
@@ -54,18 +55,28 @@ impl<'a> Node<'a> for InputNode<'a> {
         self.base.get_data()
     }
 
-    fn apply_operation(&mut self) {}
+    fn apply_operation(&mut self) -> Result<(), DataError> {
+        Ok(())
+    }
+
+    fn add_gradient(&mut self, grad: &DataContainer) -> Result<(), DataError> {
+        if self.base.is_gradient_retained() {
+            self.base.add_to_gradient(grad)?;
+        }
 
-    fn add_gradient(&mut self, _grad: &DataContainer) {
-        self.base.increment_grad_count();
+        Ok(())
     }
 
-    fn apply_jacobian(&mut self) {
-        self.base.reset_grad_count();
+    fn apply_jacobian(&mut self) -> Result<(), DataError> {
+        Ok(())
     }
 
-    fn should_process_backprop(&self) -> bool {
-        self.base.should_process_backprop()
+    fn get_gradient(&self) -> DataContainer {
+        self.base.get_retained_gradient()
+    }
+
+    fn set_retain_gradient(&mut self, retain: bool) {
+        self.base.set_retain_gradient(retain);
     }
 
     fn set_momentum(&mut self, _momentum: DataContainer) {
@@ -76,6 +87,8 @@ impl<'a> Node<'a> for InputNode<'a> {
         println!("[INPUT] Unsupported Operation: Cannot set learning rate of an input node");
     }
 
+    fn set_rate_scale(&mut self, _scale: f32) {}
+
     fn save_parameters(&self) -> LearnedParams {
         println!("[INPUT] Unsupported Operation: Cannot save parameters of an input node");
         LearnedParams::null()
@@ -4,6 +4,7 @@
 
 // internal
 use crate::data::data_container::DataContainer;
+use crate::data::error::DataError;
 use crate::network::config_types::learned_params::LearnedParams;
 use crate::node::loss::loss_function::LossFunction;
 use crate::node::NodeType;
@@ -75,21 +76,17 @@ impl<'a> Node<'a> for LossNode<'a> {
         self.base.get_data()
     }
 
-    fn apply_operation(&mut self) {
+    fn apply_operation(&mut self) -> Result<(), DataError> {
         if self.get_inputs().len() != 2 {
             println!(
                 "[LOSS] Expected 2 Inputs but got {}, terminating feedforward operation",
                 self.get_inputs().len()
             );
-            return;
+            return Ok(());
         }
 
         let inputs: Vec<NodeRef<'a>> = self.get_inputs().iter().cloned().collect();
 
-        for input in &inputs {
-            input.borrow_mut().apply_operation();
-        }
-
         let first_ref = inputs.get(0).unwrap();
         let first_data = first_ref.borrow_mut().get_data();
 
@@ -97,25 +94,25 @@ impl<'a> Node<'a> for LossNode<'a> {
         let second_data = second_ref.borrow_mut().get_data();
 
         if first_ref.get_type() == NodeType::ExpectedResponse {
-            let data = self.function.apply(&first_data, &second_data);
+            let data = self.function.apply(&first_data, &second_data)?;
             self.base.set_data(data);
         } else {
-            let data = self.function.apply(&second_data, &first_data);
+            let data = self.function.apply(&second_data, &first_data)?;
             self.base.set_data(data);
         }
+
+        Ok(())
     }
 
     fn set_data(&mut self, _data: DataContainer) {
         panic!("[LOSS] Unsupported Operation: Cannot set data of an operation node");
     }
 
-    fn add_gradient(&mut self, grad: &DataContainer) {
-        self.base.increment_grad_count();
-        self.base.add_to_gradient(grad);
+    fn add_gradient(&mut self, grad: &DataContainer) -> Result<(), DataError> {
+        self.base.add_to_gradient(grad)
     }
 
-    fn apply_jacobian(&mut self) {
-        self.base.reset_grad_count();
+    fn apply_jacobian(&mut self) -> Result<(), DataError> {
         let grad = self.base.get_gradient();
 
         let inputs: Vec<NodeRef<'a>> = self.get_inputs().iter().cloned().collect();
@@ -127,36 +124,42 @@ impl<'a> Node<'a> for LossNode<'a> {
         let second_data = second_ref.borrow_mut().get_data();
 
         if first_ref.get_type() == NodeType::Operation {
-            let mut expected_grad = self.function.get_jacobian(&second_data, &first_data, true);
-            expected_grad.times_assign(grad);
-            second_ref.borrow_mut().add_gradient(&expected_grad);
-
-            let mut actual_grad = self.function.get_jacobian(&second_data, &first_data, false);
-            actual_grad.times_assign(grad);
-            first_ref.borrow_mut().add_gradient(&actual_grad);
+            let mut expected_grad = self
+                .function
+                .get_jacobian(&second_data, &first_data, true)?;
+            expected_grad.try_times_assign(grad)?;
+            second_ref.borrow_mut().add_gradient(&expected_grad)?;
+
+            let mut actual_grad = self
+                .function
+                .get_jacobian(&second_data, &first_data, false)?;
+            actual_grad.try_times_assign(grad)?;
+            first_ref.borrow_mut().add_gradient(&actual_grad)?;
         } else {
-            let mut expected_grad = self.function.get_jacobian(&first_data, &second_data, true);
-            expected_grad.times_assign(grad);
-            first_ref.borrow_mut().add_gradient(&expected_grad);
-
-            let mut actual_grad = self.function.get_jacobian(&first_data, &second_data, false);
-            actual_grad.times_assign(grad);
-            second_ref.borrow_mut().add_gradient(&actual_grad);
-        }
-
-        for input in inputs {
-            if input.borrow().should_process_backprop() {
-                input.borrow_mut().apply_jacobian();
-            }
+            let mut expected_grad = self
+                .function
+                .get_jacobian(&first_data, &second_data, true)?;
+            expected_grad.try_times_assign(grad)?;
+            first_ref.borrow_mut().add_gradient(&expected_grad)?;
+
+            let mut actual_grad = self
+                .function
+                .get_jacobian(&first_data, &second_data, false)?;
+            actual_grad.try_times_assign(grad)?;
+            second_ref.borrow_mut().add_gradient(&actual_grad)?;
         }
 
         self.base.reset_gradient();
+
+        Ok(())
     }
 
-    fn should_process_backprop(&self) -> bool {
-        self.base.should_process_backprop()
+    fn get_gradient(&self) -> DataContainer {
+        DataContainer::Empty
     }
 
+    fn set_retain_gradient(&mut self, _retain: bool) {}
+
     fn set_momentum(&mut self, _momentum: DataContainer) {
         println!("[LOSS] Unsupported Operation: Cannot set momentum of an operation node");
     }
@@ -165,6 +168,8 @@ impl<'a> Node<'a> for LossNode<'a> {
         println!("[LOSS] Unsupported Operation: Cannot set learning rate of an operation node");
     }
 
+    fn set_rate_scale(&mut self, _scale: f32) {}
+
     fn save_parameters(&self) -> LearnedParams {
         println!("[LOSS] Unsupported Operation: Cannot save parameters of an operation node");
         LearnedParams::null()
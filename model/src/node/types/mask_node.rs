@@ -5,6 +5,9 @@
 use crate::data::Data;
 // internal
 use crate::data::data_container::DataContainer;
+use crate::data::element_fn::Scale;
+use crate::data::error::DataError;
+use crate::data::precision::Precision;
 use crate::network::config_types::learned_params::LearnedParams;
 use crate::node::NodeType;
 use crate::node::{node_base::NodeBase, Node, NodeRef};
@@ -14,19 +17,33 @@ pub struct MaskNode<'a> {
     base: NodeBase<'a>,
     dim: Vec<usize>,
     mask_probability: f32,
+    precision: Precision,
     mode: NetworkMode,
+    held: bool,
 }
 
 impl<'a> MaskNode<'a> {
-    // input probability is the keep node probability
-    pub fn new(dim: Vec<usize>, probability: f32) -> MaskNode<'a> {
+    // input probability is the keep node probability, masks are drawn in the network's precision
+    pub fn new(dim: Vec<usize>, probability: f32, precision: Precision) -> MaskNode<'a> {
         return MaskNode {
             base: NodeBase::new(),
             dim,
             mask_probability: probability,
+            precision,
             mode: NetworkMode::None,
+            held: false,
         };
     }
+
+    fn draw_mask(&mut self) {
+        let mut data: Data = match self.precision {
+            Precision::F32 => Data::bernoulli_as::<f32>(self.mask_probability, &self.dim),
+            Precision::F64 => Data::bernoulli_as::<f64>(self.mask_probability, &self.dim),
+        };
+        data.apply_inplace(Scale(1.0 / f64::from(self.mask_probability)));
+
+        self.base.set_data(DataContainer::Parameter(data));
+    }
 }
 
 impl<'a> Node<'a> for MaskNode<'a> {
@@ -56,40 +73,49 @@ impl<'a> Node<'a> for MaskNode<'a> {
         self.base.get_data()
     }
 
-    fn apply_operation(&mut self) {
+    fn apply_operation(&mut self) -> Result<(), DataError> {
         match self.mode {
             NetworkMode::Inference => {
-                let mask: DataContainer = DataContainer::Parameter(Data::one());
-                self.base.set_data(mask);
+                let one: Data = match self.precision {
+                    Precision::F32 => Data::ScalarF32(1.0),
+                    Precision::F64 => Data::ScalarF64(1.0),
+                };
+                self.base.set_data(DataContainer::Parameter(one));
             }
             NetworkMode::Train => {
-                let mut data: Data = Data::bernoulli(self.mask_probability, &self.dim);
-                let scale: Data = Data::ScalarF32(1.0 / self.mask_probability);
-                data.times_assign(&scale);
-
-                let mask: DataContainer = DataContainer::Parameter(data);
-                self.base.set_data(mask);
+                self.draw_mask();
+            }
+            NetworkMode::GradCheck => {
+                if !self.held {
+                    self.draw_mask();
+                    self.held = true;
+                }
             }
             NetworkMode::None => {
                 panic!("Network mode is set to Mode::None, which shouldn't happen for either inference/train procedures");
             }
         }
+
+        Ok(())
     }
 
-    fn add_gradient(&mut self, _grad: &DataContainer) {
-        self.base.increment_grad_count();
+    fn add_gradient(&mut self, _grad: &DataContainer) -> Result<(), DataError> {
+        Ok(())
     }
 
-    fn apply_jacobian(&mut self) {
-        self.base.reset_grad_count();
+    fn apply_jacobian(&mut self) -> Result<(), DataError> {
+        Ok(())
     }
 
-    fn should_process_backprop(&self) -> bool {
-        self.base.should_process_backprop()
+    fn get_gradient(&self) -> DataContainer {
+        DataContainer::Empty
     }
 
+    fn set_retain_gradient(&mut self, _retain: bool) {}
+
     fn set_mode(&mut self, new_mode: NetworkMode) {
         self.mode = new_mode;
+        self.held = false;
     }
 
     fn set_momentum(&mut self, _momentum: DataContainer) {
@@ -100,6 +126,8 @@ impl<'a> Node<'a> for MaskNode<'a> {
         println!("[MASK] Unsupported Operation: Cannot set learning rate of a mask node");
     }
 
+    fn set_rate_scale(&mut self, _scale: f32) {}
+
     fn save_parameters(&self) -> LearnedParams {
         println!("[MASK] Unsupported Operation: Cannot save parameters of a mask node");
         LearnedParams::null()
@@ -109,20 +137,21 @@ impl<'a> Node<'a> for MaskNode<'a> {
 #[cfg(test)]
 mod tests {
     use crate::{
+        data::precision::Precision,
         node::{types::mask_node::MaskNode, Node},
         regularization::dropout::NetworkMode,
     };
 
     #[test]
     fn sampling_test() {
-        let mut mask = MaskNode::new(vec![10], 0.5);
+        let mut mask = MaskNode::new(vec![10], 0.5, Precision::F32);
         mask.set_mode(NetworkMode::Train);
-        mask.apply_operation();
+        mask.apply_operation().unwrap();
 
         println!("Training mask: {:?}", mask.get_data());
 
         mask.set_mode(NetworkMode::Inference);
-        mask.apply_operation();
+        mask.apply_operation().unwrap();
 
         println!("Inference mask: {:?}", mask.get_data());
     }
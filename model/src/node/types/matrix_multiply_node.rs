@@ -4,6 +4,7 @@
 
 // internal
 use crate::data::data_container::DataContainer;
+use crate::data::error::DataError;
 use crate::network::config_types::learned_params::LearnedParams;
 use crate::node::NodeType;
 use crate::node::{node_base::NodeBase, Node, NodeRef};
@@ -50,40 +51,35 @@ impl<'a> Node<'a> for MatrixMultiplyNode<'a> {
         self.base.get_data()
     }
 
-    fn apply_operation(&mut self) {
+    fn apply_operation(&mut self) -> Result<(), DataError> {
         if self.get_inputs().len() != 2 {
-            return;
+            return Ok(());
         }
 
         let inputs: Vec<NodeRef<'a>> = self.get_inputs().iter().cloned().collect();
 
-        for input in &inputs {
-            input.borrow_mut().apply_operation();
-        }
-
         let mut first_ref = inputs.get(0).unwrap().borrow_mut();
         let first_data = first_ref.get_data();
 
         let mut second_ref = inputs.get(1).unwrap().borrow_mut();
         let second_data = second_ref.get_data();
 
-        let res: DataContainer = first_data.matmul(&second_data);
+        let res: DataContainer = first_data.try_matmul(&second_data)?;
 
         self.base.set_data(res);
+
+        Ok(())
     }
 
     fn set_data(&mut self, _data: DataContainer) {
         println!("[MATMUL] Unsupported Operation: Cannot set data of an operation node");
     }
 
-    fn add_gradient(&mut self, grad: &DataContainer) {
-        self.base.increment_grad_count();
-        self.base.add_to_gradient(grad);
+    fn add_gradient(&mut self, grad: &DataContainer) -> Result<(), DataError> {
+        self.base.add_to_gradient(grad)
     }
 
-    fn apply_jacobian(&mut self) {
-        self.base.reset_grad_count();
-
+    fn apply_jacobian(&mut self) -> Result<(), DataError> {
         let inputs: Vec<NodeRef<'a>> = self.get_inputs().iter().cloned().collect();
 
         let mut first_ref = inputs.get(0).unwrap().borrow_mut();
@@ -93,27 +89,23 @@ impl<'a> Node<'a> for MatrixMultiplyNode<'a> {
         let second_data = second_ref.get_data();
         let grad = self.base.get_gradient();
 
-        let first_grad = grad.matmul(&second_data.transpose());
-        let second_grad = first_data.transpose().matmul(grad);
-
-        first_ref.add_gradient(&first_grad);
-        second_ref.add_gradient(&second_grad);
+        let first_grad = grad.try_matmul(&second_data.try_transpose()?)?;
+        let second_grad = first_data.try_transpose()?.try_matmul(grad)?;
 
-        if first_ref.should_process_backprop() {
-            first_ref.apply_jacobian();
-        }
-
-        if second_ref.should_process_backprop() {
-            second_ref.apply_jacobian();
-        }
+        first_ref.add_gradient(&first_grad)?;
+        second_ref.add_gradient(&second_grad)?;
 
         self.base.reset_gradient();
+
+        Ok(())
     }
 
-    fn should_process_backprop(&self) -> bool {
-        self.base.should_process_backprop()
+    fn get_gradient(&self) -> DataContainer {
+        DataContainer::Empty
     }
 
+    fn set_retain_gradient(&mut self, _retain: bool) {}
+
     fn set_momentum(&mut self, _momentum: DataContainer) {
         println!("[MATMUL] Unsupported Operation: Cannot set momentum of an operation node");
     }
@@ -122,6 +114,8 @@ impl<'a> Node<'a> for MatrixMultiplyNode<'a> {
         println!("[MATMUL] Unsupported Operation: Cannot set learning rate of an operation node");
     }
 
+    fn set_rate_scale(&mut self, _scale: f32) {}
+
     fn save_parameters(&self) -> LearnedParams {
         println!("[MATMUL] Unsupported Operation: Cannot save parameters of an operation node");
         LearnedParams::null()
@@ -5,13 +5,15 @@
 use crate::data::Data;
 // internal
 use crate::data::data_container::DataContainer;
+use crate::data::element_fn::{Inverse, InverseSqrt, Scale};
+use crate::data::error::DataError;
 use crate::network::config_types::batch_norm_params::NormParams;
 use crate::network::config_types::learned_params::LearnedParams;
 use crate::node::NodeType;
 use crate::node::{node_base::NodeBase, Node, NodeRef};
 use crate::regularization::dropout::NetworkMode;
 
-const DELTA: f32 = 1e-6;
+const DELTA: f64 = 1e-6;
 
 pub struct NormalizationNode<'a> {
     base: NodeBase<'a>,
@@ -58,54 +60,83 @@ impl<'a> NormalizationNode<'a> {
         }
     }
 
-    fn normalize_train(&mut self, mut data: DataContainer) {
+    fn normalize_train(
+        &mut self,
+        mut data: DataContainer,
+        update_running: bool,
+    ) -> Result<(), DataError> {
         self.batch_size = data.dim().0;
 
         let mean = data.average_batch();
-        let centered = data.minus(&mean);
+        let centered = data.try_minus(&mean)?;
         let variance: DataContainer = data.variance_batch();
 
-        let inverse_scale = variance.apply_elementwise(|f| 1.0 / f32::sqrt(f + DELTA));
-        data.minus_assign(&mean);
-        data.times_assign(&inverse_scale);
+        let inverse_scale = variance.apply_elementwise(InverseSqrt {
+            numerator: 1.0,
+            offset: DELTA,
+        });
+        data.try_minus_assign(&mean)?;
+        data.try_times_assign(&inverse_scale)?;
         self.base.set_data(data);
 
-        self.update_running_mean(&mean);
-        self.update_running_variance(&variance);
+        if update_running {
+            self.update_running_mean(&mean)?;
+            self.update_running_variance(&variance)?;
+        }
 
         self.variance = variance;
         self.mean = mean;
         self.centered = centered;
+
+        Ok(())
     }
 
-    fn update_running_mean(&mut self, mean: &DataContainer) {
+    fn update_running_mean(&mut self, mean: &DataContainer) -> Result<(), DataError> {
         if self.running_mean.dim() != mean.dim() {
-            self.running_mean = mean.apply_elementwise(|f| f * self.decay);
+            self.running_mean = mean.apply_elementwise(Scale(f64::from(self.decay)));
         } else {
-            self.running_mean.apply_inplace(|f| *f *= self.decay);
-            let mean_update = mean.apply_elementwise(|f| f * (1.0 - self.decay));
-            self.running_mean.sum_assign(&mean_update);
+            self.running_mean
+                .apply_inplace(Scale(f64::from(self.decay)));
+            let mean_update = mean.apply_elementwise(Scale(1.0 - f64::from(self.decay)));
+            self.running_mean.try_sum_assign(&mean_update)?;
         }
+
+        Ok(())
     }
 
-    fn update_running_variance(&mut self, variance: &DataContainer) {
+    fn update_running_variance(&mut self, variance: &DataContainer) -> Result<(), DataError> {
         if self.running_var.dim() != variance.dim() {
-            self.running_var = variance.apply_elementwise(|f| f * self.decay);
+            self.running_var = variance.apply_elementwise(Scale(f64::from(self.decay)));
         } else {
-            self.running_var.apply_inplace(|f| *f *= self.decay);
-            let var_update = variance.apply_elementwise(|f| f * (1.0 - self.decay));
-            self.running_var.sum_assign(&var_update);
+            self.running_var.apply_inplace(Scale(f64::from(self.decay)));
+            let var_update = variance.apply_elementwise(Scale(1.0 - f64::from(self.decay)));
+            self.running_var.try_sum_assign(&var_update)?;
         }
-    }
 
-    fn normalize_inference(&mut self, mut data: DataContainer) {
-        data.minus_assign(&self.running_mean);
-        let inverse_std_dev = self
-            .running_var
-            .apply_elementwise(|f| 1.0 / f32::sqrt(f + DELTA));
-        data.times_assign(&inverse_std_dev);
+        Ok(())
+    }
 
+    fn normalize_inference(&mut self, data: DataContainer) -> Result<(), DataError> {
+        let data =
+            NormalizationNode::normalize_running(data, &self.running_mean, &self.running_var)?;
         self.base.set_data(data);
+
+        Ok(())
+    }
+
+    pub(crate) fn normalize_running(
+        mut data: DataContainer,
+        running_mean: &DataContainer,
+        running_var: &DataContainer,
+    ) -> Result<DataContainer, DataError> {
+        data.try_minus_assign(running_mean)?;
+        let inverse_std_dev = running_var.apply_elementwise(InverseSqrt {
+            numerator: 1.0,
+            offset: DELTA,
+        });
+        data.try_times_assign(&inverse_std_dev)?;
+
+        Ok(data)
     }
 }
 
@@ -138,79 +169,77 @@ impl<'a> Node<'a> for NormalizationNode<'a> {
         self.base.get_data()
     }
 
-    fn apply_operation(&mut self) {
+    fn apply_operation(&mut self) -> Result<(), DataError> {
         if self.get_inputs().len() == 0 {
-            return;
+            return Ok(());
         }
 
         let inputs: Vec<NodeRef<'a>> = self.get_inputs().iter().cloned().collect();
 
-        for input in &inputs {
-            input.borrow_mut().apply_operation();
-        }
-
         let mut input_ref = inputs.get(0).unwrap().borrow_mut();
         let data = input_ref.get_data();
 
-        if self.mode == NetworkMode::Train {
-            self.normalize_train(data);
-        } else if self.mode == NetworkMode::Inference {
-            self.normalize_inference(data);
-        } else {
-            panic!("[NORMALIZE] Tried to run batch norm in NetworkMode::None!")
+        match self.mode {
+            NetworkMode::Train => self.normalize_train(data, true)?,
+            NetworkMode::GradCheck => self.normalize_train(data, false)?,
+            NetworkMode::Inference => self.normalize_inference(data)?,
+            NetworkMode::None => {
+                panic!("[NORMALIZE] Tried to run batch norm in NetworkMode::None!")
+            }
         }
+
+        Ok(())
     }
 
     fn set_data(&mut self, _data: DataContainer) {
         panic!("[NORMALIZE] Unsupported Operation: Cannot set data of an operation node");
     }
 
-    fn add_gradient(&mut self, grad: &DataContainer) {
-        self.base.increment_grad_count();
-        self.base.add_to_gradient(grad);
+    fn add_gradient(&mut self, grad: &DataContainer) -> Result<(), DataError> {
+        self.base.add_to_gradient(grad)
     }
 
-    fn apply_jacobian(&mut self) {
-        self.base.reset_grad_count();
+    fn apply_jacobian(&mut self) -> Result<(), DataError> {
         if self.base.get_inputs().len() == 0 {
             self.base.reset_gradient();
-            return;
+            return Ok(());
         }
         let input = self.base.get_inputs().get(0).unwrap();
 
         let grad = self.base.get_gradient();
-        let var_inv = self.variance.apply_elementwise(|f| 1.0 / (f + DELTA));
-        let std_inv = self
-            .variance
-            .apply_elementwise(|f| 1.0 / f32::sqrt(f + DELTA));
+        let var_inv = self.variance.apply_elementwise(Inverse { offset: DELTA });
+        let std_inv = self.variance.apply_elementwise(InverseSqrt {
+            numerator: 1.0,
+            offset: DELTA,
+        });
         let batch_size_inv =
             DataContainer::Parameter(Data::ScalarF32(1.0 / self.batch_size as f32));
 
-        let mut centered_grad_proj = self.centered.times(grad).sum_batch();
-        centered_grad_proj = centered_grad_proj.times(&self.centered);
-        centered_grad_proj.times_assign(&var_inv);
-        centered_grad_proj.times_assign(&batch_size_inv);
+        let mut centered_grad_proj = self.centered.try_times(grad)?.sum_batch();
+        centered_grad_proj = centered_grad_proj.try_times(&self.centered)?;
+        centered_grad_proj.try_times_assign(&var_inv)?;
+        centered_grad_proj.try_times_assign(&batch_size_inv)?;
 
         let mut grad_mean = grad.sum_batch();
-        grad_mean.times_assign(&batch_size_inv);
-
-        let mut input_grad = grad.minus(&grad_mean);
-        input_grad.minus_assign(&centered_grad_proj);
-        input_grad.times_assign(&std_inv);
+        grad_mean.try_times_assign(&batch_size_inv)?;
 
-        input.borrow_mut().add_gradient(&input_grad);
+        let mut input_grad = grad.try_minus(&grad_mean)?;
+        input_grad.try_minus_assign(&centered_grad_proj)?;
+        input_grad.try_times_assign(&std_inv)?;
 
-        if input.borrow().should_process_backprop() {
-            input.borrow_mut().apply_jacobian();
-        }
+        input.borrow_mut().add_gradient(&input_grad)?;
 
         self.base.reset_gradient();
+
+        Ok(())
     }
 
-    fn should_process_backprop(&self) -> bool {
-        self.base.should_process_backprop()
+    fn get_gradient(&self) -> DataContainer {
+        DataContainer::Empty
     }
 
+    fn set_retain_gradient(&mut self, _retain: bool) {}
+
     fn set_mode(&mut self, new_mode: NetworkMode) {
         self.mode = new_mode;
     }
@@ -225,6 +254,8 @@ impl<'a> Node<'a> for NormalizationNode<'a> {
         );
     }
 
+    fn set_rate_scale(&mut self, _scale: f32) {}
+
     fn save_parameters(&self) -> LearnedParams {
         let params = NormParams::new(&self.running_mean, &self.running_var, self.decay);
 
@@ -260,10 +291,10 @@ mod tests {
             Data::VectorF32(arr1(&[3.0, 1.0, 2.0, 3.0, 2.0])),
         ];
 
-        let batch: DataContainer = DataContainer::Batch(data);
+        let batch: DataContainer = DataContainer::batch(data);
         input.borrow_mut().set_data(batch);
 
-        norm.borrow_mut().apply_operation();
+        norm.borrow_mut().apply_operation().unwrap();
 
         let output: DataContainer = norm.borrow_mut().get_data();
         println!("Normalized Output {:?}", output);
@@ -274,10 +305,10 @@ mod tests {
             Data::VectorF32(arr1(&[6.0, 2.0, 4.0, 6.0, 4.0])),
         ];
 
-        let batch2: DataContainer = DataContainer::Batch(data2);
+        let batch2: DataContainer = DataContainer::batch(data2);
         input.borrow_mut().set_data(batch2);
 
-        norm.borrow_mut().apply_operation();
+        norm.borrow_mut().apply_operation().unwrap();
 
         let output2: DataContainer = norm.borrow_mut().get_data();
         println!("Normalized Output 2 {:?}", output2);
@@ -288,7 +319,7 @@ mod tests {
             DataContainer::Inference(Data::VectorF32(arr1(&[1.0, 3.0, 1.0, 2.0, 3.0])));
         input.borrow_mut().set_data(inference);
 
-        norm.borrow_mut().apply_operation();
+        norm.borrow_mut().apply_operation().unwrap();
 
         let inference_output: DataContainer = norm.borrow_mut().get_data();
         println!("Inference Normalized Output {:?}", inference_output);
@@ -1,12 +1,12 @@
 // builtin
 
-use core::f32;
-
 // external
-use ndarray::Array2;
+use ndarray::{Array2, ArrayViewD, Axis};
 
 // internal
 use crate::data::data_container::DataContainer;
+use crate::data::error::DataError;
+use crate::data::precision::{Element, Precision};
 use crate::data::Data;
 use crate::network::config_types::learned_params::LearnedParams;
 use crate::node::NodeType;
@@ -24,55 +24,86 @@ impl<'a> SoftmaxNode<'a> {
         }
     }
 
-    fn epsilon() -> f32 {
-        1e-7
+    fn epsilon<T: Element>() -> T {
+        T::from_f64(1e-7)
     }
 
-    fn softmax(data: Data) -> Data {
-        if let Data::VectorF32(mut vec) = data {
-            let max = vec.iter().cloned().fold(f32::NEG_INFINITY, f32::max);
-            vec.mapv_inplace(|f| f32::exp(f - max));
-
-            let mut sum = vec.sum();
-            if sum <= 0.0 {
-                sum = SoftmaxNode::epsilon();
-            }
-
-            vec.mapv_inplace(|f| f / sum);
+    // Normalizes along the last axis, so a stacked batch is handled in one pass
+    pub(crate) fn softmax(data: Data) -> Data {
+        let res = match data.precision() {
+            Precision::F32 => data
+                .view_array_d::<f32>()
+                .and_then(SoftmaxNode::softmax_tensor),
+            Precision::F64 => data
+                .view_array_d::<f64>()
+                .and_then(SoftmaxNode::softmax_tensor),
+        };
 
-            Data::VectorF32(vec)
-        } else {
+        res.unwrap_or_else(|| {
             println!(
-                "[SOFTMAX] Invalid data type. Expected Data::VectorF32 but got {}",
+                "[SOFTMAX] Invalid data type. Expected a vector or batch of vectors but got {}",
                 data.variant_name()
             );
             Data::None
-        }
+        })
     }
 
-    fn softmax_jacobian(softmax: Data) -> Data {
-        if let Data::VectorF32(vec) = softmax {
-            let n = vec.len();
-
-            let mut jacobian: Array2<f32> = Array2::zeros((n, n));
-            for i in 0..n {
-                for j in 0..n {
-                    if i == j {
-                        jacobian[[i, j]] = vec[i] * (1.0 - vec[j]);
-                    } else {
-                        jacobian[[i, j]] = -vec[i] * vec[j];
-                    }
-                }
+    fn softmax_tensor<T: Element>(tensor: ArrayViewD<T>) -> Option<Data> {
+        if tensor.ndim() == 0 {
+            return None;
+        }
+        let axis = Axis(tensor.ndim() - 1);
+
+        let max = tensor
+            .fold_axis(axis, T::neg_infinity(), |acc, f| acc.max(*f))
+            .insert_axis(axis);
+        let mut exp = &tensor - &max;
+        exp.mapv_inplace(T::exp);
+
+        let mut sum = exp.sum_axis(axis).insert_axis(axis);
+        sum.mapv_inplace(|f| {
+            if f <= T::zero() {
+                SoftmaxNode::epsilon()
+            } else {
+                f
             }
+        });
 
-            Data::MatrixF32(jacobian)
-        } else {
+        Some(Data::from_array_d(exp / &sum))
+    }
+
+    // One [n x n] jacobian per softmax vector, stacked the same way as the vectors
+    fn softmax_jacobian(softmax: Data) -> Data {
+        let res = match softmax.precision() {
+            Precision::F32 => softmax
+                .view_array_d::<f32>()
+                .and_then(SoftmaxNode::jacobian_tensor),
+            Precision::F64 => softmax
+                .view_array_d::<f64>()
+                .and_then(SoftmaxNode::jacobian_tensor),
+        };
+
+        res.unwrap_or_else(|| {
             println!(
-                "[SOFTMAX] Invalid data type. Expected Data::VectorF32 but got {}",
+                "[SOFTMAX] Invalid data type. Expected a vector or batch of vectors but got {}",
                 softmax.variant_name()
             );
             Data::None
+        })
+    }
+
+    // J_ij = s_i * (d_ij - s_j)
+    fn jacobian_tensor<T: Element>(tensor: ArrayViewD<T>) -> Option<Data> {
+        let rank = tensor.ndim();
+        if rank == 0 {
+            return None;
         }
+
+        let identity = Array2::<T>::eye(tensor.shape()[rank - 1]).into_dyn();
+        let column = tensor.clone().insert_axis(Axis(rank));
+        let row = tensor.insert_axis(Axis(rank - 1));
+
+        Some(Data::from_array_d(&column * &(&identity - &row)))
     }
 }
 
@@ -105,18 +136,14 @@ impl<'a> Node<'a> for SoftmaxNode<'a> {
         self.base.get_data()
     }
 
-    fn apply_operation(&mut self) {
+    fn apply_operation(&mut self) -> Result<(), DataError> {
         if self.get_inputs().len() == 0 {
             println!("[SOFTMAX] Tried to apply operation on no inputs");
-            return;
+            return Ok(());
         }
 
         let inputs: Vec<NodeRef<'a>> = self.get_inputs().iter().cloned().collect();
 
-        for input in &inputs {
-            input.borrow_mut().apply_operation();
-        }
-
         let mut input_ref = inputs.get(0).unwrap().borrow_mut();
 
         let data = input_ref.get_data();
@@ -124,43 +151,43 @@ impl<'a> Node<'a> for SoftmaxNode<'a> {
         let res = data.apply_function(SoftmaxNode::softmax);
 
         self.base.set_data(res);
+
+        Ok(())
     }
 
     fn set_data(&mut self, _data: DataContainer) {
         panic!("[SOFTMAX] Unsupported Operation: Cannot set data of an operation node");
     }
 
-    fn add_gradient(&mut self, grad: &DataContainer) {
-        self.base.increment_grad_count();
-        self.base.add_to_gradient(grad);
+    fn add_gradient(&mut self, grad: &DataContainer) -> Result<(), DataError> {
+        self.base.add_to_gradient(grad)
     }
 
-    fn apply_jacobian(&mut self) {
-        self.base.reset_grad_count();
+    fn apply_jacobian(&mut self) -> Result<(), DataError> {
         let data = self.get_data();
 
         if self.base.get_inputs().len() == 0 {
-            return;
+            return Ok(());
         }
 
         let node = self.base.get_inputs().get(0).unwrap();
 
         let jacobian = data.apply_function(SoftmaxNode::softmax_jacobian);
-        let grad = jacobian.matmul(self.base.get_gradient());
+        let grad = jacobian.try_matmul(self.base.get_gradient())?;
 
-        node.borrow_mut().add_gradient(&grad);
-
-        if node.borrow().should_process_backprop() {
-            node.borrow_mut().apply_jacobian();
-        }
+        node.borrow_mut().add_gradient(&grad)?;
 
         self.base.reset_gradient();
+
+        Ok(())
     }
 
-    fn should_process_backprop(&self) -> bool {
-        self.base.should_process_backprop()
+    fn get_gradient(&self) -> DataContainer {
+        DataContainer::Empty
     }
 
+    fn set_retain_gradient(&mut self, _retain: bool) {}
+
     fn set_momentum(&mut self, _momentum: DataContainer) {
         println!("[SOFTMAX] Unsupported Operation: Cannot set momentum of an operation node");
     }
@@ -169,6 +196,8 @@ impl<'a> Node<'a> for SoftmaxNode<'a> {
         println!("[SOFTMAX] Unsupported Operation: Cannot set learning rate of an operation node");
     }
 
+    fn set_rate_scale(&mut self, _scale: f32) {}
+
     fn save_parameters(&self) -> LearnedParams {
         println!("[SOFTMAX] Unsupported Operation: Cannot save parameters of an operation node");
         LearnedParams::null()
@@ -176,3 +205,43 @@ impl<'a> Node<'a> for SoftmaxNode<'a> {
 
     fn set_mode(&mut self, _new_mode: NetworkMode) {}
 }
+
+#[cfg(test)]
+mod tests {
+    use ndarray::arr1;
+
+    use crate::{
+        data::{data_container::DataContainer, Data},
+        node::types::softmax_node::SoftmaxNode,
+    };
+
+    #[test]
+    fn batched_softmax_test() {
+        let examples: Vec<Data> = vec![
+            Data::VectorF32(arr1(&[1.0, 2.0, 3.0])),
+            Data::VectorF32(arr1(&[-1.0, 0.0, 4.0])),
+        ];
+
+        let batch = DataContainer::batch(examples.clone()).apply_function(SoftmaxNode::softmax);
+        let jacobian = batch.clone().apply_function(SoftmaxNode::softmax_jacobian);
+        assert_eq!(jacobian.dim(), (2, &[3, 3][..]));
+
+        for (i, example) in examples.iter().enumerate() {
+            let values: Vec<f32> = example.flatten_as::<f32>();
+            let exp: Vec<f32> = values.iter().map(|f| f.exp()).collect();
+            let sum: f32 = exp.iter().sum();
+            let softmax: Vec<f32> = exp.iter().map(|f| f / sum).collect();
+
+            let res: Vec<f32> = batch.batch_to_vec()[i].flatten_as::<f32>();
+            let res_jacobian: Vec<f32> = jacobian.batch_to_vec()[i].flatten_as::<f32>();
+            for row in 0..3 {
+                assert!((res[row] - softmax[row]).abs() < 1e-6);
+                for col in 0..3 {
+                    let delta: f32 = if row == col { 1.0 } else { 0.0 };
+                    let expected: f32 = softmax[row] * (delta - softmax[col]);
+                    assert!((res_jacobian[row * 3 + col] - expected).abs() < 1e-6);
+                }
+            }
+        }
+    }
+}
@@ -4,7 +4,7 @@
 
 // internal
 use crate::{
-    data::{data_container::DataContainer, Data},
+    data::{data_container::DataContainer, error::DataError, Data},
     network::config_types::learned_params::LearnedParams,
     node::{node_base::NodeBase, Node, NodeRef, NodeType},
     regularization::dropout::NetworkMode,
@@ -55,56 +55,53 @@ impl<'a> Node<'a> for SquareNode<'a> {
         self.base.get_data()
     }
 
-    fn apply_operation(&mut self) {
+    fn apply_operation(&mut self) -> Result<(), DataError> {
         let inputs = self.base.get_inputs();
 
-        for input in inputs {
-            input.borrow_mut().apply_operation();
-        }
-
         let input_ref = inputs.get(0).unwrap();
         let input_data = input_ref.borrow_mut().get_data();
 
-        let res = input_data.apply_function_ref(|data| data.times(data));
+        let res = input_data.try_times(&input_data)?;
 
         self.base.set_data(res);
+
+        Ok(())
     }
 
-    fn add_gradient(&mut self, grad: &DataContainer) {
-        self.base.increment_grad_count();
-        self.base.add_to_gradient(grad);
+    fn add_gradient(&mut self, grad: &DataContainer) -> Result<(), DataError> {
+        self.base.add_to_gradient(grad)
     }
 
-    fn apply_jacobian(&mut self) {
-        self.base.reset_grad_count();
+    fn apply_jacobian(&mut self) -> Result<(), DataError> {
         if self.base.get_inputs().len() == 0 {
             self.base.reset_gradient();
-            return;
+            return Ok(());
         }
 
         let scale = DataContainer::Parameter(Data::ScalarF32(2.0));
         let input = self.base.get_inputs().get(0).unwrap();
 
         let mut grad = input.borrow_mut().get_data();
-        grad.times_assign(&scale);
+        grad.try_times_assign(&scale)?;
         let prev_grad = self.base.get_gradient();
 
-        grad.times_assign(&prev_grad);
+        // Parameters can't be grown in place to a batched gradient
+        let grad = grad.try_times(prev_grad)?;
         for node in self.base.get_inputs() {
-            node.borrow_mut().add_gradient(&grad);
-
-            if node.borrow().should_process_backprop() {
-                node.borrow_mut().apply_jacobian();
-            }
+            node.borrow_mut().add_gradient(&grad)?;
         }
 
         self.base.reset_gradient();
+
+        Ok(())
     }
 
-    fn should_process_backprop(&self) -> bool {
-        self.base.should_process_backprop()
+    fn get_gradient(&self) -> DataContainer {
+        DataContainer::Empty
     }
 
+    fn set_retain_gradient(&mut self, _retain: bool) {}
+
     fn set_momentum(&mut self, _momentum: DataContainer) {
         println!("[SQUARE] Unsupported Operation: Cannot set momentum of an operation node");
     }
@@ -113,6 +110,8 @@ impl<'a> Node<'a> for SquareNode<'a> {
         println!("[SQUARE] Unsupported Operation: Cannot set learning rate of an operation node");
     }
 
+    fn set_rate_scale(&mut self, _scale: f32) {}
+
     fn save_parameters(&self) -> LearnedParams {
         println!("[SQUARE] Unsupported Operation: Cannot save parameters of an operation node");
         LearnedParams::null()
@@ -8,6 +8,7 @@ use ndarray_rand::RandomExt;
 
 // internal
 use crate::data::data_container::DataContainer;
+use crate::data::error::DataError;
 use crate::data::Data;
 use crate::network::config_types::layer_params::LayerParams;
 use crate::network::config_types::learned_params::LearnedParams;
@@ -17,6 +18,7 @@ use crate::node::NodeType;
 use crate::node::{node_base::NodeBase, Node, NodeRef};
 use crate::optimization::learning_decay::LearningDecayType;
 use crate::optimization::momentum::DescentType;
+use crate::random::with_rng;
 use crate::regularization::dropout::NetworkMode;
 
 pub struct WeightNode<'a> {
@@ -52,8 +54,9 @@ impl<'a> WeightNode<'a> {
 
     fn get_initial_weights_matrix(input_size: usize, output_size: usize) -> DataContainer {
         let scale = f32::sqrt(6.0 / (input_size + output_size) as f32);
-        let initial_weights: Array2<f32> =
-            Array2::random((output_size, input_size), Uniform::new(-scale, scale));
+        let initial_weights: Array2<f32> = with_rng(|rng| {
+            Array2::random_using((output_size, input_size), Uniform::new(-scale, scale), rng)
+        });
         DataContainer::Parameter(Data::MatrixF32(initial_weights))
     }
 
@@ -80,7 +83,8 @@ impl<'a> WeightNode<'a> {
 
     fn get_initial_weights_vec(size: usize) -> DataContainer {
         let scale = f32::sqrt(6.0 / size as f32);
-        let initial_weights: Array1<f32> = Array1::random(size, Uniform::new(-scale, scale));
+        let initial_weights: Array1<f32> =
+            with_rng(|rng| Array1::random_using(size, Uniform::new(-scale, scale), rng));
         DataContainer::Parameter(Data::VectorF32(initial_weights))
     }
 }
@@ -105,66 +109,75 @@ impl<'a> Node<'a> for WeightNode<'a> {
     }
 
     fn set_data(&mut self, input: DataContainer) {
-        if let DataContainer::Parameter(data) = input {
-            match data {
-                Data::VectorF32(vec) => {
-                    let check: bool = self.dim.len() == 1 && vec.dim() == self.dim[0];
-                    if check {
-                        let container = DataContainer::Parameter(Data::VectorF32(vec));
-                        self.base.set_data(container);
-                        return;
-                    }
-                }
-                Data::MatrixF32(matrix) => {
-                    let check: bool = self.dim.len() == 2
-                        && matrix.dim().0 == self.dim[0]
-                        && matrix.dim().1 == self.dim[1];
-                    if check {
-                        let container = DataContainer::Parameter(Data::MatrixF32(matrix));
-                        self.base.set_data(container);
-                        return;
-                    }
-                }
-                _ => {
-                    println!("[WEIGHT] type or dimension mismatch, skipping reassignment");
-                }
+        if let DataContainer::Parameter(data) = &input {
+            let check: bool =
+                (1..=2).contains(&self.dim.len()) && data.shape() == Some(self.dim.as_slice());
+            if check {
+                self.base.set_data(input);
+                return;
             }
         }
+        println!("[WEIGHT] type or dimension mismatch, skipping reassignment");
     }
 
     fn get_data(&mut self) -> DataContainer {
         let mut data = self.base.get_data();
-        self.momentum_base.alter_data(&mut data);
+        // Gradient checks perturb the stored parameters, so the nesterov lookahead is skipped
+        if !self.base.is_gradient_retained() {
+            self.momentum_base.alter_data(&mut data);
+        }
 
         data
     }
 
-    fn apply_operation(&mut self) {}
+    fn apply_operation(&mut self) -> Result<(), DataError> {
+        Ok(())
+    }
 
-    fn add_gradient(&mut self, grad: &DataContainer) {
-        self.base.increment_grad_count();
-        self.base.add_to_gradient(grad);
+    fn add_gradient(&mut self, grad: &DataContainer) -> Result<(), DataError> {
+        self.base.add_to_gradient(grad)
     }
 
-    fn apply_jacobian(&mut self) {
-        self.base.reset_grad_count();
+    fn apply_jacobian(&mut self) -> Result<(), DataError> {
+        if self.base.is_gradient_retained() {
+            return Ok(());
+        }
 
         let mut update: DataContainer = self.base.get_gradient().average_batch();
         self.learning_base.update_learning_rate(&update);
-        self.learning_base.scale_update(&mut update);
 
-        if self.momentum_base.is_momentum_update() {
+        if self.momentum_base.is_moment_estimate() {
+            let time_step: usize = self.learning_base.get_time_step();
+            let mut moment: DataContainer =
+                self.momentum_base.get_moment_estimate(&update, time_step);
+            self.learning_base.scale_update(&mut moment);
+            if let Some(decay) = self
+                .learning_base
+                .get_decoupled_decay(&self.base.get_data())
+            {
+                moment.try_sum_assign(&decay)?;
+            }
+            self.base.update_gradient(&moment)?;
+        } else if self.momentum_base.is_momentum_update() {
+            self.learning_base.scale_update(&mut update);
             let momentum_update: &DataContainer = self.momentum_base.get_momentum_update(&update);
-            self.base.update_gradient(momentum_update);
+            self.base.update_gradient(momentum_update)?;
         } else {
-            self.base.update_gradient(&update);
+            self.learning_base.scale_update(&mut update);
+            self.base.update_gradient(&update)?;
         }
 
         self.base.reset_gradient();
+
+        Ok(())
     }
 
-    fn should_process_backprop(&self) -> bool {
-        self.base.should_process_backprop()
+    fn get_gradient(&self) -> DataContainer {
+        self.base.get_retained_gradient()
+    }
+
+    fn set_retain_gradient(&mut self, retain: bool) {
+        self.base.set_retain_gradient(retain);
     }
 
     fn set_momentum(&mut self, momentum: DataContainer) {
@@ -175,6 +188,15 @@ impl<'a> Node<'a> for WeightNode<'a> {
         self.learning_base.set_learning_rate(learning_rate);
     }
 
+    fn set_update_accumulator(&mut self, update_accumulator: DataContainer) {
+        self.learning_base
+            .set_update_accumulator(update_accumulator);
+    }
+
+    fn set_rate_scale(&mut self, scale: f32) {
+        self.learning_base.set_rate_scale(scale);
+    }
+
     fn save_parameters(&self) -> LearnedParams {
         let container = self.base.get_data();
 
@@ -183,13 +205,13 @@ impl<'a> Node<'a> for WeightNode<'a> {
             let momentum = self.momentum_base.get_momentum_save();
             let learning_rate = self.learning_base.get_learning_rate_save();
 
-            let parameters: Vec<f32> = match data {
-                Data::VectorF32(vec) => vec.to_vec(),
-                Data::MatrixF32(matrix) => matrix.flatten().to_vec(),
+            let parameters: Vec<f64> = match data.shape() {
+                Some(shape) if (1..=2).contains(&shape.len()) => data.flatten_as(),
                 _ => panic!("[WEIGHT] Unexpected data type for weights!"),
             };
 
-            let params = LayerParams::new(dim, parameters, momentum, learning_rate);
+            let params =
+                LayerParams::new(dim, parameters, data.precision(), momentum, learning_rate);
             return LearnedParams::new_layer(params);
         }
 
@@ -5,4 +5,5 @@
 // internal
 pub mod batch_norm;
 pub mod learning_decay;
+pub mod learning_schedule;
 pub mod momentum;
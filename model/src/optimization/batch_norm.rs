@@ -89,6 +89,7 @@ impl<'a> BatchNormModule<'a> {
         let parameters = params.get_parameters();
         let momentum = params.get_momentum();
         let learning_rate = params.get_learning_rate();
+        let update_accumulator = params.get_update_accumulator();
 
         node.borrow_mut().set_data(parameters);
         if !matches!(&momentum, DataContainer::Empty) {
@@ -97,6 +98,9 @@ impl<'a> BatchNormModule<'a> {
         if !matches!(&learning_rate, DataContainer::Empty) {
             node.borrow_mut().set_learning_rate(learning_rate);
         }
+        if !matches!(&update_accumulator, DataContainer::Empty) {
+            node.borrow_mut().set_update_accumulator(update_accumulator);
+        }
     }
 }
 
@@ -112,6 +116,7 @@ mod tests {
     use crate::node::types::loss_node::LossNode;
     use crate::node::types::multiply_node::MultiplyNode;
     use crate::node::{
+        graph::Graph,
         types::{
             bias_node::BiasNode, normalization_node::NormalizationNode, weight_node::WeightNode,
         },
@@ -159,6 +164,9 @@ mod tests {
 
         norm_ref.borrow_mut().set_mode(NetworkMode::Train);
 
+        let graph: Graph = Graph::new(&loss_ref).unwrap();
+        let inference_graph: Graph = graph.subgraph(&add_ref).unwrap();
+
         let data: Vec<Data> = vec![
             Data::VectorF32(arr1(&[1.0, 3.0, 1.0, 2.0, 3.0])),
             Data::VectorF32(arr1(&[2.0, 2.0, 3.0, 1.0, 1.0])),
@@ -172,22 +180,20 @@ mod tests {
         ];
 
         for _i in 1..100 {
-            let batch_input: DataContainer = DataContainer::Batch(data.clone());
+            let batch_input: DataContainer = DataContainer::batch(data.clone());
             input_ref.borrow_mut().set_data(batch_input);
-            let batch_response: DataContainer = DataContainer::Batch(response.clone());
+            let batch_response: DataContainer = DataContainer::batch(response.clone());
             response_ref.borrow_mut().set_data(batch_response);
 
-            loss_ref.borrow_mut().apply_operation();
-
-            loss_ref.borrow_mut().add_gradient(&DataContainer::one());
-            loss_ref.borrow_mut().apply_jacobian();
+            graph.forward().unwrap();
+            graph.backward(&DataContainer::one()).unwrap();
         }
 
         norm_ref.borrow_mut().set_mode(NetworkMode::Inference);
         let inference: DataContainer =
             DataContainer::Inference(Data::VectorF32(arr1(&[1.0, 3.0, 1.0, 2.0, 3.0])));
         input_ref.borrow_mut().set_data(inference);
-        add_ref.borrow_mut().apply_operation();
+        inference_graph.forward().unwrap();
 
         let output: DataContainer = add_ref.borrow_mut().get_data();
         println!("Output {:?}", output);
@@ -4,9 +4,15 @@
 use serde::{Deserialize, Serialize};
 
 // internal
-use crate::data::{data_container::DataContainer, Data};
+use crate::data::{
+    data_container::DataContainer,
+    element_fn::{ElementFn, Fill, InverseSqrt, Scale, ScaledSquare, Sqrt},
+    precision::Element,
+    Data,
+};
 
-const DELTA: f32 = 1e-6;
+const DELTA: f64 = 1e-6;
+const ADAM_EPSILON: f64 = 1e-8;
 
 #[derive(Serialize, Deserialize, Clone)]
 pub enum LearningDecayType {
@@ -18,6 +24,20 @@ pub enum LearningDecayType {
         global_rate: f32,
         decay_rate: f32,
     },
+    AdaGrad {
+        global_rate: f32,
+    },
+    // Needs no global rate, the step size comes from the running average of squared updates
+    AdaDelta {
+        decay_rate: f32,
+    },
+    // Second moment half of Adam, pair it with DescentType::Adam for the first moment
+    Adam {
+        global_rate: f32,
+        beta2: f32,
+        weight_decay: f32,
+        time: usize,
+    },
     LinearSchedule {
         start_rate: f32,
         end_rate: f32,
@@ -57,22 +77,87 @@ impl LearningDecayType {
         }
     }
 
+    pub fn ada_grad(global_rate: f32) -> LearningDecayType {
+        LearningDecayType::AdaGrad { global_rate }
+    }
+
+    pub fn ada_delta(decay_rate: f32) -> LearningDecayType {
+        LearningDecayType::AdaDelta { decay_rate }
+    }
+
+    pub fn adam(global_rate: f32, beta2: f32) -> LearningDecayType {
+        LearningDecayType::adam_w(global_rate, beta2, 0.0)
+    }
+
+    // Weight decay is decoupled from the gradient and applied straight to the weights
+    pub fn adam_w(global_rate: f32, beta2: f32, weight_decay: f32) -> LearningDecayType {
+        LearningDecayType::Adam {
+            global_rate,
+            beta2,
+            weight_decay,
+            time: 0,
+        }
+    }
+
     pub fn is_adaptive(&self) -> bool {
         match self {
             LearningDecayType::Exponential { .. } => false,
             LearningDecayType::RMSProp { .. } => true,
+            LearningDecayType::AdaGrad { .. } => true,
+            LearningDecayType::AdaDelta { .. } => true,
+            LearningDecayType::Adam { .. } => true,
             LearningDecayType::None { .. } => false,
             LearningDecayType::LinearSchedule { .. } => false,
         }
     }
 
+    // Only AdaDelta keeps a second accumulator, holding the squared updates
+    pub fn has_update_accumulator(&self) -> bool {
+        matches!(self, LearningDecayType::AdaDelta { .. })
+    }
+
     pub fn get_initial_timestep(&self) -> usize {
         match self {
             LearningDecayType::LinearSchedule { time, .. } => *time,
+            LearningDecayType::Adam { time, .. } => *time,
             _ => 0,
         }
     }
 
+    // The rate each decay type starts from or scales by, AdaDelta has none so it's left at one
+    pub fn get_global_rate(&self) -> f32 {
+        match self {
+            LearningDecayType::Exponential { initial_rate, .. } => *initial_rate,
+            LearningDecayType::RMSProp { global_rate, .. } => *global_rate,
+            LearningDecayType::AdaGrad { global_rate } => *global_rate,
+            LearningDecayType::AdaDelta { .. } => 1.0,
+            LearningDecayType::Adam { global_rate, .. } => *global_rate,
+            LearningDecayType::LinearSchedule { start_rate, .. } => *start_rate,
+            LearningDecayType::None { rate } => *rate,
+        }
+    }
+
+    // Global rate of the update at `time_step`, counted from one like the nodes count their
+    // updates. Adaptive types report the global rate their per-parameter rates are scaled by
+    pub fn get_rate(&self, time_step: usize) -> f32 {
+        match self {
+            LearningDecayType::Exponential {
+                initial_rate,
+                decay_rate,
+            } => *initial_rate * f32::powi(*decay_rate, time_step as i32),
+            LearningDecayType::LinearSchedule {
+                start_rate,
+                end_rate,
+                end_time,
+                ..
+            } => {
+                let percent = (time_step as f32) / (*end_time as f32);
+                (1.0 - percent) * *start_rate + percent * *end_rate
+            }
+            _ => self.get_global_rate(),
+        }
+    }
+
     pub fn get_initial_rate(&self) -> DataContainer {
         match self {
             LearningDecayType::Exponential { initial_rate, .. } => {
@@ -90,24 +175,18 @@ impl LearningDecayType {
                 DataContainer::Parameter(Data::ScalarF32(rate))
             }
             LearningDecayType::RMSProp { .. } => DataContainer::one(),
+            LearningDecayType::AdaGrad { .. } => DataContainer::one(),
+            LearningDecayType::AdaDelta { .. } => DataContainer::one(),
+            LearningDecayType::Adam { .. } => DataContainer::one(),
             LearningDecayType::None { rate } => DataContainer::Parameter(Data::ScalarF32(*rate)),
         }
     }
 
     pub fn update_timestep(&mut self, time_step: usize) {
-        if let LearningDecayType::LinearSchedule {
-            start_rate,
-            end_rate,
-            end_time,
-            ..
-        } = self
-        {
-            *self = LearningDecayType::LinearSchedule {
-                start_rate: *start_rate,
-                end_rate: *end_rate,
-                end_time: *end_time,
-                time: time_step,
-            }
+        match self {
+            LearningDecayType::LinearSchedule { time, .. } => *time = time_step,
+            LearningDecayType::Adam { time, .. } => *time = time_step,
+            _ => {}
         }
     }
 
@@ -115,7 +194,7 @@ impl LearningDecayType {
         self.update_timestep(time_step);
         match &self {
             LearningDecayType::Exponential { decay_rate, .. } => {
-                learning_rate.apply_inplace(|f| *f *= decay_rate);
+                learning_rate.apply_inplace(Scale(f64::from(*decay_rate)));
             }
             LearningDecayType::LinearSchedule {
                 start_rate,
@@ -126,7 +205,7 @@ impl LearningDecayType {
                 let percent = (*time as f32) / (*end_time as f32);
                 let rate = (1.0 - percent) * *start_rate + percent * *end_rate;
 
-                learning_rate.apply_inplace(|f| *f = rate);
+                learning_rate.apply_inplace(Fill(f64::from(rate)));
             }
             LearningDecayType::None { .. } => {}
             _ => {
@@ -145,8 +224,24 @@ impl LearningDecayType {
         match &self {
             LearningDecayType::RMSProp { decay_rate, .. } => {
                 let learning_update: DataContainer =
-                    gradient.apply_elementwise(|f| (1.0 - decay_rate) * f32::powi(f, 2));
-                accumulator.apply_inplace(|f| *f *= decay_rate);
+                    gradient.apply_elementwise(ScaledSquare(1.0 - f64::from(*decay_rate)));
+                accumulator.apply_inplace(Scale(f64::from(*decay_rate)));
+                accumulator.sum_assign(&learning_update);
+            }
+            LearningDecayType::AdaGrad { .. } => {
+                let learning_update: DataContainer = gradient.apply_elementwise(ScaledSquare(1.0));
+                accumulator.sum_assign(&learning_update);
+            }
+            LearningDecayType::AdaDelta { decay_rate } => {
+                let learning_update: DataContainer =
+                    gradient.apply_elementwise(ScaledSquare(1.0 - f64::from(*decay_rate)));
+                accumulator.apply_inplace(Scale(f64::from(*decay_rate)));
+                accumulator.sum_assign(&learning_update);
+            }
+            LearningDecayType::Adam { beta2, .. } => {
+                let learning_update: DataContainer =
+                    gradient.apply_elementwise(ScaledSquare(1.0 - f64::from(*beta2)));
+                accumulator.apply_inplace(Scale(f64::from(*beta2)));
                 accumulator.sum_assign(&learning_update);
             }
             _ => {
@@ -155,10 +250,53 @@ impl LearningDecayType {
         }
     }
 
-    pub fn scale_adaptive(&self, update: &mut DataContainer, accumulator: &DataContainer) {
+    pub fn scale_adaptive(
+        &self,
+        update: &mut DataContainer,
+        accumulator: &DataContainer,
+        update_accumulator: &mut DataContainer,
+        time_step: usize,
+    ) {
         match self {
             LearningDecayType::RMSProp { global_rate, .. } => {
-                let scale = accumulator.apply_elementwise(|f| *global_rate / f32::sqrt(DELTA + f));
+                let scale = accumulator.apply_elementwise(InverseSqrt {
+                    numerator: f64::from(*global_rate),
+                    offset: DELTA,
+                });
+
+                update.times_assign(&scale);
+            }
+            LearningDecayType::AdaGrad { global_rate } => {
+                let scale = accumulator.apply_elementwise(InverseSqrt {
+                    numerator: f64::from(*global_rate),
+                    offset: DELTA,
+                });
+
+                update.times_assign(&scale);
+            }
+            LearningDecayType::AdaDelta { decay_rate } => {
+                let mut scale = update_accumulator.apply_elementwise(Sqrt { offset: DELTA });
+                let inverse_rms = accumulator.apply_elementwise(InverseSqrt {
+                    numerator: 1.0,
+                    offset: DELTA,
+                });
+                scale.times_assign(&inverse_rms);
+
+                update.times_assign(&scale);
+
+                let delta_update: DataContainer =
+                    update.apply_elementwise(ScaledSquare(1.0 - f64::from(*decay_rate)));
+                update_accumulator.apply_inplace(Scale(f64::from(*decay_rate)));
+                update_accumulator.sum_assign(&delta_update);
+            }
+            LearningDecayType::Adam {
+                global_rate, beta2, ..
+            } => {
+                let correction = 1.0 - f64::powi(f64::from(*beta2), time_step as i32);
+                let scale = accumulator.apply_elementwise(AdamScale {
+                    global_rate: f64::from(*global_rate),
+                    correction,
+                });
 
                 update.times_assign(&scale);
             }
@@ -169,27 +307,63 @@ impl LearningDecayType {
             }
         }
     }
+
+    // AdamW shrinks the weights by the global rate times the decay, independent of the gradient
+    pub fn decoupled_decay(&self, weights: &DataContainer) -> Option<DataContainer> {
+        match self {
+            LearningDecayType::Adam {
+                global_rate,
+                weight_decay,
+                ..
+            } if *weight_decay > 0.0 => {
+                let rate = f64::from(*global_rate) * f64::from(*weight_decay);
+                Some(weights.apply_elementwise(Scale(rate)))
+            }
+            _ => None,
+        }
+    }
+}
+
+// global_rate / (sqrt(v / correction) + epsilon) with the bias corrected second moment v
+struct AdamScale {
+    global_rate: f64,
+    correction: f64,
 }
 
-#[derive(Serialize, Deserialize)]
+impl ElementFn for AdamScale {
+    fn call<T: Element>(&self, value: T) -> T {
+        let moment: T = value / T::from_f64(self.correction);
+        T::from_f64(self.global_rate) / (moment.sqrt() + T::from_f64(ADAM_EPSILON))
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
 pub struct LearningRateParams {
-    adaptive_rate: Vec<f32>,
+    adaptive_rate: Vec<f64>,
+    #[serde(default)]
+    update_accumulator: Vec<f64>,
 }
 
 impl LearningRateParams {
-    pub fn new(learning_rate: Vec<f32>) -> LearningRateParams {
+    pub fn new(learning_rate: Vec<f64>, update_accumulator: Vec<f64>) -> LearningRateParams {
         LearningRateParams {
             adaptive_rate: learning_rate,
+            update_accumulator,
         }
     }
 
     pub fn null() -> LearningRateParams {
         LearningRateParams {
             adaptive_rate: Vec::new(),
+            update_accumulator: Vec::new(),
         }
     }
 
-    pub fn get_adaptive_learning_rate(&self) -> Vec<f32> {
+    pub fn get_adaptive_learning_rate(&self) -> Vec<f64> {
         self.adaptive_rate.clone()
     }
+
+    pub fn get_update_accumulator(&self) -> Vec<f64> {
+        self.update_accumulator.clone()
+    }
 }
@@ -0,0 +1,284 @@
+// builtin
+use std::f64::consts::PI;
+
+// external
+use serde::{Deserialize, Serialize};
+
+// internal
+use crate::optimization::learning_decay::LearningDecayType;
+
+// Global multiplier on top of the learning decay type, so it composes with the adaptive ones.
+// Time steps count the training steps taken before the current one
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub enum LearningRateSchedule {
+    #[default]
+    Constant,
+    Step {
+        step_size: usize,
+        gamma: f32,
+    },
+    // Restarts every period, each new period being `period_mult` times longer than the last
+    CosineAnnealing {
+        period: usize,
+        period_mult: usize,
+        min_factor: f32,
+    },
+    Warmup {
+        warmup_steps: usize,
+        schedule: Box<LearningRateSchedule>,
+    },
+    Triangular {
+        step_size: usize,
+        min_factor: f32,
+    },
+}
+
+impl LearningRateSchedule {
+    pub fn constant() -> LearningRateSchedule {
+        LearningRateSchedule::Constant
+    }
+
+    pub fn step(step_size: usize, gamma: f32) -> LearningRateSchedule {
+        LearningRateSchedule::check_size("step size", step_size);
+        LearningRateSchedule::Step { step_size, gamma }
+    }
+
+    pub fn cosine_annealing(period: usize, min_factor: f32) -> LearningRateSchedule {
+        LearningRateSchedule::cosine_warm_restarts(period, 1, min_factor)
+    }
+
+    pub fn cosine_warm_restarts(
+        period: usize,
+        period_mult: usize,
+        min_factor: f32,
+    ) -> LearningRateSchedule {
+        LearningRateSchedule::check_size("period", period);
+        LearningRateSchedule::check_size("period multiplier", period_mult);
+        LearningRateSchedule::CosineAnnealing {
+            period,
+            period_mult,
+            min_factor,
+        }
+    }
+
+    // Ramps linearly up to the wrapped schedule, which then starts from its own first step
+    pub fn warmup(warmup_steps: usize, schedule: LearningRateSchedule) -> LearningRateSchedule {
+        LearningRateSchedule::Warmup {
+            warmup_steps,
+            schedule: Box::new(schedule),
+        }
+    }
+
+    // Climbs from `min_factor` to the full rate over `step_size` steps and back down again
+    pub fn triangular(step_size: usize, min_factor: f32) -> LearningRateSchedule {
+        LearningRateSchedule::check_size("step size", step_size);
+        LearningRateSchedule::Triangular {
+            step_size,
+            min_factor,
+        }
+    }
+
+    fn check_size(name: &str, size: usize) {
+        if size == 0 {
+            panic!("[SCHEDULE] Invalid {name}, expected at least 1 but got 0");
+        }
+    }
+
+    pub fn get_multiplier(&self, time_step: usize) -> f32 {
+        match self {
+            LearningRateSchedule::Constant => 1.0,
+            LearningRateSchedule::Step { step_size, gamma } => {
+                f32::powi(*gamma, (time_step / *step_size) as i32)
+            }
+            LearningRateSchedule::CosineAnnealing {
+                period,
+                period_mult,
+                min_factor,
+            } => {
+                let (position, length) = if *period_mult == 1 {
+                    (time_step % *period, *period)
+                } else {
+                    let mut position: usize = time_step;
+                    let mut length: usize = *period;
+                    while position >= length {
+                        position -= length;
+                        length *= *period_mult;
+                    }
+                    (position, length)
+                };
+
+                let cosine = 0.5 * (1.0 + f64::cos(PI * position as f64 / length as f64));
+                min_factor + (1.0 - min_factor) * cosine as f32
+            }
+            LearningRateSchedule::Warmup {
+                warmup_steps,
+                schedule,
+            } => {
+                if time_step < *warmup_steps {
+                    let percent = (time_step + 1) as f32 / *warmup_steps as f32;
+                    percent * schedule.get_multiplier(0)
+                } else {
+                    schedule.get_multiplier(time_step - *warmup_steps)
+                }
+            }
+            LearningRateSchedule::Triangular {
+                step_size,
+                min_factor,
+            } => {
+                let position: usize = time_step % (2 * *step_size);
+                let distance: usize = position.abs_diff(*step_size);
+                let percent = 1.0 - distance as f32 / *step_size as f32;
+
+                min_factor + (1.0 - min_factor) * percent
+            }
+        }
+    }
+}
+
+// Global rate of a network's parameters: the decay type's rate at the current step, scaled by the
+// schedule and by the factor set through `set_rate`, e.g. by plateau scheduling
+#[derive(Clone)]
+pub struct NetworkRate {
+    decay_type: LearningDecayType,
+    schedule: LearningRateSchedule,
+    time_step: usize,
+    factor: f32,
+}
+
+impl NetworkRate {
+    pub fn new(
+        decay_type: LearningDecayType,
+        schedule: LearningRateSchedule,
+        time_step: usize,
+        factor: f32,
+    ) -> NetworkRate {
+        NetworkRate {
+            decay_type,
+            schedule,
+            time_step,
+            factor,
+        }
+    }
+
+    // Scale of the parameter updates about to be taken, moves on to the next step
+    pub fn next_scale(&mut self) -> f32 {
+        let scale: f32 = self.factor * self.schedule.get_multiplier(self.time_step);
+        self.time_step += 1;
+        self.decay_type.update_timestep(self.time_step);
+
+        scale
+    }
+
+    // The rate the next update is taken at
+    pub fn get_rate(&self) -> f32 {
+        self.decay_type.get_rate(self.time_step + 1)
+            * self.schedule.get_multiplier(self.time_step)
+            * self.factor
+    }
+
+    // Rescales the factor so the next update is taken at `rate`, later steps keep following the
+    // decay type and schedule from there
+    pub fn set_rate(&mut self, rate: f32) {
+        let current: f32 = self.decay_type.get_rate(self.time_step + 1)
+            * self.schedule.get_multiplier(self.time_step);
+        if current == 0.0 {
+            println!("[LEARNING_RATE] Cannot rescale a zero learning rate, skipping assignment");
+            return;
+        }
+
+        self.factor = rate / current;
+    }
+
+    pub fn set_schedule(&mut self, schedule: LearningRateSchedule) {
+        self.schedule = schedule;
+    }
+
+    pub fn decay_type(&self) -> &LearningDecayType {
+        &self.decay_type
+    }
+
+    pub fn schedule(&self) -> &LearningRateSchedule {
+        &self.schedule
+    }
+
+    pub fn time_step(&self) -> usize {
+        self.time_step
+    }
+
+    pub fn factor(&self) -> f32 {
+        self.factor
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::optimization::{
+        learning_decay::LearningDecayType,
+        learning_schedule::{LearningRateSchedule, NetworkRate},
+    };
+
+    fn multipliers(schedule: &LearningRateSchedule, steps: usize) -> Vec<f32> {
+        (0..steps).map(|t| schedule.get_multiplier(t)).collect()
+    }
+
+    fn assert_close(actual: Vec<f32>, expected: &[f32]) {
+        assert_eq!(actual.len(), expected.len());
+        for (a, e) in actual.iter().zip(expected) {
+            assert!((a - e).abs() < 1e-6, "{actual:?} != {expected:?}");
+        }
+    }
+
+    #[test]
+    fn step_and_triangular() {
+        let step = LearningRateSchedule::step(2, 0.5);
+        assert_close(multipliers(&step, 5), &[1.0, 1.0, 0.5, 0.5, 0.25]);
+
+        let triangular = LearningRateSchedule::triangular(2, 0.2);
+        assert_close(multipliers(&triangular, 6), &[0.2, 0.6, 1.0, 0.6, 0.2, 0.6]);
+    }
+
+    #[test]
+    fn cosine_warm_restarts() {
+        let cosine = LearningRateSchedule::cosine_warm_restarts(2, 2, 0.0);
+        assert_close(
+            multipliers(&cosine, 7),
+            &[1.0, 0.5, 1.0, 0.853553, 0.5, 0.146447, 1.0],
+        );
+    }
+
+    #[test]
+    fn warmup_wraps_schedule() {
+        let schedule =
+            LearningRateSchedule::warmup(4, LearningRateSchedule::cosine_annealing(4, 0.1));
+        assert_close(
+            multipliers(&schedule, 6),
+            &[0.25, 0.5, 0.75, 1.0, 1.0, 0.868198],
+        );
+
+        let json = serde_json::to_string(&schedule).unwrap();
+        let loaded: LearningRateSchedule = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded, schedule);
+    }
+
+    #[test]
+    fn network_rate_reports_decayed_rate() {
+        let mut rate = NetworkRate::new(
+            LearningDecayType::exponential(0.1, 0.5),
+            LearningRateSchedule::step(2, 0.1),
+            0,
+            1.0,
+        );
+        // The first update already uses the rate decayed once
+        assert_close(vec![rate.get_rate()], &[0.05]);
+
+        rate.next_scale();
+        rate.next_scale();
+        assert_close(vec![rate.get_rate()], &[0.1 * 0.125 * 0.1]);
+
+        rate.set_rate(0.01);
+        assert_close(vec![rate.get_rate()], &[0.01]);
+        // Decay and schedule carry on from the new rate
+        assert_close(vec![rate.next_scale()], &[0.1 * 8.0]);
+        assert_close(vec![rate.get_rate()], &[0.005]);
+    }
+}
@@ -10,6 +10,8 @@ pub enum DescentType {
     Base,
     Momentum { decay: f32 },
     Nesterov { decay: f32 },
+    // First moment half of Adam, pair it with LearningDecayType::Adam for the second moment
+    Adam { beta1: f32 },
 }
 
 impl DescentType {
@@ -24,15 +26,19 @@ impl DescentType {
     pub fn nesterov(decay: f32) -> DescentType {
         DescentType::Nesterov { decay }
     }
+
+    pub fn adam(beta1: f32) -> DescentType {
+        DescentType::Adam { beta1 }
+    }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct MomentumParams {
-    momentum: Vec<f32>,
+    momentum: Vec<f64>,
 }
 
 impl MomentumParams {
-    pub fn new(momentum: Vec<f32>) -> MomentumParams {
+    pub fn new(momentum: Vec<f64>) -> MomentumParams {
         MomentumParams { momentum }
     }
 
@@ -42,7 +48,7 @@ impl MomentumParams {
         }
     }
 
-    pub fn get_momentum(&self) -> Vec<f32> {
+    pub fn get_momentum(&self) -> Vec<f64> {
         self.momentum.clone()
     }
 }
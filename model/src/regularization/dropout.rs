@@ -10,6 +10,9 @@ use ndarray_rand::rand_distr::num_traits::clamp;
 pub enum NetworkMode {
     Inference,
     Train,
+    // Train mode without side effects: dropout masks are drawn once and then held, batch norm
+    // normalizes with batch statistics but leaves its running statistics untouched
+    GradCheck,
     None,
 }
 
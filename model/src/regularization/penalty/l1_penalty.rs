@@ -71,7 +71,7 @@ impl<'a> PenaltyUnit<'a> for L1PenaltyUnit<'a> {
 mod tests {
     use crate::{
         data::data_container::DataContainer,
-        node::{types::weight_node::WeightNode, NodeRef},
+        node::{graph::Graph, types::weight_node::WeightNode, NodeRef},
         optimization::{learning_decay::LearningDecayType, momentum::DescentType},
         regularization::penalty::{l1_penalty::L1PenaltyUnit, PenaltyUnit},
     };
@@ -90,7 +90,8 @@ mod tests {
 
         unit.add_parameter_input(&weight);
 
-        unit.get_output_ref().borrow_mut().apply_operation();
+        let graph: Graph = Graph::new(unit.get_output_ref()).unwrap();
+        graph.forward().unwrap();
 
         let output = unit.get_output_ref().borrow_mut().get_data();
 
@@ -112,10 +113,9 @@ mod tests {
 
         unit.add_parameter_input(&weight);
 
-        let output_ref = unit.get_output_ref();
-        output_ref.borrow_mut().apply_operation();
-        output_ref.borrow_mut().add_gradient(&DataContainer::one());
-        output_ref.borrow_mut().apply_jacobian();
+        let graph: Graph = Graph::new(unit.get_output_ref()).unwrap();
+        graph.forward().unwrap();
+        graph.backward(&DataContainer::one()).unwrap();
 
         let new_weights = weight.borrow_mut().get_data();
         println!("New weights: {:?}", new_weights);
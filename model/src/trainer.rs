@@ -1,16 +1,46 @@
 // builtin
+use std::{io, time::Instant};
 
 // external
 
 // internal
 use crate::{
-    data::{data_container::DataContainer, Data},
-    network::{config_types::Config, Network},
-    trainer::{error::PredictionError, examples::SupervisedExample, trainer_params::TrainerConfig},
+    data::{
+        data_container::DataContainer,
+        error::{DataError, Operand},
+        Data,
+    },
+    network::{
+        config_types::Config, inference_model::InferenceModel,
+        types::binary_classifier::apply_thresholds, Network,
+    },
+    random,
+    trainer::{
+        callback::{
+            progress_printer::ProgressPrinter, CallbackAction, EpochSummary, TrainerCallback,
+        },
+        checkpoint::{checkpointer::Checkpointer, Checkpoint, TrainingState},
+        cross_validation::CrossValidationReport,
+        data_subsets::DataSubsets,
+        early_stopping::EarlyStopping,
+        error::PredictionError,
+        examples::SupervisedExample,
+        history::{BatchRecord, EpochRecord, TrainingHistory},
+        metrics::{EvaluationReport, Metrics},
+        trainer_params::TrainerConfig,
+    },
 };
+pub mod callback;
+pub mod checkpoint;
+pub mod cross_validation;
 pub mod data_subsets;
+pub mod early_stopping;
 pub mod error;
 pub mod examples;
+pub mod history;
+pub mod metrics;
+pub mod plateau;
+pub mod sampler;
 pub mod trainer_params;
 
 pub struct SupervisedTrainer<N, T>
@@ -20,6 +50,16 @@ where
 {
     model: N,
     config: TrainerConfig<T>,
+    callbacks: Vec<Box<dyn TrainerCallback>>,
+    checkpointer: Option<Checkpointer>,
+    resumed: Option<ResumePoint>,
+}
+
+// Progress of an interrupted run that the next call to fit starts from
+struct ResumePoint {
+    epoch: usize,
+    kept_config: Config,
+    history: TrainingHistory,
 }
 
 impl<N, T> SupervisedTrainer<N, T>
@@ -31,87 +71,458 @@ where
         SupervisedTrainer {
             model: network,
             config,
+            callbacks: vec![Box::new(ProgressPrinter::new())],
+            checkpointer: None,
+            resumed: None,
         }
     }
 
-    fn train_epoch(&mut self) -> (Config, PredictionError) {
+    pub fn with_checkpointer(mut self, checkpointer: Checkpointer) -> Self {
+        self.checkpointer = Some(checkpointer);
+        self
+    }
+
+    // Restores the network and the trainer progress saved by a Checkpointer, the next call to
+    // train carries on from the epoch after the checkpoint. Data and trainer config are expected
+    // to match the interrupted run
+    pub fn resume(&mut self, path: &str) -> io::Result<()> {
+        let (network, state) = Checkpoint::load_from_file(path)?.into_parts();
+        let TrainingState {
+            epoch,
+            kept_config,
+            history,
+            early_stopping,
+            plateau,
+            sampler,
+            rng,
+            checkpoints,
+        } = state;
+
+        self.model = N::load_config(network)?;
+        *self.config.early_stopping_mut() = early_stopping;
+        self.config.set_plateau(plateau);
+        self.config.set_sampler_state(sampler);
+        random::set_state(rng);
+
+        if let Some(checkpointer) = self.checkpointer.as_mut() {
+            checkpointer.set_saved(checkpoints);
+        }
+
+        self.resumed = Some(ResumePoint {
+            epoch,
+            kept_config,
+            history: TrainingHistory::from_epochs(history),
+        });
+
+        Ok(())
+    }
+
+    pub fn with_callback(mut self, callback: impl TrainerCallback + 'static) -> Self {
+        self.callbacks.push(Box::new(callback));
+        self
+    }
+
+    // Replaces every callback, including the default ProgressPrinter
+    pub fn with_callbacks(mut self, callbacks: Vec<Box<dyn TrainerCallback>>) -> Self {
+        self.callbacks = callbacks;
+        self
+    }
+
+    // Returns the mean loss per training example and whether a callback asked to stop
+    fn train_epoch(
+        &mut self,
+        epoch: usize,
+        start: Instant,
+        history: &mut TrainingHistory,
+    ) -> Result<(f32, CallbackAction), DataError> {
+        let labels: Vec<usize> = SupervisedTrainer::<N, T>::get_labels(self.config.train_ref());
+        let order: Vec<usize> = self.config.sample_epoch(&labels)?;
+        let batch_size: usize = self.config.batch_size();
+        let drop_last: bool = self.config.drop_last();
+        let (train, mut built_in) = self.config.train_and_callbacks_mut();
+        let callbacks: &mut Vec<Box<dyn TrainerCallback>> = &mut self.callbacks;
+        let mut loss_sum: f32 = 0.0;
+
+        let (action, fitted) = SupervisedTrainer::<N, T>::fit_examples(
+            &mut self.model,
+            order.iter().map(|i| &train[*i]),
+            batch_size,
+            drop_last,
+            |batch, loss| {
+                loss_sum += loss;
+                history.record_batch(BatchRecord {
+                    epoch,
+                    batch,
+                    loss,
+                    wall_time: start.elapsed().as_secs_f64(),
+                });
+
+                SupervisedTrainer::<N, T>::notify(&mut built_in, callbacks, |callback| {
+                    callback.on_batch_end(epoch, batch, loss)
+                })
+            },
+        )?;
+
+        Ok((loss_sum / fitted.max(1) as f32, action))
+    }
+
+    fn get_labels<'b>(examples: impl IntoIterator<Item = &'b T>) -> Vec<usize>
+    where
+        T: 'b,
+    {
+        examples
+            .into_iter()
+            .map(|example| example.get_label_key())
+            .collect()
+    }
+
+    // Returns whether `on_batch` asked to stop and the number of examples fitted. A learning rate
+    // asked for by `on_batch` is set before the next batch
+    fn fit_examples<'b>(
+        model: &mut N,
+        examples: impl Iterator<Item = &'b T>,
+        batch_size: usize,
+        drop_last: bool,
+        mut on_batch: impl FnMut(usize, f32) -> CallbackAction,
+    ) -> Result<(CallbackAction, usize), DataError>
+    where
+        T: 'b,
+    {
         let mut inputs: Vec<Data> = Vec::new();
         let mut responses: Vec<Data> = Vec::new();
+        let mut batch: usize = 0;
+        let mut fitted: usize = 0;
 
-        for example in self.config.train_ref().iter() {
+        for example in examples {
             inputs.push(example.get_input());
             responses.push(example.get_response());
 
-            if inputs.len() == self.config.batch_size() {
-                self.model.train(
-                    DataContainer::Batch(inputs.clone()),
-                    DataContainer::Batch(responses.clone()),
-                );
+            if inputs.len() == batch_size {
+                let loss: f32 = model.train(
+                    DataContainer::try_batch(inputs.clone())?,
+                    DataContainer::try_batch(responses.clone())?,
+                )?;
+                fitted += inputs.len();
 
                 inputs.clear();
                 responses.clear();
+
+                match on_batch(batch, loss) {
+                    CallbackAction::Stop => return Ok((CallbackAction::Stop, fitted)),
+                    CallbackAction::SetLearningRate(rate) => model.set_learning_rate(rate),
+                    CallbackAction::Continue => {}
+                }
+                batch += 1;
             }
         }
 
-        if inputs.len() != 0 {
-            self.model.train(
-                DataContainer::Batch(inputs.clone()),
-                DataContainer::Batch(responses.clone()),
+        if !inputs.is_empty() && !drop_last {
+            let loss: f32 = model.train(
+                DataContainer::try_batch(inputs.clone())?,
+                DataContainer::try_batch(responses.clone())?,
+            )?;
+            fitted += inputs.len();
+
+            let action: CallbackAction = on_batch(batch, loss);
+            if let CallbackAction::SetLearningRate(rate) = action {
+                model.set_learning_rate(rate);
+            }
+            return Ok((action, fitted));
+        }
+
+        Ok((CallbackAction::Continue, fitted))
+    }
+
+    // Runs the hook on the built in callbacks and then on every other one, so all of them see the
+    // event even if an earlier one stops. The actions are merged, see CallbackAction::merge
+    fn notify(
+        built_in: &mut [&mut dyn TrainerCallback],
+        callbacks: &mut [Box<dyn TrainerCallback>],
+        mut hook: impl FnMut(&mut dyn TrainerCallback) -> CallbackAction,
+    ) -> CallbackAction {
+        built_in
+            .iter_mut()
+            .map(|callback| &mut **callback)
+            .chain(
+                callbacks
+                    .iter_mut()
+                    .map(|callback| callback.as_mut() as &mut dyn TrainerCallback),
+            )
+            .fold(CallbackAction::Continue, |action, callback| {
+                action.merge(hook(callback))
+            })
+    }
+
+    // The history ends with the test report of the saved config
+    pub fn train(&mut self, save_path: &str) -> Result<TrainingHistory, DataError> {
+        let (kept_config, history) = self.fit()?;
+
+        kept_config.save_to_file(save_path).expect("Save Failed");
+
+        Ok(history)
+    }
+
+    fn fit(&mut self) -> Result<(Config, TrainingHistory), DataError> {
+        let (first_epoch, mut kept_config, mut history) = match self.resumed.take() {
+            Some(resumed) => (resumed.epoch, resumed.kept_config, resumed.history),
+            None => (0, Config::None, TrainingHistory::new()),
+        };
+        let total_epochs: usize = self.config.total_iterations();
+        let mut epochs: usize = first_epoch;
+        let start: Instant = Instant::now();
+
+        SupervisedTrainer::<N, T>::notify(
+            &mut self.config.callbacks_mut(),
+            &mut self.callbacks,
+            |callback| {
+                callback.on_train_begin(total_epochs);
+                CallbackAction::Continue
+            },
+        );
+
+        for i in first_epoch..total_epochs {
+            SupervisedTrainer::<N, T>::notify(
+                &mut self.config.callbacks_mut(),
+                &mut self.callbacks,
+                |callback| {
+                    callback.on_epoch_begin(i);
+                    CallbackAction::Continue
+                },
             );
 
-            inputs.clear();
-            responses.clear();
+            let (train_loss, batch_action) = self.train_epoch(i, start, &mut history)?;
+            let config: Config = Config::from_network(&self.model);
+            let validation: EvaluationReport =
+                self.evaluate_examples(self.config.validation_ref())?;
+            epochs += 1;
+
+            let summary: EpochSummary = EpochSummary {
+                epoch: i,
+                time_step: config.timestep(),
+                train_loss,
+                learning_rate: self.model.get_learning_rate(),
+                wall_time: start.elapsed().as_secs_f64(),
+                validation: &validation,
+                config: &config,
+            };
+            history.record_epoch(EpochRecord::from_summary(&summary));
+
+            let epoch_action: CallbackAction = SupervisedTrainer::<N, T>::notify(
+                &mut self.config.callbacks_mut(),
+                &mut self.callbacks,
+                |callback| callback.on_epoch_end(&summary),
+            );
+            if let CallbackAction::SetLearningRate(rate) = epoch_action {
+                self.model.set_learning_rate(rate);
+            }
+
+            let stop: bool = batch_action.merge(epoch_action) == CallbackAction::Stop;
+            let early_stopping: &EarlyStopping = self.config.early_stopping();
+
+            // Without restore_best the config right before the stop is kept
+            if early_stopping.best_epoch() == i || (!early_stopping.restore_best() && !stop) {
+                kept_config = config;
+            }
+
+            self.save_checkpoint(i, validation.error().get_value(), &kept_config, &history);
+
+            if stop {
+                break;
+            }
         }
 
-        let mut error_sum: PredictionError = PredictionError::empty();
-        for example in self.config.test_ref().iter() {
-            let input = DataContainer::Inference(example.get_input());
-            let predicted = self.model.predict(input);
-            let error = example.get_test_error(predicted);
+        let test_report: EvaluationReport = self.evaluate_config(&kept_config)?;
+        SupervisedTrainer::<N, T>::notify(
+            &mut self.config.callbacks_mut(),
+            &mut self.callbacks,
+            |callback| {
+                callback.on_train_end(epochs, &test_report);
+                CallbackAction::Continue
+            },
+        );
+        history.set_test_report(test_report);
 
-            if error_sum.is_empty() {
-                error_sum = error_sum.plus(&error);
-            } else {
-                error_sum.plus_assign(&error);
+        Ok((kept_config, history))
+    }
+
+    fn save_checkpoint(
+        &mut self,
+        epoch: usize,
+        error: Option<f32>,
+        kept_config: &Config,
+        history: &TrainingHistory,
+    ) {
+        let planned = match &self.checkpointer {
+            Some(checkpointer) if checkpointer.is_due(epoch) => checkpointer.plan(epoch, error),
+            _ => return,
+        };
+
+        let state: TrainingState = TrainingState {
+            epoch: epoch + 1,
+            kept_config: kept_config.clone(),
+            history: history.epochs().clone(),
+            early_stopping: self.config.early_stopping().clone(),
+            plateau: self.config.plateau().cloned(),
+            sampler: self.config.sampler_state(),
+            rng: random::get_state(),
+            checkpoints: planned.clone(),
+        };
+        let checkpoint: Checkpoint = Checkpoint::new(Config::from_network(&self.model), state);
+
+        if let Some(checkpointer) = self.checkpointer.as_mut() {
+            if let Err(err) = checkpointer.save(&checkpoint, planned) {
+                println!("[CHECKPOINT] Failed to save the checkpoint of epoch {epoch}, {err}");
             }
         }
+    }
+
+    // Trains a fresh network from the factory on every fold and scores it on the held out part
+    pub fn cross_validate(
+        network_factory: impl Fn() -> N,
+        folds: Vec<DataSubsets<T>>,
+        total_iterations: usize,
+        batch_size: usize,
+    ) -> Result<CrossValidationReport, DataError> {
+        let mut reports: Vec<EvaluationReport> = Vec::new();
+
+        for (i, fold) in folds.into_iter().enumerate() {
+            let config: TrainerConfig<T> =
+                TrainerConfig::from_subsets(total_iterations, batch_size, fold);
+            let mut trainer: SupervisedTrainer<N, T> =
+                SupervisedTrainer::new(network_factory(), config);
+
+            let (_config, history) = trainer.fit()?;
+            let report: EvaluationReport = history.test_report().clone();
+            println!("Fold {i} error: {:?}\n", report.error());
 
-        (Config::from_network(&self.model), error_sum)
+            reports.push(report);
+        }
+
+        Ok(CrossValidationReport::new(&reports))
     }
 
-    pub fn train(&mut self, save_path: &str) {
-        let (config, error) = self.train_epoch();
+    fn evaluate_config(&self, config: &Config) -> Result<EvaluationReport, DataError> {
+        if let Config::None = config {
+            return Ok(EvaluationReport::empty());
+        }
 
-        println!("Test error 0: {:?}\n", error);
+        let model: InferenceModel = InferenceModel::from_config(config);
+        SupervisedTrainer::<N, T>::report_examples(
+            self.config.test_ref(),
+            config.label_thresholds(),
+            |input| Ok(DataContainer::Inference(model.try_predict(&input)?)),
+        )
+    }
 
-        let mut prev_config: Config = config;
-        let mut prev_error: PredictionError = error;
+    // Second pass of early stopping: a fresh network is fit on train and validation data together
+    // for as many updates as the best run of `train` took. The combined set has more batches per
+    // epoch, so the last epoch may end early. Samplers with one weight per training example can't
+    // cover the combined set and return an error
+    pub fn retrain_on_all_data(&mut self, network: N, save_path: &str) -> Result<(), DataError> {
+        self.model = network;
+        let total_steps: usize = self.config.early_stopping().best_time_step();
 
-        for i in 1..self.config.total_iterations() {
-            let (config, error) = self.train_epoch();
+        let labels: Vec<usize> = SupervisedTrainer::<N, T>::get_labels(
+            self.config
+                .train_ref()
+                .iter()
+                .chain(self.config.validation_ref()),
+        );
+
+        let mut steps: usize = 0;
+        let mut epoch: usize = 0;
+        while steps < total_steps {
+            let order: Vec<usize> = self.config.sample_epoch(&labels)?;
+            let examples: Vec<&T> = self
+                .config
+                .train_ref()
+                .iter()
+                .chain(self.config.validation_ref())
+                .collect();
 
-            println!("Test error {i}: {:?}\n", error);
+            let (_action, fitted) = SupervisedTrainer::<N, T>::fit_examples(
+                &mut self.model,
+                order.iter().map(|i| examples[*i]),
+                self.config.batch_size(),
+                self.config.drop_last(),
+                |_batch, _loss| {
+                    steps += 1;
+                    if steps == total_steps {
+                        return CallbackAction::Stop;
+                    }
+                    CallbackAction::Continue
+                },
+            )?;
 
-            if prev_error < error {
-                prev_config.save_to_file(save_path).expect("Save Failed");
-                println!("Training stopped after {i} iterations");
-                return;
+            if fitted == 0 {
+                println!("[RETRAIN] No batch could be fitted, stopping after {steps} of {total_steps} updates");
+                break;
             }
 
-            prev_config = config;
-            prev_error = error;
+            println!("Retrained epoch {epoch}, {steps} of {total_steps} updates");
+            epoch += 1;
         }
 
-        prev_config.save_to_file(save_path).expect("Save Failed");
-        println!("Training finished.");
+        Config::from_network(&self.model)
+            .save_to_file(save_path)
+            .expect("Save Failed");
+
+        Ok(())
+    }
+
+    pub fn validate(&self) -> Result<PredictionError, DataError> {
+        Ok(self
+            .evaluate_examples(self.config.validation_ref())?
+            .into_error())
+    }
+
+    pub fn evaluate(&self) -> Result<EvaluationReport, DataError> {
+        self.evaluate_examples(self.config.test_ref())
     }
 
-    pub fn evaluate(&self) -> PredictionError {
+    fn evaluate_examples(&self, examples: &[T]) -> Result<EvaluationReport, DataError> {
+        SupervisedTrainer::<N, T>::report_examples(
+            examples,
+            self.model.label_thresholds(),
+            |input| self.model.predict(DataContainer::Inference(input)),
+        )
+    }
+
+    // With label thresholds every example is scored on its thresholded labels, see
+    // SupervisedExample::get_label_error
+    fn report_examples(
+        examples: &[T],
+        thresholds: Option<&[f32]>,
+        predict: impl Fn(Data) -> Result<DataContainer, DataError>,
+    ) -> Result<EvaluationReport, DataError> {
         let mut error_sum: PredictionError = PredictionError::empty();
-        for example in self.config.test_ref().iter() {
-            let input = DataContainer::Inference(example.get_input());
-            let predicted = self.model.predict(input);
-            let error = example.get_test_error(predicted);
+        let mut metrics: Metrics = Metrics::empty();
+
+        for example in examples.iter() {
+            let predicted = predict(example.get_input())?;
+            let predicted_values: Vec<f32> = match &predicted {
+                DataContainer::Inference(data) => data.flatten_as::<f32>(),
+                _ => Vec::new(),
+            };
+
+            let response: Vec<f32> = example.get_response().flatten_as::<f32>();
+            let error = match thresholds {
+                Some(thresholds) => {
+                    if predicted_values.len() != thresholds.len()
+                        || response.len() != thresholds.len()
+                    {
+                        return Err(DataError::ShapeMismatch {
+                            operation: "MULTI_LABEL",
+                            left: Operand::new("Response", &[response.len()]),
+                            right: Operand::new("Prediction", &[predicted_values.len()]),
+                        });
+                    }
+
+                    example.get_label_error(&apply_thresholds(&predicted_values, thresholds))
+                }
+                None => example.get_test_error(predicted),
+            };
+            metrics.add_example(&error, &response, &predicted_values)?;
 
             if error_sum.is_empty() {
                 error_sum = error_sum.plus(&error);
@@ -120,31 +531,82 @@ where
             }
         }
 
-        error_sum
+        Ok(EvaluationReport::new(error_sum, metrics))
     }
 }
 
 #[cfg(test)]
-
 mod tests {
 
+    use std::{cell::RefCell, rc::Rc};
+
+    use ndarray::arr1;
     use rand::{distributions::Uniform, prelude::Distribution};
 
     use crate::{
-        network::types::regressor::RegressorNetwork,
+        data::{data_container::DataContainer, error::DataError, precision::Precision, Data},
+        network::{
+            config_types::{hyper_params::HyperParams, Config},
+            types::{
+                binary_classifier::BinaryClassifierNetwork,
+                layer_builder::LayerBuilder,
+                regressor::{config::RegressorConfig, RegressorNetwork},
+                sequential::SequentialNetwork,
+            },
+            Network,
+        },
         optimization::{
-            batch_norm::NormalizationType, learning_decay::LearningDecayType, momentum::DescentType,
+            batch_norm::NormalizationType, learning_decay::LearningDecayType,
+            learning_schedule::LearningRateSchedule, momentum::DescentType,
         },
+        random::{set_seed, with_rng},
         regularization::{
             dropout::NetworkMaskType,
             penalty::{l2_penalty::builder::L2PenaltyBuilder, PenaltyConfig},
         },
-        trainer::{examples::QuadraticExample, trainer_params::TrainerConfig, SupervisedTrainer},
+        trainer::{
+            callback::{CallbackAction, EpochSummary, TrainerCallback},
+            checkpoint::checkpointer::Checkpointer,
+            data_subsets::{k_fold::KFold, random_splitter::RandomSplitter},
+            early_stopping::EarlyStopping,
+            error::PredictionError,
+            examples::{QuadraticExample, SupervisedExample},
+            history::{
+                history_recorder::{HistoryFormat, HistoryRecorder},
+                TrainingHistory,
+            },
+            metrics::EvaluationReport,
+            plateau::PlateauScheduler,
+            sampler::{
+                sequential_sampler::SequentialSampler, shuffled_sampler::ShuffledSampler,
+                weighted_sampler::WeightedSampler, Sampler,
+            },
+            trainer_params::TrainerConfig,
+            SupervisedTrainer,
+        },
     };
 
+    fn quadratic_regressor<'a>() -> RegressorNetwork<'a> {
+        RegressorNetwork::new(
+            vec![1],
+            vec![1],
+            vec![8],
+            PenaltyConfig::new(L2PenaltyBuilder::new(0.01)),
+            NetworkMaskType::None,
+            HyperParams::new(
+                LearningDecayType::constant(0.001),
+                DescentType::nesterov(0.9),
+                NormalizationType::none(),
+                Precision::F32,
+                LearningRateSchedule::constant(),
+            ),
+        )
+    }
+
     #[test]
     fn trainer_test() {
         let mut train: Vec<QuadraticExample> = Vec::new();
+        let mut validation: Vec<QuadraticExample> = Vec::new();
         let mut test: Vec<QuadraticExample> = Vec::new();
         let mut rng = rand::thread_rng();
         let distribution = Uniform::new(1.0, 4.0);
@@ -157,8 +619,8 @@ mod tests {
 
         for i in 0..16 {
             let x: f32 = (i as f32) / 5.0 + 1.0;
-            let example: QuadraticExample = QuadraticExample::new(x);
-            test.push(example);
+            validation.push(QuadraticExample::new(x));
+            test.push(QuadraticExample::new(x + 0.1));
         }
 
         let config: PenaltyConfig = PenaltyConfig::new(L2PenaltyBuilder::new(0.2));
@@ -168,16 +630,575 @@ mod tests {
             vec![12, 6],
             config,
             NetworkMaskType::None,
-            LearningDecayType::constant(0.001),
-            DescentType::nesterov(0.95),
-            NormalizationType::none(),
+            HyperParams::new(
+                LearningDecayType::constant(0.001),
+                DescentType::nesterov(0.95),
+                NormalizationType::none(),
+                Precision::F32,
+                LearningRateSchedule::constant(),
+            ),
         );
 
-        let train_config: TrainerConfig<QuadraticExample> = TrainerConfig::new(25, 16, train, test);
+        let train_config: TrainerConfig<QuadraticExample> =
+            TrainerConfig::new(25, 16, train, validation, test);
 
         let mut trainer: SupervisedTrainer<RegressorNetwork, QuadraticExample> =
             SupervisedTrainer::new(regressor, train_config);
 
-        trainer.train("test/quadratic_training.json");
+        trainer
+            .train("test/quadratic_training.json")
+            .expect("Training failed on a data error");
+    }
+
+    #[test]
+    fn early_stopping_retrain_test() {
+        let mut rng = rand::thread_rng();
+        let distribution = Uniform::new(1.0, 4.0);
+
+        let train: Vec<QuadraticExample> = (0..100)
+            .map(|_i| QuadraticExample::new(distribution.sample(&mut rng)))
+            .collect();
+        let validation: Vec<QuadraticExample> = (0..60)
+            .map(|i| QuadraticExample::new((i as f32) / 20.0 + 1.0))
+            .collect();
+        let test: Vec<QuadraticExample> = (0..4)
+            .map(|i| QuadraticExample::new((i as f32) / 2.0 + 1.2))
+            .collect();
+
+        let train_config: TrainerConfig<QuadraticExample> =
+            TrainerConfig::new(8, 16, train, validation, test)
+                .with_early_stopping(EarlyStopping::new(3, 0.0, true));
+        let mut trainer: SupervisedTrainer<RegressorNetwork, QuadraticExample> =
+            SupervisedTrainer::new(quadratic_regressor(), train_config);
+
+        let dir = std::env::temp_dir();
+        let best_path = dir.join(format!("early_stopping_best_{}.json", std::process::id()));
+        let retrain_path = dir.join(format!(
+            "early_stopping_retrain_{}.json",
+            std::process::id()
+        ));
+
+        let history = trainer.train(best_path.to_str().unwrap()).unwrap();
+        let test_report = history.test_report();
+        assert!(matches!(test_report.error(), PredictionError::Loss { .. }));
+        let metrics = test_report.metrics().regression().unwrap();
+        assert_eq!(metrics.count(), 4);
+        if let PredictionError::Loss { loss } = test_report.error() {
+            assert!((metrics.mean_loss() - loss / 4.0).abs() <= 1e-4 * loss.max(1.0));
+        }
+        let best_epochs: usize = trainer.config.early_stopping().best_epoch() + 1;
+
+        trainer
+            .retrain_on_all_data(quadratic_regressor(), retrain_path.to_str().unwrap())
+            .unwrap();
+
+        // The 100 train examples take 7 batches per epoch, the 160 combined ones 10, so the
+        // retrained network matches the best one in updates rather than in epochs
+        let best = RegressorConfig::load_from_file(best_path.to_str().unwrap()).unwrap();
+        assert_eq!(best.timestep(), best_epochs * 7);
+
+        let retrained = RegressorConfig::load_from_file(retrain_path.to_str().unwrap()).unwrap();
+        assert_eq!(retrained.timestep(), best_epochs * 7);
+    }
+
+    #[test]
+    fn plateau_exhausted_stop_test() {
+        let train: Vec<QuadraticExample> = (0..20)
+            .map(|i| QuadraticExample::new((i as f32) / 7.0 + 1.0))
+            .collect();
+        let validation: Vec<QuadraticExample> = (0..4)
+            .map(|i| QuadraticExample::new((i as f32) / 2.0 + 1.1))
+            .collect();
+
+        // A zero rate never improves the error and can't be lowered any further
+        let regressor: RegressorNetwork = RegressorNetwork::new(
+            vec![1],
+            vec![1],
+            vec![8],
+            PenaltyConfig::none(),
+            NetworkMaskType::None,
+            HyperParams::new(
+                LearningDecayType::constant(0.0),
+                DescentType::Base,
+                NormalizationType::none(),
+                Precision::F32,
+                LearningRateSchedule::constant(),
+            ),
+        );
+        let train_config: TrainerConfig<QuadraticExample> =
+            TrainerConfig::new(10, 8, train, validation, Vec::new())
+                .with_plateau(PlateauScheduler::new(2, 0.0, 0.5, 0.0).stop_when_exhausted());
+        let mut trainer: SupervisedTrainer<RegressorNetwork, QuadraticExample> =
+            SupervisedTrainer::new(regressor, train_config);
+
+        let (_config, history) = trainer.fit().unwrap();
+
+        // The default early stopping would have ended training on the second epoch already
+        assert_eq!(history.epochs().len(), 3);
+        assert_eq!(trainer.config.early_stopping().best_epoch(), 0);
+    }
+
+    #[test]
+    fn cross_validate_test() {
+        let mut rng = rand::thread_rng();
+        let distribution = Uniform::new(1.0, 4.0);
+        let data: Vec<QuadraticExample> = (0..48)
+            .map(|_i| QuadraticExample::new(distribution.sample(&mut rng)))
+            .collect();
+
+        let report = SupervisedTrainer::<RegressorNetwork, QuadraticExample>::cross_validate(
+            quadratic_regressor,
+            KFold::new(3).folds(data),
+            2,
+            8,
+        )
+        .unwrap();
+
+        assert_eq!(report.errors().len(), 3);
+        let values: Vec<f32> = report
+            .errors()
+            .iter()
+            .map(|error| error.get_value().unwrap())
+            .collect();
+        let mean: f32 = values.iter().sum::<f32>() / 3.0;
+        assert!((report.mean() - mean).abs() < 1e-3 * mean.abs().max(1.0));
+        assert!(report.variance() >= 0.0);
+    }
+
+    // Visits the examples back to front
+    struct ReversedSampler;
+
+    impl Sampler for ReversedSampler {
+        fn sample_epoch(&mut self, labels: &[usize]) -> Result<Vec<usize>, DataError> {
+            Ok((0..labels.len()).rev().collect())
+        }
+    }
+
+    #[test]
+    fn sampler_drop_last_test() {
+        let examples: Vec<QuadraticExample> = (0..20)
+            .map(|i| QuadraticExample::new((i as f32) / 7.0 + 1.0))
+            .collect();
+        let reversed: Vec<QuadraticExample> = examples.iter().rev().cloned().collect();
+
+        fn fit(
+            train: Vec<QuadraticExample>,
+            sampler: impl Sampler + 'static,
+            drop_last: bool,
+        ) -> (String, usize) {
+            set_seed(13);
+            let validation: Vec<QuadraticExample> = (0..4)
+                .map(|i| QuadraticExample::new((i as f32) / 2.0 + 1.1))
+                .collect();
+            let train_config: TrainerConfig<QuadraticExample> =
+                TrainerConfig::new(2, 8, train, validation, Vec::new())
+                    .with_early_stopping(EarlyStopping::disabled())
+                    .with_sampler(sampler)
+                    .with_drop_last(drop_last);
+
+            let mut trainer: SupervisedTrainer<RegressorNetwork, QuadraticExample> =
+                SupervisedTrainer::new(quadratic_regressor(), train_config);
+            let (_config, history) = trainer.fit().unwrap();
+            let fitted: String =
+                serde_json::to_string(&Config::from_network(&trainer.model)).unwrap();
+
+            (fitted, history.batches().len())
+        }
+
+        // The last 4 of the 20 examples are dropped, 2 full batches of 8 are left per epoch
+        let (sampled, batches) = fit(examples.clone(), ReversedSampler, true);
+        assert_eq!(batches, 4);
+
+        let (stored, _batches) = fit(reversed.clone(), SequentialSampler::new(), true);
+        assert_eq!(sampled, stored);
+
+        // Visiting the examples in their stored order drops a different 4
+        let (forward, _batches) = fit(examples, SequentialSampler::new(), true);
+        assert_ne!(sampled, forward);
+
+        let (_kept, batches) = fit(reversed, SequentialSampler::new(), false);
+        assert_eq!(batches, 6);
+    }
+
+    #[test]
+    fn retrain_weighted_sampler_test() {
+        let train: Vec<QuadraticExample> = (0..16)
+            .map(|i| QuadraticExample::new((i as f32) / 5.0 + 1.0))
+            .collect();
+        let validation: Vec<QuadraticExample> = (0..4)
+            .map(|i| QuadraticExample::new((i as f32) / 2.0 + 1.1))
+            .collect();
+
+        let train_config: TrainerConfig<QuadraticExample> =
+            TrainerConfig::new(2, 8, train, validation, Vec::new())
+                .with_sampler(WeightedSampler::new(vec![1.0; 16], None, 3));
+        let mut trainer: SupervisedTrainer<RegressorNetwork, QuadraticExample> =
+            SupervisedTrainer::new(quadratic_regressor(), train_config);
+        trainer.fit().unwrap();
+
+        // The weights only cover the training examples, not the combined 20
+        let retrain_path =
+            std::env::temp_dir().join(format!("weighted_retrain_{}.json", std::process::id()));
+        let result =
+            trainer.retrain_on_all_data(quadratic_regressor(), retrain_path.to_str().unwrap());
+        assert!(matches!(result, Err(DataError::BatchMismatch { .. })));
+    }
+
+    #[test]
+    fn seeded_training_test() {
+        let run = |seed: u64| -> String {
+            set_seed(seed);
+            let regressor: RegressorNetwork = RegressorNetwork::new(
+                vec![1],
+                vec![1],
+                vec![8],
+                PenaltyConfig::none(),
+                NetworkMaskType::from_probabilities(0.9, 0.7),
+                HyperParams::new(
+                    LearningDecayType::constant(0.001),
+                    DescentType::nesterov(0.9),
+                    NormalizationType::none(),
+                    Precision::F32,
+                    LearningRateSchedule::constant(),
+                ),
+            );
+
+            let data: Vec<QuadraticExample> = (0..80)
+                .map(|i| QuadraticExample::new((i as f32) / 27.0 + 1.0))
+                .collect();
+            let train_config: TrainerConfig<QuadraticExample> =
+                TrainerConfig::new_with_split(3, 8, data, RandomSplitter::three_way(0.7, 0.15))
+                    .with_sampler(ShuffledSampler::from_global_rng());
+
+            let mut trainer: SupervisedTrainer<RegressorNetwork, QuadraticExample> =
+                SupervisedTrainer::new(regressor, train_config);
+            let (config, _error) = trainer.fit().unwrap();
+
+            serde_json::to_string(&config).unwrap()
+        };
+
+        assert_eq!(run(21), run(21));
+        assert_ne!(run(21), run(22));
+    }
+
+    struct RecordingCallback {
+        events: Rc<RefCell<Vec<String>>>,
+        stop_epoch: usize,
+    }
+
+    impl TrainerCallback for RecordingCallback {
+        fn on_train_begin(&mut self, total_epochs: usize) {
+            self.events
+                .borrow_mut()
+                .push(format!("begin {total_epochs}"));
+        }
+
+        fn on_batch_end(&mut self, epoch: usize, batch: usize, loss: f32) -> CallbackAction {
+            assert!(loss.is_finite());
+            self.events
+                .borrow_mut()
+                .push(format!("batch {epoch} {batch}"));
+            CallbackAction::Continue
+        }
+
+        fn on_epoch_end(&mut self, summary: &EpochSummary) -> CallbackAction {
+            assert!(summary.train_loss > 0.0);
+            self.events
+                .borrow_mut()
+                .push(format!("epoch {}", summary.epoch));
+
+            if summary.epoch == self.stop_epoch {
+                return CallbackAction::Stop;
+            }
+            CallbackAction::Continue
+        }
+
+        fn on_train_end(&mut self, epochs: usize, _test: &EvaluationReport) {
+            self.events.borrow_mut().push(format!("end {epochs}"));
+        }
+    }
+
+    #[test]
+    fn callback_stop_test() {
+        let train: Vec<QuadraticExample> = (0..20)
+            .map(|i| QuadraticExample::new((i as f32) / 7.0 + 1.0))
+            .collect();
+        let validation: Vec<QuadraticExample> = (0..4)
+            .map(|i| QuadraticExample::new((i as f32) / 2.0 + 1.1))
+            .collect();
+
+        let events: Rc<RefCell<Vec<String>>> = Rc::new(RefCell::new(Vec::new()));
+        let train_config: TrainerConfig<QuadraticExample> =
+            TrainerConfig::new(10, 8, train, validation, Vec::new())
+                .with_early_stopping(EarlyStopping::new(10, 0.0, false));
+        let mut trainer: SupervisedTrainer<RegressorNetwork, QuadraticExample> =
+            SupervisedTrainer::new(quadratic_regressor(), train_config).with_callbacks(vec![
+                Box::new(RecordingCallback {
+                    events: Rc::clone(&events),
+                    stop_epoch: 1,
+                }),
+            ]);
+
+        trainer.fit().unwrap();
+
+        let expected: Vec<&str> = vec![
+            "begin 10",
+            "batch 0 0",
+            "batch 0 1",
+            "batch 0 2",
+            "epoch 0",
+            "batch 1 0",
+            "batch 1 1",
+            "batch 1 2",
+            "epoch 1",
+            "end 2",
+        ];
+        assert_eq!(*events.borrow(), expected);
+    }
+
+    // Halves the learning rate at the end of every epoch and records the rate each epoch ran at
+    struct HalvingCallback {
+        rates: Rc<RefCell<Vec<f32>>>,
+    }
+
+    impl TrainerCallback for HalvingCallback {
+        fn on_epoch_end(&mut self, summary: &EpochSummary) -> CallbackAction {
+            self.rates.borrow_mut().push(summary.learning_rate);
+            CallbackAction::SetLearningRate(summary.learning_rate / 2.0)
+        }
+    }
+
+    #[test]
+    fn callback_learning_rate_test() {
+        let train: Vec<QuadraticExample> = (0..20)
+            .map(|i| QuadraticExample::new((i as f32) / 7.0 + 1.0))
+            .collect();
+        let validation: Vec<QuadraticExample> = (0..4)
+            .map(|i| QuadraticExample::new((i as f32) / 2.0 + 1.1))
+            .collect();
+
+        let rates: Rc<RefCell<Vec<f32>>> = Rc::new(RefCell::new(Vec::new()));
+        let train_config: TrainerConfig<QuadraticExample> =
+            TrainerConfig::new(3, 8, train, validation, Vec::new())
+                .with_early_stopping(EarlyStopping::disabled());
+        let mut trainer: SupervisedTrainer<RegressorNetwork, QuadraticExample> =
+            SupervisedTrainer::new(quadratic_regressor(), train_config).with_callback(
+                HalvingCallback {
+                    rates: Rc::clone(&rates),
+                },
+            );
+
+        trainer.fit().unwrap();
+
+        let expected: Vec<f32> = vec![0.001, 0.0005, 0.00025];
+        for (rate, expected) in rates.borrow().iter().zip(expected) {
+            assert!((rate - expected).abs() < 1e-9, "{rate}");
+        }
+        assert_eq!(rates.borrow().len(), 3);
+        assert!((trainer.model.get_learning_rate() - 0.000125).abs() < 1e-9);
+    }
+
+    // Label i is present when x_i is positive, so both labels can be on at once
+    #[derive(Clone)]
+    struct TwoLabelExample {
+        x: [f32; 2],
+    }
+
+    impl TwoLabelExample {
+        fn labels(&self) -> [f32; 2] {
+            self.x.map(|value| if value > 0.0 { 1.0 } else { 0.0 })
+        }
+    }
+
+    impl SupervisedExample for TwoLabelExample {
+        fn get_response(&self) -> Data {
+            Data::VectorF32(arr1(&self.labels()))
+        }
+
+        fn get_input(&self) -> Data {
+            Data::VectorF32(arr1(&self.x))
+        }
+
+        // Only reached without label thresholds, the trainer thresholds the labels itself otherwise
+        fn get_test_error(&self, _predicted: DataContainer) -> PredictionError {
+            panic!("Expected the network's label thresholds to be used")
+        }
+
+        fn get_label_key(&self) -> usize {
+            self.x.iter().filter(|value| **value > 0.0).count()
+        }
+    }
+
+    #[test]
+    fn multi_label_trainer_test() {
+        set_seed(23);
+        let distribution = Uniform::new(-1.0, 1.0);
+        let train: Vec<TwoLabelExample> = with_rng(|rng| {
+            (0..400)
+                .map(|_i| TwoLabelExample {
+                    x: [distribution.sample(rng), distribution.sample(rng)],
+                })
+                .collect()
+        });
+        let points: [[f32; 2]; 4] = [[-0.7, -0.6], [-0.8, 0.7], [0.6, -0.9], [0.8, 0.7]];
+        let examples: Vec<TwoLabelExample> =
+            points.iter().map(|x| TwoLabelExample { x: *x }).collect();
+
+        let hidden = SequentialNetwork::builder()
+            .input(vec![2])
+            .linear(8, "relu")
+            .decay(LearningDecayType::rms_prop(0.02, 0.9));
+        let train_config: TrainerConfig<TwoLabelExample> =
+            TrainerConfig::new(6, 8, train, examples.clone(), examples)
+                .with_early_stopping(EarlyStopping::disabled());
+        let mut trainer: SupervisedTrainer<BinaryClassifierNetwork, TwoLabelExample> =
+            SupervisedTrainer::new(BinaryClassifierNetwork::new(hidden, 2), train_config);
+
+        let (_config, history) = trainer.fit().unwrap();
+
+        assert!(history.epochs()[0]
+            .validation_metrics
+            .contains_key("hamming_loss"));
+        let metrics = history.test_report().metrics().multi_label().unwrap();
+        assert_eq!(metrics.count(), 4);
+        assert_eq!(metrics.hamming_loss(), 0.0);
+        assert_eq!(metrics.subset_accuracy(), 1.0);
+
+        // Unreachable thresholds predict no labels, missing the 4 present out of 8
+        trainer.model.set_thresholds(vec![1.1, 1.1]);
+        let report: EvaluationReport = trainer.evaluate().unwrap();
+        let metrics = report.metrics().multi_label().unwrap();
+        assert_eq!(metrics.hamming_loss(), 0.5);
+        assert_eq!(metrics.subset_accuracy(), 0.25);
+    }
+
+    #[test]
+    fn history_test() {
+        let train: Vec<QuadraticExample> = (0..20)
+            .map(|i| QuadraticExample::new((i as f32) / 7.0 + 1.0))
+            .collect();
+        let validation: Vec<QuadraticExample> = (0..4)
+            .map(|i| QuadraticExample::new((i as f32) / 2.0 + 1.1))
+            .collect();
+        let test: Vec<QuadraticExample> = (0..4)
+            .map(|i| QuadraticExample::new((i as f32) / 2.0 + 1.3))
+            .collect();
+
+        let dir = std::env::temp_dir();
+        let csv_path = dir.join(format!("history_test_{}.csv", std::process::id()));
+        let batch_path = dir.join(format!("history_test_batches_{}.csv", std::process::id()));
+
+        let recorder = HistoryRecorder::new(csv_path.to_str().unwrap(), HistoryFormat::Csv)
+            .unwrap()
+            .with_batch_log(batch_path.to_str().unwrap())
+            .unwrap();
+        let train_config: TrainerConfig<QuadraticExample> =
+            TrainerConfig::new(3, 8, train, validation, test)
+                .with_early_stopping(EarlyStopping::new(3, 0.0, false));
+        let mut trainer: SupervisedTrainer<RegressorNetwork, QuadraticExample> =
+            SupervisedTrainer::new(quadratic_regressor(), train_config).with_callback(recorder);
+
+        let (_config, history) = trainer.fit().unwrap();
+
+        assert_eq!(history.epochs().len(), 3);
+        assert_eq!(history.batches().len(), 9);
+        for (i, record) in history.epochs().iter().enumerate() {
+            assert_eq!((record.epoch, record.time_step), (i, 3 * (i + 1)));
+            assert!(record.validation_metrics.contains_key("rmse"));
+        }
+        assert!(history.test_report().metrics().regression().is_some());
+
+        let csv: String = std::fs::read_to_string(csv_path).unwrap();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[0],
+            "epoch,time_step,train_loss,learning_rate,wall_time,validation_error,mae,mean_loss,r_squared,rmse"
+        );
+        assert!(lines[3].starts_with("2,9,"));
+
+        let batches: String = std::fs::read_to_string(batch_path).unwrap();
+        let batch_lines: Vec<&str> = batches.lines().collect();
+        assert_eq!(batch_lines.len(), 10);
+        assert_eq!(batch_lines[0], "epoch,batch,loss,wall_time");
+        assert!(batch_lines[9].starts_with("2,2,"));
+    }
+
+    #[test]
+    fn checkpoint_resume_test() {
+        let trainer = |total_epochs: usize, directory: &str| {
+            set_seed(5);
+            let regressor: RegressorNetwork = RegressorNetwork::new(
+                vec![1],
+                vec![1],
+                vec![8],
+                PenaltyConfig::none(),
+                NetworkMaskType::from_probabilities(0.9, 0.8),
+                HyperParams::new(
+                    LearningDecayType::rms_prop(0.01, 0.9),
+                    DescentType::nesterov(0.9),
+                    NormalizationType::none(),
+                    Precision::F32,
+                    LearningRateSchedule::constant(),
+                ),
+            );
+
+            let data: Vec<QuadraticExample> = (0..60)
+                .map(|i| QuadraticExample::new((i as f32) / 20.0 + 1.0))
+                .collect();
+            let train_config: TrainerConfig<QuadraticExample> = TrainerConfig::new_with_split(
+                total_epochs,
+                8,
+                data,
+                RandomSplitter::three_way(0.7, 0.2),
+            )
+            .with_early_stopping(EarlyStopping::new(10, 0.0, true))
+            .with_sampler(ShuffledSampler::from_global_rng());
+
+            SupervisedTrainer::<RegressorNetwork, QuadraticExample>::new(regressor, train_config)
+                .with_checkpointer(Checkpointer::new(directory, 1, 2))
+        };
+
+        let dir = std::env::temp_dir().join(format!("checkpoint_resume_{}", std::process::id()));
+        let full_dir = dir.join("full");
+        let resumed_dir = dir.join("resumed");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let full_path = dir.join("full.json");
+        let mut full = trainer(4, full_dir.to_str().unwrap());
+        let full_history = full.train(full_path.to_str().unwrap()).unwrap();
+
+        // Kept: the last two checkpoints plus the best one if it's older
+        let files: usize = std::fs::read_dir(&full_dir).unwrap().count();
+        assert!(files == 2 || files == 3, "{files}");
+
+        let interrupted_path = dir.join("interrupted.json");
+        let mut interrupted = trainer(2, resumed_dir.to_str().unwrap());
+        interrupted
+            .train(interrupted_path.to_str().unwrap())
+            .unwrap();
+
+        let resumed_path = dir.join("resumed.json");
+        let mut resumed = trainer(4, resumed_dir.to_str().unwrap());
+        set_seed(999);
+        resumed
+            .resume(resumed_dir.join("checkpoint_00001.json").to_str().unwrap())
+            .unwrap();
+        let resumed_history = resumed.train(resumed_path.to_str().unwrap()).unwrap();
+
+        let records = |history: &TrainingHistory| -> Vec<(usize, usize, f32, Option<f32>)> {
+            history
+                .epochs()
+                .iter()
+                .map(|r| (r.epoch, r.time_step, r.train_loss, r.validation_error))
+                .collect()
+        };
+        assert_eq!(records(&resumed_history), records(&full_history));
+        assert_eq!(
+            std::fs::read_to_string(resumed_path).unwrap(),
+            std::fs::read_to_string(full_path).unwrap()
+        );
+
+        let _ = std::fs::remove_dir_all(&dir);
     }
 }
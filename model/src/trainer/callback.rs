@@ -0,0 +1,60 @@
+// builtin
+
+// external
+
+// internal
+use crate::{network::config_types::Config, trainer::metrics::EvaluationReport};
+pub mod progress_printer;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CallbackAction {
+    Continue,
+    Stop,
+    // The network trains on at this rate from the next batch on
+    SetLearningRate(f32),
+}
+
+impl CallbackAction {
+    // A Stop wins over everything, otherwise the later of two learning rates is kept
+    pub fn merge(self, other: CallbackAction) -> CallbackAction {
+        match (self, other) {
+            (CallbackAction::Stop, _) | (_, CallbackAction::Stop) => CallbackAction::Stop,
+            (action, CallbackAction::Continue) => action,
+            (_, action) => action,
+        }
+    }
+}
+
+// State at the end of an epoch, only borrowed for the duration of the hook
+pub struct EpochSummary<'e> {
+    pub epoch: usize,
+    pub time_step: usize,
+    // Mean loss per training example over the batches fitted this epoch
+    pub train_loss: f32,
+    pub learning_rate: f32,
+    // Seconds since training began
+    pub wall_time: f64,
+    pub validation: &'e EvaluationReport,
+    pub config: &'e Config,
+}
+
+// Hooks into SupervisedTrainer::train, each one does nothing by default. A Stop from any callback
+// ends training, after the current batch or epoch. Early stopping and the plateau scheduler of the
+// TrainerConfig run first, before the trainer's own callbacks
+pub trait TrainerCallback {
+    fn on_train_begin(&mut self, _total_epochs: usize) {}
+
+    fn on_epoch_begin(&mut self, _epoch: usize) {}
+
+    // `loss` is summed over the examples of the batch
+    fn on_batch_end(&mut self, _epoch: usize, _batch: usize, _loss: f32) -> CallbackAction {
+        CallbackAction::Continue
+    }
+
+    fn on_epoch_end(&mut self, _summary: &EpochSummary) -> CallbackAction {
+        CallbackAction::Continue
+    }
+
+    // `epochs` is the number of epochs that ran, the report scores the kept config on the test set
+    fn on_train_end(&mut self, _epochs: usize, _test: &EvaluationReport) {}
+}
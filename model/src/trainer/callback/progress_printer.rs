@@ -0,0 +1,54 @@
+// builtin
+
+// external
+
+// internal
+use crate::trainer::{
+    callback::{CallbackAction, EpochSummary, TrainerCallback},
+    metrics::EvaluationReport,
+};
+
+// Default console output of SupervisedTrainer
+#[derive(Default)]
+pub struct ProgressPrinter {
+    total_epochs: usize,
+    learning_rate: Option<f32>,
+}
+
+impl ProgressPrinter {
+    pub fn new() -> ProgressPrinter {
+        ProgressPrinter::default()
+    }
+}
+
+impl TrainerCallback for ProgressPrinter {
+    fn on_train_begin(&mut self, total_epochs: usize) {
+        self.total_epochs = total_epochs;
+        self.learning_rate = None;
+    }
+
+    fn on_epoch_end(&mut self, summary: &EpochSummary) -> CallbackAction {
+        let i: usize = summary.epoch;
+        println!("Validation error {i}: {:?}\n", summary.validation.error());
+
+        if let Some(previous) = self.learning_rate {
+            if previous != summary.learning_rate {
+                println!(
+                    "Learning rate changed to {} after {i} iterations",
+                    summary.learning_rate
+                );
+            }
+        }
+        self.learning_rate = Some(summary.learning_rate);
+
+        CallbackAction::Continue
+    }
+
+    fn on_train_end(&mut self, epochs: usize, test: &EvaluationReport) {
+        if epochs < self.total_epochs {
+            println!("Training stopped after {epochs} iterations");
+        }
+
+        println!("Training finished with test error: {:?}", test.error());
+    }
+}
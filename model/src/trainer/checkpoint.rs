@@ -0,0 +1,82 @@
+// builtin
+use std::{
+    fs::read_to_string,
+    io::{Error, ErrorKind, Result},
+};
+
+// external
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+
+// internal
+use crate::{
+    network::config_types::{write_atomic, Config},
+    trainer::{
+        checkpoint::checkpointer::SavedCheckpoint, early_stopping::EarlyStopping,
+        history::EpochRecord, plateau::PlateauScheduler, sampler::SamplerState,
+    },
+};
+pub mod checkpointer;
+
+// Trainer progress on top of the network's own config, enough for a resumed run to continue
+// exactly where the interrupted one left off
+#[derive(Serialize, Deserialize, Clone)]
+pub struct TrainingState {
+    // Number of epochs completed
+    pub(crate) epoch: usize,
+    pub(crate) kept_config: Config,
+    pub(crate) history: Vec<EpochRecord>,
+    pub(crate) early_stopping: EarlyStopping,
+    pub(crate) plateau: Option<PlateauScheduler>,
+    pub(crate) sampler: SamplerState,
+    pub(crate) rng: ChaCha12Rng,
+    pub(crate) checkpoints: Vec<SavedCheckpoint>,
+}
+
+impl TrainingState {
+    pub fn epoch(&self) -> usize {
+        self.epoch
+    }
+
+    pub fn history(&self) -> &Vec<EpochRecord> {
+        &self.history
+    }
+
+    pub fn early_stopping(&self) -> &EarlyStopping {
+        &self.early_stopping
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Checkpoint {
+    network: Config,
+    state: TrainingState,
+}
+
+impl Checkpoint {
+    pub fn new(network: Config, state: TrainingState) -> Checkpoint {
+        Checkpoint { network, state }
+    }
+
+    pub fn network(&self) -> &Config {
+        &self.network
+    }
+
+    pub fn state(&self) -> &TrainingState {
+        &self.state
+    }
+
+    pub fn into_parts(self) -> (Config, TrainingState) {
+        (self.network, self.state)
+    }
+
+    pub fn save_to_file(&self, path: &str) -> Result<()> {
+        let json_string = serde_json::to_string(self).unwrap();
+        write_atomic(path, &json_string)
+    }
+
+    pub fn load_from_file(path: &str) -> Result<Checkpoint> {
+        let data = read_to_string(path)?;
+        serde_json::from_str(&data).map_err(|err| Error::new(ErrorKind::InvalidData, err))
+    }
+}
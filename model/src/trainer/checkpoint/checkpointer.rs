@@ -0,0 +1,150 @@
+// builtin
+use std::{
+    fs::{create_dir_all, remove_file},
+    io::{ErrorKind, Result},
+    path::PathBuf,
+};
+
+// external
+use serde::{Deserialize, Serialize};
+
+// internal
+use crate::trainer::checkpoint::Checkpoint;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct SavedCheckpoint {
+    // Zero based index of the epoch the checkpoint was taken after
+    pub epoch: usize,
+    pub path: String,
+    // Validation error of that epoch, see PredictionError::get_value
+    pub error: Option<f32>,
+}
+
+// Writes a checkpoint every `every` epochs to `directory`, keeping the last `keep_last` of them
+// plus the one with the lowest validation error
+pub struct Checkpointer {
+    directory: PathBuf,
+    every: usize,
+    keep_last: usize,
+    saved: Vec<SavedCheckpoint>,
+}
+
+impl Checkpointer {
+    pub fn new(directory: &str, every: usize, keep_last: usize) -> Checkpointer {
+        if every == 0 || keep_last == 0 {
+            panic!("[CHECKPOINT] Invalid checkpoint schedule, expected at least 1 for every ({every}) and keep_last ({keep_last})");
+        }
+
+        Checkpointer {
+            directory: PathBuf::from(directory),
+            every,
+            keep_last,
+            saved: Vec::new(),
+        }
+    }
+
+    pub fn is_due(&self, epoch: usize) -> bool {
+        (epoch + 1).is_multiple_of(self.every)
+    }
+
+    pub fn saved(&self) -> &Vec<SavedCheckpoint> {
+        &self.saved
+    }
+
+    // Restored from a checkpoint, so rotation carries on over the files of the interrupted run
+    pub fn set_saved(&mut self, saved: Vec<SavedCheckpoint>) {
+        self.saved = saved;
+    }
+
+    pub fn latest(&self) -> Option<&SavedCheckpoint> {
+        self.saved.last()
+    }
+
+    pub fn best(&self) -> Option<&SavedCheckpoint> {
+        Checkpointer::best_index(&self.saved).map(|i| &self.saved[i])
+    }
+
+    pub fn next_path(&self, epoch: usize) -> String {
+        self.directory
+            .join(format!("checkpoint_{epoch:05}.json"))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    // The list of checkpoints that are kept once one is added for `epoch`. It goes into the
+    // checkpoint itself, before the file is written
+    pub fn plan(&self, epoch: usize, error: Option<f32>) -> Vec<SavedCheckpoint> {
+        let mut saved: Vec<SavedCheckpoint> = self.saved.clone();
+        saved.push(SavedCheckpoint {
+            epoch,
+            path: self.next_path(epoch),
+            error,
+        });
+
+        let first_kept: usize = saved.len().saturating_sub(self.keep_last);
+        let best: Option<usize> = Checkpointer::best_index(&saved);
+
+        saved
+            .into_iter()
+            .enumerate()
+            .filter(|(i, _checkpoint)| *i >= first_kept || Some(*i) == best)
+            .map(|(_i, checkpoint)| checkpoint)
+            .collect()
+    }
+
+    // Writes the checkpoint planned for `epoch`, then removes the files that rotated out
+    pub fn save(&mut self, checkpoint: &Checkpoint, planned: Vec<SavedCheckpoint>) -> Result<()> {
+        create_dir_all(&self.directory)?;
+        let latest: &SavedCheckpoint = planned
+            .last()
+            .expect("[CHECKPOINT] Planned checkpoints can't be empty");
+        checkpoint.save_to_file(&latest.path)?;
+
+        for old in &self.saved {
+            if !planned.contains(old) {
+                match remove_file(&old.path) {
+                    Err(err) if err.kind() != ErrorKind::NotFound => return Err(err),
+                    _ => {}
+                }
+            }
+        }
+
+        self.saved = planned;
+        Ok(())
+    }
+
+    // Earliest of the lowest errors, checkpoints without a comparable error never count as best
+    fn best_index(saved: &[SavedCheckpoint]) -> Option<usize> {
+        let mut best: Option<(usize, f32)> = None;
+        for (i, checkpoint) in saved.iter().enumerate() {
+            if let Some(error) = checkpoint.error {
+                if best.is_none_or(|(_i, best_error)| error < best_error) {
+                    best = Some((i, error));
+                }
+            }
+        }
+
+        best.map(|(i, _error)| i)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trainer::checkpoint::checkpointer::Checkpointer;
+
+    #[test]
+    fn keeps_last_and_best() {
+        let mut checkpointer: Checkpointer = Checkpointer::new("checkpoints", 2, 2);
+        assert!(!checkpointer.is_due(0) && checkpointer.is_due(1));
+
+        for (epoch, error) in [(1, 0.5), (3, 0.2), (5, 0.4), (7, 0.3)] {
+            let planned = checkpointer.plan(epoch, Some(error));
+            checkpointer.set_saved(planned);
+        }
+
+        let epochs: Vec<usize> = checkpointer.saved().iter().map(|c| c.epoch).collect();
+        assert_eq!(epochs, vec![3, 5, 7]);
+        assert_eq!(checkpointer.best().unwrap().epoch, 3);
+        assert_eq!(checkpointer.latest().unwrap().epoch, 7);
+    }
+}
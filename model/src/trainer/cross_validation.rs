@@ -0,0 +1,84 @@
+// builtin
+
+// external
+
+// internal
+use crate::trainer::{error::PredictionError, metrics::EvaluationReport};
+
+// Per fold errors with the mean and population variance of their values (loss or error rate). A
+// fold's loss is divided by its number of examples, so folds of different sizes compare
+#[derive(Debug, Clone)]
+pub struct CrossValidationReport {
+    errors: Vec<PredictionError>,
+    mean: f32,
+    variance: f32,
+}
+
+impl CrossValidationReport {
+    pub fn new(reports: &[EvaluationReport]) -> CrossValidationReport {
+        let errors: Vec<PredictionError> = reports
+            .iter()
+            .map(|report| report.per_example_error())
+            .collect();
+        let values: Vec<f32> = errors.iter().filter_map(|e| e.get_value()).collect();
+        if values.len() != errors.len() {
+            println!("[CROSS_VALIDATION] Skipping folds without a comparable error in the summary");
+        }
+
+        let count: f32 = values.len().max(1) as f32;
+        let mean: f32 = values.iter().sum::<f32>() / count;
+        let variance: f32 = values.iter().map(|v| (v - mean).powi(2)).sum::<f32>() / count;
+
+        CrossValidationReport {
+            errors,
+            mean,
+            variance,
+        }
+    }
+
+    pub fn errors(&self) -> &Vec<PredictionError> {
+        &self.errors
+    }
+
+    pub fn mean(&self) -> f32 {
+        self.mean
+    }
+
+    pub fn variance(&self) -> f32 {
+        self.variance
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trainer::{
+        cross_validation::CrossValidationReport,
+        error::PredictionError,
+        metrics::{EvaluationReport, Metrics},
+    };
+
+    // A fold of `size` examples, each with a loss of `loss`
+    fn fold_report(size: usize, loss: f32) -> EvaluationReport {
+        let mut metrics: Metrics = Metrics::empty();
+        let mut error: PredictionError = PredictionError::empty();
+        for _i in 0..size {
+            let example = PredictionError::Loss { loss };
+            metrics.add_example(&example, &[0.0], &[0.0]).unwrap();
+            error = error.plus(&example);
+        }
+
+        EvaluationReport::new(error, metrics)
+    }
+
+    #[test]
+    fn uneven_folds() {
+        let report: CrossValidationReport = CrossValidationReport::new(&[
+            fold_report(4, 0.5),
+            fold_report(3, 0.5),
+            fold_report(3, 0.5),
+        ]);
+
+        assert!((report.mean() - 0.5).abs() < 1e-6);
+        assert!(report.variance().abs() < 1e-6);
+    }
+}
@@ -4,22 +4,34 @@ use std::mem::take;
 // external
 
 // internal
+use crate::trainer::examples::SupervisedExample;
+pub mod k_fold;
 pub mod random_splitter;
+pub mod stratified_splitter;
 
 pub struct DataSubsets<T> {
     train: Vec<T>,
+    validation: Vec<T>,
     test: Vec<T>,
 }
 
 impl<T> DataSubsets<T> {
-    pub fn new(train: Vec<T>, test: Vec<T>) -> DataSubsets<T> {
-        DataSubsets { train, test }
+    pub fn new(train: Vec<T>, validation: Vec<T>, test: Vec<T>) -> DataSubsets<T> {
+        DataSubsets {
+            train,
+            validation,
+            test,
+        }
     }
 
     pub fn train_ref(&self) -> &Vec<T> {
         &self.train
     }
 
+    pub fn validation_ref(&self) -> &Vec<T> {
+        &self.validation
+    }
+
     pub fn test_ref(&self) -> &Vec<T> {
         &self.test
     }
@@ -28,11 +40,15 @@ impl<T> DataSubsets<T> {
         take(&mut self.train)
     }
 
+    pub fn take_validation(&mut self) -> Vec<T> {
+        take(&mut self.validation)
+    }
+
     pub fn take_test(&mut self) -> Vec<T> {
         take(&mut self.test)
     }
 }
 
 pub trait DataSplitter {
-    fn split<T>(&self, data: Vec<T>) -> DataSubsets<T>;
+    fn split<T: SupervisedExample>(&self, data: Vec<T>) -> DataSubsets<T>;
 }
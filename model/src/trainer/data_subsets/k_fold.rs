@@ -0,0 +1,99 @@
+// builtin
+
+// external
+use rand::seq::SliceRandom;
+
+// internal
+use crate::{
+    random::with_rng,
+    trainer::{data_subsets::DataSubsets, examples::SupervisedExample},
+};
+
+// Shuffles the data into k folds of nearly equal size. Fold i holds out the i-th part as its test
+// set and trains on the rest, there is no validation set so every fold trains for the same epochs
+pub struct KFold {
+    k: usize,
+}
+
+impl KFold {
+    pub fn new(k: usize) -> KFold {
+        if k < 2 {
+            panic!("[K_FOLD] Invalid fold count, expected at least 2 but got {k}");
+        }
+
+        KFold { k }
+    }
+
+    pub fn get_k(&self) -> usize {
+        self.k
+    }
+
+    pub fn folds<T: SupervisedExample + Clone>(&self, mut data: Vec<T>) -> Vec<DataSubsets<T>> {
+        if data.len() < self.k {
+            panic!(
+                "[K_FOLD] Not enough examples for {} folds, got {}",
+                self.k,
+                data.len()
+            );
+        }
+
+        with_rng(|rng| data.shuffle(rng));
+
+        let base_size: usize = data.len() / self.k;
+        let remainder: usize = data.len() % self.k;
+        let mut parts: Vec<Vec<T>> = Vec::new();
+        for i in 0..self.k {
+            let size: usize = base_size + usize::from(i < remainder);
+            parts.push(data.drain(..size).collect());
+        }
+
+        (0..self.k)
+            .map(|held_out| {
+                let train: Vec<T> = parts
+                    .iter()
+                    .enumerate()
+                    .filter(|(i, _part)| *i != held_out)
+                    .flat_map(|(_i, part)| part.iter().cloned())
+                    .collect();
+
+                DataSubsets::new(train, Vec::new(), parts[held_out].clone())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trainer::{
+        data_subsets::k_fold::KFold,
+        examples::{QuadraticExample, SupervisedExample},
+    };
+
+    fn inputs(examples: &[QuadraticExample]) -> Vec<f32> {
+        examples
+            .iter()
+            .flat_map(|example| example.get_input().flatten_as::<f32>())
+            .collect()
+    }
+
+    #[test]
+    fn folds_partition_data() {
+        let data: Vec<QuadraticExample> =
+            (0..10).map(|i| QuadraticExample::new(i as f32)).collect();
+        let folds = KFold::new(3).folds(data);
+
+        let mut held_out: Vec<f32> = Vec::new();
+        for (fold, size) in folds.iter().zip([4, 3, 3]) {
+            assert_eq!(fold.test_ref().len(), size);
+            assert_eq!(fold.train_ref().len(), 10 - size);
+            assert!(fold.validation_ref().is_empty());
+
+            let test: Vec<f32> = inputs(fold.test_ref());
+            assert!(inputs(fold.train_ref()).iter().all(|x| !test.contains(x)));
+            held_out.extend(test);
+        }
+
+        held_out.sort_by(f32::total_cmp);
+        assert_eq!(held_out, (0..10).map(|i| i as f32).collect::<Vec<f32>>());
+    }
+}
@@ -2,40 +2,94 @@
 
 // external
 use ndarray_rand::rand_distr::num_traits::clamp;
-use rand::{distributions::Bernoulli, prelude::Distribution};
+use rand::{distributions::Uniform, prelude::Distribution};
 
 // internal
-use crate::trainer::data_subsets::{DataSplitter, DataSubsets};
+use crate::{
+    random::with_rng,
+    trainer::{
+        data_subsets::{DataSplitter, DataSubsets},
+        examples::SupervisedExample,
+    },
+};
 
+// Independent draw per example, whatever isn't train or validation ends up in test
 pub struct RandomSplitter {
-    split_prob: f64,
+    train_prob: f64,
+    validation_prob: f64,
 }
 
 impl RandomSplitter {
     pub fn new(split_prob: f64) -> RandomSplitter {
+        RandomSplitter::three_way(split_prob, 0.0)
+    }
+
+    pub fn three_way(train_prob: f64, validation_prob: f64) -> RandomSplitter {
+        let train_prob: f64 = clamp(train_prob, 0.0, 1.0);
+        let validation_prob: f64 = clamp(validation_prob, 0.0, 1.0);
+
+        if train_prob + validation_prob > 1.0 {
+            panic!("[RANDOM_SPLITTER] Invalid split ratios, train ({train_prob}) and validation ({validation_prob}) add up to more than 1");
+        }
+
         RandomSplitter {
-            split_prob: clamp(split_prob, 0.0, 1.0),
+            train_prob,
+            validation_prob,
         }
     }
 }
 
 impl DataSplitter for RandomSplitter {
-    fn split<T>(&self, data: Vec<T>) -> DataSubsets<T> {
+    fn split<T: SupervisedExample>(&self, data: Vec<T>) -> DataSubsets<T> {
         let mut train: Vec<T> = Vec::new();
+        let mut validation: Vec<T> = Vec::new();
         let mut test: Vec<T> = Vec::new();
-        let mut rng = rand::thread_rng();
-        let distribution = Bernoulli::new(self.split_prob).unwrap();
+        let distribution = Uniform::new(0.0, 1.0);
 
         for example in data {
-            let is_train = distribution.sample(&mut rng);
+            let draw: f64 = with_rng(|rng| distribution.sample(rng));
 
-            if is_train {
+            if draw < self.train_prob {
                 train.push(example);
+            } else if draw < self.train_prob + self.validation_prob {
+                validation.push(example);
             } else {
                 test.push(example);
             }
         }
 
-        DataSubsets::new(train, test)
+        DataSubsets::new(train, validation, test)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trainer::{
+        data_subsets::{random_splitter::RandomSplitter, DataSplitter},
+        examples::QuadraticExample,
+    };
+
+    fn examples(count: usize) -> Vec<QuadraticExample> {
+        (0..count)
+            .map(|i| QuadraticExample::new(i as f32))
+            .collect()
+    }
+
+    #[test]
+    fn three_way_split() {
+        let subsets = RandomSplitter::three_way(0.6, 0.3).split(examples(3000));
+
+        let sizes = [
+            subsets.train_ref().len(),
+            subsets.validation_ref().len(),
+            subsets.test_ref().len(),
+        ];
+        assert_eq!(sizes.iter().sum::<usize>(), 3000);
+        for (size, expected) in sizes.iter().zip([1800, 900, 300]) {
+            assert!(size.abs_diff(expected) < 150, "{sizes:?}");
+        }
+
+        let subsets = RandomSplitter::new(1.0).split(examples(10));
+        assert_eq!(subsets.train_ref().len(), 10);
     }
 }
@@ -0,0 +1,129 @@
+// builtin
+use std::collections::BTreeMap;
+
+// external
+use ndarray_rand::rand_distr::num_traits::clamp;
+use rand::seq::SliceRandom;
+
+// internal
+use crate::{
+    random::with_rng,
+    trainer::{
+        data_subsets::{DataSplitter, DataSubsets},
+        examples::SupervisedExample,
+    },
+};
+
+// Splits every label group with the same ratios, so each subset keeps the class balance of the
+// data and the subset sizes are fixed instead of drawn
+pub struct StratifiedSplitter {
+    train_ratio: f64,
+    validation_ratio: f64,
+}
+
+impl StratifiedSplitter {
+    pub fn new(train_ratio: f64, validation_ratio: f64) -> StratifiedSplitter {
+        let train_ratio: f64 = clamp(train_ratio, 0.0, 1.0);
+        let validation_ratio: f64 = clamp(validation_ratio, 0.0, 1.0);
+
+        if train_ratio + validation_ratio > 1.0 {
+            panic!("[STRATIFIED_SPLITTER] Invalid split ratios, train ({train_ratio}) and validation ({validation_ratio}) add up to more than 1");
+        }
+
+        StratifiedSplitter {
+            train_ratio,
+            validation_ratio,
+        }
+    }
+}
+
+impl DataSplitter for StratifiedSplitter {
+    fn split<T: SupervisedExample>(&self, data: Vec<T>) -> DataSubsets<T> {
+        let mut groups: BTreeMap<usize, Vec<T>> = BTreeMap::new();
+        for example in data {
+            groups
+                .entry(example.get_label_key())
+                .or_default()
+                .push(example);
+        }
+
+        let mut train: Vec<T> = Vec::new();
+        let mut validation: Vec<T> = Vec::new();
+        let mut test: Vec<T> = Vec::new();
+
+        for (_key, mut group) in groups {
+            with_rng(|rng| group.shuffle(rng));
+
+            let total: f64 = group.len() as f64;
+            let train_end: usize = (total * self.train_ratio).round() as usize;
+            let validation_end: usize = ((total * (self.train_ratio + self.validation_ratio))
+                .round() as usize)
+                .min(group.len());
+
+            test.extend(group.drain(validation_end..));
+            validation.extend(group.drain(train_end..));
+            train.extend(group);
+        }
+
+        DataSubsets::new(train, validation, test)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::arr1;
+
+    use crate::{
+        data::{data_container::DataContainer, Data},
+        trainer::{
+            data_subsets::{stratified_splitter::StratifiedSplitter, DataSplitter},
+            error::PredictionError,
+            examples::SupervisedExample,
+        },
+    };
+
+    struct LabeledExample {
+        label: usize,
+    }
+
+    impl SupervisedExample for LabeledExample {
+        fn get_response(&self) -> Data {
+            Data::VectorF32(arr1(&[self.label as f32]))
+        }
+
+        fn get_input(&self) -> Data {
+            Data::VectorF32(arr1(&[self.label as f32]))
+        }
+
+        fn get_test_error(&self, _predicted: DataContainer) -> PredictionError {
+            PredictionError::empty()
+        }
+
+        fn get_label_key(&self) -> usize {
+            self.label
+        }
+    }
+
+    fn count_labels(examples: &[LabeledExample]) -> [usize; 3] {
+        let mut counts = [0; 3];
+        for example in examples {
+            counts[example.label] += 1;
+        }
+        counts
+    }
+
+    #[test]
+    fn keeps_class_balance() {
+        let data: Vec<LabeledExample> = [50, 30, 20]
+            .iter()
+            .enumerate()
+            .flat_map(|(label, count)| (0..*count).map(move |_i| LabeledExample { label }))
+            .collect();
+
+        let subsets = StratifiedSplitter::new(0.6, 0.2).split(data);
+
+        assert_eq!(count_labels(subsets.train_ref()), [30, 18, 12]);
+        assert_eq!(count_labels(subsets.validation_ref()), [10, 6, 4]);
+        assert_eq!(count_labels(subsets.test_ref()), [10, 6, 4]);
+    }
+}
@@ -0,0 +1,154 @@
+// builtin
+
+// external
+use serde::{Deserialize, Serialize};
+
+// internal
+use crate::trainer::{
+    callback::{CallbackAction, EpochSummary, TrainerCallback},
+    error::PredictionError,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum StoppingStatus {
+    Improved,
+    Waiting { epochs: usize },
+    Stop,
+}
+
+// Stops once the error hasn't improved on its best value by more than `min_delta` for `patience`
+// epochs in a row. As a callback it follows the per example validation error, the mean loss or
+// the error rate, so `min_delta` means the same for any validation set size
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct EarlyStopping {
+    patience: usize,
+    min_delta: f32,
+    restore_best: bool,
+    best_error: PredictionError,
+    best_epoch: usize,
+    // Number of updates the network had taken by the end of the best epoch
+    best_time_step: usize,
+    wait: usize,
+}
+
+impl EarlyStopping {
+    // `restore_best` keeps the best epoch's config, otherwise the one right before the stop is kept
+    pub fn new(patience: usize, min_delta: f32, restore_best: bool) -> EarlyStopping {
+        if patience == 0 {
+            panic!("[EARLY_STOPPING] Invalid patience, expected at least 1 epoch but got 0");
+        }
+
+        EarlyStopping {
+            patience,
+            min_delta,
+            restore_best,
+            best_error: PredictionError::empty(),
+            best_epoch: 0,
+            best_time_step: 0,
+            wait: 0,
+        }
+    }
+
+    // Stops on the first epoch that doesn't improve
+    pub fn immediate() -> EarlyStopping {
+        EarlyStopping::new(1, 0.0, true)
+    }
+
+    // Never stops, the best epoch is still tracked so its config is the one kept
+    pub fn disabled() -> EarlyStopping {
+        EarlyStopping::new(usize::MAX, 0.0, true)
+    }
+
+    pub fn update(&mut self, epoch: usize, error: &PredictionError) -> StoppingStatus {
+        if self.is_improvement(error) {
+            self.best_error = error.clone();
+            self.best_epoch = epoch;
+            self.wait = 0;
+            return StoppingStatus::Improved;
+        }
+
+        self.wait += 1;
+        if self.wait < self.patience {
+            return StoppingStatus::Waiting { epochs: self.wait };
+        }
+
+        StoppingStatus::Stop
+    }
+
+    fn is_improvement(&self, error: &PredictionError) -> bool {
+        if self.best_error.is_empty() {
+            return true;
+        }
+
+        match (error.get_value(), self.best_error.get_value()) {
+            (Some(value), Some(best)) => value < best - self.min_delta,
+            _ => false,
+        }
+    }
+
+    pub fn restore_best(&self) -> bool {
+        self.restore_best
+    }
+
+    pub fn best_error(&self) -> &PredictionError {
+        &self.best_error
+    }
+
+    // Zero based, so the best config was reached after `best_epoch() + 1` epochs
+    pub fn best_epoch(&self) -> usize {
+        self.best_epoch
+    }
+
+    pub fn best_time_step(&self) -> usize {
+        self.best_time_step
+    }
+}
+
+impl TrainerCallback for EarlyStopping {
+    fn on_epoch_end(&mut self, summary: &EpochSummary) -> CallbackAction {
+        match self.update(summary.epoch, &summary.validation.per_example_error()) {
+            StoppingStatus::Improved => {
+                self.best_time_step = summary.time_step;
+                CallbackAction::Continue
+            }
+            StoppingStatus::Waiting { .. } => CallbackAction::Continue,
+            StoppingStatus::Stop => CallbackAction::Stop,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trainer::{
+        early_stopping::{EarlyStopping, StoppingStatus},
+        error::PredictionError,
+    };
+
+    #[test]
+    fn patience_and_min_delta() {
+        let mut stopping: EarlyStopping = EarlyStopping::new(3, 0.1, true);
+        let loss = |loss: f32| PredictionError::Loss { loss };
+
+        let statuses: Vec<StoppingStatus> = [2.0, 1.5, 1.45, 1.6, 1.3, 1.25, 1.3, 1.35]
+            .iter()
+            .enumerate()
+            .map(|(epoch, error)| stopping.update(epoch, &loss(*error)))
+            .collect();
+
+        assert_eq!(
+            statuses,
+            vec![
+                StoppingStatus::Improved,
+                StoppingStatus::Improved,
+                StoppingStatus::Waiting { epochs: 1 },
+                StoppingStatus::Waiting { epochs: 2 },
+                StoppingStatus::Improved,
+                StoppingStatus::Waiting { epochs: 1 },
+                StoppingStatus::Waiting { epochs: 2 },
+                StoppingStatus::Stop,
+            ]
+        );
+        assert_eq!(stopping.best_epoch(), 4);
+        assert_eq!(stopping.best_error(), &loss(1.3));
+    }
+}
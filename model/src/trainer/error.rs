@@ -1,13 +1,26 @@
 // builtin
 
 // external
+use serde::{Deserialize, Serialize};
 
 // internal
 
-#[derive(Clone, Debug)]
+#[derive(Serialize, Deserialize, Clone, Debug)]
 pub enum PredictionError {
-    Loss { loss: f32 },
-    Misclassification { incorrect: usize, total: usize },
+    Loss {
+        loss: f32,
+    },
+    Misclassification {
+        incorrect: usize,
+        total: usize,
+    },
+    // Wrong labels out of `labels` and wrong label sets out of `total` examples
+    MultiLabel {
+        incorrect_labels: usize,
+        labels: usize,
+        incorrect_sets: usize,
+        total: usize,
+    },
     Empty,
     None,
 }
@@ -25,13 +38,74 @@ impl PredictionError {
         PredictionError::Empty
     }
 
-    pub fn is_empty(&self) -> bool {
+    // Scores one example, a response above 0.5 marks a label as present
+    pub fn multi_label(response: &[f32], predicted: &[bool]) -> PredictionError {
+        if response.len() != predicted.len() {
+            panic!(
+                "[MULTI_LABEL] Expected {} predicted labels but got {}",
+                response.len(),
+                predicted.len()
+            );
+        }
+
+        let incorrect_labels: usize = response
+            .iter()
+            .zip(predicted)
+            .filter(|(response, predicted)| (**response > 0.5) != **predicted)
+            .count();
+
+        PredictionError::MultiLabel {
+            incorrect_labels,
+            labels: response.len(),
+            incorrect_sets: usize::from(incorrect_labels > 0),
+            total: 1,
+        }
+    }
+
+    // Share of labels predicted wrong, for multi label errors
+    pub fn hamming_loss(&self) -> Option<f32> {
         match self {
-            PredictionError::Empty => true,
-            _ => false,
+            PredictionError::MultiLabel {
+                incorrect_labels,
+                labels,
+                ..
+            } => Some(rate(*incorrect_labels, *labels)),
+            _ => None,
         }
     }
 
+    // Share of examples with every label predicted right, for multi label errors
+    pub fn subset_accuracy(&self) -> Option<f32> {
+        match self {
+            PredictionError::MultiLabel {
+                incorrect_sets,
+                total,
+                ..
+            } => Some(1.0 - rate(*incorrect_sets, *total)),
+            _ => None,
+        }
+    }
+
+    // Loss or error rate, the lower the better
+    pub fn get_value(&self) -> Option<f32> {
+        match self {
+            PredictionError::Loss { loss } => Some(*loss),
+            PredictionError::Misclassification { incorrect, total } => {
+                if *total > 0 {
+                    Some((*incorrect as f32) / (*total as f32))
+                } else {
+                    Some(0.0)
+                }
+            }
+            PredictionError::MultiLabel { .. } => self.hamming_loss(),
+            _ => None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        matches!(self, PredictionError::Empty)
+    }
+
     pub fn plus(&self, other: &PredictionError) -> PredictionError {
         match (self, other) {
             (PredictionError::Loss { loss }, PredictionError::Loss { loss: second_loss }) => {
@@ -52,6 +126,25 @@ impl PredictionError {
                 incorrect: l_incorrect + r_incorrect,
                 total: l_total + r_total,
             },
+            (
+                PredictionError::MultiLabel {
+                    incorrect_labels: l_incorrect_labels,
+                    labels: l_labels,
+                    incorrect_sets: l_incorrect_sets,
+                    total: l_total,
+                },
+                PredictionError::MultiLabel {
+                    incorrect_labels: r_incorrect_labels,
+                    labels: r_labels,
+                    incorrect_sets: r_incorrect_sets,
+                    total: r_total,
+                },
+            ) => PredictionError::MultiLabel {
+                incorrect_labels: l_incorrect_labels + r_incorrect_labels,
+                labels: l_labels + r_labels,
+                incorrect_sets: l_incorrect_sets + r_incorrect_sets,
+                total: l_total + r_total,
+            },
             (PredictionError::Empty, _) => other.clone(),
             (_, PredictionError::Empty) => self.clone(),
             _ => {
@@ -79,6 +172,25 @@ impl PredictionError {
                 *l_incorrect += r_incorrect;
                 *l_total += r_total;
             }
+            (
+                PredictionError::MultiLabel {
+                    incorrect_labels: l_incorrect_labels,
+                    labels: l_labels,
+                    incorrect_sets: l_incorrect_sets,
+                    total: l_total,
+                },
+                PredictionError::MultiLabel {
+                    incorrect_labels: r_incorrect_labels,
+                    labels: r_labels,
+                    incorrect_sets: r_incorrect_sets,
+                    total: r_total,
+                },
+            ) => {
+                *l_incorrect_labels += r_incorrect_labels;
+                *l_labels += r_labels;
+                *l_incorrect_sets += r_incorrect_sets;
+                *l_total += r_total;
+            }
             _ => {
                 PredictionError::warn_mutate();
             }
@@ -112,7 +224,10 @@ impl PartialEq for PredictionError {
                     0.0
                 };
 
-                return l_error_rate == r_error_rate;
+                l_error_rate == r_error_rate
+            }
+            (Self::MultiLabel { .. }, Self::MultiLabel { .. }) => {
+                self.hamming_loss() == other.hamming_loss()
             }
             _ => false,
         }
@@ -149,8 +264,19 @@ impl PartialOrd for PredictionError {
 
                 f32::partial_cmp(&l_error_rate, &r_error_rate)
             }
+            (PredictionError::MultiLabel { .. }, PredictionError::MultiLabel { .. }) => {
+                f32::partial_cmp(&self.hamming_loss()?, &other.hamming_loss()?)
+            }
 
             _ => None,
         }
     }
 }
+
+fn rate(numerator: usize, denominator: usize) -> f32 {
+    if denominator > 0 {
+        (numerator as f32) / (denominator as f32)
+    } else {
+        0.0
+    }
+}
@@ -16,8 +16,18 @@ pub trait SupervisedExample {
     fn get_input(&self) -> Data;
 
     fn get_test_error(&self, predicted: DataContainer) -> PredictionError;
+
+    // Scores the thresholded labels of a network with one decision per label, in place of
+    // get_test_error. A response above 0.5 marks a label as present
+    fn get_label_error(&self, predicted: &[bool]) -> PredictionError {
+        PredictionError::multi_label(&self.get_response().flatten_as::<f32>(), predicted)
+    }
+
+    // Groups examples for stratified splitting, e.g. the class index of a classification example
+    fn get_label_key(&self) -> usize;
 }
 
+#[derive(Clone)]
 pub struct QuadraticExample {
     input: f32,
     output: f32,
@@ -44,8 +54,8 @@ impl SupervisedExample for QuadraticExample {
     fn get_test_error(&self, predicted: DataContainer) -> PredictionError {
         match predicted {
             DataContainer::Inference(data) => {
-                if let Data::VectorF32(vec) = data {
-                    let mut loss = self.output - vec[0];
+                if let Some(&prediction) = data.flatten_as::<f32>().first() {
+                    let mut loss = self.output - prediction;
                     loss *= loss;
                     return PredictionError::Loss { loss };
                 }
@@ -54,4 +64,9 @@ impl SupervisedExample for QuadraticExample {
             _ => panic!("Invalid data type input for test error"),
         }
     }
+
+    // Regression targets are continuous, so every example shares a single stratum
+    fn get_label_key(&self) -> usize {
+        0
+    }
 }
@@ -0,0 +1,106 @@
+// builtin
+use std::collections::BTreeMap;
+
+// external
+use serde::{Deserialize, Serialize};
+
+// internal
+use crate::trainer::{callback::EpochSummary, metrics::EvaluationReport};
+pub mod history_recorder;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct EpochRecord {
+    pub epoch: usize,
+    pub time_step: usize,
+    pub train_loss: f32,
+    pub learning_rate: f32,
+    // Seconds since training began
+    pub wall_time: f64,
+    pub validation_error: Option<f32>,
+    pub validation_metrics: BTreeMap<String, f32>,
+}
+
+impl EpochRecord {
+    pub fn from_summary(summary: &EpochSummary) -> EpochRecord {
+        EpochRecord {
+            epoch: summary.epoch,
+            time_step: summary.time_step,
+            train_loss: summary.train_loss,
+            learning_rate: summary.learning_rate,
+            wall_time: summary.wall_time,
+            validation_error: summary.validation.error().get_value(),
+            validation_metrics: summary
+                .validation
+                .metrics()
+                .values()
+                .into_iter()
+                .map(|(name, value)| (name.to_string(), value))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Serialize, Clone, Debug, PartialEq)]
+pub struct BatchRecord {
+    pub epoch: usize,
+    pub batch: usize,
+    pub loss: f32,
+    pub wall_time: f64,
+}
+
+// Everything a call to SupervisedTrainer::train went through, for tests and plotting
+#[derive(Clone, Debug)]
+pub struct TrainingHistory {
+    epochs: Vec<EpochRecord>,
+    batches: Vec<BatchRecord>,
+    test: EvaluationReport,
+}
+
+impl TrainingHistory {
+    pub fn new() -> TrainingHistory {
+        TrainingHistory {
+            epochs: Vec::new(),
+            batches: Vec::new(),
+            test: EvaluationReport::empty(),
+        }
+    }
+
+    // Picks up the epochs of an interrupted run, batches aren't kept in checkpoints
+    pub fn from_epochs(epochs: Vec<EpochRecord>) -> TrainingHistory {
+        TrainingHistory {
+            epochs,
+            ..TrainingHistory::new()
+        }
+    }
+
+    pub fn record_epoch(&mut self, record: EpochRecord) {
+        self.epochs.push(record);
+    }
+
+    pub fn record_batch(&mut self, record: BatchRecord) {
+        self.batches.push(record);
+    }
+
+    pub fn set_test_report(&mut self, test: EvaluationReport) {
+        self.test = test;
+    }
+
+    pub fn epochs(&self) -> &Vec<EpochRecord> {
+        &self.epochs
+    }
+
+    pub fn batches(&self) -> &Vec<BatchRecord> {
+        &self.batches
+    }
+
+    // Scores the kept config, which is the only time the test set is used
+    pub fn test_report(&self) -> &EvaluationReport {
+        &self.test
+    }
+}
+
+impl Default for TrainingHistory {
+    fn default() -> Self {
+        TrainingHistory::new()
+    }
+}
@@ -0,0 +1,200 @@
+// builtin
+use std::{
+    fs::File,
+    io::{Result, Write},
+    time::Instant,
+};
+
+// external
+use serde::Serialize;
+
+// internal
+use crate::trainer::{
+    callback::{CallbackAction, EpochSummary, TrainerCallback},
+    history::{BatchRecord, EpochRecord},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HistoryFormat {
+    Csv,
+    JsonLines,
+}
+
+// Appends a line per epoch as training goes, so an interrupted run still leaves its log behind.
+// Batch records have their own columns and go to a separate file
+pub struct HistoryRecorder {
+    format: HistoryFormat,
+    epoch_file: File,
+    batch_file: Option<File>,
+    epoch_header: bool,
+    batch_header: bool,
+    start: Instant,
+}
+
+impl HistoryRecorder {
+    pub fn new(path: &str, format: HistoryFormat) -> Result<HistoryRecorder> {
+        Ok(HistoryRecorder {
+            format,
+            epoch_file: File::create(path)?,
+            batch_file: None,
+            epoch_header: false,
+            batch_header: false,
+            start: Instant::now(),
+        })
+    }
+
+    pub fn with_batch_log(mut self, path: &str) -> Result<HistoryRecorder> {
+        self.batch_file = Some(File::create(path)?);
+        Ok(self)
+    }
+
+    fn epoch_csv(record: &EpochRecord, header: bool) -> String {
+        let mut lines: String = String::new();
+        if header {
+            let metrics: Vec<&str> = record
+                .validation_metrics
+                .keys()
+                .map(|k| k.as_str())
+                .collect();
+            lines.push_str("epoch,time_step,train_loss,learning_rate,wall_time,validation_error");
+            for name in metrics {
+                lines.push(',');
+                lines.push_str(name);
+            }
+            lines.push('\n');
+        }
+
+        let validation_error: String = match record.validation_error {
+            Some(error) => error.to_string(),
+            None => String::new(),
+        };
+        lines.push_str(&format!(
+            "{},{},{},{},{},{}",
+            record.epoch,
+            record.time_step,
+            record.train_loss,
+            record.learning_rate,
+            record.wall_time,
+            validation_error
+        ));
+        for value in record.validation_metrics.values() {
+            lines.push_str(&format!(",{value}"));
+        }
+
+        lines
+    }
+
+    fn batch_csv(record: &BatchRecord, header: bool) -> String {
+        let header: &str = if header {
+            "epoch,batch,loss,wall_time\n"
+        } else {
+            ""
+        };
+        format!(
+            "{header}{},{},{},{}",
+            record.epoch, record.batch, record.loss, record.wall_time
+        )
+    }
+
+    fn json_line(record: &impl Serialize) -> String {
+        serde_json::to_string(record).expect("[HISTORY] Failed to serialize a history record")
+    }
+
+    fn write_line(file: &mut File, line: &str) {
+        if let Err(err) = writeln!(file, "{line}") {
+            println!("[HISTORY] Failed to write a history record, {err}");
+        }
+    }
+}
+
+impl TrainerCallback for HistoryRecorder {
+    fn on_train_begin(&mut self, _total_epochs: usize) {
+        self.start = Instant::now();
+    }
+
+    fn on_batch_end(&mut self, epoch: usize, batch: usize, loss: f32) -> CallbackAction {
+        let wall_time: f64 = self.start.elapsed().as_secs_f64();
+
+        if let Some(file) = &mut self.batch_file {
+            let record: BatchRecord = BatchRecord {
+                epoch,
+                batch,
+                loss,
+                wall_time,
+            };
+            let line: String = match self.format {
+                HistoryFormat::Csv => HistoryRecorder::batch_csv(&record, !self.batch_header),
+                HistoryFormat::JsonLines => HistoryRecorder::json_line(&record),
+            };
+
+            HistoryRecorder::write_line(file, &line);
+            self.batch_header = true;
+        }
+
+        CallbackAction::Continue
+    }
+
+    fn on_epoch_end(&mut self, summary: &EpochSummary) -> CallbackAction {
+        let record: EpochRecord = EpochRecord::from_summary(summary);
+        let line: String = match self.format {
+            HistoryFormat::Csv => HistoryRecorder::epoch_csv(&record, !self.epoch_header),
+            HistoryFormat::JsonLines => HistoryRecorder::json_line(&record),
+        };
+
+        HistoryRecorder::write_line(&mut self.epoch_file, &line);
+        self.epoch_header = true;
+
+        CallbackAction::Continue
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        network::config_types::Config,
+        trainer::{
+            callback::{EpochSummary, TrainerCallback},
+            error::PredictionError,
+            history::history_recorder::{HistoryFormat, HistoryRecorder},
+            metrics::{EvaluationReport, Metrics},
+        },
+    };
+
+    #[test]
+    fn json_lines_records() {
+        let path = std::env::temp_dir().join(format!(
+            "history_recorder_records_{}.jsonl",
+            std::process::id()
+        ));
+        let mut recorder =
+            HistoryRecorder::new(path.to_str().unwrap(), HistoryFormat::JsonLines).unwrap();
+
+        let mut metrics: Metrics = Metrics::empty();
+        let error: PredictionError = PredictionError::Loss { loss: 0.25 };
+        metrics.add_example(&error, &[1.0], &[1.5]).unwrap();
+        let validation: EvaluationReport = EvaluationReport::new(error, metrics);
+
+        for epoch in 0..2 {
+            recorder.on_epoch_end(&EpochSummary {
+                epoch,
+                time_step: 4 * (epoch + 1),
+                train_loss: 0.5,
+                learning_rate: 0.01,
+                wall_time: 0.0,
+                validation: &validation,
+                config: &Config::None,
+            });
+        }
+
+        let log: String = std::fs::read_to_string(path).unwrap();
+        let records: Vec<serde_json::Value> = log
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1]["time_step"], 8);
+        assert_eq!(records[1]["validation_error"], 0.25);
+        assert_eq!(records[1]["validation_metrics"]["mae"], 0.5);
+    }
+}
@@ -0,0 +1,206 @@
+// builtin
+
+// external
+
+// internal
+use crate::{
+    data::error::DataError,
+    trainer::{
+        error::PredictionError,
+        metrics::{
+            classification_metrics::ClassificationMetrics, multi_label_metrics::MultiLabelMetrics,
+            regression_metrics::RegressionMetrics,
+        },
+    },
+};
+pub mod classification_metrics;
+pub mod multi_label_metrics;
+pub mod regression_metrics;
+
+// The kind of metrics follows the errors the examples report, losses give regression metrics,
+// misclassifications give classification metrics and multi label errors give multi label metrics
+#[derive(Clone, Debug)]
+pub enum Metrics {
+    Regression(RegressionMetrics),
+    Classification(ClassificationMetrics),
+    MultiLabel(MultiLabelMetrics),
+    None,
+}
+
+impl Metrics {
+    pub fn empty() -> Metrics {
+        Metrics::None
+    }
+
+    // The class of a classification example is read off its one hot response, which has to line up
+    // with the predicted scores
+    pub fn add_example(
+        &mut self,
+        error: &PredictionError,
+        response: &[f32],
+        predicted: &[f32],
+    ) -> Result<(), DataError> {
+        if let Metrics::None = self {
+            match error {
+                PredictionError::Loss { .. } => {
+                    *self = Metrics::Regression(RegressionMetrics::new());
+                }
+                PredictionError::Misclassification { .. } => {
+                    *self = Metrics::Classification(ClassificationMetrics::new(predicted.len()));
+                }
+                PredictionError::MultiLabel { .. } => {
+                    *self = Metrics::MultiLabel(MultiLabelMetrics::new());
+                }
+                _ => return Ok(()),
+            }
+        }
+
+        match (self, error) {
+            (Metrics::Regression(metrics), PredictionError::Loss { loss }) => {
+                metrics.add(*loss, response, predicted);
+            }
+            (Metrics::Classification(metrics), PredictionError::Misclassification { .. }) => {
+                metrics.add(response, predicted)?;
+            }
+            (Metrics::MultiLabel(metrics), PredictionError::MultiLabel { .. }) => {
+                metrics.add(error);
+            }
+            _ => println!("[METRICS] Got mismatched PredictionError types, skipping example"),
+        }
+
+        Ok(())
+    }
+
+    // Headline values by name, e.g. for logging
+    pub fn values(&self) -> Vec<(&'static str, f32)> {
+        match self {
+            Metrics::Regression(metrics) => vec![
+                ("mean_loss", metrics.mean_loss()),
+                ("rmse", metrics.rmse()),
+                ("mae", metrics.mae()),
+                ("r_squared", metrics.r_squared()),
+            ],
+            Metrics::Classification(metrics) => {
+                let macro_average = metrics.macro_average();
+                vec![
+                    ("accuracy", metrics.accuracy()),
+                    ("macro_precision", macro_average.precision),
+                    ("macro_recall", macro_average.recall),
+                    ("macro_f1", macro_average.f1),
+                ]
+            }
+            Metrics::MultiLabel(metrics) => vec![
+                ("hamming_loss", metrics.hamming_loss()),
+                ("subset_accuracy", metrics.subset_accuracy()),
+            ],
+            Metrics::None => Vec::new(),
+        }
+    }
+
+    pub fn regression(&self) -> Option<&RegressionMetrics> {
+        match self {
+            Metrics::Regression(metrics) => Some(metrics),
+            _ => None,
+        }
+    }
+
+    pub fn classification(&self) -> Option<&ClassificationMetrics> {
+        match self {
+            Metrics::Classification(metrics) => Some(metrics),
+            _ => None,
+        }
+    }
+
+    pub fn multi_label(&self) -> Option<&MultiLabelMetrics> {
+        match self {
+            Metrics::MultiLabel(metrics) => Some(metrics),
+            _ => None,
+        }
+    }
+}
+
+// Summed error as used for model selection, along with the metrics of the same predictions
+#[derive(Clone, Debug)]
+pub struct EvaluationReport {
+    error: PredictionError,
+    metrics: Metrics,
+}
+
+impl EvaluationReport {
+    pub fn new(error: PredictionError, metrics: Metrics) -> EvaluationReport {
+        EvaluationReport { error, metrics }
+    }
+
+    pub fn empty() -> EvaluationReport {
+        EvaluationReport::new(PredictionError::empty(), Metrics::empty())
+    }
+
+    pub fn error(&self) -> &PredictionError {
+        &self.error
+    }
+
+    pub fn into_error(self) -> PredictionError {
+        self.error
+    }
+
+    // The error of an average example, so values compare across sets of different sizes. A loss is
+    // divided by the number of examples scored, error rates are per example already
+    pub fn per_example_error(&self) -> PredictionError {
+        match (&self.error, self.metrics.regression()) {
+            (PredictionError::Loss { loss }, Some(metrics)) => PredictionError::Loss {
+                loss: loss / metrics.count().max(1) as f32,
+            },
+            _ => self.error.clone(),
+        }
+    }
+
+    pub fn metrics(&self) -> &Metrics {
+        &self.metrics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trainer::{
+        error::PredictionError,
+        metrics::{EvaluationReport, Metrics},
+    };
+
+    #[test]
+    fn classification_class_from_response() {
+        let wrong = PredictionError::Misclassification {
+            incorrect: 1,
+            total: 1,
+        };
+        let mut metrics: Metrics = Metrics::empty();
+
+        metrics
+            .add_example(&wrong, &[0.0, 0.0, 1.0], &[0.6, 0.3, 0.1])
+            .unwrap();
+        assert!(metrics
+            .add_example(&wrong, &[0.0, 1.0], &[0.6, 0.3, 0.1])
+            .is_err());
+
+        let classification = metrics.classification().unwrap();
+        assert_eq!(classification.count(), 1);
+        assert_eq!(classification.confusion_matrix()[2][0], 1);
+    }
+
+    #[test]
+    fn per_example_loss() {
+        let mut metrics: Metrics = Metrics::empty();
+        let mut error: PredictionError = PredictionError::empty();
+        for (response, predicted) in [(1.0, 2.0), (3.0, 1.0), (2.0, 2.0), (0.0, 1.0)] {
+            let loss: f32 = (response - predicted) * (response - predicted);
+            let example = PredictionError::Loss { loss };
+            metrics
+                .add_example(&example, &[response], &[predicted])
+                .unwrap();
+            error = error.plus(&example);
+        }
+
+        let report: EvaluationReport = EvaluationReport::new(error, metrics);
+        assert_eq!(report.error().get_value(), Some(6.0));
+        assert_eq!(report.per_example_error().get_value(), Some(1.5));
+    }
+}
@@ -0,0 +1,213 @@
+// builtin
+
+// external
+
+// internal
+use crate::data::error::{DataError, Operand};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ClassScores {
+    pub precision: f32,
+    pub recall: f32,
+    pub f1: f32,
+    pub support: usize,
+}
+
+impl ClassScores {
+    fn from_counts(true_positives: usize, false_positives: usize, false_negatives: usize) -> Self {
+        let precision: f32 = ratio(true_positives, true_positives + false_positives);
+        let recall: f32 = ratio(true_positives, true_positives + false_negatives);
+        let f1: f32 = if precision + recall > 0.0 {
+            2.0 * precision * recall / (precision + recall)
+        } else {
+            0.0
+        };
+
+        ClassScores {
+            precision,
+            recall,
+            f1,
+            support: true_positives + false_negatives,
+        }
+    }
+}
+
+fn ratio(numerator: usize, denominator: usize) -> f32 {
+    if denominator > 0 {
+        (numerator as f32) / (denominator as f32)
+    } else {
+        0.0
+    }
+}
+
+fn argmax(values: &[f32]) -> usize {
+    let mut best: usize = 0;
+    for (i, value) in values.iter().enumerate() {
+        if *value > values[best] {
+            best = i;
+        }
+    }
+
+    best
+}
+
+// Confusion matrix indexed as [actual][predicted], both classes are the highest value of the
+// one hot response and of the scores respectively. The rank of the actual class among the scores
+// is kept too, so any top-k accuracy can be read off
+#[derive(Clone, Debug)]
+pub struct ClassificationMetrics {
+    confusion: Vec<Vec<usize>>,
+    rank_counts: Vec<usize>,
+    total: usize,
+}
+
+impl ClassificationMetrics {
+    pub fn new(num_classes: usize) -> ClassificationMetrics {
+        ClassificationMetrics {
+            confusion: vec![vec![0; num_classes]; num_classes],
+            rank_counts: vec![0; num_classes],
+            total: 0,
+        }
+    }
+
+    pub fn add(&mut self, response: &[f32], scores: &[f32]) -> Result<(), DataError> {
+        let num_classes: usize = self.num_classes();
+        if response.len() != num_classes || scores.len() != num_classes {
+            return Err(DataError::ShapeMismatch {
+                operation: "CLASSIFICATION_METRICS",
+                left: Operand::new("Response", &[response.len()]),
+                right: Operand::new("Scores", &[scores.len()]),
+            });
+        }
+
+        let label: usize = argmax(response);
+        let predicted: usize = argmax(scores);
+
+        let rank: usize = scores
+            .iter()
+            .enumerate()
+            .filter(|(i, score)| {
+                **score > scores[label] || (**score == scores[label] && *i < label)
+            })
+            .count();
+
+        self.confusion[label][predicted] += 1;
+        self.rank_counts[rank] += 1;
+        self.total += 1;
+
+        Ok(())
+    }
+
+    pub fn num_classes(&self) -> usize {
+        self.confusion.len()
+    }
+
+    pub fn count(&self) -> usize {
+        self.total
+    }
+
+    pub fn confusion_matrix(&self) -> &Vec<Vec<usize>> {
+        &self.confusion
+    }
+
+    pub fn accuracy(&self) -> f32 {
+        let correct: usize = (0..self.num_classes()).map(|i| self.confusion[i][i]).sum();
+        ratio(correct, self.total)
+    }
+
+    pub fn top_k_accuracy(&self, k: usize) -> f32 {
+        let hits: usize = self.rank_counts.iter().take(k).sum();
+        ratio(hits, self.total)
+    }
+
+    pub fn class_scores(&self, class: usize) -> ClassScores {
+        let true_positives: usize = self.confusion[class][class];
+        let predicted: usize = self.confusion.iter().map(|row| row[class]).sum();
+        let actual: usize = self.confusion[class].iter().sum();
+
+        ClassScores::from_counts(
+            true_positives,
+            predicted - true_positives,
+            actual - true_positives,
+        )
+    }
+
+    pub fn per_class(&self) -> Vec<ClassScores> {
+        (0..self.num_classes())
+            .map(|class| self.class_scores(class))
+            .collect()
+    }
+
+    // Unweighted mean over classes, with the total support
+    pub fn macro_average(&self) -> ClassScores {
+        let scores: Vec<ClassScores> = self.per_class();
+        let count: f32 = scores.len().max(1) as f32;
+
+        ClassScores {
+            precision: scores.iter().map(|s| s.precision).sum::<f32>() / count,
+            recall: scores.iter().map(|s| s.recall).sum::<f32>() / count,
+            f1: scores.iter().map(|s| s.f1).sum::<f32>() / count,
+            support: self.total,
+        }
+    }
+
+    // Pools the counts of every class, for single label data all three equal the accuracy
+    pub fn micro_average(&self) -> ClassScores {
+        let mut true_positives: usize = 0;
+        let mut false_positives: usize = 0;
+        let mut false_negatives: usize = 0;
+
+        for class in 0..self.num_classes() {
+            let hits: usize = self.confusion[class][class];
+            let predicted: usize = self.confusion.iter().map(|row| row[class]).sum();
+            let actual: usize = self.confusion[class].iter().sum();
+
+            true_positives += hits;
+            false_positives += predicted - hits;
+            false_negatives += actual - hits;
+        }
+
+        ClassScores::from_counts(true_positives, false_positives, false_negatives)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trainer::metrics::classification_metrics::ClassificationMetrics;
+
+    #[test]
+    fn classification_values() {
+        let mut metrics = ClassificationMetrics::new(3);
+        let one_hot = |class: usize| {
+            let mut response: Vec<f32> = vec![0.0; 3];
+            response[class] = 1.0;
+            response
+        };
+        metrics.add(&one_hot(0), &[0.7, 0.2, 0.1]).unwrap();
+        metrics.add(&one_hot(0), &[0.3, 0.6, 0.1]).unwrap();
+        metrics.add(&one_hot(1), &[0.1, 0.8, 0.1]).unwrap();
+        metrics.add(&one_hot(2), &[0.5, 0.1, 0.4]).unwrap();
+        assert!(metrics.add(&[0.0, 1.0], &[0.5, 0.1, 0.4]).is_err());
+
+        assert_eq!(
+            metrics.confusion_matrix(),
+            &vec![vec![1, 1, 0], vec![0, 1, 0], vec![1, 0, 0]]
+        );
+        assert_eq!(metrics.accuracy(), 0.5);
+        assert_eq!(metrics.top_k_accuracy(2), 1.0);
+        assert_eq!(metrics.top_k_accuracy(3), 1.0);
+
+        let class_zero = metrics.class_scores(0);
+        assert_eq!((class_zero.precision, class_zero.recall), (0.5, 0.5));
+        let class_two = metrics.class_scores(2);
+        assert_eq!(
+            (class_two.precision, class_two.f1, class_two.support),
+            (0.0, 0.0, 1)
+        );
+
+        let macro_average = metrics.macro_average();
+        assert!((macro_average.recall - 0.5).abs() < 1e-6);
+        assert!((macro_average.precision - 1.0 / 3.0).abs() < 1e-6);
+        assert_eq!(metrics.micro_average().f1, metrics.accuracy());
+    }
+}
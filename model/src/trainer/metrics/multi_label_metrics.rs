@@ -0,0 +1,88 @@
+// builtin
+
+// external
+
+// internal
+use crate::trainer::error::PredictionError;
+
+// Accumulates the thresholded label decisions reported through PredictionError::MultiLabel
+#[derive(Clone, Debug, Default)]
+pub struct MultiLabelMetrics {
+    incorrect_labels: usize,
+    labels: usize,
+    incorrect_sets: usize,
+    total: usize,
+}
+
+impl MultiLabelMetrics {
+    pub fn new() -> MultiLabelMetrics {
+        MultiLabelMetrics::default()
+    }
+
+    pub fn add(&mut self, error: &PredictionError) {
+        if let PredictionError::MultiLabel {
+            incorrect_labels,
+            labels,
+            incorrect_sets,
+            total,
+        } = error
+        {
+            self.incorrect_labels += incorrect_labels;
+            self.labels += labels;
+            self.incorrect_sets += incorrect_sets;
+            self.total += total;
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.total
+    }
+
+    // Share of individual labels predicted wrong
+    pub fn hamming_loss(&self) -> f32 {
+        ratio(self.incorrect_labels, self.labels)
+    }
+
+    // Share of examples with every label predicted right
+    pub fn subset_accuracy(&self) -> f32 {
+        1.0 - ratio(self.incorrect_sets, self.total)
+    }
+}
+
+fn ratio(numerator: usize, denominator: usize) -> f32 {
+    if denominator > 0 {
+        (numerator as f32) / (denominator as f32)
+    } else {
+        0.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trainer::{error::PredictionError, metrics::multi_label_metrics::MultiLabelMetrics};
+
+    #[test]
+    fn multi_label_values() {
+        let errors: Vec<PredictionError> = vec![
+            PredictionError::multi_label(&[1.0, 0.0, 1.0], &[true, false, true]),
+            PredictionError::multi_label(&[0.0, 1.0, 0.0], &[true, true, false]),
+            PredictionError::multi_label(&[1.0, 1.0, 0.0], &[false, false, true]),
+            PredictionError::multi_label(&[0.0, 0.0, 0.0], &[false, false, false]),
+        ];
+
+        let mut metrics = MultiLabelMetrics::new();
+        let mut summed = PredictionError::empty();
+        for error in &errors {
+            metrics.add(error);
+            summed = summed.plus(error);
+        }
+
+        assert_eq!(metrics.count(), 4);
+        assert_eq!(metrics.hamming_loss(), 4.0 / 12.0);
+        assert_eq!(metrics.subset_accuracy(), 0.5);
+
+        assert_eq!(summed.get_value(), Some(metrics.hamming_loss()));
+        assert_eq!(summed.subset_accuracy(), Some(metrics.subset_accuracy()));
+        assert!(errors[0] < errors[1]);
+    }
+}
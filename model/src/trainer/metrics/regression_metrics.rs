@@ -0,0 +1,104 @@
+// builtin
+
+// external
+
+// internal
+
+// Running sums over every output value, multi output responses are pooled into one set of metrics
+#[derive(Clone, Debug, Default)]
+pub struct RegressionMetrics {
+    examples: usize,
+    values: usize,
+    loss_sum: f64,
+    squared_error_sum: f64,
+    absolute_error_sum: f64,
+    response_sum: f64,
+    response_squared_sum: f64,
+}
+
+impl RegressionMetrics {
+    pub fn new() -> RegressionMetrics {
+        RegressionMetrics::default()
+    }
+
+    pub fn add(&mut self, loss: f32, response: &[f32], predicted: &[f32]) {
+        if response.len() != predicted.len() {
+            panic!(
+                "[METRICS] Mismatched regression output, expected {} values but got {}",
+                response.len(),
+                predicted.len()
+            );
+        }
+
+        self.examples += 1;
+        self.loss_sum += loss as f64;
+
+        for (&actual, &prediction) in response.iter().zip(predicted) {
+            let residual: f64 = (actual - prediction) as f64;
+
+            self.values += 1;
+            self.squared_error_sum += residual * residual;
+            self.absolute_error_sum += residual.abs();
+            self.response_sum += actual as f64;
+            self.response_squared_sum += (actual as f64) * (actual as f64);
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        self.examples
+    }
+
+    pub fn mean_loss(&self) -> f32 {
+        (self.loss_sum / self.examples.max(1) as f64) as f32
+    }
+
+    pub fn mse(&self) -> f32 {
+        (self.squared_error_sum / self.values.max(1) as f64) as f32
+    }
+
+    pub fn rmse(&self) -> f32 {
+        self.mse().sqrt()
+    }
+
+    pub fn mae(&self) -> f32 {
+        (self.absolute_error_sum / self.values.max(1) as f64) as f32
+    }
+
+    // A constant response has no variance to explain, so it only scores 1 when predicted exactly
+    pub fn r_squared(&self) -> f32 {
+        let values: f64 = self.values.max(1) as f64;
+        let total_sum_squares: f64 =
+            self.response_squared_sum - self.response_sum * self.response_sum / values;
+
+        if total_sum_squares <= f64::EPSILON {
+            return if self.squared_error_sum <= f64::EPSILON {
+                1.0
+            } else {
+                0.0
+            };
+        }
+
+        (1.0 - self.squared_error_sum / total_sum_squares) as f32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trainer::metrics::regression_metrics::RegressionMetrics;
+
+    #[test]
+    fn regression_values() {
+        let mut metrics = RegressionMetrics::new();
+        for (actual, predicted) in [(1.0, 2.0), (2.0, 2.0), (3.0, 1.0), (6.0, 7.0)] {
+            let loss: f32 = (actual - predicted) * (actual - predicted);
+            metrics.add(loss, &[actual], &[predicted]);
+        }
+
+        assert_eq!(metrics.count(), 4);
+        assert!((metrics.mean_loss() - 1.5).abs() < 1e-6);
+        assert!((metrics.rmse() - 1.5f32.sqrt()).abs() < 1e-6);
+        assert!((metrics.mae() - 1.0).abs() < 1e-6);
+        // Responses have mean 3 and a total sum of squares of 14
+        assert!((metrics.r_squared() - (1.0 - 6.0 / 14.0)).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,207 @@
+// builtin
+
+// external
+use serde::{Deserialize, Serialize};
+
+// internal
+use crate::trainer::{
+    callback::{CallbackAction, EpochSummary, TrainerCallback},
+    error::PredictionError,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlateauStatus {
+    Improved,
+    Waiting { epochs: usize },
+    Reduced { learning_rate: f32 },
+    // Still no improvement with the learning rate already at its minimum
+    Exhausted,
+}
+
+// Multiplies the network's learning rate by `factor` whenever the error hasn't improved on its
+// best value by more than `min_delta` for `patience` epochs, never going below `min_rate`. As a
+// callback it follows the per example validation error, the same way EarlyStopping does
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PlateauScheduler {
+    patience: usize,
+    #[serde(default)]
+    min_delta: f32,
+    factor: f32,
+    min_rate: f32,
+    #[serde(default)]
+    stop_when_exhausted: bool,
+    best_error: PredictionError,
+    wait: usize,
+}
+
+impl PlateauScheduler {
+    pub fn new(patience: usize, min_delta: f32, factor: f32, min_rate: f32) -> PlateauScheduler {
+        if factor <= 0.0 || factor >= 1.0 {
+            panic!(
+                "[PLATEAU] Invalid reduction factor, expected a value in (0, 1) but got {factor}"
+            );
+        }
+
+        PlateauScheduler {
+            patience,
+            min_delta,
+            factor,
+            min_rate,
+            stop_when_exhausted: false,
+            best_error: PredictionError::empty(),
+            wait: 0,
+        }
+    }
+
+    // Ends training once the rate is at its minimum and the error still doesn't improve, otherwise
+    // training goes on at the minimum rate
+    pub fn stop_when_exhausted(mut self) -> PlateauScheduler {
+        self.stop_when_exhausted = true;
+        self
+    }
+
+    // `learning_rate` is the network's current rate, a reduced one is returned rather than set
+    pub fn update(&mut self, learning_rate: f32, error: &PredictionError) -> PlateauStatus {
+        if self.is_improvement(error) {
+            self.best_error = error.clone();
+            self.wait = 0;
+            return PlateauStatus::Improved;
+        }
+
+        self.wait += 1;
+        if self.wait < self.patience {
+            return PlateauStatus::Waiting { epochs: self.wait };
+        }
+
+        let reduced: f32 = f32::max(learning_rate * self.factor, self.min_rate);
+        // Rescaling through the network can round, so a rate at the minimum may come back a hair above it
+        if learning_rate - reduced <= f32::EPSILON * learning_rate {
+            return PlateauStatus::Exhausted;
+        }
+
+        self.wait = 0;
+
+        PlateauStatus::Reduced {
+            learning_rate: reduced,
+        }
+    }
+
+    fn is_improvement(&self, error: &PredictionError) -> bool {
+        if self.best_error.is_empty() {
+            return true;
+        }
+
+        match (error.get_value(), self.best_error.get_value()) {
+            (Some(value), Some(best)) => value < best - self.min_delta,
+            _ => false,
+        }
+    }
+}
+
+impl TrainerCallback for PlateauScheduler {
+    fn on_epoch_end(&mut self, summary: &EpochSummary) -> CallbackAction {
+        let error: PredictionError = summary.validation.per_example_error();
+        match self.update(summary.learning_rate, &error) {
+            PlateauStatus::Reduced { learning_rate } => {
+                CallbackAction::SetLearningRate(learning_rate)
+            }
+            PlateauStatus::Exhausted if self.stop_when_exhausted => CallbackAction::Stop,
+            PlateauStatus::Improved | PlateauStatus::Waiting { .. } | PlateauStatus::Exhausted => {
+                CallbackAction::Continue
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        data::precision::Precision,
+        network::{
+            config_types::hyper_params::HyperParams, types::regressor::RegressorNetwork, Network,
+        },
+        optimization::{
+            batch_norm::NormalizationType, learning_decay::LearningDecayType,
+            learning_schedule::LearningRateSchedule, momentum::DescentType,
+        },
+        regularization::{dropout::NetworkMaskType, penalty::PenaltyConfig},
+        trainer::{
+            error::PredictionError,
+            plateau::{PlateauScheduler, PlateauStatus},
+        },
+    };
+
+    #[test]
+    fn plateau_reduces_to_minimum() {
+        let mut regressor: RegressorNetwork = RegressorNetwork::new(
+            vec![1],
+            vec![1],
+            vec![4],
+            PenaltyConfig::none(),
+            NetworkMaskType::None,
+            HyperParams::new(
+                LearningDecayType::rms_prop(0.04, 0.9),
+                DescentType::Base,
+                NormalizationType::none(),
+                Precision::F32,
+                LearningRateSchedule::constant(),
+            ),
+        );
+        let mut plateau: PlateauScheduler = PlateauScheduler::new(2, 0.0, 0.5, 0.015);
+        let loss = |loss: f32| PredictionError::Loss { loss };
+
+        let statuses: Vec<PlateauStatus> = [2.0, 1.0, 1.5, 1.0, 0.5, 0.5, 0.6, 0.7, 0.8]
+            .iter()
+            .map(|error| {
+                let status = plateau.update(regressor.get_learning_rate(), &loss(*error));
+                if let PlateauStatus::Reduced { learning_rate } = status {
+                    regressor.set_learning_rate(learning_rate);
+                }
+                status
+            })
+            .collect();
+
+        assert_eq!(
+            statuses,
+            vec![
+                PlateauStatus::Improved,
+                PlateauStatus::Improved,
+                PlateauStatus::Waiting { epochs: 1 },
+                PlateauStatus::Reduced {
+                    learning_rate: 0.02
+                },
+                PlateauStatus::Improved,
+                PlateauStatus::Waiting { epochs: 1 },
+                PlateauStatus::Reduced {
+                    learning_rate: 0.015
+                },
+                PlateauStatus::Waiting { epochs: 1 },
+                PlateauStatus::Exhausted,
+            ]
+        );
+        assert!((regressor.get_learning_rate() - 0.015).abs() < 1e-7);
+    }
+
+    #[test]
+    fn plateau_min_delta() {
+        let mut plateau: PlateauScheduler = PlateauScheduler::new(2, 0.1, 0.5, 0.001);
+        let loss = |loss: f32| PredictionError::Loss { loss };
+
+        let statuses: Vec<PlateauStatus> = [1.0, 0.95, 0.92, 0.85]
+            .iter()
+            .map(|error| plateau.update(0.04, &loss(*error)))
+            .collect();
+
+        assert_eq!(
+            statuses,
+            vec![
+                PlateauStatus::Improved,
+                PlateauStatus::Waiting { epochs: 1 },
+                PlateauStatus::Reduced {
+                    learning_rate: 0.02
+                },
+                PlateauStatus::Improved,
+            ]
+        );
+    }
+}
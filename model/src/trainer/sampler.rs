@@ -0,0 +1,32 @@
+// builtin
+
+// external
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+
+// internal
+use crate::data::error::DataError;
+pub mod sequential_sampler;
+pub mod shuffled_sampler;
+pub mod weighted_sampler;
+
+// Picks the order training examples are visited in for one epoch. Samplers only see the label key
+// of every example, see SupervisedExample::get_label_key
+pub trait Sampler {
+    // Fails when the sampler can't cover the given examples, e.g. per-example weights for a
+    // different number of examples
+    fn sample_epoch(&mut self, labels: &[usize]) -> Result<Vec<usize>, DataError>;
+
+    // Saved with checkpoints, so a resumed run visits the examples in the same order
+    fn get_state(&self) -> SamplerState {
+        SamplerState::Stateless
+    }
+
+    fn set_state(&mut self, _state: SamplerState) {}
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub enum SamplerState {
+    Stateless,
+    Rng(Box<ChaCha12Rng>),
+}
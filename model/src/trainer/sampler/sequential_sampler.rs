@@ -0,0 +1,22 @@
+// builtin
+
+// external
+
+// internal
+use crate::{data::error::DataError, trainer::sampler::Sampler};
+
+// Visits the examples in their stored order every epoch
+#[derive(Default)]
+pub struct SequentialSampler;
+
+impl SequentialSampler {
+    pub fn new() -> SequentialSampler {
+        SequentialSampler
+    }
+}
+
+impl Sampler for SequentialSampler {
+    fn sample_epoch(&mut self, labels: &[usize]) -> Result<Vec<usize>, DataError> {
+        Ok((0..labels.len()).collect())
+    }
+}
@@ -0,0 +1,74 @@
+// builtin
+
+// external
+use rand::{seq::SliceRandom, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+
+// internal
+use crate::{
+    data::error::DataError,
+    random::fork,
+    trainer::sampler::{Sampler, SamplerState},
+};
+
+// Draws a new permutation of the examples every epoch
+pub struct ShuffledSampler {
+    rng: ChaCha12Rng,
+}
+
+impl ShuffledSampler {
+    pub fn new(seed: u64) -> ShuffledSampler {
+        ShuffledSampler {
+            rng: ChaCha12Rng::seed_from_u64(seed),
+        }
+    }
+
+    // Follows the global seed, see random::set_seed
+    pub fn from_global_rng() -> ShuffledSampler {
+        ShuffledSampler { rng: fork() }
+    }
+}
+
+impl Sampler for ShuffledSampler {
+    fn sample_epoch(&mut self, labels: &[usize]) -> Result<Vec<usize>, DataError> {
+        let mut order: Vec<usize> = (0..labels.len()).collect();
+        order.shuffle(&mut self.rng);
+
+        Ok(order)
+    }
+
+    fn get_state(&self) -> SamplerState {
+        SamplerState::Rng(Box::new(self.rng.clone()))
+    }
+
+    fn set_state(&mut self, state: SamplerState) {
+        match state {
+            SamplerState::Rng(rng) => self.rng = *rng,
+            SamplerState::Stateless => {
+                println!("[SHUFFLED_SAMPLER] Got a stateless sampler state, keeping the current generator")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::trainer::sampler::{shuffled_sampler::ShuffledSampler, Sampler};
+
+    #[test]
+    fn seeded_permutations() {
+        let labels: Vec<usize> = vec![0; 20];
+        let mut sampler: ShuffledSampler = ShuffledSampler::new(7);
+        let mut repeat: ShuffledSampler = ShuffledSampler::new(7);
+
+        let first: Vec<usize> = sampler.sample_epoch(&labels).unwrap();
+        let second: Vec<usize> = sampler.sample_epoch(&labels).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(repeat.sample_epoch(&labels).unwrap(), first);
+        assert_eq!(repeat.sample_epoch(&labels).unwrap(), second);
+
+        let mut sorted: Vec<usize> = first.clone();
+        sorted.sort();
+        assert_eq!(sorted, (0..20).collect::<Vec<usize>>());
+    }
+}
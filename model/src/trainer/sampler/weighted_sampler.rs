@@ -0,0 +1,133 @@
+// builtin
+use std::collections::HashMap;
+
+// external
+use rand::{distributions::WeightedIndex, prelude::Distribution, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+
+// internal
+use crate::{
+    data::error::{DataError, Operand},
+    trainer::sampler::{Sampler, SamplerState},
+};
+
+enum Weighting {
+    PerExample(Vec<f64>),
+    // Every label gets the same total weight, shared among its examples
+    ClassBalanced,
+}
+
+// Samples with replacement, so rare examples or classes can be seen several times per epoch
+pub struct WeightedSampler {
+    weighting: Weighting,
+    num_samples: Option<usize>,
+    rng: ChaCha12Rng,
+}
+
+impl WeightedSampler {
+    // `weights` lines up with the training examples, so sampling any other set of examples fails.
+    // Epochs default to one draw per example
+    pub fn new(weights: Vec<f64>, num_samples: Option<usize>, seed: u64) -> WeightedSampler {
+        WeightedSampler {
+            weighting: Weighting::PerExample(weights),
+            num_samples,
+            rng: ChaCha12Rng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn class_balanced(num_samples: Option<usize>, seed: u64) -> WeightedSampler {
+        WeightedSampler {
+            weighting: Weighting::ClassBalanced,
+            num_samples,
+            rng: ChaCha12Rng::seed_from_u64(seed),
+        }
+    }
+
+    fn get_weights(&self, labels: &[usize]) -> Result<Vec<f64>, DataError> {
+        match &self.weighting {
+            Weighting::PerExample(weights) => {
+                if weights.len() != labels.len() {
+                    return Err(DataError::BatchMismatch {
+                        operation: "WEIGHTED_SAMPLER",
+                        left: Operand::new("Weights", &[weights.len()]),
+                        right: Operand::new("Examples", &[labels.len()]),
+                    });
+                }
+                Ok(weights.clone())
+            }
+            Weighting::ClassBalanced => {
+                let mut counts: HashMap<usize, usize> = HashMap::new();
+                for label in labels {
+                    *counts.entry(*label).or_default() += 1;
+                }
+
+                Ok(labels
+                    .iter()
+                    .map(|label| 1.0 / counts[label] as f64)
+                    .collect())
+            }
+        }
+    }
+}
+
+impl Sampler for WeightedSampler {
+    fn sample_epoch(&mut self, labels: &[usize]) -> Result<Vec<usize>, DataError> {
+        if labels.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let weights: Vec<f64> = self.get_weights(labels)?;
+        // Negative, non finite or all zero weights
+        let distribution =
+            WeightedIndex::new(&weights).map_err(|_err| DataError::InvalidOperand {
+                operation: "WEIGHTED_SAMPLER",
+                operand: Operand::new("Weights", &[weights.len()]),
+            })?;
+        let num_samples: usize = self.num_samples.unwrap_or(labels.len());
+
+        Ok((0..num_samples)
+            .map(|_i| distribution.sample(&mut self.rng))
+            .collect())
+    }
+
+    fn get_state(&self) -> SamplerState {
+        SamplerState::Rng(Box::new(self.rng.clone()))
+    }
+
+    fn set_state(&mut self, state: SamplerState) {
+        match state {
+            SamplerState::Rng(rng) => self.rng = *rng,
+            SamplerState::Stateless => {
+                println!("[WEIGHTED_SAMPLER] Got a stateless sampler state, keeping the current generator")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        data::error::DataError,
+        trainer::sampler::{weighted_sampler::WeightedSampler, Sampler},
+    };
+
+    #[test]
+    fn class_balanced_sampling() {
+        // 90 examples of label 0 and 10 of label 1
+        let labels: Vec<usize> = (0..100).map(|i| usize::from(i >= 90)).collect();
+        let mut sampler: WeightedSampler = WeightedSampler::class_balanced(Some(4000), 3);
+
+        let order: Vec<usize> = sampler.sample_epoch(&labels).unwrap();
+        assert_eq!(order.len(), 4000);
+
+        let minority: usize = order.iter().filter(|i| labels[**i] == 1).count();
+        assert!(minority.abs_diff(2000) < 200, "{minority}");
+
+        let mut explicit: WeightedSampler = WeightedSampler::new(vec![0.0, 1.0, 0.0], None, 3);
+        assert_eq!(explicit.sample_epoch(&[0, 0, 0]).unwrap(), vec![1, 1, 1]);
+        assert!(matches!(
+            explicit.sample_epoch(&[0, 0, 0, 0]),
+            Err(DataError::BatchMismatch { .. })
+        ));
+    }
+}
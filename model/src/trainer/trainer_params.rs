@@ -4,33 +4,55 @@ use std::mem::take;
 // external
 
 // internal
-use crate::trainer::{
-    data_subsets::{DataSplitter, DataSubsets},
-    examples::SupervisedExample,
+use crate::{
+    data::error::DataError,
+    trainer::{
+        callback::TrainerCallback,
+        data_subsets::{DataSplitter, DataSubsets},
+        early_stopping::EarlyStopping,
+        examples::SupervisedExample,
+        plateau::PlateauScheduler,
+        sampler::{sequential_sampler::SequentialSampler, Sampler, SamplerState},
+    },
 };
 
 pub struct TrainerConfig<T: SupervisedExample> {
     total_iterations: usize,
     batch_size: usize,
     train: Vec<T>,
+    validation: Vec<T>,
     test: Vec<T>,
+    early_stopping: EarlyStopping,
+    // Set once early stopping is configured, so adding a plateau keeps it
+    explicit_stopping: bool,
+    plateau: Option<PlateauScheduler>,
+    sampler: Box<dyn Sampler>,
+    drop_last: bool,
 }
 
 impl<T> TrainerConfig<T>
 where
     T: SupervisedExample,
 {
+    // Epochs are selected on the validation set, the test set is only scored once training ends
     pub fn new(
         total_iterations: usize,
         batch_size: usize,
         train: Vec<T>,
+        validation: Vec<T>,
         test: Vec<T>,
     ) -> TrainerConfig<T> {
         TrainerConfig {
             total_iterations,
             batch_size,
             train,
+            validation,
             test,
+            early_stopping: EarlyStopping::immediate(),
+            explicit_stopping: false,
+            plateau: None,
+            sampler: Box::new(SequentialSampler::new()),
+            drop_last: false,
         }
     }
 
@@ -40,14 +62,57 @@ where
         data: Vec<T>,
         splitter: impl DataSplitter,
     ) -> TrainerConfig<T> {
-        let mut subsets: DataSubsets<T> = splitter.split(data);
+        TrainerConfig::from_subsets(total_iterations, batch_size, splitter.split(data))
+    }
 
+    pub fn from_subsets(
+        total_iterations: usize,
+        batch_size: usize,
+        mut subsets: DataSubsets<T>,
+    ) -> TrainerConfig<T> {
         TrainerConfig {
             total_iterations,
             batch_size,
             train: subsets.take_train(),
+            validation: subsets.take_validation(),
             test: subsets.take_test(),
+            early_stopping: EarlyStopping::immediate(),
+            explicit_stopping: false,
+            plateau: None,
+            sampler: Box::new(SequentialSampler::new()),
+            drop_last: false,
+        }
+    }
+
+    // Defaults to stopping on the first epoch that doesn't improve, or to never stopping early
+    // when a plateau scheduler is set
+    pub fn with_early_stopping(mut self, early_stopping: EarlyStopping) -> TrainerConfig<T> {
+        self.early_stopping = early_stopping;
+        self.explicit_stopping = true;
+        self
+    }
+
+    // Lowers the learning rate when the validation error stalls, training only ends on the plateau
+    // when it was set to stop once exhausted. Unless early stopping was configured it's disabled,
+    // so the plateau gets to lower the rate before training ends
+    pub fn with_plateau(mut self, plateau: PlateauScheduler) -> TrainerConfig<T> {
+        self.plateau = Some(plateau);
+        if !self.explicit_stopping {
+            self.early_stopping = EarlyStopping::disabled();
         }
+        self
+    }
+
+    // Defaults to visiting the training examples in their stored order
+    pub fn with_sampler(mut self, sampler: impl Sampler + 'static) -> TrainerConfig<T> {
+        self.sampler = Box::new(sampler);
+        self
+    }
+
+    // Skips the last batch of an epoch when it's smaller than the batch size
+    pub fn with_drop_last(mut self, drop_last: bool) -> TrainerConfig<T> {
+        self.drop_last = drop_last;
+        self
     }
 
     pub fn total_iterations(&self) -> usize {
@@ -58,18 +123,74 @@ where
         self.batch_size
     }
 
+    pub fn drop_last(&self) -> bool {
+        self.drop_last
+    }
+
+    pub fn sample_epoch(&mut self, labels: &[usize]) -> Result<Vec<usize>, DataError> {
+        self.sampler.sample_epoch(labels)
+    }
+
+    pub fn sampler_state(&self) -> SamplerState {
+        self.sampler.get_state()
+    }
+
+    pub fn set_sampler_state(&mut self, state: SamplerState) {
+        self.sampler.set_state(state);
+    }
+
     pub fn train_ref(&self) -> &Vec<T> {
         &self.train
     }
 
+    pub fn validation_ref(&self) -> &Vec<T> {
+        &self.validation
+    }
+
     pub fn test_ref(&self) -> &Vec<T> {
         &self.test
     }
 
+    pub fn early_stopping(&self) -> &EarlyStopping {
+        &self.early_stopping
+    }
+
+    pub fn early_stopping_mut(&mut self) -> &mut EarlyStopping {
+        &mut self.early_stopping
+    }
+
+    // The built in callbacks, early stopping first and then the plateau scheduler if one is set
+    pub fn callbacks_mut(&mut self) -> Vec<&mut dyn TrainerCallback> {
+        self.train_and_callbacks_mut().1
+    }
+
+    // Borrows the training examples alongside the built in callbacks, so batches can be fit while
+    // the callbacks are notified
+    pub fn train_and_callbacks_mut(&mut self) -> (&Vec<T>, Vec<&mut dyn TrainerCallback>) {
+        let mut callbacks: Vec<&mut dyn TrainerCallback> = vec![&mut self.early_stopping];
+        if let Some(plateau) = self.plateau.as_mut() {
+            callbacks.push(plateau);
+        }
+
+        (&self.train, callbacks)
+    }
+
+    pub fn plateau(&self) -> Option<&PlateauScheduler> {
+        self.plateau.as_ref()
+    }
+
+    pub fn set_plateau(&mut self, plateau: Option<PlateauScheduler>) {
+        self.plateau = plateau;
+    }
+
     pub fn take_train(&mut self) -> Vec<T> {
         take(&mut self.train)
     }
 
+    pub fn take_validation(&mut self) -> Vec<T> {
+        take(&mut self.validation)
+    }
+
     pub fn take_test(&mut self) -> Vec<T> {
         take(&mut self.test)
     }
@@ -4,6 +4,8 @@
 
 // internal
 pub mod input_unit;
+pub mod layer_unit;
 pub mod linear_unit;
 pub mod loss_unit;
+pub mod merge_unit;
 pub mod softmax_unit;
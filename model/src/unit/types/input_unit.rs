@@ -4,7 +4,7 @@
 
 // internal
 use crate::{
-    data::data_container::DataContainer,
+    data::{data_container::DataContainer, precision::Precision},
     network::config_types::input_params::InputParams,
     node::{
         types::{input_node::InputNode, mask_node::MaskNode, multiply_node::MultiplyNode},
@@ -22,7 +22,11 @@ pub struct InputUnit<'a> {
 }
 
 impl<'a> InputUnit<'a> {
-    pub fn new(input_size: Vec<usize>, mask_type: UnitMaskType) -> InputUnit<'a> {
+    pub fn new(
+        input_size: Vec<usize>,
+        mask_type: UnitMaskType,
+        precision: Precision,
+    ) -> InputUnit<'a> {
         let input_ref = NodeRef::new(InputNode::new(input_size.clone()));
 
         let mut output_ref: NodeRef = NodeRef::clone(&input_ref);
@@ -33,7 +37,11 @@ impl<'a> InputUnit<'a> {
         let multiply_ref: NodeRef;
 
         if let UnitMaskType::Dropout { keep_probability } = &mask_type {
-            mask_ref = NodeRef::new(MaskNode::new(input_size.clone(), *keep_probability));
+            mask_ref = NodeRef::new(MaskNode::new(
+                input_size.clone(),
+                *keep_probability,
+                precision,
+            ));
             multiply_ref = NodeRef::new(MultiplyNode::new());
 
             multiply_ref
@@ -62,10 +70,11 @@ impl<'a> InputUnit<'a> {
         }
     }
 
-    pub fn from_config(config: &InputParams) -> InputUnit<'a> {
+    pub fn from_config(config: &InputParams, precision: Precision) -> InputUnit<'a> {
         Self::new(
             config.get_input_size().clone(),
             UnitMaskType::from_keep_probability(config.get_keep_probability()),
+            precision,
         )
     }
 
@@ -104,7 +113,9 @@ impl<'a> Unit<'a> for InputUnit<'a> {
     }
 
     fn update_mode(&mut self, new_mode: NetworkMode) {
-        self.base.update_mode(new_mode);
+        if !self.base.update_mode(new_mode) {
+            return;
+        }
 
         for unit in self.base.get_outputs() {
             unit.borrow_mut().update_mode(new_mode);
@@ -0,0 +1,68 @@
+// builtin
+
+// external
+
+// internal
+use crate::{
+    network::config_types::unit_params::UnitParams,
+    node::NodeRef,
+    optimization::{learning_decay::LearningDecayType, momentum::DescentType},
+    unit::{
+        types::{linear_unit::LinearUnit, softmax_unit::SoftmaxUnit},
+        UnitContainer, UnitRef,
+    },
+};
+
+// Any unit with learned weights, built with the batch norm setting saved in its own params
+pub enum LayerUnit<'a> {
+    Linear(UnitContainer<'a, LinearUnit<'a>>),
+    Softmax(UnitContainer<'a, SoftmaxUnit<'a>>),
+}
+
+impl<'a> LayerUnit<'a> {
+    pub fn from_config(
+        config: &UnitParams,
+        decay_type: &LearningDecayType,
+        descent_type: &DescentType,
+    ) -> LayerUnit<'a> {
+        match config {
+            UnitParams::Linear { .. } => {
+                LayerUnit::Linear(UnitContainer::new(LinearUnit::from_config(
+                    config,
+                    decay_type.clone(),
+                    descent_type.clone(),
+                    config.normalization_type(),
+                )))
+            }
+            UnitParams::Softmax { .. } => {
+                LayerUnit::Softmax(UnitContainer::new(SoftmaxUnit::from_config(
+                    config,
+                    decay_type.clone(),
+                    descent_type.clone(),
+                    config.normalization_type(),
+                )))
+            }
+        }
+    }
+
+    pub fn get_ref(&self) -> UnitRef<'a> {
+        match self {
+            LayerUnit::Linear(unit) => unit.get_ref(),
+            LayerUnit::Softmax(unit) => unit.get_ref(),
+        }
+    }
+
+    pub fn get_weights_ref(&self) -> NodeRef<'a> {
+        match self {
+            LayerUnit::Linear(unit) => NodeRef::clone(unit.borrow().get_weights_ref()),
+            LayerUnit::Softmax(unit) => NodeRef::clone(unit.borrow().get_weights_ref()),
+        }
+    }
+
+    pub fn to_params(&self) -> UnitParams {
+        match self {
+            LayerUnit::Linear(unit) => UnitParams::from_linear_unit(unit),
+            LayerUnit::Softmax(unit) => UnitParams::from_softmax_unit(unit),
+        }
+    }
+}
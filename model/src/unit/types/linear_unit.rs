@@ -4,7 +4,7 @@
 
 // internal
 use crate::{
-    data::{data_container::DataContainer, Data},
+    data::data_container::DataContainer,
     network::config_types::{
         batch_norm_params::BatchNormParams, layer_params::LayerParams,
         learned_params::LearnedParams, unit_params::UnitParams,
@@ -77,6 +77,7 @@ impl<'a> LinearUnit<'a> {
             let parameters: DataContainer = data.get_parameters();
             let momentum: DataContainer = data.get_momentum();
             let learning_rate: DataContainer = data.get_learning_rate();
+            let update_accumulator: DataContainer = data.get_update_accumulator();
 
             biases.borrow_mut().set_data(parameters);
             if !matches!(&momentum, DataContainer::Empty) {
@@ -85,6 +86,11 @@ impl<'a> LinearUnit<'a> {
             if !matches!(&learning_rate, DataContainer::Empty) {
                 biases.borrow_mut().set_learning_rate(learning_rate);
             }
+            if !matches!(&update_accumulator, DataContainer::Empty) {
+                biases
+                    .borrow_mut()
+                    .set_update_accumulator(update_accumulator);
+            }
         }
     }
 
@@ -92,6 +98,7 @@ impl<'a> LinearUnit<'a> {
         let weights: DataContainer = data.get_parameters();
         let momentum: DataContainer = data.get_momentum();
         let learning_rate: DataContainer = data.get_learning_rate();
+        let update_accumulator: DataContainer = data.get_update_accumulator();
 
         self.weights.borrow_mut().set_data(weights);
         if !matches!(&momentum, DataContainer::Empty) {
@@ -100,6 +107,11 @@ impl<'a> LinearUnit<'a> {
         if !matches!(&learning_rate, DataContainer::Empty) {
             self.weights.borrow_mut().set_learning_rate(learning_rate);
         }
+        if !matches!(&update_accumulator, DataContainer::Empty) {
+            self.weights
+                .borrow_mut()
+                .set_update_accumulator(update_accumulator);
+        }
     }
 
     pub fn set_normalization(&self, norm_params: &BatchNormParams) {
@@ -120,21 +132,21 @@ impl<'a> LinearUnit<'a> {
         self.output_size
     }
 
-    pub fn get_weights(&self) -> Vec<f32> {
+    pub fn get_weights(&self) -> Vec<f64> {
         let data: DataContainer = self.weights.borrow_mut().get_data();
-        if let DataContainer::Parameter(Data::MatrixF32(matrix)) = data {
-            return matrix.flatten().to_vec();
+        if let DataContainer::Parameter(matrix) = data {
+            return matrix.flatten_as();
         }
 
         println!("Couldn't package weights for serialization due to invalid data container/type");
         Vec::new()
     }
 
-    pub fn get_biases(&self) -> Vec<f32> {
+    pub fn get_biases(&self) -> Vec<f64> {
         if let Option::Some(biases) = &self.biases {
             let data = biases.borrow_mut().get_data();
-            if let DataContainer::Parameter(Data::VectorF32(vec)) = data {
-                return vec.to_vec();
+            if let DataContainer::Parameter(vec) = data {
+                return vec.flatten_as();
             }
 
             println!(
@@ -179,7 +191,9 @@ impl<'a> Unit<'a> for LinearUnit<'a> {
     }
 
     fn update_mode(&mut self, new_mode: NetworkMode) {
-        self.base.update_mode(new_mode);
+        if !self.base.update_mode(new_mode) {
+            return;
+        }
 
         for unit in self.base.get_outputs() {
             unit.borrow_mut().update_mode(new_mode);
@@ -97,7 +97,9 @@ impl<'a> Unit<'a> for LossUnit<'a> {
     }
 
     fn update_mode(&mut self, new_mode: NetworkMode) {
-        self.base.update_mode(new_mode);
+        if !self.base.update_mode(new_mode) {
+            return;
+        }
 
         for unit in self.base.get_outputs() {
             unit.borrow_mut().update_mode(new_mode);
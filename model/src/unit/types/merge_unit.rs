@@ -0,0 +1,81 @@
+// builtin
+
+// external
+
+// internal
+use crate::{
+    node::{
+        types::{add_node::AddNode, concat_node::ConcatNode},
+        NodeRef,
+    },
+    regularization::dropout::NetworkMode,
+    unit::{unit_base::UnitBase, Unit, UnitRef},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MergeType {
+    Concat,
+    Add,
+}
+
+// Joins the outputs of several units into one, without any learned parameters
+pub struct MergeUnit<'a> {
+    base: UnitBase<'a>,
+    merge_type: MergeType,
+}
+
+impl<'a> MergeUnit<'a> {
+    pub fn new(merge_type: MergeType) -> MergeUnit<'a> {
+        let merge_ref: NodeRef = match merge_type {
+            MergeType::Concat => NodeRef::new(ConcatNode::new()),
+            MergeType::Add => NodeRef::new(AddNode::new()),
+        };
+
+        MergeUnit {
+            base: UnitBase::new(
+                NodeRef::clone(&merge_ref),
+                merge_ref,
+                Option::None,
+                Option::None,
+                false,
+            ),
+            merge_type,
+        }
+    }
+
+    pub fn get_merge_type(&self) -> MergeType {
+        self.merge_type
+    }
+}
+
+impl<'a> Unit<'a> for MergeUnit<'a> {
+    fn add_input(&mut self, this: &UnitRef<'a>, input: &UnitRef<'a>) {
+        self.base.add_input(this, input);
+    }
+
+    fn add_output(&mut self, output: &UnitRef<'a>) {
+        self.base.add_output(output);
+    }
+
+    fn get_inputs(&self) -> &Vec<UnitRef<'a>> {
+        self.base.get_inputs()
+    }
+
+    fn get_outputs(&self) -> &Vec<UnitRef<'a>> {
+        self.base.get_outputs()
+    }
+
+    fn get_output_node(&self) -> &NodeRef<'a> {
+        self.base.get_output_node()
+    }
+
+    fn update_mode(&mut self, new_mode: NetworkMode) {
+        if !self.base.update_mode(new_mode) {
+            return;
+        }
+
+        for unit in self.base.get_outputs() {
+            unit.borrow_mut().update_mode(new_mode);
+        }
+    }
+}
@@ -4,7 +4,10 @@
 
 // internal
 use crate::{
-    network::config_types::{batch_norm_params::NormParams, unit_params::UnitParams},
+    data::precision::Precision,
+    network::config_types::{
+        batch_norm_params::NormParams, hyper_params::HyperParams, unit_params::UnitParams,
+    },
     node::{
         types::{
             activation_node::ActivationNode, add_node::AddNode, bias_node::BiasNode,
@@ -17,6 +20,7 @@ use crate::{
     optimization::{
         batch_norm::{BatchNormModule, NormalizationType},
         learning_decay::LearningDecayType,
+        learning_schedule::LearningRateSchedule,
         momentum::DescentType,
     },
     regularization::dropout::UnitMaskType,
@@ -40,14 +44,19 @@ pub fn build_softmax_unit_from_config<'a>(
         norm_params,
     } = config
     {
+        let hyperparams: HyperParams = HyperParams::new(
+            decay_type,
+            descent_type,
+            normalization_type,
+            weights.get_precision(),
+            LearningRateSchedule::default(),
+        );
         let unit: SoftmaxUnit = create_softmax_unit(
             activation,
             *input_size,
             *output_size,
-            decay_type,
-            descent_type,
+            &hyperparams,
             UnitMaskType::from_keep_probability(*keep_probability),
-            normalization_type,
             norm_params.get_normalization(),
             *is_last_layer,
         );
@@ -67,13 +76,14 @@ fn create_softmax_unit<'a>(
     function: &str,
     input_size: usize,
     output_size: usize,
-    decay_type: LearningDecayType,
-    descent_type: DescentType,
+    hyperparams: &HyperParams,
     mask_type: UnitMaskType,
-    normalization_type: NormalizationType,
     norm_params: &NormParams,
     is_last_layer: bool,
 ) -> SoftmaxUnit<'a> {
+    let decay_type: &LearningDecayType = hyperparams.decay_type();
+    let descent_type: &DescentType = hyperparams.descent_type();
+    let normalization_type: &NormalizationType = hyperparams.normalization_type();
     let batch_norm_enabled: bool = normalization_type.is_batch_norm_enabled() && !is_last_layer;
     let dropout_enabled: bool = mask_type.is_dropout_enabled() && !is_last_layer;
     let mut output_ref: NodeRef;
@@ -93,8 +103,8 @@ fn create_softmax_unit<'a>(
         let (biases_ref, out_ref) = create_biases(
             NodeRef::clone(&output_ref),
             output_size,
-            &decay_type,
-            &descent_type,
+            decay_type,
+            descent_type,
         );
 
         biases = Option::Some(biases_ref);
@@ -106,11 +116,11 @@ fn create_softmax_unit<'a>(
     if batch_norm_enabled {
         let (module, norm_ref, out_ref) = create_batch_norm(
             NodeRef::clone(&output_ref),
-            &normalization_type,
+            normalization_type,
             norm_params,
             output_size,
-            &decay_type,
-            &descent_type,
+            decay_type,
+            descent_type,
         );
         norm_module = Option::Some(module);
         norm = Option::Some(norm_ref);
@@ -131,8 +141,12 @@ fn create_softmax_unit<'a>(
     let mut mask: Option<NodeRef> = Option::None;
 
     if dropout_enabled {
-        let (mask_ref, out_ref) =
-            create_dropout(NodeRef::clone(&output_ref), &mask_type, output_size);
+        let (mask_ref, out_ref) = create_dropout(
+            NodeRef::clone(&output_ref),
+            &mask_type,
+            output_size,
+            hyperparams.precision(),
+        );
         mask = Option::Some(mask_ref);
         output_ref = NodeRef::clone(&out_ref);
     }
@@ -227,12 +241,13 @@ fn create_dropout<'a>(
     output: NodeRef<'a>,
     mask_type: &UnitMaskType,
     size: usize,
+    precision: Precision,
 ) -> (NodeRef<'a>, NodeRef<'a>) {
     if let UnitMaskType::Dropout {
         keep_probability: probability,
     } = mask_type
     {
-        let mask_ref: NodeRef = NodeRef::new(MaskNode::new(vec![size], *probability));
+        let mask_ref: NodeRef = NodeRef::new(MaskNode::new(vec![size], *probability, precision));
         let multiply_ref: NodeRef = NodeRef::new(MultiplyNode::new());
 
         multiply_ref.borrow_mut().add_input(&multiply_ref, &output);
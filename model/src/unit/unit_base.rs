@@ -78,7 +78,8 @@ impl<'a> UnitBase<'a> {
         self.is_last_layer
     }
 
-    pub fn update_mode(&mut self, new_mode: NetworkMode) {
+    // Returns whether the mode changed, units reached through several inputs only pass it on once
+    pub fn update_mode(&mut self, new_mode: NetworkMode) -> bool {
         if new_mode != self.mode {
             self.mode = new_mode;
 
@@ -89,6 +90,10 @@ impl<'a> UnitBase<'a> {
             if let Option::Some(norm) = &self.norm_node {
                 norm.borrow_mut().set_mode(new_mode)
             }
+
+            return true;
         }
+
+        false
     }
 }